@@ -0,0 +1,67 @@
+//! Compares deserializing samples into owned and borrowed types.
+//!
+//! Run with `cargo bench --bench deserialize`.
+
+#[path = "../tests/test_utils/mod.rs"]
+mod test_utils;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use test_utils::{
+    TEST_TIMEOUT,
+    types::{LargeStringRef, LargeStringStruct},
+};
+
+fn bench_deserialize(c: &mut Criterion) {
+    let mut context = test_utils::TestContextBuilder::large_strings()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This benchmark expects an available output");
+    let mut input = entities
+        .input
+        .expect("This benchmark expects an available input");
+
+    let data = LargeStringStruct {
+        first: "a".repeat(256),
+        second: "b".repeat(1024),
+        third: "c".repeat(4096),
+    };
+    output
+        .write_typed(&data)
+        .expect("Failed to write typed data");
+    input
+        .wait_with_timeout(TEST_TIMEOUT)
+        .expect("Failed to wait for data");
+    input.take().expect("Failed to take data");
+    let sample = input
+        .last_sample()
+        .expect("Failed to access samples")
+        .expect("Expected a sample");
+
+    let mut group = c.benchmark_group("deserialize");
+    group.bench_function("owned", |b| {
+        b.iter(|| {
+            sample
+                .deserialize::<LargeStringStruct>()
+                .expect("Failed to deserialize data")
+        })
+    });
+    group.bench_function("borrowed", |b| {
+        let mut buf = String::new();
+        b.iter(|| {
+            sample
+                .deserialize_borrowed::<LargeStringRef>(&mut buf)
+                .map(|data| data.first.len() + data.second.len() + data.third.len())
+                .expect("Failed to deserialize data")
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_deserialize);
+criterion_main!(benches);
@@ -81,11 +81,11 @@ pub struct ShapeType {
     /// The color of the shape (used as the key field)
     pub color: String,
     /// The X coordinate of the shape
-    pub x: i64,
+    pub x: i32,
     /// The Y coordinate of the shape
-    pub y: i64,
+    pub y: i32,
     /// The size of the shape
-    pub shapesize: i64,
+    pub shapesize: i32,
 }
 
 /// Command-line arguments for the shapes example application
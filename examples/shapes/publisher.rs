@@ -40,22 +40,27 @@ pub fn main(
 
     let mut output = connector
         .take_output(OUTPUT_NAME)
-        .map_err(|e| format!("Failed to take output: {}", e))?;
+        .map_err(|e| format!("Failed to take output: {}", e))?
+        .typed::<super::ShapeType>();
 
     loop {
-        let wait_result = match discovery_duration {
-            Some(timeout) => output.wait_for_subscriptions_with_timeout(timeout),
-            None => output.wait_for_subscriptions(),
-        };
-
-        match wait_result {
-            Ok(count) => {
+        match output
+            .as_untyped()
+            .wait_for_subscription_event(discovery_duration)
+        {
+            Ok(event) if event.total > 0 => {
                 tlog!(
-                    "Discovered {} subscriptions, proceeding to publish...",
-                    count
+                    "Matched {} subscriptions, proceeding to publish...",
+                    event.total
                 );
                 break;
             }
+            Ok(event) => {
+                tlog!(
+                    "Subscriptions changed by {}, none matched, retrying...",
+                    event.change
+                );
+            }
             Err(e) if e.is_timeout() => {
                 tlog!("No subscriptions discovered yet, retrying...");
             }
@@ -68,43 +73,43 @@ pub fn main(
     for sample_id in 1..=samples {
         tlog!("Writing sample #{}...", sample_id);
 
-        output
-            .clear_members()
-            .map_err(|e| format!("Failed to clear members: {}", e))?;
-
         // Compute the value of the fields based on the sample ID
         let shape: super::ShapeType = compute_sample_for_id(sample_id);
 
-        // Set the value of the fields
-        let mut instance = output.instance();
         match typed_mode {
             TypedMode::Enabled => {
-                // Use typed serialization when the feature is enabled
-                instance
-                    .serialize(&shape)
-                    .expect("Failed to serialize typed shape");
+                // Clear, serialize and write the typed shape in one go
+                output
+                    .write(&shape)
+                    .map_err(|e| format!("Failed to write typed sample: {}", e))?;
             }
             TypedMode::Disabled => {
+                let output = output.as_untyped_mut();
+                output
+                    .clear_members()
+                    .map_err(|e| format!("Failed to clear members: {}", e))?;
+
                 // Manual field setting when typed feature is disabled
+                let mut instance = output.instance();
                 instance
-                    .set_number("x", shape.x as f64)
+                    .set_i32("x", shape.x)
                     .expect("Failed to set x coordinate");
                 instance
-                    .set_number("y", shape.y as f64)
+                    .set_i32("y", shape.y)
                     .expect("Failed to set y coordinate");
                 instance
-                    .set_number("shapesize", shape.shapesize as f64)
+                    .set_i32("shapesize", shape.shapesize)
                     .expect("Failed to set shapesize");
                 instance
                     .set_string("color", &shape.color)
                     .expect("Failed to set color");
+
+                output
+                    .write()
+                    .map_err(|e| format!("Failed to write sample: {}", e))?;
             }
         }
 
-        output
-            .write()
-            .map_err(|e| format!("Failed to write sample: {}", e))?;
-
         if sample_id < samples {
             thread::sleep(sleep_duration);
         }
@@ -122,10 +127,10 @@ fn compute_sample_for_id(sample_id: usize) -> super::ShapeType {
     const CENTER: (f64, f64) = (CANVAS.0 / 2.0, CANVAS.1 / 2.0);
     const INCREMENT: (f64, f64) = (CANVAS.0 / 5.0, CANVAS.1 / 5.0);
 
-    let x = (CENTER.0 + f64::sin(sample_id as f64) * INCREMENT.0) as i64;
-    let y = (CENTER.1 + f64::cos(sample_id as f64) * INCREMENT.1) as i64;
+    let x = (CENTER.0 + f64::sin(sample_id as f64) * INCREMENT.0) as i32;
+    let y = (CENTER.1 + f64::cos(sample_id as f64) * INCREMENT.1) as i32;
     let shapesize =
-        (CANVAS.0 / 10.0 + f64::cos(sample_id as f64) * CANVAS.0 / 20.0) as i64;
+        (CANVAS.0 / 10.0 + f64::cos(sample_id as f64) * CANVAS.0 / 20.0) as i32;
 
     super::ShapeType {
         color: COLOR.to_string(),
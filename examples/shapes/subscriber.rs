@@ -38,22 +38,27 @@ pub fn main(
 
     let mut input = connector
         .take_input(INPUT_NAME)
-        .map_err(|e| format!("Failed to take input: {}", e))?;
+        .map_err(|e| format!("Failed to take input: {}", e))?
+        .typed::<super::ShapeType>();
 
     loop {
-        let wait_result = match discovery_duration {
-            Some(timeout) => input.wait_for_publications_with_timeout(timeout),
-            None => input.wait_for_publications(),
-        };
-
-        match wait_result {
-            Ok(count) => {
+        match input
+            .as_untyped()
+            .wait_for_publication_event(discovery_duration)
+        {
+            Ok(event) if event.total > 0 => {
                 tlog!(
-                    "Discovered {} publications, proceeding to subscribe...",
-                    count
+                    "Matched {} publications, proceeding to subscribe...",
+                    event.total
                 );
                 break;
             }
+            Ok(event) => {
+                tlog!(
+                    "Publications changed by {}, none matched, retrying...",
+                    event.change
+                );
+            }
             Err(e) if e.is_timeout() => {
                 tlog!("No publications discovered yet, retrying...");
             }
@@ -68,52 +73,57 @@ pub fn main(
     while samples_read < samples {
         tlog!("Waiting for data...");
 
-        let wait_result = match wait_timeout {
-            Some(timeout_duration) => input.wait_with_timeout(timeout_duration),
-            None => input.wait(),
-        };
-
-        match wait_result {
-            Ok(_) => {
-                tlog!("Data available, reading samples...");
+        match input.as_untyped_mut().wait_and_take(wait_timeout) {
+            Ok(0) => {
+                tlog!("No samples were taken, waiting again...");
+                continue;
+            }
+            Ok(count) => {
+                tlog!("Data available, took {} samples...", count);
             }
             Err(e) if e.is_timeout() => {
                 tlog!("Wait timed out, no data available yet.");
                 continue; // Retry waiting
             }
             Err(e) => {
-                return Err(format!("Wait failed: {}", e).into());
+                return Err(format!("Failed to wait and take samples: {}", e).into());
             }
         }
 
-        input
-            .take()
-            .map_err(|e| format!("Failed to take samples: {}", e))?;
-
-        for s in input.into_iter().valid_only() {
-            samples_read += 1;
-
-            let sample_string: String = match typed_mode {
-                TypedMode::Enabled => {
-                    let shape: super::ShapeType = s.deserialize()?;
+        let sample_strings: Vec<String> = match typed_mode {
+            TypedMode::Enabled => input
+                .iter()
+                .map(|shape| {
+                    let shape =
+                        shape.map_err(|e| format!("Failed to access sample: {}", e))?;
 
-                    format!(
+                    Ok(format!(
                         "Shape {{ x: {}, y: {}, shapesize: {}, color: '{}' }}",
                         shape.x, shape.y, shape.shapesize, shape.color
-                    )
-                }
-                TypedMode::Disabled => {
-                    let x = s.get_number("x")?;
-                    let y = s.get_number("y")?;
-                    let shapesize = s.get_number("shapesize")?;
+                    ))
+                })
+                .collect::<super::Result<_>>()?,
+            TypedMode::Disabled => input
+                .as_untyped()
+                .valid_samples()
+                .map_err(|e| format!("Failed to access samples: {}", e))?
+                .map(|s| {
+                    let s = s.map_err(|e| format!("Failed to access sample: {}", e))?;
+                    let x = s.get_i32("x")?;
+                    let y = s.get_i32("y")?;
+                    let shapesize = s.get_i32("shapesize")?;
                     let color = s.get_string("color")?;
 
-                    format!(
+                    Ok(format!(
                         "Shape {{ x: {}, y: {}, shapesize: {}, color: '{}' }}",
                         x, y, shapesize, color
-                    )
-                }
-            };
+                    ))
+                })
+                .collect::<super::Result<_>>()?,
+        };
+
+        for sample_string in sample_strings {
+            samples_read += 1;
 
             tlog!("Sample #{}: {}", samples_read, sample_string);
 
@@ -0,0 +1,303 @@
+/*******************************************************************************
+ * (c) 2025 Copyright, Real-Time Innovations.  All rights reserved.            *
+ * No duplications, whole or partial, manual or electronic, may be made        *
+ * without express written permission.  Any such copies, or revisions thereof, *
+ * must display this notice unaltered.                                         *
+ * This code contains trade secrets of Real-Time Innovations, Inc.             *
+ *******************************************************************************/
+
+//! Derive macros for RTI Connector for Rust.
+//!
+//! Don't depend on this crate directly: enable the `derive` feature of
+//! `rtiddsconnector` instead, which re-exports its macros.
+
+#![deny(missing_docs)]
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    Attribute, Data, DataEnum, DeriveInput, Fields, LitStr, ext::IdentExt,
+    parse_macro_input, spanned::Spanned,
+};
+
+/// Derive `DdsData` and `DdsField`, mapping a type to DDS data field by field.
+///
+/// Structs with named fields map to DDS structs, enums without data to DDS
+/// enumerations, by their discriminant, and enums whose variants hold a single
+/// value to DDS unions. Members are named like the fields or variants, unless
+/// renamed with `#[dds(rename = "name")]`.
+#[proc_macro_derive(DdsData, attributes(dds))]
+pub fn derive_dds_data(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let (set_body, get_body, is_struct) = match &input.data {
+        Data::Struct(data) => {
+            let (set_body, get_body) = expand_struct(&data.fields, input)?;
+            (set_body, get_body, true)
+        }
+        Data::Enum(data) => {
+            let (set_body, get_body) = expand_enum(data, input)?;
+            (set_body, get_body, false)
+        }
+        Data::Union(_) => {
+            return Err(syn::Error::new(
+                input.span(),
+                "DdsData can't be derived for unions, use an enum instead",
+            ));
+        }
+    };
+
+    // Only structs can be whole samples
+    let dds_data = is_struct.then(|| {
+        quote! {
+            impl #impl_generics ::rtiddsconnector::DdsData for #name #ty_generics #where_clause {}
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::rtiddsconnector::DdsField for #name #ty_generics #where_clause {
+            fn set_field(
+                &self,
+                instance: &mut ::rtiddsconnector::Instance,
+                path: &::rtiddsconnector::FieldPath,
+            ) -> ::rtiddsconnector::ConnectorFallible {
+                #set_body
+            }
+
+            fn get_field(
+                sample: &::rtiddsconnector::Sample,
+                path: &::rtiddsconnector::FieldPath,
+            ) -> ::rtiddsconnector::ConnectorResult<Self> {
+                #get_body
+            }
+        }
+
+        #dds_data
+    })
+}
+
+/// Derive `DdsEnum` for an enum without data, modeling a DDS enumeration.
+///
+/// The enumerators are named like the variants, unless renamed with
+/// `#[dds(rename = "name")]`, and their values are the discriminants.
+#[proc_macro_derive(DdsEnum, attributes(dds))]
+pub fn derive_dds_enum(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand_dds_enum(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_dds_enum(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new(
+            input.span(),
+            "DdsEnum can only be derived for enums",
+        ));
+    };
+    if let Some(variant) = data
+        .variants
+        .iter()
+        .find(|variant| !matches!(variant.fields, Fields::Unit))
+    {
+        return Err(syn::Error::new(
+            variant.span(),
+            "DdsEnum can only be derived for enums whose variants hold no data",
+        ));
+    }
+
+    let variants: Vec<_> = data.variants.iter().map(|variant| &variant.ident).collect();
+    let names = data
+        .variants
+        .iter()
+        .map(|variant| member_name(&variant.attrs, variant.ident.unraw().to_string()))
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    Ok(quote! {
+        impl #impl_generics ::rtiddsconnector::DdsEnum for #name #ty_generics #where_clause {
+            fn name(&self) -> &'static str {
+                match self {
+                    #( Self::#variants => #names, )*
+                }
+            }
+
+            fn value(&self) -> i64 {
+                match self {
+                    #( Self::#variants => Self::#variants as i64, )*
+                }
+            }
+
+            fn from_name(name: &str) -> ::std::option::Option<Self> {
+                match name {
+                    #( #names => ::std::option::Option::Some(Self::#variants), )*
+                    _ => ::std::option::Option::None,
+                }
+            }
+
+            fn from_value(value: i64) -> ::std::option::Option<Self> {
+                #(
+                    if value == Self::#variants as i64 {
+                        return ::std::option::Option::Some(Self::#variants);
+                    }
+                )*
+                ::std::option::Option::None
+            }
+        }
+    })
+}
+
+/// Set and get each member of a struct.
+fn expand_struct(
+    fields: &Fields,
+    input: &DeriveInput,
+) -> syn::Result<(TokenStream, TokenStream)> {
+    let Fields::Named(fields) = fields else {
+        return Err(syn::Error::new(
+            input.span(),
+            "DdsData can only be derived for structs with named fields",
+        ));
+    };
+
+    let mut idents = Vec::new();
+    let mut names = Vec::new();
+    for field in &fields.named {
+        let Some(ident) = &field.ident else {
+            continue;
+        };
+        names.push(member_name(&field.attrs, ident.unraw().to_string())?);
+        idents.push(ident);
+    }
+
+    let set_body = quote! {
+        #(
+            ::rtiddsconnector::DdsField::set_field(
+                &self.#idents,
+                instance,
+                &path.member(#names),
+            )?;
+        )*
+        Ok(())
+    };
+    let get_body = quote! {
+        Ok(Self {
+            #(
+                #idents: ::rtiddsconnector::DdsField::get_field(
+                    sample,
+                    &path.member(#names),
+                )?,
+            )*
+        })
+    };
+
+    Ok((set_body, get_body))
+}
+
+/// Map an enum without data to an enumeration, or one whose variants hold a
+/// single value to a union.
+fn expand_enum(
+    data: &DataEnum,
+    input: &DeriveInput,
+) -> syn::Result<(TokenStream, TokenStream)> {
+    let variants: Vec<_> = data.variants.iter().map(|variant| &variant.ident).collect();
+
+    if data
+        .variants
+        .iter()
+        .all(|variant| matches!(variant.fields, Fields::Unit))
+    {
+        let set_body = quote! {
+            let value: i64 = match self {
+                #( Self::#variants => Self::#variants as i64, )*
+            };
+            instance.set_integer(path.as_str(), value)
+        };
+        let get_body = quote! {
+            let value = sample.get_number(path.as_str())?;
+            #(
+                if value == (Self::#variants as i64) as f64 {
+                    return Ok(Self::#variants);
+                }
+            )*
+            Err(path.invalid_value(value))
+        };
+
+        return Ok((set_body, get_body));
+    }
+
+    if data.variants.iter().all(
+        |variant| matches!(&variant.fields, Fields::Unnamed(fields) if fields.unnamed.len() == 1),
+    ) {
+        let names = data
+            .variants
+            .iter()
+            .map(|variant| {
+                member_name(&variant.attrs, variant.ident.unraw().to_string())
+            })
+            .collect::<syn::Result<Vec<_>>>()?;
+
+        let set_body = quote! {
+            match self {
+                #(
+                    Self::#variants(value) => ::rtiddsconnector::DdsField::set_field(
+                        value,
+                        instance,
+                        &path.member(#names),
+                    ),
+                )*
+            }
+        };
+        let get_body = quote! {
+            let member = path.selected_member(sample)?;
+            match member.as_str() {
+                #(
+                    #names => ::rtiddsconnector::DdsField::get_field(
+                        sample,
+                        &path.member(#names),
+                    )
+                    .map(Self::#variants),
+                )*
+                _ => Err(path.invalid_value(member)),
+            }
+        };
+
+        return Ok((set_body, get_body));
+    }
+
+    Err(syn::Error::new(
+        input.span(),
+        "DdsData can only be derived for enums whose variants either hold no data \
+         or hold a single value",
+    ))
+}
+
+/// The DDS member name of a field or variant, from `#[dds(rename = "name")]`
+/// or its Rust name otherwise.
+fn member_name(attrs: &[Attribute], default: String) -> syn::Result<String> {
+    let mut name = default;
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("dds")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                name = meta.value()?.parse::<LitStr>()?.value();
+                Ok(())
+            } else {
+                Err(meta.error("unsupported dds attribute, expected `rename`"))
+            }
+        })?;
+    }
+
+    Ok(name)
+}
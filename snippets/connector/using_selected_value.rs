@@ -10,7 +10,7 @@ fn using_selected_value(
     // Ensure that the input has received the data by waiting
     input.take()?;
 
-    for s in input.into_iter().take(1) {
+    for s in input.samples()?.take(1) {
         assert!(matches!(
             s.get_value("field_name")?,
             SelectedValue::Boolean(true)
@@ -1,7 +1,7 @@
 use rtiddsconnector::{self, Input};
 
 fn using_input(
-    input: &Input,
+    input: &mut Input,
     samples_to_process: usize,
 ) -> rtiddsconnector::ConnectorFallible {
     let mut processed_samples: usize = 0;
@@ -23,8 +23,14 @@ fn using_input(
             }
         };
 
+        // Move the samples into the Input's cache
+        if input.take()? == 0 {
+            continue;
+        }
+
         // Iterate over the samples
-        for sample in input.into_iter().valid_only() {
+        for sample in input.valid_samples()? {
+            let sample = sample?;
             println!("Received sample #{}: {}", processed_samples, sample);
 
             processed_samples += 1;
@@ -1,7 +1,7 @@
 use rtiddsconnector::{self, Input};
 
 fn using_sample_iterator(input: &Input) -> rtiddsconnector::ConnectorFallible {
-    for (i, sample) in input.into_iter().enumerate() {
+    for (i, sample) in input.samples()?.enumerate() {
         match sample.is_valid() {
             Ok(true) => {
                 println!("Valid sample #{}: {}", i, sample)
@@ -36,7 +36,7 @@ fn start_using_connector() -> rtiddsconnector::ConnectorFallible {
     let connector = Connector::new(XML_PARTICIPANT, XML_PATH)?;
 
     // Fetch the Output associated with the Connector instance
-    let mut output: Output<'_> = connector.get_output(XML_OUTPUT)?;
+    let mut output: Output = connector.get_output(XML_OUTPUT)?;
 
     // Modifies the data contained by the Output instance
     let mut instance = output.instance();
@@ -49,16 +49,18 @@ fn start_using_connector() -> rtiddsconnector::ConnectorFallible {
     output.write()?;
 
     // Fetch the Input associated with the Connector instance
-    let mut input: Input<'_> = connector.get_input(XML_INPUT)?;
+    let mut input: Input = connector.get_input(XML_INPUT)?;
 
     // Wait for the data to be available before actually retrieving the samples
     input.wait_with_timeout(std::time::Duration::from_secs(5))?;
 
     // Retrieve the available samples from the Input instance
-    input.take()?; // Take available samples
+    let count = input.take()?; // Take available samples
+    println!("Took {} samples", count);
 
     // Iterate through the valid samples and print their content
-    for sample in input.into_iter().valid_only() {
+    for sample in input.valid_samples()? {
+        let sample = sample?;
         println!(
             "Position: x={}, y={}, shapesize={}, color={}",
             sample.get_number("x")?,
@@ -9,24 +9,39 @@
 #![doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/docs/connector.md"))]
 
 use crate::{
-    ConnectorFallible, ConnectorResult, Input, Output, ffi::FfiConnector,
-    result::ErrorKind,
+    ConnectorFallible, ConnectorResult, Input, Output,
+    ffi::FfiConnector,
+    result::{ErrorKind, InvalidErrorKind},
 };
 use std::{
-    collections::HashMap,
-    sync::{Condvar, Mutex, RwLock},
+    collections::{HashMap, VecDeque},
+    ffi::{CStr, CString},
+    sync::{
+        Arc, Condvar, Mutex, RwLock,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
 };
 
+/// Maximum duration of a single native wait, bounding the latency of [`WakeHandle::trigger`].
+pub(crate) const WAIT_CHUNK: Duration = Duration::from_millis(100);
+
 /// A variant type that can hold a [number][selected_number],
-/// a [boolean][selected_boolean], or a [string][selected_string] value.
+/// a [boolean][selected_boolean], a [string][selected_string] or a
+/// [JSON][selected_json] value, or [no value][selected_null] at all.
 ///
 /// This type is used for both [setting][set_value] and [retrieving][get_value]
 /// values from DDS samples in a type-safe manner, respectively with
 /// [`Instance::set_value`][set_value] and [`Sample::get_value`][get_value].
 ///
-/// Note that complex types (such as nested structures) are
-/// internally represented as JSON strings, and should be set and retrieved
-/// using [`SelectedValue::String`].
+/// Complex members, such as nested structures, arrays or sequences, are
+/// retrieved as a [`SelectedValue::Json`], and can be set with one as well.
+///
+/// More variants may be added in the future, so matches on a [`SelectedValue`]
+/// need a wildcard arm.
+///
+/// A [`SelectedValue`] serializes to, and deserializes from, the matching JSON
+/// scalar, with [`SelectedValue::Null`] as `null`.
 ///
 /// # Examples
 ///
@@ -37,9 +52,13 @@ use std::{
 /// [selected_number]: SelectedValue::Number
 /// [selected_boolean]: SelectedValue::Boolean
 /// [selected_string]: SelectedValue::String
+/// [selected_json]: SelectedValue::Json
+/// [selected_null]: SelectedValue::Null
 /// [set_value]: crate::Instance::set_value
 /// [get_value]: crate::Sample::get_value
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+#[non_exhaustive]
 pub enum SelectedValue {
     /// A numeric value
     Number(f64),
@@ -49,8 +68,161 @@ pub enum SelectedValue {
 
     /// A string value
     String(String),
+
+    /// No value, such as an unset optional member
+    ///
+    /// Setting it with [`Instance::set_value`](crate::Instance::set_value) clears the member.
+    Null,
+
+    /// The JSON representation of a complex member
+    ///
+    /// Setting it only modifies the members present in the JSON, and requires
+    /// a member path without element indexes.
+    Json(serde_json::Value),
+}
+
+impl SelectedValue {
+    /// The number held, if this is a [`SelectedValue::Number`].
+    pub fn as_number(&self) -> Option<f64> {
+        match self {
+            SelectedValue::Number(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    /// The boolean held, if this is a [`SelectedValue::Boolean`].
+    pub fn as_boolean(&self) -> Option<bool> {
+        match self {
+            SelectedValue::Boolean(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    /// The string held, if this is a [`SelectedValue::String`].
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            SelectedValue::String(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// The JSON value held, if this is a [`SelectedValue::Json`].
+    pub fn as_json(&self) -> Option<&serde_json::Value> {
+        match self {
+            SelectedValue::Json(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Whether this is a [`SelectedValue::Null`].
+    pub fn is_null(&self) -> bool {
+        matches!(self, SelectedValue::Null)
+    }
+}
+
+/// Display the value without quotes, and numbers without a trailing `.0`.
+impl std::fmt::Display for SelectedValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SelectedValue::Number(v) => write!(f, "{}", v),
+            SelectedValue::Boolean(v) => write!(f, "{}", v),
+            SelectedValue::String(v) => write!(f, "{}", v),
+            SelectedValue::Null => write!(f, "null"),
+            SelectedValue::Json(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+/// Parse a [SelectedValue::Boolean] from `true` or `false`, then a finite
+/// [SelectedValue::Number], and otherwise keep the text as a [SelectedValue::String].
+///
+/// Parsing never fails, and never returns [SelectedValue::Null].
+impl std::str::FromStr for SelectedValue {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "true" => SelectedValue::Boolean(true),
+            "false" => SelectedValue::Boolean(false),
+            _ => match s.parse::<f64>() {
+                Ok(v) if v.is_finite() => SelectedValue::Number(v),
+                _ => SelectedValue::String(s.to_string()),
+            },
+        })
+    }
+}
+
+/// Converts a [SelectedValue::Number] into [f64], failing for any other variant.
+impl TryFrom<SelectedValue> for f64 {
+    type Error = crate::ConnectorError;
+
+    fn try_from(v: SelectedValue) -> ConnectorResult<Self> {
+        v.as_number()
+            .ok_or_else(|| ErrorKind::selected_value_mismatch_error("number", &v).into())
+    }
+}
+
+/// Converts a [SelectedValue::Boolean] into [bool], failing for any other variant.
+impl TryFrom<SelectedValue> for bool {
+    type Error = crate::ConnectorError;
+
+    fn try_from(v: SelectedValue) -> ConnectorResult<Self> {
+        v.as_boolean()
+            .ok_or_else(|| ErrorKind::selected_value_mismatch_error("boolean", &v).into())
+    }
 }
 
+/// Converts a [SelectedValue::String] into [String], failing for any other variant.
+impl TryFrom<SelectedValue> for String {
+    type Error = crate::ConnectorError;
+
+    fn try_from(v: SelectedValue) -> ConnectorResult<Self> {
+        match v {
+            SelectedValue::String(v) => Ok(v),
+            v => ErrorKind::selected_value_mismatch_error("string", &v).into_err(),
+        }
+    }
+}
+
+/// Implements conversions from a [SelectedValue::Number] into integer types,
+/// failing for other variants and for numbers the type can't hold exactly.
+macro_rules! impl_try_into_integer {
+    ($($t:ty),*) => {
+        $(
+            #[doc = concat!("Converts a [SelectedValue::Number] holding an integer into [", stringify!($t), "].")]
+            impl TryFrom<SelectedValue> for $t {
+                type Error = crate::ConnectorError;
+
+                fn try_from(v: SelectedValue) -> ConnectorResult<Self> {
+                    let number = v.as_number().ok_or_else(|| {
+                        crate::ConnectorError::from(
+                            ErrorKind::selected_value_mismatch_error("number", &v),
+                        )
+                    })?;
+
+                    // `MAX as f64` may round up, so the upper bound is exclusive
+                    let in_range = number >= <$t>::MIN as f64 && number < <$t>::MAX as f64 + 1.0;
+                    if in_range && number.fract() == 0.0 {
+                        Ok(number as $t)
+                    } else {
+                        ErrorKind::Invalid {
+                            what: InvalidErrorKind::Conversion,
+                            context: std::format!(
+                                "Number {} can't be converted exactly into {}",
+                                number,
+                                stringify!($t)
+                            ),
+                        }
+                        .into_err()
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_try_into_integer!(i64, i32, u32);
+
 /// Allows quick conversion from [f64] to [SelectedValue::Number].
 impl From<f64> for SelectedValue {
     fn from(v: f64) -> Self {
@@ -79,6 +251,145 @@ impl From<&str> for SelectedValue {
     }
 }
 
+/// Allows quick conversion from [&String](String) to [SelectedValue::String].
+impl From<&String> for SelectedValue {
+    fn from(v: &String) -> Self {
+        v.clone().into()
+    }
+}
+
+/// Allows quick conversion from [`Cow<str>`](std::borrow::Cow) to [SelectedValue::String].
+impl From<std::borrow::Cow<'_, str>> for SelectedValue {
+    fn from(v: std::borrow::Cow<'_, str>) -> Self {
+        v.into_owned().into()
+    }
+}
+
+/// Allows quick conversion from [char] to a single-character [SelectedValue::String].
+impl From<char> for SelectedValue {
+    fn from(v: char) -> Self {
+        v.to_string().into()
+    }
+}
+
+/// Implements conversions to [SelectedValue::Number] for numeric types which
+/// a [f64] represents exactly.
+macro_rules! impl_from_exact_number {
+    ($($t:ty),*) => {
+        $(
+            #[doc = concat!("Allows quick conversion from [", stringify!($t), "] to [SelectedValue::Number].")]
+            impl From<$t> for SelectedValue {
+                fn from(v: $t) -> Self {
+                    SelectedValue::Number(f64::from(v))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_exact_number!(i8, i16, i32, u8, u16, u32, f32);
+
+/// Converts an [i64] to [SelectedValue::Number], failing if a [f64] can't represent it exactly.
+impl TryFrom<i64> for SelectedValue {
+    type Error = crate::ConnectorError;
+
+    fn try_from(v: i64) -> ConnectorResult<Self> {
+        if v.unsigned_abs() <= crate::output::MAX_EXACT_INTEGER {
+            Ok(SelectedValue::Number(v as f64))
+        } else {
+            ErrorKind::inexact_number_error(v).into_err()
+        }
+    }
+}
+
+/// Converts an [u64] to [SelectedValue::Number], failing if a [f64] can't represent it exactly.
+impl TryFrom<u64> for SelectedValue {
+    type Error = crate::ConnectorError;
+
+    fn try_from(v: u64) -> ConnectorResult<Self> {
+        if v <= crate::output::MAX_EXACT_INTEGER {
+            Ok(SelectedValue::Number(v as f64))
+        } else {
+            ErrorKind::inexact_number_error(v).into_err()
+        }
+    }
+}
+
+/// Converts an [usize] to [SelectedValue::Number], failing if a [f64] can't represent it exactly.
+impl TryFrom<usize> for SelectedValue {
+    type Error = crate::ConnectorError;
+
+    fn try_from(v: usize) -> ConnectorResult<Self> {
+        u64::try_from(v)
+            .map_err(|_| ErrorKind::inexact_number_error(v).into())
+            .and_then(SelectedValue::try_from)
+    }
+}
+
+/// A remote endpoint matched by an [`Input`] or an [`Output`].
+///
+/// Returned by [`Input::matched_publications`] and
+/// [`Output::matched_subscriptions`]. New fields may be added as *Connext DDS*
+/// reports more information about the matched endpoints.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+#[non_exhaustive]
+pub struct EndpointInfo {
+    /// The entity name of the remote endpoint, if it was given one.
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+impl EndpointInfo {
+    /// Parse the JSON list of matched endpoints returned by the native API.
+    pub(crate) fn parse_list(json: &str) -> ConnectorResult<Vec<Self>> {
+        serde_json::from_str(json).map_err(|e| {
+            ErrorKind::Invalid {
+                what: InvalidErrorKind::Deserialization,
+                context: std::format!(
+                    "Failed parsing the matched endpoints ({}): {}",
+                    json,
+                    e
+                ),
+            }
+            .caused_by(e)
+        })
+    }
+}
+
+/// A change in the remote endpoints matched by an [`Input`] or an [`Output`].
+///
+/// Returned by [`Input::wait_for_publication_event`] and
+/// [`Output::wait_for_subscription_event`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MatchEvent {
+    /// The change in the number of matched endpoints since the previous wait,
+    /// negative when endpoints were unmatched.
+    pub change: i32,
+
+    /// The number of endpoints matched once the wait returned.
+    pub total: i32,
+}
+
+impl MatchEvent {
+    /// Whether new endpoints were matched.
+    pub fn is_match(&self) -> bool {
+        self.change > 0
+    }
+
+    /// Whether previously matched endpoints were unmatched.
+    pub fn is_unmatch(&self) -> bool {
+        self.change < 0
+    }
+
+    /// Create an event from a count change and the list of matched endpoints.
+    pub(crate) fn new(change: i32, matched: &[EndpointInfo]) -> Self {
+        MatchEvent {
+            change,
+            total: matched.len().try_into().unwrap_or(i32::MAX),
+        }
+    }
+}
+
 /// The main interface to the RTI Connector for Rust API.
 ///
 /// Representing a DDS `DomainParticipant` and its contained
@@ -90,14 +401,39 @@ impl From<&str> for SelectedValue {
 /// methods of this struct, allowing to acquire owned references to
 /// [`Input`] and [`Output`] objects for reading and writing DDS data.
 ///
+/// The returned entities are not bound to the lifetime of the [`Connector`]:
+/// they share ownership of the underlying native connector, which is only
+/// deleted once the [`Connector`] and all of its entities have been dropped.
+///
+/// Cloning a [`Connector`] is cheap and yields another handle to the same
+/// native connector, which can be sent to other threads. Ownership of each
+/// [`Input`] and [`Output`] is still exclusive across all clones.
+///
 /// # Examples
 /// ```rust
 #[doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/snippets/connector/using_connector.rs"))]
 /// ```
+#[derive(Clone)]
 pub struct Connector {
+    /// The shared state of this Connector, also referenced by its entities.
+    inner: Arc<ConnectorInner>,
+}
+
+/// Display implementation for Connector; displaying only the name and domain id.
+impl std::fmt::Debug for Connector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(&self.inner, f)
+    }
+}
+
+/// The state shared between a [`Connector`] and its [`Input`] and [`Output`] entities.
+pub(crate) struct ConnectorInner {
     /// The name of the configuration used to create this Connector.
     name: String,
 
+    /// The domain id of the participant, retrieved once at creation.
+    domain_id: i32,
+
     /// The native connector instance, protected by a RwLock for thread-safe access.
     native: RwLock<FfiConnector>,
 
@@ -106,24 +442,30 @@ pub struct Connector {
 
     /// Thread-safe holders for Output entities.
     outputs: ThreadSafeEntityHolder<OutputRecord>,
+
+    /// Counter incremented by every [`WakeHandle::trigger`], observed by waits.
+    wake_epoch: Arc<AtomicU64>,
 }
 
-/// Unsafe marker traits for Connector; disables sharing between threads.
+/// Unsafe marker traits for ConnectorInner; access to native resources is guarded by locks.
 #[allow(unsafe_code)]
-unsafe impl Sync for Connector {
+unsafe impl Sync for ConnectorInner {
     /* Marker trait */
 }
 
-/// Unsafe marker traits for Connector; disables sharing between threads.
+/// Unsafe marker traits for ConnectorInner; access to native resources is guarded by locks.
 #[allow(unsafe_code)]
-unsafe impl Send for Connector {
+unsafe impl Send for ConnectorInner {
     /* Marker trait */
 }
 
-/// Display implementation for Connector; displaying only the name.
-impl std::fmt::Debug for Connector {
+/// Display implementation for ConnectorInner; displaying only the name and domain id.
+impl std::fmt::Debug for ConnectorInner {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, r#"Connector {{ name: "{}" }}"#, self.name)
+        f.debug_struct("Connector")
+            .field("name", &self.name)
+            .field("domain_id", &self.domain_id)
+            .finish()
     }
 }
 
@@ -157,24 +499,53 @@ impl Connector {
     pub fn new(config_name: &str, config_file: &str) -> ConnectorResult<Connector> {
         static NATIVE_CONNECTOR_CREATION_LOCK: Mutex<()> = Mutex::new(());
 
-        let native: FfiConnector = {
+        let (native, domain_id): (FfiConnector, i32) = {
             let _guard = NATIVE_CONNECTOR_CREATION_LOCK
                 .lock()
                 .inspect_err(|_| {
                     eprintln!("An error occurred while trying to lock the global native connector creation lock, continuing anyway...");
                 })
                 .unwrap_or_else(|poisoned| poisoned.into_inner());
-            FfiConnector::new(config_name, config_file)?
+            let native = FfiConnector::new(config_name, config_file)?;
+            let domain_id = native.participant().domain_id();
+            (native, domain_id)
         };
 
         Ok(Connector {
-            name: config_name.to_string(),
-            native: RwLock::new(native),
-            inputs: ThreadSafeEntityHolder::new(),
-            outputs: ThreadSafeEntityHolder::new(),
+            inner: Arc::new(ConnectorInner {
+                name: config_name.to_string(),
+                domain_id,
+                native: RwLock::new(native),
+                inputs: ThreadSafeEntityHolder::new(),
+                outputs: ThreadSafeEntityHolder::new(),
+                wake_epoch: Arc::new(AtomicU64::new(0)),
+            }),
         })
     }
 
+    /// Check that a named configuration contained in an external XML file can be loaded.
+    ///
+    /// The participant is created and immediately deleted within this call,
+    /// so no [`Connector`] or native participant is left behind. Errors of any
+    /// kind name the profile and the file being validated.
+    pub fn validate_config(config_name: &str, config_file: &str) -> ConnectorFallible {
+        Connector::new(config_name, config_file)
+            .map(drop)
+            .map_err(|e| {
+                e.with_context(format!(
+                    "validating profile '{}' from '{}'",
+                    config_name, config_file
+                ))
+            })
+    }
+
+    /// Get the DDS domain id joined by this [`Connector`]'s participant.
+    ///
+    /// The domain id is retrieved once when the [`Connector`] is created.
+    pub fn domain_id(&self) -> ConnectorResult<i32> {
+        Ok(self.inner.domain_id)
+    }
+
     /// Wait until data is available to read from any of its [`Input`], indefinitely.
     pub fn wait_for_data(&self) -> ConnectorFallible {
         self.impl_wait_for_data(None)
@@ -185,15 +556,132 @@ impl Connector {
         &self,
         timeout: std::time::Duration,
     ) -> ConnectorFallible {
-        self.impl_wait_for_data(Some(
-            // Durations cannot be negative
-            timeout.as_millis().try_into().unwrap_or(i32::MAX),
-        ))
+        self.impl_wait_for_data(Some(timeout))
+    }
+
+    /// Wait until data is available to read from any of its [`Input`], without
+    /// blocking the async runtime.
+    ///
+    /// This is the async counterpart of [`Connector::wait_for_data_with_timeout`],
+    /// or of [`Connector::wait_for_data`] if no timeout is given. Dropping the
+    /// future cancels the wait like [`Input::wait_async`].
+    #[cfg(feature = "async")]
+    pub async fn wait_for_data_async(
+        &self,
+        timeout: Option<Duration>,
+    ) -> ConnectorFallible {
+        let inner = Arc::clone(&self.inner);
+
+        spawn_blocking(move |cancelled| {
+            inner.interruptible_wait(timeout, Some(cancelled), |chunk_ms| {
+                inner.native_ref()?.wait_for_data(Some(chunk_ms))
+            })
+        })
+        .await
+    }
+
+    /// Dispatch the data received by some of its [`Input`] to async handlers,
+    /// until `cancel` is triggered.
+    ///
+    /// `handlers` maps the name of each [`Input`] to the [`DataHandler`]
+    /// invoked with an [`OwnedSample`](crate::OwnedSample) of every valid
+    /// sample it takes. The [`Input`]s are acquired like with
+    /// [`Connector::get_input`], and released when dispatching ends.
+    ///
+    /// The handlers of each [`Input`] run one at a time, in the order in which
+    /// the samples were taken, while those of different [`Input`]s may run
+    /// concurrently, on tasks of the Tokio runtime. Once `cancel` is triggered,
+    /// dispatching ends after the handlers in progress and within the
+    /// [granularity](CancelToken::with_granularity) of the token.
+    ///
+    /// If an [`Input`] fails, dispatching ends for all of them and the first
+    /// error is returned. A panic of a handler is resumed in the caller.
+    #[cfg(feature = "async")]
+    pub async fn dispatch_async(
+        &self,
+        handlers: HashMap<String, DataHandler>,
+        cancel: &CancelToken,
+    ) -> ConnectorFallible {
+        let inputs = handlers
+            .into_iter()
+            .map(|(name, handler)| Ok((self.get_input(&name)?, handler)))
+            .collect::<ConnectorResult<Vec<_>>>()?;
+
+        // Stops the other inputs once one of them fails
+        let failed = CancelToken::new();
+        let mut tasks = tokio::task::JoinSet::new();
+        for (input, handler) in inputs {
+            let cancel = cancel.clone();
+            let failed = failed.clone();
+            tasks.spawn(dispatch_input(input, handler, cancel, failed));
+        }
+
+        let mut result = Ok(());
+        while let Some(joined) = tasks.join_next().await {
+            match joined {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => {
+                    failed.cancel();
+                    if result.is_ok() {
+                        result = Err(e);
+                    }
+                }
+                Err(e) if e.is_panic() => std::panic::resume_unwind(e.into_panic()),
+                Err(_) => {
+                    failed.cancel();
+                    if result.is_ok() {
+                        result = ErrorKind::interrupted_error().into_err();
+                    }
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Wait until data is available to read from any of its [`Input`], until
+    /// the timeout expires, indefinitely if no timeout is given, or until the
+    /// [`CancelToken`] is cancelled.
+    pub fn wait_for_data_cancellable(
+        &self,
+        timeout: Option<Duration>,
+        cancel: &CancelToken,
+    ) -> ConnectorFallible {
+        self.inner.cancellable_wait(timeout, cancel, |chunk_ms| {
+            self.inner.native_ref()?.wait_for_data(Some(chunk_ms))
+        })
     }
 
     /// Implementation of wait for data functionality.
-    fn impl_wait_for_data(&self, timeout: Option<i32>) -> ConnectorFallible {
-        self.native_ref()?.wait_for_data(timeout)
+    fn impl_wait_for_data(&self, timeout: Option<Duration>) -> ConnectorFallible {
+        self.inner.interruptible_wait(timeout, None, |chunk_ms| {
+            self.inner.native_ref()?.wait_for_data(Some(chunk_ms))
+        })
+    }
+
+    /// Get a pointer to the native `DDS_DomainParticipant` of this [`Connector`],
+    /// for use with the *Connext DDS* C API.
+    ///
+    /// The returned [`NativeEntityGuard`](crate::NativeEntityGuard) holds the
+    /// connector lock, and the pointer is only valid while it's alive.
+    #[cfg(feature = "unsafe-interop")]
+    pub fn native_participant_ptr(
+        &self,
+    ) -> ConnectorResult<crate::NativeEntityGuard<'_>> {
+        let native = self.inner.native_mut()?;
+        let participant = native.participant().as_native_ptr();
+
+        Ok(crate::NativeEntityGuard::new(native, participant))
+    }
+
+    /// Get a [`WakeHandle`] able to interrupt the waits in progress on this [`Connector`].
+    ///
+    /// This covers [`Connector::wait_for_data`], [`Input::wait`] and [`Output::wait`],
+    /// as well as their variants with a timeout.
+    pub fn wake_handle(&self) -> WakeHandle {
+        WakeHandle {
+            epoch: Arc::clone(&self.inner.wake_epoch),
+        }
     }
 
     /// Get an [`Input`] instance contained in this [`Connector`].
@@ -205,24 +693,43 @@ impl Connector {
     ///
     /// An error will be returned if another thread already owns the named [`Input`],
     /// or if named [`Input`] is not contained in the Connector.
-    pub fn get_input(&self, name: &str) -> ConnectorResult<Input<'_>> {
-        self.inputs
-            .acquire_entity(name, &self, BlockingBehavior::NonBlocking)
+    pub fn get_input(&self, name: &str) -> ConnectorResult<Input> {
+        self.inner
+            .inputs
+            .acquire_entity(name, &self.inner, BlockingBehavior::NonBlocking)
     }
 
     /// Get an [`Input`] instance contained in this [`Connector`], potentially
     /// blocking until it becomes available.
     ///
     /// This is a thread-aware operation that enforces single-threaded ownership,
-    /// and the blocking counterpart of [`Connector::get_input`].
-    pub fn take_input(&self, name: &str) -> ConnectorResult<Input<'_>> {
-        self.inputs
-            .acquire_entity(name, &self, BlockingBehavior::BlockForever)
+    /// and the blocking counterpart of [`Connector::get_input`]. Threads
+    /// blocked on the same [`Input`] acquire it in the order they started
+    /// waiting.
+    pub fn take_input(&self, name: &str) -> ConnectorResult<Input> {
+        self.inner.inputs.acquire_entity(
+            name,
+            &self.inner,
+            BlockingBehavior::BlockForever,
+        )
     }
 
-    /// Mark an [`Input`] as released, making it available to other threads.
-    pub(crate) fn release_input(&self, name: &str) -> ConnectorFallible {
-        self.inputs.release_entity(name)
+    /// Get an [`Input`] instance contained in this [`Connector`], blocking
+    /// until it becomes available or the timeout expires.
+    ///
+    /// This is the same as [`Connector::take_input`], returning an error for
+    /// which [`ConnectorError::is_timeout`](crate::ConnectorError::is_timeout)
+    /// returns `true` if another thread still owns the [`Input`] after `timeout`.
+    pub fn take_input_with_timeout(
+        &self,
+        name: &str,
+        timeout: Duration,
+    ) -> ConnectorResult<Input> {
+        self.inner.inputs.acquire_entity(
+            name,
+            &self.inner,
+            BlockingBehavior::BlockWithTimeout(timeout),
+        )
     }
 
     /// Get an [`Output`] instance contained in this [`Connector`].
@@ -234,19 +741,213 @@ impl Connector {
     ///
     /// An error will be returned if another thread already owns the named [`Output`],
     /// or if named [`Output`] is not contained in the Connector.
-    pub fn get_output(&self, name: &str) -> ConnectorResult<Output<'_>> {
-        self.outputs
-            .acquire_entity(name, &self, BlockingBehavior::NonBlocking)
+    pub fn get_output(&self, name: &str) -> ConnectorResult<Output> {
+        self.inner.outputs.acquire_entity(
+            name,
+            &self.inner,
+            BlockingBehavior::NonBlocking,
+        )
     }
 
     /// Get an [`Output`] instance contained in this [`Connector`], potentially
     /// blocking until it becomes available.
     ///
     /// This is a thread-aware operation that enforces single-threaded ownership,
-    /// and the blocking counterpart of [`Connector::get_output`].
-    pub fn take_output(&self, name: &str) -> ConnectorResult<Output<'_>> {
-        self.outputs
-            .acquire_entity(name, &self, BlockingBehavior::BlockForever)
+    /// and the blocking counterpart of [`Connector::get_output`]. Threads
+    /// blocked on the same [`Output`] acquire it in the order they started
+    /// waiting.
+    pub fn take_output(&self, name: &str) -> ConnectorResult<Output> {
+        self.inner.outputs.acquire_entity(
+            name,
+            &self.inner,
+            BlockingBehavior::BlockForever,
+        )
+    }
+
+    /// Get an [`Output`] instance contained in this [`Connector`], blocking
+    /// until it becomes available or the timeout expires.
+    ///
+    /// This is the same as [`Connector::take_output`], returning an error for
+    /// which [`ConnectorError::is_timeout`](crate::ConnectorError::is_timeout)
+    /// returns `true` if another thread still owns the [`Output`] after `timeout`.
+    pub fn take_output_with_timeout(
+        &self,
+        name: &str,
+        timeout: Duration,
+    ) -> ConnectorResult<Output> {
+        self.inner.outputs.acquire_entity(
+            name,
+            &self.inner,
+            BlockingBehavior::BlockWithTimeout(timeout),
+        )
+    }
+}
+
+/// The deadline of a wait with the given timeout, or `None` if it waits
+/// indefinitely, either because there's no timeout or because the deadline
+/// can't be represented, such as for [`Duration::MAX`].
+fn wait_deadline(timeout: Option<Duration>) -> Option<Instant> {
+    timeout.and_then(|timeout| Instant::now().checked_add(timeout))
+}
+
+/// Convert a timeout into the milliseconds of a native wait, rounding up so
+/// that the wait doesn't end before the timeout, and clamping it to `i32::MAX`.
+fn native_timeout_ms(timeout: Duration) -> i32 {
+    timeout
+        .as_nanos()
+        .div_ceil(1_000_000)
+        .try_into()
+        .unwrap_or(i32::MAX)
+}
+
+/// Run a blocking wait on a blocking thread of the Tokio runtime.
+///
+/// The wait receives a flag which is set when the returned future is dropped,
+/// so that it can stop within one chunk of an interruptible wait instead of
+/// running to completion. A panic of the wait is resumed in the caller, and
+/// the runtime shutting down before running it is reported as an interrupted
+/// wait.
+#[cfg(feature = "async")]
+pub(crate) async fn spawn_blocking<T: Send + 'static>(
+    wait: impl FnOnce(&AtomicBool) -> ConnectorResult<T> + Send + 'static,
+) -> ConnectorResult<T> {
+    /// Sets the flag when dropped, including when the future is cancelled.
+    struct CancelOnDrop(Arc<AtomicBool>);
+
+    impl Drop for CancelOnDrop {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::Release);
+        }
+    }
+
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let _cancel_on_drop = CancelOnDrop(Arc::clone(&cancelled));
+
+    match tokio::task::spawn_blocking(move || wait(&cancelled)).await {
+        Ok(result) => result,
+        Err(e) if e.is_panic() => std::panic::resume_unwind(e.into_panic()),
+        Err(_) => ErrorKind::interrupted_error().into_err(),
+    }
+}
+
+/// Take the data of an [`Input`] and pass it to its handler, until either
+/// token is cancelled or the [`Input`] fails.
+#[cfg(feature = "async")]
+async fn dispatch_input(
+    mut input: Input,
+    mut handler: DataHandler,
+    cancel: CancelToken,
+    failed: CancelToken,
+) -> ConnectorFallible {
+    while !cancel.is_cancelled() && !failed.is_cancelled() {
+        match input.wait_async(Some(cancel.granularity())).await {
+            Ok(()) => {}
+            Err(e) if e.is_timeout() => continue,
+            Err(e) => return Err(e),
+        }
+
+        input.take()?;
+        for sample in input.snapshot()? {
+            if sample.is_valid() {
+                handler(sample).await;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Run a native wait in chunks of at most `chunk`, so that it can be
+/// interrupted by a [`WakeHandle`] incrementing `wake_epoch`, or cancelled
+/// when `cancelled` returns the kind of error to end it with.
+///
+/// The `wait` closure receives the timeout of each chunk in milliseconds.
+/// Timeouts too long to compute a deadline, such as [`Duration::MAX`], wait
+/// indefinitely.
+fn wait_in_chunks<T>(
+    wake_epoch: &AtomicU64,
+    timeout: Option<Duration>,
+    chunk: Duration,
+    cancelled: impl Fn() -> Option<ErrorKind>,
+    mut wait: impl FnMut(i32) -> ConnectorResult<T>,
+) -> ConnectorResult<T> {
+    let epoch = wake_epoch.load(Ordering::Acquire);
+    let deadline = wait_deadline(timeout);
+
+    loop {
+        if let Some(kind) = cancelled() {
+            return kind.into_err();
+        }
+
+        let chunk = match deadline {
+            Some(deadline) => deadline
+                .saturating_duration_since(Instant::now())
+                .min(chunk),
+            None => chunk,
+        };
+
+        match wait(native_timeout_ms(chunk)) {
+            Err(e) if e.is_timeout() => {
+                if wake_epoch.load(Ordering::Acquire) != epoch {
+                    return ErrorKind::interrupted_error().into_err();
+                }
+
+                if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                    return Err(e);
+                }
+            }
+            result => return result,
+        }
+    }
+}
+
+impl ConnectorInner {
+    /// Run a native wait in bounded chunks, so that it can be interrupted by a
+    /// [`WakeHandle`], or by setting the `cancelled` flag.
+    ///
+    /// The `wait` closure receives the timeout of each chunk in milliseconds.
+    /// Timeouts too long to compute a deadline, such as [`Duration::MAX`],
+    /// wait indefinitely.
+    pub(crate) fn interruptible_wait<T>(
+        &self,
+        timeout: Option<Duration>,
+        cancelled: Option<&AtomicBool>,
+        wait: impl FnMut(i32) -> ConnectorResult<T>,
+    ) -> ConnectorResult<T> {
+        wait_in_chunks(
+            &self.wake_epoch,
+            timeout,
+            WAIT_CHUNK,
+            || {
+                cancelled
+                    .is_some_and(|cancelled| cancelled.load(Ordering::Acquire))
+                    .then(ErrorKind::interrupted_error)
+            },
+            wait,
+        )
+    }
+
+    /// Run a native wait in chunks of the granularity of a [`CancelToken`],
+    /// so that it can be cancelled by it, as well as interrupted by a
+    /// [`WakeHandle`].
+    pub(crate) fn cancellable_wait<T>(
+        &self,
+        timeout: Option<Duration>,
+        cancel: &CancelToken,
+        wait: impl FnMut(i32) -> ConnectorResult<T>,
+    ) -> ConnectorResult<T> {
+        wait_in_chunks(
+            &self.wake_epoch,
+            timeout,
+            cancel.granularity,
+            || cancel.is_cancelled().then(ErrorKind::cancelled_error),
+            wait,
+        )
+    }
+
+    /// Mark an [`Input`] as released, making it available to other threads.
+    pub(crate) fn release_input(&self, name: &str) -> ConnectorFallible {
+        self.inputs.release_entity(name)
     }
 
     /// Mark an [`Output`] as released, making it available to other threads.
@@ -280,13 +981,13 @@ impl Connector {
 }
 
 // Trait specializations for Input entities
-impl<'a> EntityHandler<Input<'a>, InputRecord> for &'a Connector {
-    fn validate_name(&self, name: &str) -> ConnectorFallible {
+impl EntityHandler<Input, InputRecord> for Arc<ConnectorInner> {
+    fn validate_name(&self, name: &CStr) -> ConnectorFallible {
         self.native_ref()?.get_input(name).map(drop)
     }
 
-    fn create_entity(&self, name: &str) -> Input<'a> {
-        Input::new(name, self)
+    fn create_entity(&self, name: Arc<str>, c_name: CString) -> Input {
+        Input::new(name, c_name, Arc::clone(self))
     }
 
     fn create_record() -> InputRecord {
@@ -295,13 +996,13 @@ impl<'a> EntityHandler<Input<'a>, InputRecord> for &'a Connector {
 }
 
 // Trait specializations for Output entities
-impl<'a> EntityHandler<Output<'a>, OutputRecord> for &'a Connector {
-    fn validate_name(&self, name: &str) -> ConnectorFallible {
+impl EntityHandler<Output, OutputRecord> for Arc<ConnectorInner> {
+    fn validate_name(&self, name: &CStr) -> ConnectorFallible {
         self.native_ref()?.get_output(name).map(drop)
     }
 
-    fn create_entity(&self, name: &str) -> Output<'a> {
-        Output::new(name, self)
+    fn create_entity(&self, name: Arc<str>, c_name: CString) -> Output {
+        Output::new(name, c_name, Arc::clone(self))
     }
 
     fn create_record() -> OutputRecord {
@@ -309,6 +1010,102 @@ impl<'a> EntityHandler<Output<'a>, OutputRecord> for &'a Connector {
     }
 }
 
+/// A handle which interrupts the blocking waits in progress on a [`Connector`].
+///
+/// Obtained with [`Connector::wake_handle`], it can be cloned and sent to other
+/// threads. Once [`WakeHandle::trigger`] is called, every wait that was already in
+/// progress returns an error for which [`ConnectorError::is_interrupted`] is `true`
+/// within a small bounded latency. Waits started afterwards are not affected.
+///
+/// [`ConnectorError::is_interrupted`]: crate::ConnectorError::is_interrupted
+#[derive(Debug, Clone)]
+pub struct WakeHandle {
+    /// The wake counter shared with the parent [`Connector`].
+    epoch: Arc<AtomicU64>,
+}
+
+impl WakeHandle {
+    /// Interrupt all the waits currently in progress on the parent [`Connector`].
+    pub fn trigger(&self) {
+        self.epoch.fetch_add(1, Ordering::AcqRel);
+    }
+}
+
+/// An async callback invoked by [`Connector::dispatch_async`] with the data of
+/// an [`Input`].
+#[cfg(feature = "async")]
+pub type DataHandler = Box<
+    dyn FnMut(
+            crate::OwnedSample,
+        ) -> std::pin::Pin<Box<dyn std::future::Future<Output = ()> + Send>>
+        + Send,
+>;
+
+/// A handle to cancel the waits given to it, such as [`Connector::wait_for_data_cancellable`].
+///
+/// Created with [`CancelToken::new`], it isn't tied to any [`Connector`], so a
+/// single token can cancel waits on several of them. It can be cloned and sent
+/// to other threads. Once [`CancelToken::cancel`] is called
+/// on any of its clones, every wait given one of them returns an error for
+/// which [`ConnectorError::is_cancelled`] is `true`, within the
+/// [granularity](CancelToken::with_granularity) of the token. Unlike a
+/// [`WakeHandle`], the cancellation is permanent, so waits started afterwards
+/// are cancelled at once.
+///
+/// [`ConnectorError::is_cancelled`]: crate::ConnectorError::is_cancelled
+#[derive(Debug, Clone)]
+pub struct CancelToken {
+    /// The flag shared by the clones of the token.
+    cancelled: Arc<AtomicBool>,
+
+    /// The maximum duration of each native wait, between checks of the flag.
+    granularity: Duration,
+}
+
+/// A token which is not cancelled, with the default granularity of 100 ms.
+impl Default for CancelToken {
+    fn default() -> Self {
+        Self {
+            cancelled: Arc::default(),
+            granularity: WAIT_CHUNK,
+        }
+    }
+}
+
+impl CancelToken {
+    /// Create a token which is not cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set how often the waits given this token check whether it was
+    /// cancelled, 100 ms by default, bounding the time they take to return.
+    ///
+    /// Waits are split in native waits of at most this duration, so a
+    /// smaller granularity returns sooner at the cost of more wake-ups. It's
+    /// rounded up to whole milliseconds, and at least one. The token keeps
+    /// sharing its cancellation with the clones it was cloned from.
+    pub fn with_granularity(mut self, granularity: Duration) -> Self {
+        self.granularity = granularity.max(Duration::from_millis(1));
+        self
+    }
+
+    /// The granularity of the token, set with [`CancelToken::with_granularity`].
+    pub fn granularity(&self) -> Duration {
+        self.granularity
+    }
+
+    /// Cancel the token and all of its clones.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Release);
+    }
+
+    /// Returns whether the token has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Acquire)
+    }
+}
+
 /// Marker struct for Input ownership records
 #[derive(Debug)]
 struct InputRecord;
@@ -336,25 +1133,75 @@ unsafe impl Send for OutputRecord {}
 /// Trait for handling entity operations (validation, creation, and record management)
 trait EntityHandler<T, R> {
     /// Validate that the given name corresponds to a valid entity
-    fn validate_name(&self, name: &str) -> ConnectorFallible;
+    fn validate_name(&self, name: &CStr) -> ConnectorFallible;
 
-    /// Create a new entity with the given name
-    fn create_entity(&self, name: &str) -> T;
+    /// Create a new entity with the given name, also converted for the native library
+    ///
+    /// The name is shared between the entity and its ownership record.
+    fn create_entity(&self, name: Arc<str>, c_name: CString) -> T;
 
     /// Create a record for tracking entity ownership
     fn create_record() -> R;
 }
 
 /// Thread-safe holder for entities with blocking acquisition behavior
+///
+/// Threads blocked acquiring the same entity are served in FIFO order: when
+/// the entity is released, only the thread which has waited the longest may
+/// acquire it, and non-blocking acquisitions fail while any thread is waiting.
 #[derive(Debug)]
 struct ThreadSafeEntityHolder<R> {
-    /// Map of entity names to their ownership records
-    entities: Mutex<HashMap<String, R>>,
+    /// Ownership records and queues of waiting threads
+    entities: Mutex<EntityTable<R>>,
 
     /// Condition variable for managing blocking behavior
     queue: Condvar,
 }
 
+/// The state of a [`ThreadSafeEntityHolder`], protected by its mutex
+#[derive(Debug)]
+struct EntityTable<R> {
+    /// Map of entity names, shared with the entities, to their ownership records
+    owned: HashMap<Arc<str>, R>,
+
+    /// Map of entity names to the tickets of the threads waiting for them, in
+    /// arrival order
+    waiting: HashMap<Arc<str>, VecDeque<u64>>,
+
+    /// The ticket given to the next thread which starts waiting
+    next_ticket: u64,
+}
+
+impl<R> EntityTable<R> {
+    /// Whether the entity is free and can be acquired by the holder of
+    /// `ticket`, or by a thread which isn't waiting if `ticket` is `None`.
+    fn is_available(&self, name: &str, ticket: Option<u64>) -> bool {
+        !self.owned.contains_key(name)
+            && self.waiting.get(name).and_then(VecDeque::front).copied() == ticket
+    }
+
+    /// Queue a thread waiting for the entity, returning its ticket.
+    fn enqueue(&mut self, name: &Arc<str>) -> u64 {
+        let ticket = self.next_ticket;
+        self.next_ticket += 1;
+        self.waiting
+            .entry(Arc::clone(name))
+            .or_default()
+            .push_back(ticket);
+        ticket
+    }
+
+    /// Remove a thread from the queue of the entity.
+    fn dequeue(&mut self, name: &str, ticket: u64) {
+        if let Some(queue) = self.waiting.get_mut(name) {
+            queue.retain(|queued| *queued != ticket);
+            if queue.is_empty() {
+                self.waiting.remove(name);
+            }
+        }
+    }
+}
+
 /// Blocking behavior configuration for entity acquisition
 #[derive(Debug, Clone)]
 enum BlockingBehavior {
@@ -363,28 +1210,47 @@ enum BlockingBehavior {
 
     /// Block indefinitely until entity becomes available
     BlockForever,
+
+    /// Block until entity becomes available, or fail after the timeout
+    BlockWithTimeout(Duration),
 }
 
 impl<R> ThreadSafeEntityHolder<R> {
     /// Create a new ThreadSafeEntityHolder
     fn new() -> Self {
         ThreadSafeEntityHolder {
-            entities: Mutex::new(HashMap::new()),
+            entities: Mutex::new(EntityTable {
+                owned: HashMap::new(),
+                waiting: HashMap::new(),
+                next_ticket: 0,
+            }),
             queue: Condvar::new(),
         }
     }
 
+    /// Lock the map of entities, even if another thread panicked while holding it.
+    ///
+    /// The map is only modified by single inserts and removals, so it is
+    /// consistent even then, and records of entities owned by a thread which
+    /// panicked are removed when the entities are dropped during unwinding.
+    fn lock_entities(&self) -> std::sync::MutexGuard<'_, EntityTable<R>> {
+        self.entities
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
     /// Helper function to create and register
     fn get_entity_from_guard<T, H>(
         &self,
-        name: &str,
-        entities: &mut HashMap<String, R>,
+        name: Arc<str>,
+        c_name: CString,
+        entities: &mut HashMap<Arc<str>, R>,
         handler: &H,
     ) -> ConnectorResult<T>
     where
         H: EntityHandler<T, R>,
     {
-        if entities.contains_key(name) {
+        if entities.contains_key(&name) {
             ErrorKind::entity_busy_error(format!(
                 "{} named '{}' already in use",
                 std::any::type_name::<T>(),
@@ -392,23 +1258,22 @@ impl<R> ThreadSafeEntityHolder<R> {
             ))
             .into_err()
         } else {
-            let entity = handler.create_entity(name);
             let record = H::create_record();
-            entities.insert(name.to_string(), record);
+            entities.insert(Arc::clone(&name), record);
+            let entity = handler.create_entity(name, c_name);
 
             Ok(entity)
         }
     }
 
     /// Release an entity, making it available to other threads
+    ///
+    /// All waiting threads are woken up, as only the first one in the queue of
+    /// the entity may acquire it.
     fn release_entity(&self, name: &str) -> ConnectorFallible {
-        let mut entities = self.entities.lock().map_err(|_| {
-            ErrorKind::lock_poisoned_error(
-                "Another thread panicked while holding the entities lock",
-            )
-        })?;
+        let mut entities = self.lock_entities();
 
-        match entities.remove(name) {
+        match entities.owned.remove(name) {
             None => ErrorKind::entity_busy_error(format!(
                 "{} named '{}' not found or already released",
                 std::any::type_name::<R>(),
@@ -432,40 +1297,195 @@ impl<R> ThreadSafeEntityHolder<R> {
     where
         H: EntityHandler<T, R>,
     {
-        let mut entities = self.entities.lock().map_err(|_| {
-            ErrorKind::lock_poisoned_error(
-                "Another thread panicked while holding the entities lock",
-            )
-        })?;
+        let mut entities = self.lock_entities();
 
-        // Validate the name first
-        handler.validate_name(name)?;
+        // Validate the name first, converting it once for the native library
+        let c_name = crate::ffi::entity_c_string(name)?;
+        handler.validate_name(&c_name)?;
+        let name: Arc<str> = Arc::from(name);
 
-        loop {
-            // Try to acquire the entity
-            if !entities.contains_key(name) {
-                return self.get_entity_from_guard(name, &mut entities, handler);
+        // Try to acquire the entity, unless it's taken or other threads wait for it
+        if entities.is_available(&name, None) {
+            return self.get_entity_from_guard(
+                name,
+                c_name,
+                &mut entities.owned,
+                handler,
+            );
+        }
+
+        // Entity is not available, decide what to do based on blocking behavior
+        let deadline = match &behavior {
+            BlockingBehavior::NonBlocking => {
+                return ErrorKind::entity_busy_error(format!(
+                    "{} '{}' already in use",
+                    std::any::type_name::<T>(),
+                    name,
+                ))
+                .into_err();
             }
+            BlockingBehavior::BlockForever => None,
+            BlockingBehavior::BlockWithTimeout(timeout) => wait_deadline(Some(*timeout)),
+        };
 
-            // Entity is already taken, decide what to do based on blocking behavior
-            match &behavior {
-                BlockingBehavior::NonBlocking => {
-                    return ErrorKind::entity_busy_error(format!(
-                        "{} '{}' already in use",
-                        std::any::type_name::<T>(),
-                        name,
-                    ))
-                    .into_err();
+        let ticket = entities.enqueue(&name);
+        loop {
+            entities = match deadline {
+                None => self
+                    .queue
+                    .wait(entities)
+                    .unwrap_or_else(|poisoned| poisoned.into_inner()),
+                Some(deadline) => {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    self.queue
+                        .wait_timeout(entities, remaining)
+                        .unwrap_or_else(|poisoned| poisoned.into_inner())
+                        .0
                 }
+            };
 
-                BlockingBehavior::BlockForever => {
-                    entities = self.queue.wait(entities).map_err(|_| {
-                        ErrorKind::lock_poisoned_error(
-                            "Another thread panicked while holding the entities lock",
-                        )
-                    })?;
-                }
+            if entities.is_available(&name, Some(ticket)) {
+                entities.dequeue(&name, ticket);
+                return self.get_entity_from_guard(
+                    name,
+                    c_name,
+                    &mut entities.owned,
+                    handler,
+                );
+            }
+
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                entities.dequeue(&name, ticket);
+                // The next thread in the queue may be able to acquire it now
+                self.queue.notify_all();
+                return ErrorKind::timeout_error().into_err();
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{CancelToken, native_timeout_ms, wait_deadline, wait_in_chunks};
+    use crate::{ConnectorResult, result::ErrorKind};
+    use std::{
+        sync::atomic::AtomicU64,
+        time::{Duration, Instant},
+    };
+
+    /// A native wait in which nothing happens until the timeout.
+    fn time_out(timeout_ms: i32) -> ConnectorResult<()> {
+        std::thread::sleep(Duration::from_millis(timeout_ms as u64));
+        ErrorKind::timeout_error().into_err()
+    }
+
+    /// Wait on `token` like [`ConnectorInner::cancellable_wait`](super::ConnectorInner::cancellable_wait).
+    fn cancellable_wait<T>(
+        timeout: Option<Duration>,
+        token: &CancelToken,
+        wait: impl FnMut(i32) -> ConnectorResult<T>,
+    ) -> ConnectorResult<T> {
+        wait_in_chunks(
+            &AtomicU64::new(0),
+            timeout,
+            token.granularity(),
+            || token.is_cancelled().then(ErrorKind::cancelled_error),
+            wait,
+        )
+    }
+
+    #[test]
+    fn test_native_timeout_ms() {
+        assert_eq!(0, native_timeout_ms(Duration::ZERO));
+        assert_eq!(1, native_timeout_ms(Duration::from_nanos(1)));
+        assert_eq!(1, native_timeout_ms(Duration::from_millis(1)));
+        assert_eq!(2, native_timeout_ms(Duration::from_micros(1500)));
+        assert_eq!(
+            i32::MAX,
+            native_timeout_ms(Duration::from_millis(i32::MAX as u64))
+        );
+        assert_eq!(
+            i32::MAX,
+            native_timeout_ms(Duration::from_millis(i32::MAX as u64 + 1))
+        );
+        assert_eq!(i32::MAX, native_timeout_ms(Duration::MAX));
+    }
+
+    #[test]
+    fn test_wait_deadline() {
+        assert_eq!(None, wait_deadline(None));
+        assert_eq!(None, wait_deadline(Some(Duration::MAX)));
+        assert!(wait_deadline(Some(Duration::from_secs(1))).is_some());
+        assert!(wait_deadline(Some(Duration::from_secs(60 * 86_400))).is_some());
+    }
+
+    #[test]
+    fn test_cancellation_latency() {
+        for granularity in [Duration::from_millis(10), Duration::from_millis(50)] {
+            let token = CancelToken::new().with_granularity(granularity);
+
+            let (result, latency) = std::thread::scope(|scope| {
+                let canceller = scope.spawn(|| {
+                    std::thread::sleep(Duration::from_millis(100));
+                    token.cancel();
+                    Instant::now()
+                });
+                let result = cancellable_wait(None, &token, time_out);
+                let returned_at = Instant::now();
+                let latency = canceller.join().ok().map(|cancelled_at| {
+                    returned_at.saturating_duration_since(cancelled_at)
+                });
+                (result, latency)
+            });
+
+            assert!(result.is_err_and(|e| e.is_cancelled()));
+            assert!(
+                latency.is_some_and(
+                    |latency| latency < granularity + Duration::from_millis(50)
+                ),
+                "Cancellation took too long with a granularity of {:?}: {:?}",
+                granularity,
+                latency
+            );
+
+            // A cancelled token cancels later waits without waiting
+            let mut waits = 0;
+            let result = cancellable_wait(None, &token, |_| {
+                waits += 1;
+                Ok(())
+            });
+            assert!(result.is_err_and(|e| e.is_cancelled()));
+            assert_eq!(0, waits);
+        }
+    }
+
+    #[test]
+    fn test_no_spurious_cancellation() {
+        let token = CancelToken::new().with_granularity(Duration::from_millis(10));
+
+        // The wait times out after the whole timeout, not after a chunk
+        let start = Instant::now();
+        let result = cancellable_wait(Some(Duration::from_millis(200)), &token, time_out);
+        assert!(result.is_err_and(|e| e.is_timeout()));
+        assert!(start.elapsed() >= Duration::from_millis(200));
+
+        // Every chunk is bounded by the granularity, and data ends the wait
+        let mut timeouts = Vec::new();
+        let result = cancellable_wait(None, &token, |timeout_ms| {
+            timeouts.push(timeout_ms);
+            if timeouts.len() < 5 {
+                time_out(timeout_ms)
+            } else {
+                Ok(())
+            }
+        });
+        assert!(result.is_ok());
+        assert_eq!(vec![10; 5], timeouts);
+
+        // Cancelling a clone of the token cancels it, whatever its granularity
+        let clone = token.clone().with_granularity(Duration::ZERO);
+        assert_eq!(Duration::from_millis(1), clone.granularity());
+        clone.cancel();
+        assert!(token.is_cancelled());
+    }
+}
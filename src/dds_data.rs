@@ -0,0 +1,313 @@
+/*******************************************************************************
+ * (c) 2025 Copyright, Real-Time Innovations.  All rights reserved.            *
+ * No duplications, whole or partial, manual or electronic, may be made        *
+ * without express written permission.  Any such copies, or revisions thereof, *
+ * must display this notice unaltered.                                         *
+ * This code contains trade secrets of Real-Time Innovations, Inc.             *
+ *******************************************************************************/
+
+//! Field-by-field mapping of Rust types to DDS data, without going through
+//! JSON or Serde.
+//!
+//! Each field is set and retrieved with the typed setters and getters of
+//! [`Instance`] and [`Sample`], addressing it with the field-name syntax of
+//! RTI Connector, the same one used by [`Instance::serialize_direct`].
+
+use crate::{
+    ConnectorError, ConnectorFallible, ConnectorResult, Instance, Sample,
+    json_path::FieldNameBuf,
+    result::{ErrorKind, InvalidErrorKind},
+};
+
+/// A type which can be set into an [`Instance`] and retrieved from a
+/// [`Sample`] field by field, as a whole DDS sample.
+///
+/// Usually derived with `#[derive(DdsData)]`, enabling the `derive` feature,
+/// for structs whose members match those of the DDS type:
+///
+/// ```rust
+/// # #[cfg(feature = "derive")]
+/// # mod shape {
+/// use rtiddsconnector::{DdsData, Input, Output};
+///
+/// #[derive(DdsData)]
+/// struct Shape {
+///     color: String,
+///     x: i32,
+///     y: i32,
+///     shapesize: i32,
+/// }
+///
+/// fn write_shape(output: &mut Output, shape: &Shape) -> rtiddsconnector::ConnectorFallible {
+///     shape.set_into(&mut output.instance())?;
+///     output.write()
+/// }
+///
+/// fn read_shapes(input: &Input) -> rtiddsconnector::ConnectorResult<Vec<Shape>> {
+///     input
+///         .valid_samples()?
+///         .map(|sample| Shape::get_from(&sample?))
+///         .collect()
+/// }
+/// # }
+/// ```
+///
+/// The derive also implements [`DdsField`], so that the struct can be nested
+/// in other types. Members can be renamed with `#[dds(rename = "name")]`.
+/// Enums without data map to DDS enumerations by their discriminant, and
+/// enums whose variants hold a single value map to DDS unions, whose members
+/// are named like the variants unless renamed.
+///
+/// Unlike [`Instance::serialize`], 64-bit integers keep their precision, and
+/// no JSON is built or parsed.
+pub trait DdsData: DdsField {
+    /// Set every field of the data into the instance.
+    ///
+    /// Sequences are replaced, and optional members which are `None` are
+    /// cleared, so the instance holds exactly this data afterwards.
+    fn set_into(&self, instance: &mut Instance) -> ConnectorFallible {
+        self.set_field(instance, &FieldPath::default())
+    }
+
+    /// Retrieve every field of the data from the sample.
+    fn get_from(sample: &Sample) -> ConnectorResult<Self> {
+        Self::get_field(sample, &FieldPath::default())
+    }
+}
+
+/// A type which can be set into, and retrieved from, a field of a DDS sample.
+///
+/// Implemented for booleans, numbers, strings, [`Option`]s of optional
+/// members, [`Vec`]s of sequences and arrays of arrays, as well as for the
+/// types deriving [`DdsData`].
+pub trait DdsField: Sized {
+    /// Set the value into the field of the instance at `path`.
+    fn set_field(&self, instance: &mut Instance, path: &FieldPath) -> ConnectorFallible;
+
+    /// Retrieve the value from the field of the sample at `path`.
+    fn get_field(sample: &Sample, path: &FieldPath) -> ConnectorResult<Self>;
+}
+
+/// The path of a field of a DDS sample, such as `simple.long_field` or
+/// `long_matrix[1,2]`.
+///
+/// The default path is the whole sample, whose members are addressed by name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FieldPath {
+    /// The field name, in the field-name syntax of RTI Connector.
+    path: String,
+
+    /// Whether the path ends with the index of an array element, so that the
+    /// elements of a nested array are indexed as another dimension.
+    in_array: bool,
+}
+
+impl FieldPath {
+    /// The path of a member of this field.
+    pub fn member(&self, name: &str) -> Self {
+        let mut path = self.path.clone();
+        path.push_member(name);
+
+        FieldPath {
+            path,
+            in_array: false,
+        }
+    }
+
+    /// The path of an element of this sequence field, `field[index]`.
+    pub fn element(&self, index: usize) -> Self {
+        let mut path = self.path.clone();
+        path.push_index(index, false);
+
+        FieldPath {
+            path,
+            in_array: false,
+        }
+    }
+
+    /// The path of an element of this array field, `field[index]`, or
+    /// `field[i,index]` if this field is itself an element of an array.
+    pub fn array_element(&self, index: usize) -> Self {
+        let mut path = self.path.clone();
+        path.push_index(index, self.in_array && self.path.ends_with(']'));
+
+        FieldPath {
+            path,
+            in_array: true,
+        }
+    }
+
+    /// The field name, to use with the setters of [`Instance`] and the
+    /// getters of [`Sample`].
+    pub fn as_str(&self) -> &str {
+        &self.path
+    }
+
+    /// Retrieve the name of the selected member of this union field.
+    pub fn selected_member(&self, sample: &Sample) -> ConnectorResult<String> {
+        sample.get_string(&std::format!("{}#", self.path))
+    }
+
+    /// An error for this field holding a value which the type retrieving it
+    /// can't represent, such as an unknown enumerator.
+    pub fn invalid_value(&self, value: impl std::fmt::Display) -> ConnectorError {
+        ErrorKind::Invalid {
+            what: InvalidErrorKind::Deserialization,
+            context: std::format!(
+                "Field '{}' has an invalid value: {}",
+                self.path,
+                value
+            ),
+        }
+        .into()
+    }
+}
+
+/// Display the [`FieldPath`] as its field name.
+impl std::fmt::Display for FieldPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.path)
+    }
+}
+
+impl DdsField for bool {
+    fn set_field(&self, instance: &mut Instance, path: &FieldPath) -> ConnectorFallible {
+        instance.set_boolean(path.as_str(), *self)
+    }
+
+    fn get_field(sample: &Sample, path: &FieldPath) -> ConnectorResult<Self> {
+        sample.get_boolean(path.as_str())
+    }
+}
+
+impl DdsField for String {
+    fn set_field(&self, instance: &mut Instance, path: &FieldPath) -> ConnectorFallible {
+        instance.set_string(path.as_str(), self)
+    }
+
+    fn get_field(sample: &Sample, path: &FieldPath) -> ConnectorResult<Self> {
+        sample.get_string(path.as_str())
+    }
+}
+
+impl DdsField for f64 {
+    fn set_field(&self, instance: &mut Instance, path: &FieldPath) -> ConnectorFallible {
+        instance.set_number(path.as_str(), *self)
+    }
+
+    fn get_field(sample: &Sample, path: &FieldPath) -> ConnectorResult<Self> {
+        sample.get_number(path.as_str())
+    }
+}
+
+impl DdsField for f32 {
+    fn set_field(&self, instance: &mut Instance, path: &FieldPath) -> ConnectorFallible {
+        instance.set_number(path.as_str(), f64::from(*self))
+    }
+
+    fn get_field(sample: &Sample, path: &FieldPath) -> ConnectorResult<Self> {
+        sample.get_number(path.as_str()).map(|value| value as f32)
+    }
+}
+
+/// Integers up to 32 bits are retrieved as numbers, checking their range.
+macro_rules! impl_dds_field_for_integer {
+    ($($integer:ty),*) => {
+        $(
+            impl DdsField for $integer {
+                fn set_field(
+                    &self,
+                    instance: &mut Instance,
+                    path: &FieldPath,
+                ) -> ConnectorFallible {
+                    instance.set_integer(path.as_str(), *self)
+                }
+
+                fn get_field(sample: &Sample, path: &FieldPath) -> ConnectorResult<Self> {
+                    sample.get_checked_integer(path.as_str())
+                }
+            }
+        )*
+    };
+}
+
+impl_dds_field_for_integer!(i8, u8, i16, u16, i32, u32);
+
+impl DdsField for i64 {
+    fn set_field(&self, instance: &mut Instance, path: &FieldPath) -> ConnectorFallible {
+        instance.set_i64(path.as_str(), *self)
+    }
+
+    fn get_field(sample: &Sample, path: &FieldPath) -> ConnectorResult<Self> {
+        sample.get_i64(path.as_str())
+    }
+}
+
+impl DdsField for u64 {
+    fn set_field(&self, instance: &mut Instance, path: &FieldPath) -> ConnectorFallible {
+        instance.set_u64(path.as_str(), *self)
+    }
+
+    fn get_field(sample: &Sample, path: &FieldPath) -> ConnectorResult<Self> {
+        sample.get_u64(path.as_str())
+    }
+}
+
+/// Optional members: `None` clears the member, and unset members are `None`.
+impl<T: DdsField> DdsField for Option<T> {
+    fn set_field(&self, instance: &mut Instance, path: &FieldPath) -> ConnectorFallible {
+        match self {
+            Some(value) => value.set_field(instance, path),
+            None => instance.clear(path.as_str()),
+        }
+    }
+
+    fn get_field(sample: &Sample, path: &FieldPath) -> ConnectorResult<Self> {
+        match T::get_field(sample, path) {
+            Ok(value) => Ok(Some(value)),
+            Err(e) if e.is_unset_optional() => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// Sequences are cleared first, so that they don't keep any trailing elements
+/// from a previous value.
+impl<T: DdsField> DdsField for Vec<T> {
+    fn set_field(&self, instance: &mut Instance, path: &FieldPath) -> ConnectorFallible {
+        instance.clear(path.as_str())?;
+
+        self.iter().enumerate().try_for_each(|(index, element)| {
+            element.set_field(instance, &path.element(index))
+        })
+    }
+
+    fn get_field(sample: &Sample, path: &FieldPath) -> ConnectorResult<Self> {
+        (0..sample.get_length(path.as_str())?)
+            .map(|index| T::get_field(sample, &path.element(index)))
+            .collect()
+    }
+}
+
+/// Nested arrays are indexed as multi-dimensional arrays, `[i,j]`.
+impl<T: DdsField, const N: usize> DdsField for [T; N] {
+    fn set_field(&self, instance: &mut Instance, path: &FieldPath) -> ConnectorFallible {
+        self.iter().enumerate().try_for_each(|(index, element)| {
+            element.set_field(instance, &path.array_element(index))
+        })
+    }
+
+    fn get_field(sample: &Sample, path: &FieldPath) -> ConnectorResult<Self> {
+        let elements = (0..N)
+            .map(|index| T::get_field(sample, &path.array_element(index)))
+            .collect::<ConnectorResult<Vec<T>>>()?;
+
+        elements.try_into().map_err(|elements: Vec<T>| {
+            path.invalid_value(std::format!(
+                "expected {} elements, found {}",
+                N,
+                elements.len()
+            ))
+        })
+    }
+}
@@ -0,0 +1,154 @@
+/*******************************************************************************
+ * (c) 2025 Copyright, Real-Time Innovations.  All rights reserved.            *
+ * No duplications, whole or partial, manual or electronic, may be made        *
+ * without express written permission.  Any such copies, or revisions thereof, *
+ * must display this notice unaltered.                                         *
+ * This code contains trade secrets of Real-Time Innovations, Inc.             *
+ *******************************************************************************/
+
+//! Serialization of DDS enumerations by the name of their enumerators.
+//!
+//! The JSON representation of a DDS enumeration holds the value of its
+//! enumerator, such as `1`, but RTI Connector also accepts its name, such as
+//! `"Green"`, which is what other applications, such as those using the
+//! Connector for Python, may send and expect. Implementing [`DdsEnum`] for a
+//! Rust enum allows serializing it either way, with the `with` modules of this
+//! module, while deserializing it from both:
+//!
+//! ```rust
+//! use rtiddsconnector::DdsEnum;
+//!
+//! #[derive(Debug, Clone, Copy, PartialEq)]
+//! enum Color {
+//!     Red,
+//!     Green,
+//! }
+//!
+//! impl DdsEnum for Color {
+//!     fn name(&self) -> &'static str {
+//!         match self {
+//!             Color::Red => "Red",
+//!             Color::Green => "Green",
+//!         }
+//!     }
+//!
+//!     fn value(&self) -> i64 {
+//!         *self as i64
+//!     }
+//!
+//!     fn from_name(name: &str) -> Option<Self> {
+//!         [Color::Red, Color::Green].into_iter().find(|color| color.name() == name)
+//!     }
+//!
+//!     fn from_value(value: i64) -> Option<Self> {
+//!         [Color::Red, Color::Green].into_iter().find(|color| color.value() == value)
+//!     }
+//! }
+//!
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct Shape {
+//!     #[serde(with = "rtiddsconnector::dds_enum::by_name")]
+//!     color: Color,
+//! }
+//! ```
+//!
+//! With the `derive` feature, `#[derive(DdsEnum)]` implements the trait for
+//! enums without data, naming the enumerators like the variants unless
+//! renamed with `#[dds(rename = "name")]`.
+
+use serde::de::{self, Unexpected, Visitor};
+
+/// A Rust enum which models a DDS enumeration, whose enumerators have a name
+/// and an integer value.
+pub trait DdsEnum: Sized {
+    /// The name of the enumerator, as in the DDS type.
+    fn name(&self) -> &'static str;
+
+    /// The value of the enumerator, as in the DDS type.
+    fn value(&self) -> i64;
+
+    /// The enumerator with this name, if any.
+    fn from_name(name: &str) -> Option<Self>;
+
+    /// The enumerator with this value, if any.
+    fn from_value(value: i64) -> Option<Self>;
+}
+
+/// Serialize a [`DdsEnum`] as the name of its enumerator, and deserialize it
+/// from either its name or its value, with `#[serde(with = "rtiddsconnector::dds_enum::by_name")]`.
+pub mod by_name {
+    use super::{DdsEnum, EnumVisitor};
+
+    /// Serialize the enum as the name of its enumerator.
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: DdsEnum,
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(value.name())
+    }
+
+    /// Deserialize the enum from either the name or the value of its enumerator.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: DdsEnum,
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(EnumVisitor(std::marker::PhantomData))
+    }
+}
+
+/// Serialize a [`DdsEnum`] as the value of its enumerator, and deserialize it
+/// from either its name or its value, with `#[serde(with = "rtiddsconnector::dds_enum::by_value")]`.
+pub mod by_value {
+    use super::{DdsEnum, EnumVisitor};
+
+    /// Serialize the enum as the value of its enumerator.
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: DdsEnum,
+        S: serde::Serializer,
+    {
+        serializer.serialize_i64(value.value())
+    }
+
+    /// Deserialize the enum from either the name or the value of its enumerator.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: DdsEnum,
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(EnumVisitor(std::marker::PhantomData))
+    }
+}
+
+/// Visit the name or the value of an enumerator.
+struct EnumVisitor<T>(std::marker::PhantomData<T>);
+
+impl<T: DdsEnum> Visitor<'_> for EnumVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            formatter,
+            "the name or value of an enumerator of {}",
+            std::any::type_name::<T>()
+        )
+    }
+
+    fn visit_str<E: de::Error>(self, name: &str) -> Result<T, E> {
+        T::from_name(name).ok_or_else(|| E::invalid_value(Unexpected::Str(name), &self))
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<T, E> {
+        T::from_value(value)
+            .ok_or_else(|| E::invalid_value(Unexpected::Signed(value), &self))
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<T, E> {
+        i64::try_from(value)
+            .ok()
+            .and_then(T::from_value)
+            .ok_or_else(|| E::invalid_value(Unexpected::Unsigned(value), &self))
+    }
+}
@@ -0,0 +1,207 @@
+/*******************************************************************************
+ * (c) 2025 Copyright, Real-Time Innovations.  All rights reserved.            *
+ * No duplications, whole or partial, manual or electronic, may be made        *
+ * without express written permission.  Any such copies, or revisions thereof, *
+ * must display this notice unaltered.                                         *
+ * This code contains trade secrets of Real-Time Innovations, Inc.             *
+ *******************************************************************************/
+
+//! Conversions between DDS `Time_t`-style structs and [`SystemTime`].
+
+use crate::{
+    ConnectorError,
+    result::{ErrorKind, InvalidErrorKind},
+};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The number of nanoseconds in a second.
+const NANOS_PER_SEC: u32 = 1_000_000_000;
+
+/// A point in time modeled as a DDS `Time_t`-style struct, with the seconds
+/// and nanoseconds since the epoch as `sec` and `nanosec` members.
+///
+/// Its Serde representation matches the JSON of such a struct, so it can be
+/// used as a field of the types passed to [`Instance::serialize`](crate::Instance::serialize)
+/// and [`Sample::deserialize`](crate::Sample::deserialize). Times before the
+/// epoch have negative seconds, while the nanoseconds are always positive:
+/// half a second before the epoch is `{ sec: -1, nanosec: 500_000_000 }`.
+///
+/// ```rust
+/// use rtiddsconnector::DdsTime;
+/// use std::time::{Duration, SystemTime, UNIX_EPOCH};
+///
+/// let time = UNIX_EPOCH + Duration::from_millis(1500);
+/// let dds_time = DdsTime::try_from(time)?;
+/// assert_eq!(dds_time, DdsTime { sec: 1, nanosec: 500_000_000 });
+/// assert_eq!(SystemTime::try_from(dds_time)?, time);
+/// # Ok::<(), rtiddsconnector::ConnectorError>(())
+/// ```
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct DdsTime {
+    /// The whole seconds since the epoch.
+    pub sec: i32,
+
+    /// The nanoseconds within the second, below one billion.
+    pub nanosec: u32,
+}
+
+/// Convert a [`SystemTime`] into a [`DdsTime`], which fails if its seconds
+/// don't fit in an `i32`.
+impl TryFrom<SystemTime> for DdsTime {
+    type Error = ConnectorError;
+
+    fn try_from(time: SystemTime) -> Result<Self, Self::Error> {
+        let (sec, nanosec) = match time.duration_since(UNIX_EPOCH) {
+            Ok(offset) => (i64::try_from(offset.as_secs()).ok(), offset.subsec_nanos()),
+            Err(e) => {
+                // Round down to the previous second, keeping the nanoseconds positive
+                let offset = e.duration();
+                let sec = i64::try_from(offset.as_secs())
+                    .ok()
+                    .and_then(i64::checked_neg);
+                match offset.subsec_nanos() {
+                    0 => (sec, 0),
+                    nanos => (
+                        sec.and_then(|sec| sec.checked_sub(1)),
+                        NANOS_PER_SEC - nanos,
+                    ),
+                }
+            }
+        };
+
+        match sec.and_then(|sec| i32::try_from(sec).ok()) {
+            Some(sec) => Ok(DdsTime { sec, nanosec }),
+            None => ErrorKind::Invalid {
+                what: InvalidErrorKind::Conversion,
+                context: std::format!("Time out of range for a DDS time: {:?}", time),
+            }
+            .into_err(),
+        }
+    }
+}
+
+/// Convert a [`DdsTime`] into a [`SystemTime`], which fails if its
+/// nanoseconds aren't below one billion.
+impl TryFrom<DdsTime> for SystemTime {
+    type Error = ConnectorError;
+
+    fn try_from(time: DdsTime) -> Result<Self, Self::Error> {
+        let invalid = || {
+            ErrorKind::Invalid {
+                what: InvalidErrorKind::Conversion,
+                context: std::format!(
+                    "Invalid DDS time: {} s, {} ns",
+                    time.sec,
+                    time.nanosec
+                ),
+            }
+            .into()
+        };
+
+        if time.nanosec >= NANOS_PER_SEC {
+            return Err(invalid());
+        }
+
+        let seconds = Duration::from_secs(u64::from(time.sec.unsigned_abs()));
+        let nanos = Duration::from_nanos(u64::from(time.nanosec));
+        let system_time = if time.sec >= 0 {
+            UNIX_EPOCH.checked_add(seconds)
+        } else {
+            UNIX_EPOCH.checked_sub(seconds)
+        };
+
+        system_time
+            .and_then(|system_time| system_time.checked_add(nanos))
+            .ok_or_else(invalid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DdsTime;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn test_dds_time_round_trip() {
+        for (time, expected) in [
+            (UNIX_EPOCH, DdsTime { sec: 0, nanosec: 0 }),
+            (
+                UNIX_EPOCH + Duration::from_nanos(1),
+                DdsTime { sec: 0, nanosec: 1 },
+            ),
+            (
+                UNIX_EPOCH + Duration::new(1_700_000_000, 123_456_789),
+                DdsTime {
+                    sec: 1_700_000_000,
+                    nanosec: 123_456_789,
+                },
+            ),
+            (
+                UNIX_EPOCH - Duration::from_nanos(1),
+                DdsTime {
+                    sec: -1,
+                    nanosec: 999_999_999,
+                },
+            ),
+            (
+                UNIX_EPOCH - Duration::from_secs(2),
+                DdsTime {
+                    sec: -2,
+                    nanosec: 0,
+                },
+            ),
+            (
+                UNIX_EPOCH + Duration::new(i32::MAX as u64, 999_999_999),
+                DdsTime {
+                    sec: i32::MAX,
+                    nanosec: 999_999_999,
+                },
+            ),
+        ] {
+            assert!(
+                matches!(DdsTime::try_from(time), Ok(dds_time) if dds_time == expected),
+                "Expected {:?} to be converted into {:?}",
+                time,
+                expected
+            );
+            assert!(
+                matches!(SystemTime::try_from(expected), Ok(back) if back == time),
+                "Expected {:?} to be converted back into {:?}",
+                expected,
+                time
+            );
+        }
+    }
+
+    #[test]
+    fn test_dds_time_out_of_range() {
+        for time in [
+            UNIX_EPOCH + Duration::from_secs(i32::MAX as u64 + 1),
+            UNIX_EPOCH - Duration::new(i32::MAX as u64 + 1, 1),
+        ] {
+            assert!(
+                DdsTime::try_from(time).is_err(),
+                "Expected {:?} to be out of range",
+                time
+            );
+        }
+
+        let invalid = DdsTime {
+            sec: 0,
+            nanosec: 1_000_000_000,
+        };
+        assert!(SystemTime::try_from(invalid).is_err());
+    }
+}
@@ -0,0 +1,292 @@
+/*******************************************************************************
+ * (c) 2025 Copyright, Real-Time Innovations.  All rights reserved.            *
+ * No duplications, whole or partial, manual or electronic, may be made        *
+ * without express written permission.  Any such copies, or revisions thereof, *
+ * must display this notice unaltered.                                         *
+ * This code contains trade secrets of Real-Time Innovations, Inc.             *
+ *******************************************************************************/
+
+//! Serialization of DDS unions as Rust enums.
+//!
+//! The JSON representation of a DDS union is an object holding its active
+//! member only, such as `{ "number": 2.5 }`. Implementing [`DdsUnion`] for a
+//! Rust enum with a variant for each member allows serializing it that way,
+//! and deserializing it from whichever member is present. The
+//! [`dds_union!`](crate::dds_union!) macro implements it, along with
+//! `Serialize` and `Deserialize`, naming the member of each variant:
+//!
+//! ```rust
+//! #[derive(Debug, Clone, PartialEq)]
+//! enum Value {
+//!     Text(String),
+//!     Number(f64),
+//! }
+//!
+//! rtiddsconnector::dds_union!(Value {
+//!     "text" => Text(String),
+//!     "number" => Number(f64),
+//! });
+//!
+//! let value: Value = serde_json::from_str(r#"{ "number": 2.5 }"#)?;
+//! assert_eq!(value, Value::Number(2.5));
+//! assert_eq!(serde_json::to_string(&Value::Text("Hi".into()))?, r#"{"text":"Hi"}"#);
+//! # Ok::<(), serde_json::Error>(())
+//! ```
+//!
+//! For enums which implement [`DdsUnion`] but not Serde's traits, the
+//! [`serialize`] and [`deserialize`] functions of this module can be used with
+//! `#[serde(with = "rtiddsconnector::dds_union")]`. To use the discriminator of
+//! a union in a sample rather than probing its members, use
+//! [`Sample::deserialize_union`](crate::Sample::deserialize_union).
+
+use serde::de::{self, DeserializeSeed, MapAccess, Visitor};
+
+/// A Rust enum which models a DDS union, with a variant for each member.
+pub trait DdsUnion: Sized {
+    /// The names of the members, as in the DDS type.
+    const MEMBERS: &'static [&'static str];
+
+    /// The name of the active member.
+    fn member_name(&self) -> &'static str;
+
+    /// Serialize the value of the active member.
+    fn serialize_member<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer;
+
+    /// Deserialize the value of the member with this name.
+    ///
+    /// An error is returned if the union doesn't have such a member.
+    fn deserialize_member<'de, D>(
+        member: &str,
+        deserializer: D,
+    ) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>;
+}
+
+/// Serialize a [`DdsUnion`] as an object holding its active member only.
+pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: DdsUnion,
+    S: serde::Serializer,
+{
+    use serde::ser::SerializeMap;
+
+    let mut map = serializer.serialize_map(Some(1))?;
+    map.serialize_entry(value.member_name(), &ActiveMember(value))?;
+    map.end()
+}
+
+/// Deserialize a [`DdsUnion`] from an object holding one of its members.
+///
+/// Members set to `null` and keys which aren't members are ignored, but an
+/// error is returned if there isn't exactly one member left.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: DdsUnion,
+    D: serde::Deserializer<'de>,
+{
+    deserializer.deserialize_map(UnionVisitor(std::marker::PhantomData))
+}
+
+/// The value of the active member of a union, to serialize it.
+struct ActiveMember<'a, T>(&'a T);
+
+impl<T: DdsUnion> serde::Serialize for ActiveMember<'_, T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.0.serialize_member(serializer)
+    }
+}
+
+/// Visit the object holding the active member of a union.
+struct UnionVisitor<T>(std::marker::PhantomData<T>);
+
+impl<'de, T: DdsUnion> Visitor<'de> for UnionVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            formatter,
+            "an object holding one of the members {:?} of {}",
+            T::MEMBERS,
+            std::any::type_name::<T>()
+        )
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<T, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut value = None;
+        while let Some(key) = map.next_key::<std::borrow::Cow<'de, str>>()? {
+            let Some(member) = T::MEMBERS.iter().find(|member| **member == key) else {
+                map.next_value::<de::IgnoredAny>()?;
+                continue;
+            };
+
+            match (map.next_value_seed(MemberSeed::<T>::new(member))?, &value) {
+                (None, _) => {}
+                (Some(_), Some(_)) => {
+                    return Err(de::Error::custom(format!(
+                        "more than one member of {} is set",
+                        std::any::type_name::<T>()
+                    )));
+                }
+                (member_value, None) => value = member_value,
+            }
+        }
+
+        value.ok_or_else(|| {
+            de::Error::custom(format!(
+                "no member of {} is set",
+                std::any::type_name::<T>()
+            ))
+        })
+    }
+}
+
+/// Deserialize the value of a member of a union, or `None` if it is `null`.
+struct MemberSeed<'a, T> {
+    member: &'a str,
+    _type: std::marker::PhantomData<T>,
+}
+
+impl<'a, T> MemberSeed<'a, T> {
+    fn new(member: &'a str) -> Self {
+        MemberSeed {
+            member,
+            _type: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<'de, T: DdsUnion> DeserializeSeed<'de> for MemberSeed<'_, T> {
+    type Value = Option<T>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_option(self)
+    }
+}
+
+impl<'de, T: DdsUnion> Visitor<'de> for MemberSeed<'_, T> {
+    type Value = Option<T>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(formatter, "the value of member '{}'", self.member)
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Option<T>, E> {
+        Ok(None)
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Option<T>, E> {
+        Ok(None)
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        T::deserialize_member(self.member, deserializer).map(Some)
+    }
+}
+
+/// Implement [`DdsUnion`], `Serialize` and `Deserialize` for an enum whose
+/// variants hold the value of each member of a DDS union.
+///
+/// Each variant is listed with the name of its member and the type of its
+/// value, which must implement `Serialize` and `Deserialize`:
+///
+/// ```rust
+/// # #[derive(serde::Serialize, serde::Deserialize)]
+/// # struct Point { x: i32, y: i32 }
+/// enum Shape {
+///     Circle(f64),
+///     Polygon(Vec<Point>),
+/// }
+///
+/// rtiddsconnector::dds_union!(Shape {
+///     "circle_radius" => Circle(f64),
+///     "polygon_points" => Polygon(Vec<Point>),
+/// });
+/// ```
+#[macro_export]
+macro_rules! dds_union {
+    ($union:ty { $($member:literal => $variant:ident($value:ty)),+ $(,)? }) => {
+        impl $crate::dds_union::DdsUnion for $union {
+            const MEMBERS: &'static [&'static str] = &[$($member),+];
+
+            fn member_name(&self) -> &'static str {
+                match self {
+                    $(Self::$variant(_) => $member,)+
+                }
+            }
+
+            fn serialize_member<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+            where
+                S: $crate::dds_union::__private::serde::Serializer,
+            {
+                match self {
+                    $(Self::$variant(value) => {
+                        $crate::dds_union::__private::serde::Serialize::serialize(value, serializer)
+                    })+
+                }
+            }
+
+            fn deserialize_member<'de, D>(
+                member: &str,
+                deserializer: D,
+            ) -> ::std::result::Result<Self, D::Error>
+            where
+                D: $crate::dds_union::__private::serde::Deserializer<'de>,
+            {
+                match member {
+                    $($member => {
+                        <$value as $crate::dds_union::__private::serde::Deserialize>::deserialize(
+                            deserializer,
+                        )
+                        .map(Self::$variant)
+                    })+
+                    _ => ::std::result::Result::Err(
+                        <D::Error as $crate::dds_union::__private::serde::de::Error>::unknown_field(
+                            member,
+                            <Self as $crate::dds_union::DdsUnion>::MEMBERS,
+                        ),
+                    ),
+                }
+            }
+        }
+
+        impl $crate::dds_union::__private::serde::Serialize for $union {
+            fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+            where
+                S: $crate::dds_union::__private::serde::Serializer,
+            {
+                $crate::dds_union::serialize(self, serializer)
+            }
+        }
+
+        impl<'de> $crate::dds_union::__private::serde::Deserialize<'de> for $union {
+            fn deserialize<D>(deserializer: D) -> ::std::result::Result<Self, D::Error>
+            where
+                D: $crate::dds_union::__private::serde::Deserializer<'de>,
+            {
+                $crate::dds_union::deserialize(deserializer)
+            }
+        }
+    };
+}
+
+/// Reexports for [`dds_union!`](crate::dds_union!), which aren't part of the
+/// public API.
+#[doc(hidden)]
+pub mod __private {
+    pub use serde;
+}
@@ -20,7 +20,10 @@ pub use rtiddsconnector::ReturnCode;
 
 use crate::result::ErrorKind;
 use rtiddsconnector::{ConnectorIndex, NativeAllocatedString, NativeStringTrait};
-use std::{ffi::CString, ptr::NonNull};
+use std::{
+    ffi::{CStr, CString},
+    ptr::NonNull,
+};
 
 /// Helper for converting a [`std::ffi::NulError`] into a [`ConnectorError`][crate::ConnectorError]
 impl From<std::ffi::NulError> for crate::ConnectorError {
@@ -29,6 +32,71 @@ impl From<std::ffi::NulError> for crate::ConnectorError {
     }
 }
 
+/// Convert a string into a [`CString`] for the native library.
+///
+/// If the string contains a NUL byte, the error names the string with
+/// `describe` and reports the offset of the byte.
+fn c_string(
+    value: &str,
+    describe: impl FnOnce() -> String,
+) -> crate::ConnectorResult<CString> {
+    CString::new(value)
+        .map_err(|e| ErrorKind::nul_byte_error(describe(), e.nul_position()).into())
+}
+
+/// Convert the name of an entity into a [`CString`] for the native library.
+pub fn entity_c_string(name: &str) -> crate::ConnectorResult<CString> {
+    c_string(name, || {
+        std::format!("Entity name '{}'", name.escape_debug())
+    })
+}
+
+/// Convert the name of a field into a [`CString`] for the native library.
+fn field_c_string(name: &str) -> crate::ConnectorResult<CString> {
+    c_string(name, || {
+        std::format!("Field name '{}'", name.escape_debug())
+    })
+}
+
+/// Attach what an FFI call on an entity was doing to its error, if any.
+trait OperationContext {
+    /// Describe the operation as `action` on the field, if any, of the `entity`
+    /// named `entity_name`, such as "while setting 'color' on output 'MyWriter'".
+    fn with_operation(
+        self,
+        action: &str,
+        entity: &str,
+        entity_name: &CStr,
+        field_name: Option<&CStr>,
+    ) -> Self;
+}
+
+impl<T> OperationContext for crate::ConnectorResult<T> {
+    fn with_operation(
+        self,
+        action: &str,
+        entity: &str,
+        entity_name: &CStr,
+        field_name: Option<&CStr>,
+    ) -> Self {
+        self.map_err(|e| {
+            let entity_name = entity_name.to_string_lossy();
+            e.with_context(match field_name {
+                Some(field_name) => std::format!(
+                    "Error while {} '{}' on {} '{}'",
+                    action,
+                    field_name.to_string_lossy(),
+                    entity,
+                    entity_name
+                ),
+                None => {
+                    std::format!("Error while {} {} '{}'", action, entity, entity_name)
+                }
+            })
+        })
+    }
+}
+
 /// A guard that finalizes [RTI Connext] globals when dropped.
 ///
 /// When an instance of this struct goes out of scope, it will call the
@@ -66,13 +134,100 @@ impl GlobalsDropGuard {
 }
 
 /// Newtype wrappers for native Sample pointers
-#[allow(unused)]
+#[cfg_attr(not(feature = "unsafe-interop"), allow(unused))]
 pub struct FfiSample(NonNull<rtiddsconnector::OpaqueSample>);
 
+#[cfg(feature = "unsafe-interop")]
+impl FfiSample {
+    /// Expose the pointer to users, bound to the lifetime of its entity.
+    pub fn into_native_pointer<'a>(self) -> NativePointer<'a> {
+        NativePointer {
+            ptr: self.0.cast(),
+            _owner: std::marker::PhantomData,
+        }
+    }
+}
+
+/// A pointer to a native `DDS_DynamicData` sample, for use with the
+/// *Connext DDS* C API.
+///
+/// It borrows the [`Input`](crate::Input) or [`Output`](crate::Output) it was
+/// obtained from, since the sample it points to is only valid until the next
+/// [`Input::take`](crate::Input::take), [`Input::read`](crate::Input::read) or
+/// [`Output::write`](crate::Output::write), which require a mutable borrow.
+/// Dereferencing it is `unsafe` and up to the caller, who must not use it
+/// concurrently with other operations on the same entity.
+#[cfg(feature = "unsafe-interop")]
+#[derive(Debug, Clone, Copy)]
+pub struct NativePointer<'a> {
+    /// The pointer to the native sample, which is never null
+    ptr: NonNull<std::ffi::c_void>,
+
+    /// Ties the pointer to the borrow of its entity
+    _owner: std::marker::PhantomData<&'a ()>,
+}
+
+#[cfg(feature = "unsafe-interop")]
+impl NativePointer<'_> {
+    /// Get the raw pointer, to pass it to the *Connext DDS* C API.
+    pub fn as_ptr(&self) -> *mut std::ffi::c_void {
+        self.ptr.as_ptr()
+    }
+}
+
+/// A pointer to a native DDS entity, such as a `DDS_DomainParticipant`, for use
+/// with the *Connext DDS* C API, which holds the connector lock while alive.
+///
+/// The pointer is valid while the guard is alive: it borrows the
+/// [`Connector`](crate::Connector), [`Input`](crate::Input) or
+/// [`Output`](crate::Output) owning the entity, and no other thread can use
+/// the native connector in the meantime. Calling any other operation of the
+/// same connector from the thread holding the guard deadlocks, so drop it as
+/// soon as the native calls are done.
+#[cfg(feature = "unsafe-interop")]
+pub struct NativeEntityGuard<'a> {
+    /// The pointer to the native entity, which is never null
+    ptr: NonNull<std::ffi::c_void>,
+
+    /// The connector lock, held until the guard is dropped
+    _lock: std::sync::RwLockWriteGuard<'a, FfiConnector>,
+}
+
+#[cfg(feature = "unsafe-interop")]
+impl<'a> NativeEntityGuard<'a> {
+    /// Create a guard for an entity of the connector whose lock is held.
+    pub(crate) fn new(
+        lock: std::sync::RwLockWriteGuard<'a, FfiConnector>,
+        ptr: NonNull<std::ffi::c_void>,
+    ) -> Self {
+        NativeEntityGuard { ptr, _lock: lock }
+    }
+
+    /// Get the raw pointer, to pass it to the *Connext DDS* C API.
+    pub fn as_ptr(&self) -> *mut std::ffi::c_void {
+        self.ptr.as_ptr()
+    }
+}
+
+#[cfg(feature = "unsafe-interop")]
+impl std::fmt::Debug for NativeEntityGuard<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NativeEntityGuard")
+            .field("ptr", &self.ptr)
+            .finish_non_exhaustive()
+    }
+}
+
 /// Newtype wrappers for native DataReader pointers
 pub struct FfiInput(NonNull<rtiddsconnector::OpaqueDataReader>);
 
 impl FfiInput {
+    /// The `DDS_DataReader` pointer, for users of the *Connext DDS* C API.
+    #[cfg(feature = "unsafe-interop")]
+    pub fn as_native_ptr(&self) -> NonNull<std::ffi::c_void> {
+        self.0.cast()
+    }
+
     pub fn wait_for_matched_publication(
         &self,
         timeout: Option<i32>,
@@ -109,6 +264,12 @@ impl FfiInput {
 pub struct FfiOutput(NonNull<rtiddsconnector::OpaqueDataWriter>);
 
 impl FfiOutput {
+    /// The `DDS_DataWriter` pointer, for users of the *Connext DDS* C API.
+    #[cfg(feature = "unsafe-interop")]
+    pub fn as_native_ptr(&self) -> NonNull<std::ffi::c_void> {
+        self.0.cast()
+    }
+
     pub fn wait_for_matched_subscription(
         &self,
         timeout: Option<i32>,
@@ -145,7 +306,9 @@ impl FfiOutput {
 }
 
 /// Newtype wrappers for native Connector pointers
-pub struct FfiConnector(NonNull<rtiddsconnector::OpaqueConnector>);
+///
+/// It also holds the `DDS_DomainParticipant` created for the connector.
+pub struct FfiConnector(NonNull<rtiddsconnector::OpaqueConnector>, FfiParticipant);
 
 impl Drop for FfiConnector {
     fn drop(&mut self) {
@@ -155,66 +318,195 @@ impl Drop for FfiConnector {
     }
 }
 
+/// Serializes the creation and deletion of native connectors, so that the
+/// participant created for a connector can be told apart from the others.
+static PARTICIPANT_LIFECYCLE_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// Lock [`PARTICIPANT_LIFECYCLE_LOCK`], even if another thread panicked while holding it.
+fn lock_participant_lifecycle() -> std::sync::MutexGuard<'static, ()> {
+    PARTICIPANT_LIFECYCLE_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Newtype wrapper for the native DomainParticipant pointer of a connector
+pub struct FfiParticipant(NonNull<rtiddsconnector::OpaqueParticipant>);
+
+impl FfiParticipant {
+    /// The domain id joined by the participant.
+    pub fn domain_id(&self) -> i32 {
+        unsafe { rtiddsconnector::DDS_DomainParticipant_get_domain_id(self.0) }
+    }
+
+    /// The `DDS_DomainParticipant` pointer, for users of the *Connext DDS* C API.
+    #[cfg(feature = "unsafe-interop")]
+    pub fn as_native_ptr(&self) -> NonNull<std::ffi::c_void> {
+        self.0.cast()
+    }
+
+    /// List the participants which exist in the process.
+    fn list() -> crate::ConnectorResult<Vec<NonNull<rtiddsconnector::OpaqueParticipant>>>
+    {
+        /// A `DDS_DomainParticipantSeq`, finalized when dropped.
+        struct ParticipantSeq(rtiddsconnector::OpaqueParticipantSeq);
+
+        impl Drop for ParticipantSeq {
+            fn drop(&mut self) {
+                unsafe {
+                    rtiddsconnector::DDS_DomainParticipantSeq_finalize(&mut self.0)
+                };
+            }
+        }
+
+        let factory = NonNull::new(unsafe {
+            rtiddsconnector::DDS_DomainParticipantFactory_get_instance()
+        })
+        .ok_or_else(|| ErrorKind::entity_not_found_error("DomainParticipantFactory"))?;
+
+        let mut participants =
+            ParticipantSeq(rtiddsconnector::OpaqueParticipantSeq::uninitialized());
+        unsafe {
+            rtiddsconnector::DDS_DomainParticipantSeq_initialize(&mut participants.0)
+        };
+
+        InvokeResult::no_output(|| unsafe {
+            rtiddsconnector::DDS_DomainParticipantFactory_get_participants(
+                factory,
+                &mut participants.0,
+            )
+        })
+        .into_result()?;
+
+        let len = unsafe {
+            rtiddsconnector::DDS_DomainParticipantSeq_get_length(&participants.0)
+        };
+        Ok((0..len)
+            .filter_map(|index| {
+                NonNull::new(unsafe {
+                    rtiddsconnector::DDS_DomainParticipantSeq_get(&participants.0, index)
+                })
+            })
+            .collect())
+    }
+}
+
 impl FfiConnector {
     pub fn new(
         connector_name: &str,
         config_file: &str,
     ) -> crate::ConnectorResult<FfiConnector> {
-        let config_name = CString::new(connector_name)?;
-        let config_file = CString::new(config_file)?;
-
-        NonNull::new(unsafe {
+        let config_name = c_string(connector_name, || {
+            std::format!("Configuration name '{}'", connector_name.escape_debug())
+        })?;
+        let config_file = c_string(config_file, || {
+            std::format!("Configuration file '{}'", config_file.escape_debug())
+        })?;
+
+        // The participant of the connector is the one which didn't exist before
+        let _lifecycle = lock_participant_lifecycle();
+        let existing = FfiParticipant::list()?;
+
+        let connector = NonNull::new(unsafe {
             rtiddsconnector::RTI_Connector_new(
                 config_name.as_ptr(),
                 config_file.as_ptr(),
                 &rtiddsconnector::ConnectorOptions::default(),
             )
         })
-        .map(FfiConnector)
-        .ok_or_else(|| ErrorKind::entity_not_found_error(connector_name).into())
+        .ok_or_else(|| {
+            crate::ConnectorError::from_native(ErrorKind::entity_not_found_error(
+                connector_name,
+            ))
+        })?;
+
+        // Creating a connector creates exactly one participant, and the
+        // connectors of this crate are created and deleted one at a time under
+        // the lifecycle lock, so the diff holds a single participant. Any other
+        // participant in it was created outside of this crate meanwhile, and
+        // then the one of the connector can't be told apart.
+        let participant = FfiParticipant::list().and_then(|participants| {
+            let mut created = participants
+                .into_iter()
+                .filter(|participant| !existing.contains(participant));
+            match (created.next(), created.count()) {
+                (Some(participant), 0) => Ok(FfiParticipant(participant)),
+                (None, _) => Err(ErrorKind::entity_not_found_error(std::format!(
+                    "Participant of '{}'",
+                    connector_name
+                ))
+                .into()),
+                (Some(_), others) => Err(ErrorKind::entity_busy_error(std::format!(
+                    "Can't identify the participant of '{}': {} participants were created instead of one",
+                    connector_name,
+                    others + 1
+                ))
+                .into()),
+            }
+        });
+
+        match participant {
+            Ok(participant) => Ok(FfiConnector(connector, participant)),
+            Err(e) => {
+                unsafe { rtiddsconnector::RTI_Connector_delete(connector.as_ptr()) };
+                Err(e)
+            }
+        }
     }
 
-    pub fn get_output(&self, output_name: &str) -> crate::ConnectorResult<FfiOutput> {
-        let entity_name = CString::new(output_name)?;
+    /// The participant created for the connector.
+    pub fn participant(&self) -> &FfiParticipant {
+        &self.1
+    }
 
+    pub fn get_output(&self, output_name: &CStr) -> crate::ConnectorResult<FfiOutput> {
         NonNull::new(unsafe {
-            rtiddsconnector::RTI_Connector_get_datawriter(self.0, entity_name.as_ptr())
+            rtiddsconnector::RTI_Connector_get_datawriter(self.0, output_name.as_ptr())
         })
         .map(FfiOutput)
-        .ok_or_else(|| ErrorKind::entity_not_found_error(output_name).into())
+        .ok_or_else(|| {
+            crate::ConnectorError::from_native(ErrorKind::entity_not_found_error(
+                output_name.to_string_lossy(),
+            ))
+        })
     }
 
-    pub fn get_input(&self, input_name: &str) -> crate::ConnectorResult<FfiInput> {
-        let entity_name = CString::new(input_name)?;
-
+    pub fn get_input(&self, input_name: &CStr) -> crate::ConnectorResult<FfiInput> {
         NonNull::new(unsafe {
-            rtiddsconnector::RTI_Connector_get_datareader(self.0, entity_name.as_ptr())
+            rtiddsconnector::RTI_Connector_get_datareader(self.0, input_name.as_ptr())
         })
         .map(FfiInput)
-        .ok_or_else(|| ErrorKind::entity_not_found_error(input_name).into())
+        .ok_or_else(|| {
+            crate::ConnectorError::from_native(ErrorKind::entity_not_found_error(
+                input_name.to_string_lossy(),
+            ))
+        })
     }
 
-    #[allow(unused)]
+    #[cfg_attr(not(feature = "unsafe-interop"), allow(unused))]
     pub fn get_native_sample(
         &self,
-        output_name: &str,
+        output_name: &CStr,
         index: usize,
     ) -> crate::ConnectorResult<FfiSample> {
-        let entity_name: CString = CString::new(output_name)?;
         let index: ConnectorIndex = index.try_into()?;
 
         NonNull::new(unsafe {
             rtiddsconnector::RTI_Connector_get_native_sample(
                 self.0,
-                entity_name.as_ptr(),
+                output_name.as_ptr(),
                 index,
             )
         })
         .map(FfiSample)
-        .ok_or_else(|| ErrorKind::entity_not_found_error(output_name).into())
+        .ok_or_else(|| {
+            crate::ConnectorError::from_native(ErrorKind::entity_not_found_error(
+                output_name.to_string_lossy(),
+            ))
+        })
     }
 
     fn delete(&mut self) -> crate::ConnectorFallible {
+        let _lifecycle = lock_participant_lifecycle();
         InvokeResult::never_fails(|| unsafe {
             rtiddsconnector::RTI_Connector_delete(self.0.as_ptr())
         })
@@ -223,13 +515,22 @@ impl FfiConnector {
 
     pub fn set_number_into_samples(
         &self,
-        entity_name: &str,
+        entity_name: &CStr,
         field_name: &str,
         value: f64,
     ) -> crate::ConnectorFallible {
-        let entity_name = CString::new(entity_name)?;
-        let field_name = CString::new(field_name)?;
+        let field_name = field_c_string(field_name)?;
 
+        self.set_number_into_samples_c(entity_name, &field_name, value)
+    }
+
+    /// Same as [`FfiConnector::set_number_into_samples`], taking C strings.
+    pub fn set_number_into_samples_c(
+        &self,
+        entity_name: &CStr,
+        field_name: &CStr,
+        value: f64,
+    ) -> crate::ConnectorFallible {
         InvokeResult::no_output(|| unsafe {
             rtiddsconnector::RTI_Connector_set_number_into_samples(
                 self.0,
@@ -238,18 +539,28 @@ impl FfiConnector {
                 value,
             )
         })
-        .into()
+        .into_result()
+        .with_operation("setting", "output", entity_name, Some(field_name))
     }
 
     pub fn set_boolean_into_samples(
         &self,
-        entity_name: &str,
+        entity_name: &CStr,
         name: &str,
         value: bool,
     ) -> crate::ConnectorFallible {
-        let entity_name = CString::new(entity_name)?;
-        let name = CString::new(name)?;
+        let name = field_c_string(name)?;
 
+        self.set_boolean_into_samples_c(entity_name, &name, value)
+    }
+
+    /// Same as [`FfiConnector::set_boolean_into_samples`], taking C strings.
+    pub fn set_boolean_into_samples_c(
+        &self,
+        entity_name: &CStr,
+        name: &CStr,
+        value: bool,
+    ) -> crate::ConnectorFallible {
         InvokeResult::no_output(|| unsafe {
             rtiddsconnector::RTI_Connector_set_boolean_into_samples(
                 self.0,
@@ -258,57 +569,91 @@ impl FfiConnector {
                 value as i32,
             )
         })
-        .into()
+        .into_result()
+        .with_operation("setting", "output", entity_name, Some(name))
     }
 
     pub fn set_string_into_samples(
         &self,
-        entity_name: &str,
+        entity_name: &CStr,
         name: &str,
         value: &str,
     ) -> crate::ConnectorFallible {
-        let entity_name = CString::new(entity_name)?;
-        let name = CString::new(name)?;
-        let c_value = CString::new(value)?;
+        let c_value = c_string(value, || {
+            std::format!("Value for field '{}'", name.escape_debug())
+        })?;
+        let name = field_c_string(name)?;
+
+        self.set_string_into_samples_c(entity_name, &name, &c_value)
+    }
 
+    /// Same as [`FfiConnector::set_string_into_samples`], taking C strings.
+    pub fn set_string_into_samples_c(
+        &self,
+        entity_name: &CStr,
+        name: &CStr,
+        value: &CStr,
+    ) -> crate::ConnectorFallible {
         InvokeResult::no_output(|| unsafe {
             rtiddsconnector::RTI_Connector_set_string_into_samples(
                 self.0,
                 entity_name.as_ptr(),
                 name.as_ptr(),
-                c_value.as_ptr(),
+                value.as_ptr(),
             )
         })
-        .into()
+        .into_result()
+        .with_operation("setting", "output", entity_name, Some(name))
     }
 
     pub fn set_into_samples(
         &self,
-        entity_name: &str,
+        entity_name: &CStr,
         name: &str,
-        value: crate::SelectedValue,
+        value: &crate::SelectedValue,
     ) -> crate::ConnectorFallible {
         match value {
             crate::SelectedValue::Number(v) => {
-                self.set_number_into_samples(entity_name, name, v)
+                self.set_number_into_samples(entity_name, name, *v)
             }
             crate::SelectedValue::Boolean(v) => {
-                self.set_boolean_into_samples(entity_name, name, v)
+                self.set_boolean_into_samples(entity_name, name, *v)
             }
             crate::SelectedValue::String(v) => {
-                self.set_string_into_samples(entity_name, name, &v)
+                self.set_string_into_samples(entity_name, name, v)
+            }
+            crate::SelectedValue::Null => self.clear_member(entity_name, name),
+            crate::SelectedValue::Json(v) => {
+                match crate::json_path::fragment(name, v.clone()) {
+                    Some(fragment) => {
+                        self.set_json_instance(entity_name, &fragment.to_string())
+                    }
+                    None => ErrorKind::invalid_argument_error(format!(
+                        "Field '{}' must be a member path without element indexes",
+                        name
+                    ))
+                    .into_err(),
+                }
             }
         }
     }
 
     pub fn clear_member(
         &self,
-        entity_name: &str,
+        entity_name: &CStr,
         name: &str,
     ) -> crate::ConnectorFallible {
-        let entity_name = CString::new(entity_name)?;
-        let name = CString::new(name)?;
+        let name = field_c_string(name)?;
+
+        self.clear_member_c(entity_name, &name)
+    }
 
+    /// Same as [`FfiConnector::clear_member`], taking C strings.
+    pub fn clear_member_c(
+        &self,
+        entity_name: &CStr,
+        name: &CStr,
+    ) -> crate::ConnectorFallible {
         InvokeResult::no_output(|| unsafe {
             rtiddsconnector::RTI_Connector_clear_member(
                 self.0,
@@ -316,17 +661,18 @@ impl FfiConnector {
                 name.as_ptr(),
             )
         })
-        .into()
+        .into_result()
+        .with_operation("clearing", "output", entity_name, Some(name))
     }
 
-    pub fn write(&self, entity_name: &str) -> crate::ConnectorFallible {
+    pub fn write(&self, entity_name: &CStr) -> crate::ConnectorFallible {
         self.write_impl(entity_name, None)
     }
 
     #[allow(unused)]
     pub fn write_with_params(
         &self,
-        entity_name: &str,
+        entity_name: &CStr,
         params_json: &str,
     ) -> crate::ConnectorFallible {
         self.write_impl(entity_name, Some(params_json))
@@ -334,14 +680,12 @@ impl FfiConnector {
 
     fn write_impl(
         &self,
-        entity_name: &str,
+        entity_name: &CStr,
         params_json: Option<&str>,
     ) -> crate::ConnectorFallible {
-        let entity_name = CString::new(entity_name)?;
-        let params_json = match params_json.map(CString::new) {
-            Some(r) => Some(r?),
-            None => None,
-        };
+        let params_json = params_json
+            .map(|params_json| c_string(params_json, || "WriteParams JSON".to_string()))
+            .transpose()?;
 
         InvokeResult::no_output(|| unsafe {
             rtiddsconnector::RTI_Connector_write(
@@ -353,34 +697,32 @@ impl FfiConnector {
                 },
             )
         })
-        .into()
+        .into_result()
+        .with_operation("writing", "output", entity_name, None)
     }
 
-    pub fn read(&self, entity_name: &str) -> crate::ConnectorFallible {
-        let entity_name = CString::new(entity_name)?;
-
+    pub fn read(&self, entity_name: &CStr) -> crate::ConnectorFallible {
         InvokeResult::no_output(|| unsafe {
             rtiddsconnector::RTI_Connector_read(self.0, entity_name.as_ptr())
         })
-        .into()
+        .into_result()
+        .with_operation("reading", "input", entity_name, None)
     }
 
-    pub fn take(&self, entity_name: &str) -> crate::ConnectorFallible {
-        let entity_name = CString::new(entity_name)?;
-
+    pub fn take(&self, entity_name: &CStr) -> crate::ConnectorFallible {
         InvokeResult::no_output(|| unsafe {
             rtiddsconnector::RTI_Connector_take(self.0, entity_name.as_ptr())
         })
-        .into()
+        .into_result()
+        .with_operation("taking", "input", entity_name, None)
     }
 
-    pub fn return_loan(&self, entity_name: &str) -> crate::ConnectorFallible {
-        let entity_name = CString::new(entity_name)?;
-
+    pub fn return_loan(&self, entity_name: &CStr) -> crate::ConnectorFallible {
         InvokeResult::no_output(|| unsafe {
             rtiddsconnector::RTI_Connector_return_loan(self.0, entity_name.as_ptr())
         })
-        .into()
+        .into_result()
+        .with_operation("returning the loan of", "input", entity_name, None)
     }
 
     pub fn wait_for_data(&self, timeout: Option<i32>) -> crate::ConnectorFallible {
@@ -393,23 +735,21 @@ impl FfiConnector {
         .into()
     }
 
-    pub fn clear(&self, entity_name: &str) -> crate::ConnectorFallible {
-        let entity_name = CString::new(entity_name)?;
-
+    pub fn clear(&self, entity_name: &CStr) -> crate::ConnectorFallible {
         InvokeResult::no_output(|| unsafe {
             rtiddsconnector::RTI_Connector_clear(self.0, entity_name.as_ptr())
         })
-        .into()
+        .into_result()
+        .with_operation("clearing", "output", entity_name, None)
     }
 
     pub fn get_boolean_from_infos(
         &self,
-        entity_name: &str,
+        entity_name: &CStr,
         index: usize,
         name: &str,
     ) -> crate::ConnectorResult<bool> {
-        let entity_name = CString::new(entity_name)?;
-        let name = CString::new(name)?;
+        let name = field_c_string(name)?;
         let index: ConnectorIndex = index.try_into()?;
 
         InvokeResult::with_output(|out_value: &mut bool| unsafe {
@@ -421,18 +761,18 @@ impl FfiConnector {
                 name.as_ptr(),
             )
         })
-        .into()
+        .into_result()
+        .with_operation("getting the info", "input", entity_name, Some(&name))
     }
 
     pub fn get_json_from_infos(
         &self,
-        entity_name: &str,
+        entity_name: &CStr,
         index: usize,
         name: &str,
     ) -> crate::ConnectorResult<String> {
-        let entity_name = CString::new(entity_name)?;
         let index: ConnectorIndex = index.try_into()?;
-        let name = CString::new(name)?;
+        let name = field_c_string(name)?;
 
         InvokeResult::with_output(|out_value: &mut NativeAllocatedString| unsafe {
             rtiddsconnector::RTI_Connector_get_json_from_infos(
@@ -444,11 +784,10 @@ impl FfiConnector {
             )
         })
         .into_string()
+        .with_operation("getting the info", "input", entity_name, Some(&name))
     }
 
-    pub fn get_sample_count(&self, entity_name: &str) -> crate::ConnectorResult<f64> {
-        let entity_name = CString::new(entity_name)?;
-
+    pub fn get_sample_count(&self, entity_name: &CStr) -> crate::ConnectorResult<f64> {
         InvokeResult::with_output(|out_value: &mut f64| unsafe {
             rtiddsconnector::RTI_Connector_get_sample_count(
                 self.0,
@@ -456,17 +795,28 @@ impl FfiConnector {
                 out_value,
             )
         })
-        .into()
+        .into_result()
+        .with_operation("counting the samples of", "input", entity_name, None)
     }
 
     pub fn get_number_from_sample(
         &self,
-        entity_name: &str,
+        entity_name: &CStr,
         index: usize,
         name: &str,
     ) -> crate::ConnectorResult<f64> {
-        let entity_name = CString::new(entity_name)?;
-        let name = CString::new(name)?;
+        let name = field_c_string(name)?;
+
+        self.get_number_from_sample_c(entity_name, index, &name)
+    }
+
+    /// Same as [`FfiConnector::get_number_from_sample`], taking C strings.
+    pub fn get_number_from_sample_c(
+        &self,
+        entity_name: &CStr,
+        index: usize,
+        name: &CStr,
+    ) -> crate::ConnectorResult<f64> {
         let index: ConnectorIndex = index.try_into()?;
 
         InvokeResult::with_output(|out_value: &mut f64| unsafe {
@@ -478,17 +828,28 @@ impl FfiConnector {
                 name.as_ptr(),
             )
         })
-        .into()
+        .into_result()
+        .with_operation("getting", "input", entity_name, Some(name))
     }
 
     pub fn get_boolean_from_sample(
         &self,
-        entity_name: &str,
+        entity_name: &CStr,
         index: usize,
         name: &str,
     ) -> crate::ConnectorResult<bool> {
-        let entity_name = CString::new(entity_name)?;
-        let name = CString::new(name)?;
+        let name = field_c_string(name)?;
+
+        self.get_boolean_from_sample_c(entity_name, index, &name)
+    }
+
+    /// Same as [`FfiConnector::get_boolean_from_sample`], taking C strings.
+    pub fn get_boolean_from_sample_c(
+        &self,
+        entity_name: &CStr,
+        index: usize,
+        name: &CStr,
+    ) -> crate::ConnectorResult<bool> {
         let index: ConnectorIndex = index.try_into()?;
 
         InvokeResult::with_output(|out_value: &mut bool| unsafe {
@@ -500,18 +861,29 @@ impl FfiConnector {
                 name.as_ptr(),
             )
         })
-        .into()
+        .into_result()
+        .with_operation("getting", "input", entity_name, Some(name))
     }
 
     pub fn get_string_from_sample(
         &self,
-        entity_name: &str,
+        entity_name: &CStr,
         index: usize,
         name: &str,
-    ) -> crate::ConnectorResult<String> {
-        let entity_name = CString::new(entity_name)?;
+    ) -> crate::ConnectorResult<NativeString> {
+        let name = field_c_string(name)?;
+
+        self.get_string_from_sample_c(entity_name, index, &name)
+    }
+
+    /// Same as [`FfiConnector::get_string_from_sample`], taking C strings.
+    pub fn get_string_from_sample_c(
+        &self,
+        entity_name: &CStr,
+        index: usize,
+        name: &CStr,
+    ) -> crate::ConnectorResult<NativeString> {
         let index: ConnectorIndex = index.try_into()?;
-        let name = CString::new(name)?;
 
         InvokeResult::with_output(|out_value: &mut NativeAllocatedString| unsafe {
             rtiddsconnector::RTI_Connector_get_string_from_sample(
@@ -522,18 +894,29 @@ impl FfiConnector {
                 name.as_ptr(),
             )
         })
-        .into_string()
+        .into_native_string()
+        .with_operation("getting", "input", entity_name, Some(name))
     }
 
     pub fn get_from_sample(
         &self,
-        entity_name: &str,
+        entity_name: &CStr,
         index: usize,
         name: &str,
     ) -> crate::ConnectorResult<crate::SelectedValue> {
-        let entity_name = CString::new(entity_name)?;
+        let name = field_c_string(name)?;
+
+        self.get_from_sample_c(entity_name, index, &name)
+    }
+
+    /// Same as [`FfiConnector::get_from_sample`], taking C strings.
+    pub fn get_from_sample_c(
+        &self,
+        entity_name: &CStr,
+        index: usize,
+        name: &CStr,
+    ) -> crate::ConnectorResult<crate::SelectedValue> {
         let index: ConnectorIndex = index.try_into()?;
-        let name = CString::new(name)?;
 
         InvokeResult::with_output(|holder: &mut NativeAnyValueHolder| unsafe {
             rtiddsconnector::RTI_Connector_get_any_from_sample(
@@ -548,17 +931,17 @@ impl FfiConnector {
             )
         })
         .into_selected_value()
+        .with_operation("getting", "input", entity_name, Some(name))
     }
 
     pub fn get_from_info(
         &self,
-        entity_name: &str,
+        entity_name: &CStr,
         index: usize,
         name: &str,
     ) -> crate::ConnectorResult<crate::SelectedValue> {
-        let entity_name = CString::new(entity_name)?;
         let index: ConnectorIndex = index.try_into()?;
-        let name = CString::new(name)?;
+        let name = field_c_string(name)?;
 
         InvokeResult::with_output(|holder: &mut NativeAnyValueHolder| unsafe {
             rtiddsconnector::RTI_Connector_get_any_from_info(
@@ -573,14 +956,14 @@ impl FfiConnector {
             )
         })
         .into_selected_value()
+        .with_operation("getting the info", "input", entity_name, Some(&name))
     }
 
     pub fn get_json_sample(
         &self,
-        entity_name: &str,
+        entity_name: &CStr,
         index: usize,
-    ) -> crate::ConnectorResult<String> {
-        let entity_name = CString::new(entity_name)?;
+    ) -> crate::ConnectorResult<NativeString> {
         let index: ConnectorIndex = index.try_into()?;
 
         InvokeResult::with_output(|out_value: &mut NativeAllocatedString| unsafe {
@@ -591,18 +974,29 @@ impl FfiConnector {
                 out_value,
             )
         })
-        .into_string()
+        .into_native_string()
+        .with_operation("getting a sample of", "input", entity_name, None)
     }
 
     pub fn get_json_member(
         &self,
-        entity_name: &str,
+        entity_name: &CStr,
         index: usize,
         member_name: &str,
-    ) -> crate::ConnectorResult<String> {
-        let entity_name = CString::new(entity_name)?;
+    ) -> crate::ConnectorResult<NativeString> {
+        let member_name = field_c_string(member_name)?;
+
+        self.get_json_member_c(entity_name, index, &member_name)
+    }
+
+    /// Same as [`FfiConnector::get_json_member`], taking C strings.
+    pub fn get_json_member_c(
+        &self,
+        entity_name: &CStr,
+        index: usize,
+        member_name: &CStr,
+    ) -> crate::ConnectorResult<NativeString> {
         let index: ConnectorIndex = index.try_into()?;
-        let member_name = CString::new(member_name)?;
 
         InvokeResult::with_output(|out_value: &mut NativeAllocatedString| unsafe {
             rtiddsconnector::RTI_Connector_get_json_member(
@@ -613,16 +1007,21 @@ impl FfiConnector {
                 out_value,
             )
         })
-        .into_string()
+        .into_native_string()
+        .with_operation("getting", "input", entity_name, Some(member_name))
     }
 
     pub fn set_json_instance(
         &self,
-        entity_name: &str,
+        entity_name: &CStr,
         json: &str,
     ) -> crate::ConnectorFallible {
-        let entity_name = CString::new(entity_name)?;
-        let json = CString::new(json)?;
+        let json = c_string(json, || {
+            std::format!(
+                "JSON for entity '{}'",
+                entity_name.to_string_lossy().escape_debug()
+            )
+        })?;
 
         InvokeResult::no_output(|| unsafe {
             rtiddsconnector::RTI_Connector_set_json_instance(
@@ -631,7 +1030,8 @@ impl FfiConnector {
                 json.as_ptr(),
             )
         })
-        .into()
+        .into_result()
+        .with_operation("setting the JSON of", "output", entity_name, None)
     }
 
     pub fn get_last_error_message() -> Option<String> {
@@ -641,23 +1041,48 @@ impl FfiConnector {
             .map(str::to_string)
     }
 
-    #[allow(unused)]
+    #[cfg_attr(not(feature = "unsafe-interop"), allow(unused))]
     pub fn get_native_instance(
         &self,
-        entity_name: &str,
-    ) -> crate::ConnectorResult<*const rtiddsconnector::OpaqueSample> {
-        unimplemented!();
-    }
+        entity_name: &CStr,
+    ) -> crate::ConnectorResult<FfiSample> {
+        let mut native_pointer: *const rtiddsconnector::OpaqueSample = std::ptr::null();
 
-    pub fn get_json_instance(&self, entity_name: &str) -> crate::ConnectorResult<String> {
-        let entity_name = CString::new(entity_name)?;
+        InvokeResult::no_output(|| unsafe {
+            rtiddsconnector::RTI_Connector_get_native_instance(
+                self.0,
+                entity_name.as_ptr(),
+                &mut native_pointer,
+            )
+        })
+        .into_result()
+        .with_operation(
+            "getting the native instance of",
+            "output",
+            entity_name,
+            None,
+        )?;
+
+        NonNull::new(native_pointer.cast_mut())
+            .map(FfiSample)
+            .ok_or_else(|| {
+                crate::ConnectorError::from_native(ErrorKind::entity_not_found_error(
+                    entity_name.to_string_lossy(),
+                ))
+            })
+    }
 
+    pub fn get_json_instance(
+        &self,
+        entity_name: &CStr,
+    ) -> crate::ConnectorResult<String> {
         // We need to call a function that returns a pointer. Then, based on this pointer, we can move the value
         // into a String and free the output pointer, or fail if the pointer is null.
         InvokeResult::with_string_return(|| unsafe {
             rtiddsconnector::RTIDDSConnector_getJSONInstance(self.0, entity_name.as_ptr())
         })
         .into_string()
+        .with_operation("getting the JSON of", "output", entity_name, None)
     }
 
     pub fn get_build_versions() -> crate::ConnectorResult<(String, String)> {
@@ -713,9 +1138,7 @@ impl InvokeResult<NativeAnyValueHolder> {
                 code
             ))
             .into_err(),
-            AnyValue::None => {
-                ErrorKind::assertion_failed_error("Unavaiable AnyValue kind").into_err()
-            }
+            AnyValue::None => Ok(crate::SelectedValue::Null),
         }
     }
 }
@@ -744,15 +1167,58 @@ impl InvokeResult<rtiddsconnector::NativeAllocatedString> {
 
     /// Helper to convert a NativeAllocatedString result into a Rust String.
     pub fn into_string(self) -> crate::ConnectorResult<String> {
+        self.into_native_string().map(|native| native.to_string())
+    }
+
+    /// Helper to convert a NativeAllocatedString result into a [`NativeString`],
+    /// checking that it is valid UTF-8 without copying it.
+    pub fn into_native_string(self) -> crate::ConnectorResult<NativeString> {
         self.into_result().and_then(|native| {
-            native
+            let len = native
                 .as_str()
-                .map(str::to_string)
-                .ok_or_else(|| ErrorKind::invalid_string_conversion_error().into())
+                .map(str::len)
+                .ok_or_else(ErrorKind::invalid_string_conversion_error)?;
+            Ok(NativeString { native, len })
         })
     }
 }
 
+/// A string allocated by the native library, which is freed when dropped.
+///
+/// It dereferences to `&str`, so it can be read without copying it.
+pub struct NativeString {
+    native: NativeAllocatedString,
+    /// The length in bytes, not including the NUL terminator.
+    len: usize,
+}
+
+impl std::ops::Deref for NativeString {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        // SAFETY: the string was checked to be non-null and valid UTF-8 of
+        // `len` bytes on creation, and is owned until dropped.
+        unsafe {
+            std::str::from_utf8_unchecked(std::slice::from_raw_parts(
+                self.native.as_raw_ptr().cast::<u8>(),
+                self.len,
+            ))
+        }
+    }
+}
+
+impl std::fmt::Display for NativeString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self)
+    }
+}
+
+impl std::fmt::Debug for NativeString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(&**self, f)
+    }
+}
+
 // TODO: Review if this can be turned into an Enum or into Result outright.
 pub struct InvokeResult<T>(rtiddsconnector::ReturnCode, T);
 
@@ -762,7 +1228,9 @@ impl<T> InvokeResult<T> {
         match self.0 {
             rtiddsconnector::ReturnCode::Ok => Ok(self.1),
             rtiddsconnector::ReturnCode::Timeout => ErrorKind::timeout_error().into_err(),
-            other => ErrorKind::native_error(other).into_err(),
+            other => Err(crate::ConnectorError::from_native(ErrorKind::native_error(
+                other,
+            ))),
         }
     }
 }
@@ -13,16 +13,23 @@ use std::{ffi, ptr::NonNull};
 // C representation of the ReturnCode enum.
 pub type NativeReturnCode = ffi::c_int;
 
-/// Rust representation of the ReturnCode enum.
+/// A return code of the native library, carried by [`ErrorKind::Native`](crate::ErrorKind::Native).
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ReturnCode {
+    /// The operation succeeded
     Ok,
+    /// A generic error
     Error,
+    /// The entity was already deleted
     AlreadyDeleted,
+    /// The operation timed out
     Timeout,
+    /// There was no data, such as an unset optional member
     NoData,
+    /// The operation isn't allowed in the current state
     IllegalOperation,
-    Unknown(NativeReturnCode),
+    /// Any other return code
+    Unknown(i32),
 }
 
 impl From<ReturnCode> for NativeReturnCode {
@@ -90,6 +97,25 @@ pub struct OpaqueDataReader(ffi::c_void);
 #[repr(transparent)]
 pub struct OpaqueSample(ffi::c_void);
 
+#[repr(transparent)]
+pub struct OpaqueParticipantFactory(ffi::c_void);
+
+#[repr(transparent)]
+pub struct OpaqueParticipant(ffi::c_void);
+
+/// Storage for a `DDS_DomainParticipantSeq`, which is only accessed through
+/// the `DDS_DomainParticipantSeq_*` functions, so its layout is not needed.
+///
+/// It's larger than the native struct, and aligned like its pointer members.
+#[repr(C, align(16))]
+pub struct OpaqueParticipantSeq([u8; 256]);
+
+impl OpaqueParticipantSeq {
+    pub fn uninitialized() -> Self {
+        OpaqueParticipantSeq([0; 256])
+    }
+}
+
 pub trait NativeStringTrait {
     fn as_raw_ptr(&self) -> *const ffi::c_char;
     fn as_str(&self) -> Option<&str> {
@@ -427,4 +453,33 @@ unsafe extern "C" {
 #[link(name = "nddsc")]
 unsafe extern "C" {
     pub unsafe fn DDS_DomainParticipantFactory_finalize_instance() -> NativeReturnCode;
+
+    pub unsafe fn DDS_DomainParticipantFactory_get_instance()
+    -> *mut OpaqueParticipantFactory;
+
+    pub unsafe fn DDS_DomainParticipantFactory_get_participants(
+        factory: NonNull<OpaqueParticipantFactory>,
+        participants: *mut OpaqueParticipantSeq,
+    ) -> NativeReturnCode;
+
+    pub unsafe fn DDS_DomainParticipantSeq_initialize(
+        participants: *mut OpaqueParticipantSeq,
+    ) -> ffi::c_uchar; // DDS_Boolean
+
+    pub unsafe fn DDS_DomainParticipantSeq_finalize(
+        participants: *mut OpaqueParticipantSeq,
+    ) -> ffi::c_uchar; // DDS_Boolean
+
+    pub unsafe fn DDS_DomainParticipantSeq_get_length(
+        participants: *const OpaqueParticipantSeq,
+    ) -> ffi::c_uint;
+
+    pub unsafe fn DDS_DomainParticipantSeq_get(
+        participants: *const OpaqueParticipantSeq,
+        index: ffi::c_uint,
+    ) -> *mut OpaqueParticipant;
+
+    pub unsafe fn DDS_DomainParticipant_get_domain_id(
+        participant: NonNull<OpaqueParticipant>,
+    ) -> ffi::c_int;
 }
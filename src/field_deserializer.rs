@@ -0,0 +1,561 @@
+/*******************************************************************************
+ * (c) 2025 Copyright, Real-Time Innovations.  All rights reserved.            *
+ * No duplications, whole or partial, manual or electronic, may be made        *
+ * without express written permission.  Any such copies, or revisions thereof, *
+ * must display this notice unaltered.                                         *
+ * This code contains trade secrets of Real-Time Innovations, Inc.             *
+ *******************************************************************************/
+
+//! A Serde deserializer which gets the fields of a received sample one by one,
+//! without going through a JSON representation of the data.
+//!
+//! Driven by the type being deserialized, each leaf value is read with the
+//! native getter for its kind, addressing it with the same field-name syntax
+//! as [`crate::field_serializer`]. Only the values which can't be represented
+//! by those getters, such as 64-bit integers, maps or enums, are read as JSON.
+
+use crate::{
+    ConnectorError, ConnectorResult, SelectedValue,
+    ffi::{FfiConnector, NativeString},
+    json_path::FieldNameBuf,
+    output::MAX_EXACT_INTEGER,
+    result::{ErrorKind, InvalidErrorKind},
+};
+use serde::de::{self, DeserializeOwned, DeserializeSeed, IntoDeserializer, Visitor};
+use std::ffi::CStr;
+
+/// Deserialize the sample at `index` of the input named `entity_name`.
+///
+/// Unless it is deserialized from JSON as a whole, the data must be a struct.
+pub(crate) fn deserialize_from<T>(
+    native: &FfiConnector,
+    entity_name: &CStr,
+    index: usize,
+) -> ConnectorResult<T>
+where
+    T: DeserializeOwned,
+{
+    let mut state = State {
+        native,
+        entity_name,
+        index,
+        buffer: Vec::with_capacity(256),
+    };
+
+    serde_path_to_error::deserialize(FieldDeserializer {
+        state: &mut state,
+        in_array: false,
+    })
+    .map_err(|e| {
+        let path = crate::json_path::from_serde_path("", e.path());
+        e.into_inner().with_field_path(path)
+    })
+}
+
+/// The state shared by the deserializers of every field.
+struct State<'a> {
+    native: &'a FfiConnector,
+    entity_name: &'a CStr,
+    index: usize,
+
+    /// The field name of the value being deserialized.
+    buffer: Vec<u8>,
+}
+
+impl State<'_> {
+    /// Check that a field is being deserialized, rather than the whole sample.
+    fn ensure_field(&self) -> ConnectorResult<()> {
+        if self.buffer.is_empty() {
+            ErrorKind::Invalid {
+                what: InvalidErrorKind::Deserialization,
+                context: "Only structs can be deserialized field by field from a sample"
+                    .to_string(),
+            }
+            .into_err()
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Call a native getter with the field name in the buffer.
+    fn with_field<R>(
+        &mut self,
+        get: impl FnOnce(&FfiConnector, &CStr, usize, &CStr) -> ConnectorResult<R>,
+    ) -> ConnectorResult<R> {
+        let field_len = self.buffer.len();
+        self.buffer.push(0);
+
+        let result = match CStr::from_bytes_with_nul(&self.buffer) {
+            Ok(field) => get(self.native, self.entity_name, self.index, field),
+            Err(_) => ErrorKind::invalid_string_conversion_error().into_err(),
+        };
+
+        self.buffer.truncate(field_len);
+        result
+    }
+
+    fn get_number(&mut self) -> ConnectorResult<f64> {
+        self.ensure_field()?;
+        self.with_field(|native, entity_name, index, field| {
+            native.get_number_from_sample_c(entity_name, index, field)
+        })
+    }
+
+    fn get_boolean(&mut self) -> ConnectorResult<bool> {
+        self.ensure_field()?;
+        self.with_field(|native, entity_name, index, field| {
+            native.get_boolean_from_sample_c(entity_name, index, field)
+        })
+    }
+
+    fn get_string(&mut self) -> ConnectorResult<NativeString> {
+        self.ensure_field()?;
+        self.with_field(|native, entity_name, index, field| {
+            native.get_string_from_sample_c(entity_name, index, field)
+        })
+    }
+
+    /// Get the field as JSON, or the whole sample if no field is being
+    /// deserialized.
+    fn get_json(&mut self) -> ConnectorResult<serde_json::Value> {
+        let json = if self.buffer.is_empty() {
+            self.native.get_json_sample(self.entity_name, self.index)?
+        } else {
+            self.with_field(|native, entity_name, index, field| {
+                native.get_json_member_c(entity_name, index, field)
+            })?
+        };
+
+        serde_json::from_str(&json).map_err(de::Error::custom)
+    }
+
+    /// The number of elements of the sequence being deserialized.
+    fn get_length(&mut self) -> ConnectorResult<usize> {
+        self.ensure_field()?;
+        self.buffer.push(b'#');
+        let result = self.with_field(|native, entity_name, index, field| {
+            native.get_number_from_sample_c(entity_name, index, field)
+        });
+        self.buffer.pop();
+
+        crate::input::count_from_native("sequence length", result?)
+    }
+
+    /// Check whether the optional member being deserialized is set.
+    ///
+    /// Members which don't exist, as well as the members of a union other
+    /// than the selected one, are considered unset, since they would be
+    /// missing from the JSON representation of the sample.
+    fn is_set(&mut self) -> ConnectorResult<bool> {
+        if self.is_unselected_member() {
+            return Ok(false);
+        }
+
+        let result = match self.with_field(|native, entity_name, index, field| {
+            native.get_from_sample_c(entity_name, index, field)
+        }) {
+            Ok(_) => return Ok(true),
+            Err(e) if e.is_unset_optional() || e.is_field_not_found() => {
+                return Ok(false);
+            }
+            // Not every member can be accessed as a single value
+            Err(_) => self.with_field(|native, entity_name, index, field| {
+                native.get_json_member_c(entity_name, index, field)
+            }),
+        };
+
+        match result {
+            Ok(_) => Ok(true),
+            Err(e) if e.is_unset_optional() || e.is_field_not_found() => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Check whether the member being deserialized belongs to a union which
+    /// has a different member selected.
+    fn is_unselected_member(&mut self) -> bool {
+        let Some(separator) = self.buffer.iter().rposition(|&c| c == b'.') else {
+            return false;
+        };
+        let member = &self.buffer[separator + 1..];
+        if member.contains(&b'[') {
+            return false;
+        }
+
+        let member = String::from_utf8_lossy(member).into_owned();
+        let field_len = self.buffer.len();
+        self.buffer.truncate(separator);
+        self.buffer.push(b'#');
+        let discriminator = self.with_field(|native, entity_name, index, field| {
+            native.get_from_sample_c(entity_name, index, field)
+        });
+        self.buffer.truncate(separator + 1);
+        self.buffer.extend_from_slice(member.as_bytes());
+        debug_assert_eq!(field_len, self.buffer.len());
+
+        // The `#` of a struct isn't meaningful, so errors aren't reported
+        let selected = match discriminator {
+            Ok(SelectedValue::String(selected)) => selected,
+            _ => return false,
+        };
+        selected != member
+    }
+}
+
+/// Deserializes the value of the field whose name is in the [`State`] buffer.
+struct FieldDeserializer<'s, 'a> {
+    state: &'s mut State<'a>,
+
+    /// Whether the value is an element of an array.
+    in_array: bool,
+}
+
+impl FieldDeserializer<'_, '_> {
+    /// Read a number, visiting it as an integer when it is one, like the
+    /// JSON deserializer would.
+    fn number<'de, V: Visitor<'de>>(self, visitor: V) -> ConnectorResult<V::Value> {
+        let value = self.state.get_number()?;
+
+        if value.fract() == 0.0 && value.abs() <= MAX_EXACT_INTEGER as f64 {
+            if value < 0.0 {
+                visitor.visit_i64(value as i64)
+            } else {
+                visitor.visit_u64(value as u64)
+            }
+        } else {
+            visitor.visit_f64(value)
+        }
+    }
+
+    /// Read an integer which may not fit in a `f64`, from its JSON text.
+    ///
+    /// Only JSON numbers are accepted, as when deserializing from the JSON of
+    /// the whole sample.
+    fn integer<'de, V: Visitor<'de>>(self, visitor: V) -> ConnectorResult<V::Value> {
+        self.state.ensure_field()?;
+        let json = self.state.with_field(|native, entity_name, index, field| {
+            native.get_json_member_c(entity_name, index, field)
+        })?;
+
+        match serde_json::from_str(&json) {
+            Ok(serde_json::Value::Number(number)) => {
+                if let Some(value) = number.as_u64() {
+                    visitor.visit_u64(value)
+                } else if let Some(value) = number.as_i64() {
+                    visitor.visit_i64(value)
+                } else if let Some(value) = number.as_f64() {
+                    visitor.visit_f64(value)
+                } else {
+                    Err(de::Error::invalid_type(
+                        de::Unexpected::Other("number"),
+                        &visitor,
+                    ))
+                }
+            }
+            Ok(serde_json::Value::String(text)) => Err(de::Error::invalid_type(
+                de::Unexpected::Str(&text),
+                &visitor,
+            )),
+            _ => Err(de::Error::invalid_type(
+                de::Unexpected::Other(json.trim()),
+                &visitor,
+            )),
+        }
+    }
+
+    /// Read the value as JSON, for the kinds which have no native getter.
+    fn json<'de, V: Visitor<'de>>(self, visitor: V) -> ConnectorResult<V::Value> {
+        de::Deserializer::deserialize_any(self.state.get_json()?, visitor)
+            .map_err(de::Error::custom)
+    }
+}
+
+impl<'de> de::Deserializer<'de> for FieldDeserializer<'_, '_> {
+    type Error = ConnectorError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> ConnectorResult<V::Value> {
+        self.json(visitor)
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> ConnectorResult<V::Value> {
+        visitor.visit_bool(self.state.get_boolean()?)
+    }
+
+    fn deserialize_i8<V: Visitor<'de>>(self, visitor: V) -> ConnectorResult<V::Value> {
+        self.number(visitor)
+    }
+
+    fn deserialize_i16<V: Visitor<'de>>(self, visitor: V) -> ConnectorResult<V::Value> {
+        self.number(visitor)
+    }
+
+    fn deserialize_i32<V: Visitor<'de>>(self, visitor: V) -> ConnectorResult<V::Value> {
+        self.number(visitor)
+    }
+
+    fn deserialize_i64<V: Visitor<'de>>(self, visitor: V) -> ConnectorResult<V::Value> {
+        self.integer(visitor)
+    }
+
+    fn deserialize_i128<V: Visitor<'de>>(self, visitor: V) -> ConnectorResult<V::Value> {
+        self.integer(visitor)
+    }
+
+    fn deserialize_u8<V: Visitor<'de>>(self, visitor: V) -> ConnectorResult<V::Value> {
+        self.number(visitor)
+    }
+
+    fn deserialize_u16<V: Visitor<'de>>(self, visitor: V) -> ConnectorResult<V::Value> {
+        self.number(visitor)
+    }
+
+    fn deserialize_u32<V: Visitor<'de>>(self, visitor: V) -> ConnectorResult<V::Value> {
+        self.number(visitor)
+    }
+
+    fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> ConnectorResult<V::Value> {
+        self.integer(visitor)
+    }
+
+    fn deserialize_u128<V: Visitor<'de>>(self, visitor: V) -> ConnectorResult<V::Value> {
+        self.integer(visitor)
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> ConnectorResult<V::Value> {
+        visitor.visit_f64(self.state.get_number()?)
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> ConnectorResult<V::Value> {
+        visitor.visit_f64(self.state.get_number()?)
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> ConnectorResult<V::Value> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> ConnectorResult<V::Value> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> ConnectorResult<V::Value> {
+        visitor.visit_str(&self.state.get_string()?)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> ConnectorResult<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> ConnectorResult<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    /// Unset optional members are `None`, like when they are missing from
+    /// the JSON representation of the sample.
+    fn deserialize_option<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> ConnectorResult<V::Value> {
+        if self.state.buffer.is_empty() || self.state.is_set()? {
+            visitor.visit_some(self)
+        } else {
+            visitor.visit_none()
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> ConnectorResult<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> ConnectorResult<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> ConnectorResult<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> ConnectorResult<V::Value> {
+        let len = self.state.get_length()?;
+
+        visitor.visit_seq(Elements {
+            state: self.state,
+            index: 0,
+            len,
+            flatten: false,
+            is_array: false,
+        })
+    }
+
+    /// Tuples are read as arrays, whose elements may be nested arrays.
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> ConnectorResult<V::Value> {
+        self.state.ensure_field()?;
+
+        visitor.visit_seq(Elements {
+            state: self.state,
+            index: 0,
+            len,
+            flatten: self.in_array,
+            is_array: true,
+        })
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> ConnectorResult<V::Value> {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> ConnectorResult<V::Value> {
+        self.json(visitor)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> ConnectorResult<V::Value> {
+        visitor.visit_map(Members {
+            state: self.state,
+            fields: fields.iter(),
+            len: None,
+        })
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> ConnectorResult<V::Value> {
+        de::Deserializer::deserialize_enum(
+            self.state.get_json()?,
+            name,
+            variants,
+            visitor,
+        )
+        .map_err(de::Error::custom)
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> ConnectorResult<V::Value> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> ConnectorResult<V::Value> {
+        visitor.visit_unit()
+    }
+}
+
+/// Gives access to the elements of a sequence or an array.
+struct Elements<'s, 'a> {
+    state: &'s mut State<'a>,
+
+    /// The index of the next element.
+    index: usize,
+
+    /// The number of elements.
+    len: usize,
+
+    /// Whether the elements are indexed as a dimension of a parent array.
+    flatten: bool,
+
+    /// Whether this is an array, whose elements may be nested arrays.
+    is_array: bool,
+}
+
+impl<'de> de::SeqAccess<'de> for Elements<'_, '_> {
+    type Error = ConnectorError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> ConnectorResult<Option<T::Value>> {
+        if self.index >= self.len {
+            return Ok(None);
+        }
+
+        let len = self.state.buffer.push_index(self.index, self.flatten);
+        self.index += 1;
+
+        let result = seed.deserialize(FieldDeserializer {
+            state: self.state,
+            in_array: self.is_array,
+        });
+        self.state.buffer.pop_index(len, self.flatten);
+        result.map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len - self.index)
+    }
+}
+
+/// Gives access to the members of a struct, in the order they are declared.
+struct Members<'s, 'a> {
+    state: &'s mut State<'a>,
+    fields: std::slice::Iter<'static, &'static str>,
+
+    /// The length of the field name before the current member.
+    len: Option<usize>,
+}
+
+impl<'de> de::MapAccess<'de> for Members<'_, '_> {
+    type Error = ConnectorError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> ConnectorResult<Option<K::Value>> {
+        let Some(&field) = self.fields.next() else {
+            return Ok(None);
+        };
+
+        self.len = Some(self.state.buffer.push_member(field));
+        seed.deserialize(field.into_deserializer()).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> ConnectorResult<V::Value> {
+        let result = seed.deserialize(FieldDeserializer {
+            state: self.state,
+            in_array: false,
+        });
+        if let Some(len) = self.len.take() {
+            self.state.buffer.truncate(len);
+        }
+        result
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.fields.len())
+    }
+}
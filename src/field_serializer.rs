@@ -0,0 +1,648 @@
+/*******************************************************************************
+ * (c) 2025 Copyright, Real-Time Innovations.  All rights reserved.            *
+ * No duplications, whole or partial, manual or electronic, may be made        *
+ * without express written permission.  Any such copies, or revisions thereof, *
+ * must display this notice unaltered.                                         *
+ * This code contains trade secrets of Real-Time Innovations, Inc.             *
+ *******************************************************************************/
+
+//! A Serde serializer which sets the fields of an output instance one by one,
+//! without going through a JSON representation of the data.
+//!
+//! Each leaf value is set with the native setter for its kind, using the
+//! field-name syntax of RTI Connector to address it: `.` separates members,
+//! `[i]` indexes sequences and `[i,j]` indexes multi-dimensional arrays. The
+//! field names and string values are written into a single reused buffer, so
+//! serializing does not allocate per field.
+
+use crate::{
+    ConnectorError, ConnectorFallible, ConnectorResult,
+    ffi::FfiConnector,
+    json_path::FieldNameBuf,
+    output::MAX_EXACT_INTEGER,
+    result::{ErrorKind, InvalidErrorKind},
+};
+use serde::ser::{self, Serialize};
+use std::ffi::CStr;
+
+/// Serialize `data` into the instance of the output named `entity_name`.
+///
+/// The data must serialize as a struct or as a map with string keys. Unless
+/// `allow_non_finite` is set, NaN and infinite numbers are rejected.
+pub(crate) fn serialize_into<T>(
+    native: &FfiConnector,
+    entity_name: &CStr,
+    data: &T,
+    allow_non_finite: bool,
+) -> ConnectorFallible
+where
+    T: Serialize + ?Sized,
+{
+    let mut state = State {
+        native: Some(native),
+        entity_name,
+        allow_non_finite,
+        buffer: Vec::with_capacity(256),
+    };
+
+    serde_path_to_error::serialize(
+        data,
+        FieldSerializer {
+            state: &mut state,
+            in_array: false,
+        },
+    )
+    .map_err(|e| {
+        let path = crate::json_path::from_serde_path("", e.path());
+        e.into_inner().with_field_path(path)
+    })
+}
+
+/// Check that `data`, to be set into `field`, contains no NaN or infinite
+/// numbers, without setting anything.
+///
+/// JSON can't represent those numbers, which `serde_json` serializes as
+/// `null` instead, so this reports the field which holds them.
+pub(crate) fn check_finite<T>(field: &str, data: &T) -> ConnectorFallible
+where
+    T: Serialize + ?Sized,
+{
+    let mut state = State {
+        native: None,
+        entity_name: c"",
+        allow_non_finite: false,
+        buffer: field.as_bytes().to_vec(),
+    };
+
+    serde_path_to_error::serialize(
+        data,
+        FieldSerializer {
+            state: &mut state,
+            in_array: false,
+        },
+    )
+    .map_err(|e| {
+        let path = crate::json_path::from_serde_path(field, e.path());
+        e.into_inner().with_field_path(path)
+    })
+}
+
+/// The state shared by the serializers of every field.
+struct State<'a> {
+    /// The native connector, or `None` to only check the values.
+    native: Option<&'a FfiConnector>,
+    entity_name: &'a CStr,
+
+    /// Whether NaN and infinite numbers can be set.
+    allow_non_finite: bool,
+
+    /// The field name of the value being serialized. String values are
+    /// appended after it, separated by a NUL character, when being set.
+    buffer: Vec<u8>,
+}
+
+impl State<'_> {
+    /// Check that a field is being serialized, rather than the whole instance.
+    fn ensure_field(&self) -> ConnectorFallible {
+        if self.buffer.is_empty() && self.native.is_some() {
+            ErrorKind::Invalid {
+                what: InvalidErrorKind::Serialization,
+                context: "Only structs and maps can be serialized into an instance"
+                    .to_string(),
+            }
+            .into_err()
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Split the buffer into the field name and the value appended after it.
+    fn with_field(
+        &mut self,
+        value: Option<&str>,
+        set: impl FnOnce(&FfiConnector, &CStr, &CStr, Option<&CStr>) -> ConnectorFallible,
+    ) -> ConnectorFallible {
+        self.ensure_field()?;
+        let Some(native) = self.native else {
+            return Ok(());
+        };
+
+        if let Some(offset) = self.buffer.iter().position(|&c| c == 0) {
+            return ErrorKind::nul_byte_error(
+                std::format!(
+                    "Field name '{}'",
+                    String::from_utf8_lossy(&self.buffer).escape_debug()
+                ),
+                offset,
+            )
+            .into_err();
+        }
+        if let Some(offset) = value.and_then(|value| value.find('\0')) {
+            return ErrorKind::nul_byte_error(
+                std::format!(
+                    "Value for field '{}'",
+                    String::from_utf8_lossy(&self.buffer)
+                ),
+                offset,
+            )
+            .into_err();
+        }
+
+        let field_len = self.buffer.len();
+        self.buffer.push(0);
+        if let Some(value) = value {
+            self.buffer.extend_from_slice(value.as_bytes());
+            self.buffer.push(0);
+        }
+
+        let (field, value) = self.buffer.split_at(field_len + 1);
+        let result = CStr::from_bytes_with_nul(field).and_then(|field| {
+            let value = match value {
+                [] => None,
+                value => Some(CStr::from_bytes_with_nul(value)?),
+            };
+            Ok(set(native, self.entity_name, field, value))
+        });
+
+        self.buffer.truncate(field_len);
+        result.map_err(|_| {
+            ConnectorError::from(ErrorKind::invalid_string_conversion_error())
+        })?
+    }
+
+    fn set_number(&mut self, value: f64) -> ConnectorFallible {
+        if !value.is_finite() && !self.allow_non_finite {
+            return ErrorKind::non_finite_number_error(
+                String::from_utf8_lossy(&self.buffer),
+                value,
+            )
+            .into_err();
+        }
+
+        self.with_field(None, |native, entity_name, field, _| {
+            native.set_number_into_samples_c(entity_name, field, value)
+        })
+    }
+
+    fn set_boolean(&mut self, value: bool) -> ConnectorFallible {
+        self.with_field(None, |native, entity_name, field, _| {
+            native.set_boolean_into_samples_c(entity_name, field, value)
+        })
+    }
+
+    fn set_string(&mut self, value: &str) -> ConnectorFallible {
+        self.with_field(Some(value), |native, entity_name, field, value| {
+            native.set_string_into_samples_c(
+                entity_name,
+                field,
+                value.unwrap_or_default(),
+            )
+        })
+    }
+
+    fn clear(&mut self) -> ConnectorFallible {
+        self.with_field(None, |native, entity_name, field, _| {
+            native.clear_member_c(entity_name, field)
+        })
+    }
+
+    /// Integers beyond the exact range of a `f64` are set from their decimal
+    /// representation, as [`Instance::set_i64`](crate::Instance::set_i64) does.
+    fn set_integer(&mut self, value: impl Into<i128>) -> ConnectorFallible {
+        let value: i128 = value.into();
+        if value.unsigned_abs() <= u128::from(MAX_EXACT_INTEGER) {
+            self.set_number(value as f64)
+        } else {
+            self.set_string(&value.to_string())
+        }
+    }
+}
+
+/// Serializes a value into the field whose name is in the [`State`] buffer.
+struct FieldSerializer<'s, 'a> {
+    state: &'s mut State<'a>,
+
+    /// Whether the value is an element of an array.
+    in_array: bool,
+}
+
+/// Serializes the members or elements of a value.
+struct Compound<'s, 'a> {
+    state: &'s mut State<'a>,
+
+    /// The index of the next element, for sequences and arrays.
+    index: usize,
+
+    /// Whether the elements are indexed as a dimension of a parent array.
+    flatten: bool,
+
+    /// Whether this is an array, whose elements may be nested arrays.
+    is_array: bool,
+
+    /// The length of the field name before the current map key.
+    key_len: Option<usize>,
+}
+
+impl<'s, 'a> Compound<'s, 'a> {
+    fn new(state: &'s mut State<'a>) -> Self {
+        Compound {
+            state,
+            index: 0,
+            flatten: false,
+            is_array: false,
+            key_len: None,
+        }
+    }
+
+    fn serialize_element<T>(&mut self, value: &T) -> ConnectorFallible
+    where
+        T: Serialize + ?Sized,
+    {
+        let len = self.state.buffer.push_index(self.index, self.flatten);
+        self.index += 1;
+
+        let result = value.serialize(FieldSerializer {
+            state: self.state,
+            in_array: self.is_array,
+        });
+        self.state.buffer.pop_index(len, self.flatten);
+        result
+    }
+
+    fn serialize_member<T>(&mut self, name: &str, value: &T) -> ConnectorFallible
+    where
+        T: Serialize + ?Sized,
+    {
+        let len = self.state.buffer.push_member(name);
+        let result = value.serialize(FieldSerializer {
+            state: self.state,
+            in_array: false,
+        });
+        self.state.buffer.truncate(len);
+        result
+    }
+}
+
+impl<'s, 'a> FieldSerializer<'s, 'a> {
+    /// Serialize an array, whose elements are indexed as a new dimension of
+    /// the parent array if this is one of its elements.
+    fn array(self) -> ConnectorResult<Compound<'s, 'a>> {
+        self.state.ensure_field()?;
+
+        Ok(Compound {
+            flatten: self.in_array,
+            is_array: true,
+            ..Compound::new(self.state)
+        })
+    }
+
+    /// Serialize a variant of an enum as a member of a union.
+    fn variant(self, variant: &'static str) -> (usize, Compound<'s, 'a>) {
+        let len = self.state.buffer.push_member(variant);
+        (len, Compound::new(self.state))
+    }
+}
+
+impl<'s, 'a> ser::Serializer for FieldSerializer<'s, 'a> {
+    type Ok = ();
+    type Error = ConnectorError;
+
+    type SerializeSeq = Compound<'s, 'a>;
+    type SerializeTuple = Compound<'s, 'a>;
+    type SerializeTupleStruct = Compound<'s, 'a>;
+    type SerializeTupleVariant = Compound<'s, 'a>;
+    type SerializeMap = Compound<'s, 'a>;
+    type SerializeStruct = Compound<'s, 'a>;
+    type SerializeStructVariant = Compound<'s, 'a>;
+
+    fn serialize_bool(self, v: bool) -> ConnectorFallible {
+        self.state.set_boolean(v)
+    }
+
+    fn serialize_i8(self, v: i8) -> ConnectorFallible {
+        self.state.set_number(f64::from(v))
+    }
+
+    fn serialize_i16(self, v: i16) -> ConnectorFallible {
+        self.state.set_number(f64::from(v))
+    }
+
+    fn serialize_i32(self, v: i32) -> ConnectorFallible {
+        self.state.set_number(f64::from(v))
+    }
+
+    fn serialize_i64(self, v: i64) -> ConnectorFallible {
+        self.state.set_integer(v)
+    }
+
+    fn serialize_u8(self, v: u8) -> ConnectorFallible {
+        self.state.set_number(f64::from(v))
+    }
+
+    fn serialize_u16(self, v: u16) -> ConnectorFallible {
+        self.state.set_number(f64::from(v))
+    }
+
+    fn serialize_u32(self, v: u32) -> ConnectorFallible {
+        self.state.set_number(f64::from(v))
+    }
+
+    fn serialize_u64(self, v: u64) -> ConnectorFallible {
+        self.state.set_integer(v)
+    }
+
+    fn serialize_f32(self, v: f32) -> ConnectorFallible {
+        self.state.set_number(f64::from(v))
+    }
+
+    fn serialize_f64(self, v: f64) -> ConnectorFallible {
+        self.state.set_number(v)
+    }
+
+    fn serialize_char(self, v: char) -> ConnectorFallible {
+        self.state.set_string(v.encode_utf8(&mut [0; 4]))
+    }
+
+    fn serialize_str(self, v: &str) -> ConnectorFallible {
+        self.state.set_string(v)
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> ConnectorFallible {
+        use ser::SerializeSeq;
+
+        let mut seq = self.serialize_seq(Some(v.len()))?;
+        for byte in v {
+            seq.serialize_element(byte)?;
+        }
+        seq.end()
+    }
+
+    /// Unset optional members, like with [`Instance::clear`](crate::Instance::clear).
+    fn serialize_none(self) -> ConnectorFallible {
+        self.state.clear()
+    }
+
+    fn serialize_some<T>(self, value: &T) -> ConnectorFallible
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> ConnectorFallible {
+        self.state.clear()
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> ConnectorFallible {
+        self.serialize_unit()
+    }
+
+    /// Enumerators are set by name, as their JSON representation would be.
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> ConnectorFallible {
+        self.state.set_string(variant)
+    }
+
+    fn serialize_newtype_struct<T>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> ConnectorFallible
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> ConnectorFallible
+    where
+        T: Serialize + ?Sized,
+    {
+        let len = self.state.buffer.push_member(variant);
+        let result = value.serialize(FieldSerializer {
+            state: self.state,
+            in_array: false,
+        });
+        self.state.buffer.truncate(len);
+        result
+    }
+
+    /// Sequences are cleared first, so that they don't keep any trailing
+    /// elements from a previous value.
+    fn serialize_seq(self, _len: Option<usize>) -> ConnectorResult<Compound<'s, 'a>> {
+        self.state.clear()?;
+        Ok(Compound::new(self.state))
+    }
+
+    fn serialize_tuple(self, _len: usize) -> ConnectorResult<Compound<'s, 'a>> {
+        self.array()
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> ConnectorResult<Compound<'s, 'a>> {
+        self.array()
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> ConnectorResult<Compound<'s, 'a>> {
+        let (len, compound) = self.variant(variant);
+        Ok(Compound {
+            is_array: true,
+            key_len: Some(len),
+            ..compound
+        })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> ConnectorResult<Compound<'s, 'a>> {
+        Ok(Compound::new(self.state))
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> ConnectorResult<Compound<'s, 'a>> {
+        Ok(Compound::new(self.state))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> ConnectorResult<Compound<'s, 'a>> {
+        let (len, compound) = self.variant(variant);
+        Ok(Compound {
+            key_len: Some(len),
+            ..compound
+        })
+    }
+}
+
+impl Compound<'_, '_> {
+    /// Restore the field name of a variant, once all of its members are set.
+    fn end_variant(self) -> ConnectorFallible {
+        if let Some(len) = self.key_len {
+            self.state.buffer.truncate(len);
+        }
+        Ok(())
+    }
+}
+
+impl ser::SerializeSeq for Compound<'_, '_> {
+    type Ok = ();
+    type Error = ConnectorError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> ConnectorFallible
+    where
+        T: Serialize + ?Sized,
+    {
+        Compound::serialize_element(self, value)
+    }
+
+    fn end(self) -> ConnectorFallible {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTuple for Compound<'_, '_> {
+    type Ok = ();
+    type Error = ConnectorError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> ConnectorFallible
+    where
+        T: Serialize + ?Sized,
+    {
+        Compound::serialize_element(self, value)
+    }
+
+    fn end(self) -> ConnectorFallible {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTupleStruct for Compound<'_, '_> {
+    type Ok = ();
+    type Error = ConnectorError;
+
+    fn serialize_field<T>(&mut self, value: &T) -> ConnectorFallible
+    where
+        T: Serialize + ?Sized,
+    {
+        Compound::serialize_element(self, value)
+    }
+
+    fn end(self) -> ConnectorFallible {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTupleVariant for Compound<'_, '_> {
+    type Ok = ();
+    type Error = ConnectorError;
+
+    fn serialize_field<T>(&mut self, value: &T) -> ConnectorFallible
+    where
+        T: Serialize + ?Sized,
+    {
+        Compound::serialize_element(self, value)
+    }
+
+    fn end(self) -> ConnectorFallible {
+        self.end_variant()
+    }
+}
+
+/// Maps are set as structs, so their keys must serialize as strings.
+impl ser::SerializeMap for Compound<'_, '_> {
+    type Ok = ();
+    type Error = ConnectorError;
+
+    fn serialize_key<T>(&mut self, key: &T) -> ConnectorFallible
+    where
+        T: Serialize + ?Sized,
+    {
+        let key = match serde_json::to_value(key) {
+            Ok(serde_json::Value::String(key)) => key,
+            _ => {
+                return ErrorKind::Invalid {
+                    what: InvalidErrorKind::Serialization,
+                    context: "Map keys must serialize as strings".to_string(),
+                }
+                .into_err();
+            }
+        };
+
+        self.key_len = Some(self.state.buffer.push_member(&key));
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> ConnectorFallible
+    where
+        T: Serialize + ?Sized,
+    {
+        let result = value.serialize(FieldSerializer {
+            state: self.state,
+            in_array: false,
+        });
+        if let Some(len) = self.key_len.take() {
+            self.state.buffer.truncate(len);
+        }
+        result
+    }
+
+    fn end(self) -> ConnectorFallible {
+        Ok(())
+    }
+}
+
+impl ser::SerializeStruct for Compound<'_, '_> {
+    type Ok = ();
+    type Error = ConnectorError;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> ConnectorFallible
+    where
+        T: Serialize + ?Sized,
+    {
+        self.serialize_member(key, value)
+    }
+
+    fn end(self) -> ConnectorFallible {
+        Ok(())
+    }
+}
+
+impl ser::SerializeStructVariant for Compound<'_, '_> {
+    type Ok = ();
+    type Error = ConnectorError;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> ConnectorFallible
+    where
+        T: Serialize + ?Sized,
+    {
+        self.serialize_member(key, value)
+    }
+
+    fn end(self) -> ConnectorFallible {
+        self.end_variant()
+    }
+}
@@ -9,9 +9,13 @@
 #![doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/docs/input.md"))]
 
 use crate::{
-    Connector, ConnectorFallible, ConnectorResult, SelectedValue,
+    ConnectorError, ConnectorFallible, ConnectorResult, EndpointInfo, MatchEvent,
+    SelectedValue, WriteParamsIdentity,
+    connector::ConnectorInner,
+    ffi::NativeString,
     result::{ErrorKind, InvalidErrorKind},
 };
+use std::{ffi::CString, sync::Arc};
 
 /// A wrapper which provides access to a single sample owned by an [`Input`].
 ///
@@ -33,13 +37,36 @@ use crate::{
 /// - `view_state`: A string representing the view state of the sample.
 /// - `sample_state`: A string representing the sample state of the sample.
 /// - `identity`: A string representing the identity of the sample publisher.
+///
+/// Use [`Sample::info_field_names`] to find which ones the native library
+/// provides.
+///
+/// A [`Sample`] borrows the [`Input`] it was obtained from, and the operations
+/// which replace or release the samples of an [`Input`] ([`Input::read`],
+/// [`Input::take`] and [`Input::return_loan`]) require exclusive access to it.
+/// Holding a [`Sample`] across any of them is therefore rejected at compile
+/// time, and accessors never operate on a stale sample:
+///
+/// ```rust,compile_fail,E0502
+/// # fn stale(mut input: rtiddsconnector::Input) -> rtiddsconnector::ConnectorFallible {
+/// let sample = input.sample_at(0)?;
+/// input.take()?; // Error: `input` is still borrowed by `sample`
+/// println!("{}", sample.get_number("x")?);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Use [`Input::snapshot`] to keep the contents of the samples instead.
 #[derive(Debug)]
 pub struct Sample<'a> {
     /// The index of the sample within the [`Input`]'s samples cache.
     index: usize,
 
     /// A reference to the parent [`Input`] object.
-    input: &'a Input<'a>,
+    input: &'a Input,
+
+    /// The parsed JSON of the sample, retrieved on first use by [`Sample::has_field`].
+    json: std::sync::OnceLock<Option<serde_json::Value>>,
 }
 
 /// Display the [`Sample`] as a JSON string.
@@ -52,7 +79,16 @@ impl std::fmt::Display for Sample<'_> {
     }
 }
 
-impl Sample<'_> {
+impl<'a> Sample<'a> {
+    /// Create a [`Sample`] for a position of an [`Input`]'s received sample cache.
+    fn new(index: usize, input: &'a Input) -> Self {
+        Sample {
+            index,
+            input,
+            json: std::sync::OnceLock::new(),
+        }
+    }
+
     /// Returns whether the sample contains valid data.
     pub fn is_valid(&self) -> ConnectorResult<bool> {
         self.input.is_valid(self.index)
@@ -68,6 +104,24 @@ impl Sample<'_> {
         self.input.get_info_json(self.index, field_name)
     }
 
+    /// Get the names of the info fields which the native library provides for
+    /// the sample, such as `source_timestamp`.
+    ///
+    /// The native library can't list them, so every info field known to this
+    /// crate is probed, keeping those which are found.
+    pub fn info_field_names(&self) -> ConnectorResult<Vec<String>> {
+        let mut names = Vec::new();
+        for name in INFO_FIELDS {
+            match self.get_info(name) {
+                Ok(_) => names.push(name.to_string()),
+                Err(e) if e.is_field_not_found() => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(names)
+    }
+
     /// Access a boolean field in the sample.
     pub fn get_boolean(&self, field_name: &str) -> ConnectorResult<bool> {
         self.input.get_boolean(self.index, field_name)
@@ -75,7 +129,24 @@ impl Sample<'_> {
 
     /// Access a string field in the sample.
     pub fn get_string(&self, field_name: &str) -> ConnectorResult<String> {
-        self.input.get_string(self.index, field_name)
+        self.input
+            .get_string(self.index, field_name)
+            .map(|value| value.to_string())
+    }
+
+    /// Access a string field in the sample, copying it into `buffer`.
+    ///
+    /// The contents of `buffer` are replaced, reusing its allocation, so that
+    /// reading a string field of many samples doesn't allocate each time.
+    pub fn get_string_into(
+        &self,
+        field_name: &str,
+        buffer: &mut String,
+    ) -> ConnectorFallible {
+        let value = self.input.get_string(self.index, field_name)?;
+        buffer.clear();
+        buffer.push_str(&value);
+        Ok(())
     }
 
     /// Access a numeric field in the sample.
@@ -84,13 +155,400 @@ impl Sample<'_> {
     }
 
     /// Access a variant-type field in the sample.
+    ///
+    /// Complex members, such as nested structures, arrays or sequences, are
+    /// returned as a [`SelectedValue::Json`].
     pub fn get_value(&self, field_name: &str) -> ConnectorResult<SelectedValue> {
-        self.input.get_field(self.index, field_name)
+        self.input
+            .get_field(self.index, field_name)
+            .or_else(|e| self.get_complex_value(field_name, e))
+    }
+
+    /// Access several variant-type fields in the sample at once.
+    ///
+    /// The values are returned in the order of `field_names`, and are the same
+    /// as those of calling [`Sample::get_value`] for each of them, but the
+    /// connector lock is acquired once for all the fields instead of once per
+    /// field. The first field which can't be retrieved fails the whole call.
+    ///
+    /// ```rust
+    /// # fn example(sample: &rtiddsconnector::Sample) -> rtiddsconnector::ConnectorFallible {
+    /// let values = sample.get_fields(&["x", "y", "color"])?;
+    /// println!("x={}, y={}, color={}", values[0], values[1], values[2]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_fields(
+        &self,
+        field_names: &[&str],
+    ) -> ConnectorResult<Vec<SelectedValue>> {
+        self.input
+            .get_fields(self.index, field_names)?
+            .into_iter()
+            .zip(field_names)
+            .map(|(result, field_name)| {
+                result.or_else(|e| self.get_complex_value(field_name, e))
+            })
+            .collect()
+    }
+
+    /// Access several variant-type fields in the sample at once, keyed by
+    /// field name.
+    ///
+    /// This is the same as [`Sample::get_fields`], returning a map instead.
+    pub fn get_fields_map(
+        &self,
+        field_names: &[&str],
+    ) -> ConnectorResult<std::collections::HashMap<String, SelectedValue>> {
+        Ok(field_names
+            .iter()
+            .map(|field_name| field_name.to_string())
+            .zip(self.get_fields(field_names)?)
+            .collect())
+    }
+
+    /// Retrieve a member which the native library can't return as a scalar
+    /// as JSON, or return the original error if it isn't a complex member.
+    fn get_complex_value(
+        &self,
+        field_name: &str,
+        error: ConnectorError,
+    ) -> ConnectorResult<SelectedValue> {
+        if error.is_field_not_found() || error.is_unset_optional() {
+            return Err(error);
+        }
+
+        match self
+            .get_value_json(field_name)
+            .map(|json| serde_json::from_str(&json))
+        {
+            Ok(Ok(
+                value @ (serde_json::Value::Object(_) | serde_json::Value::Array(_)),
+            )) => Ok(SelectedValue::Json(value)),
+            _ => Err(self.input.field_error(self.index, field_name, None, error)),
+        }
     }
 
     /// Access a field (as JSON) in the sample.
     pub fn get_value_json(&self, field_name: &str) -> ConnectorResult<String> {
-        self.input.get_field_json(self.index, field_name)
+        self.input
+            .get_field_json(self.index, field_name)
+            .map(|json| json.to_string())
+    }
+
+    /// Access a field in the sample as a [`serde_json::Value`], whatever its
+    /// type, such as `simple` for a nested structure or `simple.long_field`.
+    ///
+    /// Integers are kept exactly, unlike with [`Sample::get_value`].
+    pub fn get_member_value(
+        &self,
+        field_name: &str,
+    ) -> ConnectorResult<serde_json::Value> {
+        self.deserialize_field(field_name)
+    }
+
+    /// Access a signed integer field in the sample, without loss of precision.
+    ///
+    /// Unlike [`Sample::get_number`], the value isn't converted into a `f64`, so
+    /// `int64` fields beyond 2^53 are returned exactly.
+    pub fn get_i64(&self, field_name: &str) -> ConnectorResult<i64> {
+        self.get_integer(field_name)
+    }
+
+    /// Access an unsigned integer field in the sample, without loss of precision.
+    ///
+    /// Unlike [`Sample::get_number`], the value isn't converted into a `f64`, so
+    /// `uint64` fields beyond 2^53 are returned exactly.
+    pub fn get_u64(&self, field_name: &str) -> ConnectorResult<u64> {
+        self.get_integer(field_name)
+    }
+
+    /// Access a numeric field in the sample as an `i32`.
+    ///
+    /// An error is returned if the value isn't an integer within the range of `i32`.
+    pub fn get_i32(&self, field_name: &str) -> ConnectorResult<i32> {
+        self.get_checked_integer(field_name)
+    }
+
+    /// Access a numeric field in the sample as an `u32`.
+    ///
+    /// An error is returned if the value isn't an integer within the range of `u32`.
+    pub fn get_u32(&self, field_name: &str) -> ConnectorResult<u32> {
+        self.get_checked_integer(field_name)
+    }
+
+    /// Access a DDS `Time_t`-style struct field, such as `timestamp`, as a
+    /// [`SystemTime`](std::time::SystemTime), through its `sec` and `nanosec`
+    /// members.
+    pub fn get_time(&self, field_name: &str) -> ConnectorResult<std::time::SystemTime> {
+        crate::DdsTime {
+            sec: self.get_i32(&std::format!("{}.sec", field_name))?,
+            nanosec: self.get_u32(&std::format!("{}.nanosec", field_name))?,
+        }
+        .try_into()
+    }
+
+    /// Access a numeric field in the sample as an `i16`.
+    ///
+    /// An error is returned if the value isn't an integer within the range of `i16`.
+    pub fn get_i16(&self, field_name: &str) -> ConnectorResult<i16> {
+        self.get_checked_integer(field_name)
+    }
+
+    /// Access a numeric field in the sample as an `u16`.
+    ///
+    /// An error is returned if the value isn't an integer within the range of `u16`.
+    pub fn get_u16(&self, field_name: &str) -> ConnectorResult<u16> {
+        self.get_checked_integer(field_name)
+    }
+
+    /// Access a numeric field in the sample as an `u8`.
+    ///
+    /// An error is returned if the value isn't an integer within the range of `u8`.
+    pub fn get_u8(&self, field_name: &str) -> ConnectorResult<u8> {
+        self.get_checked_integer(field_name)
+    }
+
+    /// Access a numeric field, checking that it holds an integer which fits in `T`.
+    pub(crate) fn get_checked_integer<T: TryFrom<i64>>(
+        &self,
+        field_name: &str,
+    ) -> ConnectorResult<T> {
+        let value = self.get_number(field_name)?;
+        let integer = value as i64;
+
+        // NaN, infinities and fractional or out-of-range values don't round-trip
+        if integer as f64 == value
+            && let Ok(integer) = T::try_from(integer)
+        {
+            Ok(integer)
+        } else {
+            ErrorKind::Invalid {
+                what: InvalidErrorKind::Conversion,
+                context: std::format!(
+                    "Field '{}' is not a {}: {}",
+                    field_name,
+                    std::any::type_name::<T>(),
+                    value
+                ),
+            }
+            .into_err()
+        }
+    }
+
+    /// Access an integer field through its exact JSON representation.
+    ///
+    /// Only JSON numbers are accepted, so string members holding digits are
+    /// rejected like any other non-integer value.
+    fn get_integer<T>(&self, field_name: &str) -> ConnectorResult<T>
+    where
+        T: TryFrom<i64> + TryFrom<u64>,
+    {
+        let json = self.get_value_json(field_name)?;
+        let value = json.trim();
+
+        let integer = serde_json::from_str::<serde_json::Number>(value)
+            .ok()
+            .and_then(|number| match (number.as_u64(), number.as_i64()) {
+                (Some(unsigned), _) => T::try_from(unsigned).ok(),
+                (None, Some(signed)) => T::try_from(signed).ok(),
+                (None, None) => None,
+            });
+
+        integer.ok_or_else(|| {
+            ErrorKind::Invalid {
+                what: InvalidErrorKind::Conversion,
+                context: std::format!(
+                    "Field '{}' is not a {}: {}",
+                    field_name,
+                    std::any::type_name::<T>(),
+                    value
+                ),
+            }
+            .into()
+        })
+    }
+
+    /// Access a byte sequence or array field in the sample.
+    ///
+    /// The whole field is retrieved at once, which is much faster than
+    /// accessing each element individually.
+    pub fn get_bytes(&self, field_name: &str) -> ConnectorResult<Vec<u8>> {
+        self.get_sequence(field_name)
+    }
+
+    /// Access a union field in the sample, returning the name of its active
+    /// member along with its value.
+    ///
+    /// If the active member is of a complex type, its value is a [`SelectedValue::Json`].
+    pub fn get_union(
+        &self,
+        field_name: &str,
+    ) -> ConnectorResult<(String, SelectedValue)> {
+        let member = self.get_string(&std::format!("{}#", field_name))?;
+        let value = self.get_value(&std::format!("{}.{}", field_name, member))?;
+
+        Ok((member, value))
+    }
+
+    /// Access an enumeration field in the sample by the name of its enumerator.
+    ///
+    /// If the name of the enumerator isn't available, the numeric value is
+    /// returned as a string instead.
+    pub fn get_enum_string(&self, field_name: &str) -> ConnectorResult<String> {
+        let json = self.get_value_json(field_name)?;
+
+        match serde_json::from_str(&json) {
+            Ok(serde_json::Value::String(label)) => Ok(label),
+            Ok(serde_json::Value::Number(value)) => Ok(value.to_string()),
+            _ => ErrorKind::Invalid {
+                what: InvalidErrorKind::Conversion,
+                context: std::format!(
+                    "Field '{}' is not an enumeration: {}",
+                    field_name,
+                    json
+                ),
+            }
+            .into_err(),
+        }
+    }
+
+    /// Get the number of elements of a sequence or array field in the sample.
+    ///
+    /// For sequences this is their current length, and for arrays their fixed
+    /// size. An error is returned for fields which are neither.
+    pub fn get_length(&self, field_name: &str) -> ConnectorResult<usize> {
+        // Sequences support the `field#` syntax for their length, otherwise
+        // count the elements of the JSON member
+        if let Ok(length) = self.get_number(&std::format!("{}#", field_name)) {
+            return count_from_native("sequence length", length);
+        }
+
+        let json = self.get_value_json(field_name)?;
+        match serde_json::from_str(&json) {
+            Ok(serde_json::Value::Array(elements)) => Ok(elements.len()),
+            _ => ErrorKind::Invalid {
+                what: InvalidErrorKind::Conversion,
+                context: std::format!(
+                    "Field '{}' is not a sequence or array: {}",
+                    field_name,
+                    json
+                ),
+            }
+            .into_err(),
+        }
+    }
+
+    /// Access a sequence or array field in the sample, deserializing each element into `T`.
+    ///
+    /// The whole field is retrieved at once, so its length doesn't need to be
+    /// known in advance. Elements which can't be converted are reported by index.
+    pub fn get_sequence<T>(&self, field_name: &str) -> ConnectorResult<Vec<T>>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let json = self.get_value_json(field_name)?;
+        let conversion_error = |context: String| -> ConnectorError {
+            ErrorKind::Invalid {
+                what: InvalidErrorKind::Conversion,
+                context,
+            }
+            .into()
+        };
+
+        let elements = match serde_json::from_str(&json) {
+            Ok(serde_json::Value::Array(elements)) => elements,
+            _ => {
+                return Err(conversion_error(std::format!(
+                    "Field '{}' is not a sequence: {}",
+                    field_name,
+                    json
+                )));
+            }
+        };
+
+        elements
+            .into_iter()
+            .enumerate()
+            .map(|(index, element)| {
+                serde_json::from_value(element).map_err(|e| {
+                    conversion_error(std::format!(
+                        "Element #{} of field '{}' is not a {}: {}",
+                        index,
+                        field_name,
+                        std::any::type_name::<T>(),
+                        e
+                    ))
+                })
+            })
+            .collect()
+    }
+
+    /// Returns whether a field, such as `simple.long_field`, exists in the sample.
+    ///
+    /// Unset optional members exist, even if they hold no value. The JSON of
+    /// the sample is parsed once and reused by subsequent calls, so that
+    /// probing fields doesn't go through the native error path, except for
+    /// the members missing from a structure, which may be unset optionals.
+    pub fn has_field(&self, field_name: &str) -> ConnectorResult<bool> {
+        let json = self.json.get_or_init(|| {
+            self.get_as_json()
+                .ok()
+                .and_then(|json| serde_json::from_str(&json).ok())
+        });
+
+        if let Some(json) = json {
+            if crate::json_path::resolve(json, field_name).is_some() {
+                return Ok(true);
+            }
+
+            if crate::json_path::resolve_parent(json, field_name).is_none() {
+                return Ok(false);
+            }
+        }
+
+        // Unset optional members are missing from the JSON, ask the native library
+        match self.get_value_json(field_name) {
+            Ok(_) => Ok(true),
+            Err(e) if e.is_unset_optional() => Ok(true),
+            Err(e) if e.is_field_not_found() => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Access a boolean field in the sample, which may be an unset optional member.
+    ///
+    /// Returns `None` if the field is an unset optional member, and an error
+    /// if the field doesn't exist in the type.
+    pub fn try_get_boolean(&self, field_name: &str) -> ConnectorResult<Option<bool>> {
+        unset_optional_as_none(self.get_boolean(field_name))
+    }
+
+    /// Access a string field in the sample, which may be an unset optional member.
+    ///
+    /// Returns `None` if the field is an unset optional member, and an error
+    /// if the field doesn't exist in the type.
+    pub fn try_get_string(&self, field_name: &str) -> ConnectorResult<Option<String>> {
+        unset_optional_as_none(self.get_string(field_name))
+    }
+
+    /// Access a numeric field in the sample, which may be an unset optional member.
+    ///
+    /// Returns `None` if the field is an unset optional member, and an error
+    /// if the field doesn't exist in the type.
+    pub fn try_get_number(&self, field_name: &str) -> ConnectorResult<Option<f64>> {
+        unset_optional_as_none(self.get_number(field_name))
+    }
+
+    /// Access a variant-type field in the sample, which may be an unset optional member.
+    ///
+    /// Returns `None` if the field is an unset optional member, and an error
+    /// if the field doesn't exist in the type.
+    pub fn try_get_value(
+        &self,
+        field_name: &str,
+    ) -> ConnectorResult<Option<SelectedValue>> {
+        unset_optional_as_none(self.get_value(field_name))
     }
 
     /// Deserialize the sample into a concrete type using Serde.
@@ -106,173 +564,1996 @@ impl Sample<'_> {
     where
         T: for<'de> serde::Deserialize<'de>,
     {
-        let json = self.get_as_json().map_err(|e| ErrorKind::Invalid {
-            what: InvalidErrorKind::Deserialization,
-            context: std::format!(
-                "Failed getting JSON for deserialization of type '{}': {}",
-                std::any::type_name::<T>(),
-                e
-            ),
-        })?;
+        self.deserialize_json(|json| from_json_str(json))
+    }
 
-        let json = serde_json::from_str::<T>(&json).map_err(|e| ErrorKind::Invalid {
-            what: InvalidErrorKind::Deserialization,
-            context: std::format!(
-                "Failed deserializing JSON ({}) into type '{}': {}",
-                json,
-                std::any::type_name::<T>(),
-                e
-            ),
-        })?;
+    /// Deserialize the sample into a concrete type using Serde, choosing how
+    /// strictly the data must match it.
+    ///
+    /// With the default [`DeserializeOptions`], this is the same as
+    /// [`Sample::deserialize`]: members of the sample which the type doesn't
+    /// have are ignored, and members missing from the sample, such as unset
+    /// optional members, are errors unless the type makes them optional.
+    ///
+    /// ```rust
+    /// # #[derive(serde::Deserialize)]
+    /// # struct Shape { color: String, x: i32, y: i32, shapesize: i32 }
+    /// use rtiddsconnector::{DeserializeOptions, Sample};
+    ///
+    /// fn strict_shape(sample: &Sample) -> rtiddsconnector::ConnectorResult<Shape> {
+    ///     sample.deserialize_with(DeserializeOptions::new().deny_unknown_fields(true))
+    /// }
+    /// ```
+    pub fn deserialize_with<T>(&self, options: DeserializeOptions) -> ConnectorResult<T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        if options == DeserializeOptions::default() {
+            return self.deserialize();
+        }
 
-        Ok(json)
+        self.deserialize_json(|json| {
+            from_json_str(json).and_then(|value| {
+                crate::value_deserializer::deserialize_value(value, &options)
+            })
+        })
     }
 
-    /// Turn the sample into a JSON string.
-    pub(crate) fn get_as_json(&self) -> ConnectorResult<String> {
-        self.input.get_json(self.index)
+    /// Deserialize the sample into a type which borrows from its JSON, such as
+    /// one with `&str` or `Cow<str>` fields, to avoid allocating them.
+    ///
+    /// The JSON of the sample is copied into `buf`, replacing its contents, and
+    /// the returned value borrows from it, so reusing the same buffer for every
+    /// sample avoids allocations once it is large enough.
+    ///
+    /// Strings containing escape sequences, such as `\"` or `\n`, can't be
+    /// borrowed from the JSON. Fields of type `&str` fail to deserialize them,
+    /// while fields of type `Cow<str>` annotated with `#[serde(borrow)]` own
+    /// those strings only.
+    ///
+    /// ```rust
+    /// use rtiddsconnector::{ConnectorResult, Input};
+    /// use std::borrow::Cow;
+    ///
+    /// #[derive(serde::Deserialize)]
+    /// struct Shape<'a> {
+    ///     #[serde(borrow)]
+    ///     color: Cow<'a, str>,
+    ///     x: i32,
+    /// }
+    ///
+    /// fn print_shapes(input: &Input) -> ConnectorResult<()> {
+    ///     let mut buf = String::new();
+    ///     for sample in input.valid_samples()? {
+    ///         let shape: Shape = sample?.deserialize_borrowed(&mut buf)?;
+    ///         println!("{} at {}", shape.color, shape.x);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn deserialize_borrowed<'s, T>(&self, buf: &'s mut String) -> ConnectorResult<T>
+    where
+        T: serde::Deserialize<'s>,
+    {
+        let json = self.get_as_json().map_err(json_error::<T>)?;
+        buf.clear();
+        buf.push_str(&json);
+
+        let json: &'s str = buf;
+        from_json_str(json).map_err(|e| parse_error::<T>(json, e))
     }
-}
 
-/// An [`Iterator`] which returns individual [`Sample`] elements.
-///
-/// ```rust
-#[doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/snippets/input/using_sample_iterator.rs"))]
-/// ```
-pub struct SampleIterator<'a> {
-    /// The current index in the iteration.
-    index: usize,
+    /// Get the JSON of the sample and deserialize it with `parse`.
+    fn deserialize_json<T>(
+        &self,
+        parse: impl FnOnce(&str) -> Result<T, PathError>,
+    ) -> ConnectorResult<T> {
+        let json = self.get_as_json().map_err(json_error::<T>)?;
 
-    /// The total number of samples available.
-    samples_len: usize,
+        parse(&json).map_err(|e| parse_error::<T>(&json, e))
+    }
 
-    /// A reference to the parent [`Input`] object.
-    input: &'a Input<'a>,
-}
+    /// Deserialize a single field of the sample, such as a nested structure,
+    /// into a concrete type using Serde.
+    ///
+    /// Only the JSON of the field is retrieved, so `T` needs to model that
+    /// field only, rather than the whole type.
+    pub fn deserialize_field<T>(&self, field_name: &str) -> ConnectorResult<T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let json = self.get_value_json(field_name)?;
 
-/// Implements the core iteration logic for [`SampleIterator`].
-impl<'a> Iterator for SampleIterator<'a> {
-    type Item = Sample<'a>;
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.index < self.samples_len {
-            let result = Some(Self::Item {
-                index: self.index,
-                input: self.input,
-            });
-            self.index += 1;
+        from_json_str::<T>(&json).map_err(|e| {
+            let path = crate::json_path::from_serde_path(field_name, e.path());
+            ErrorKind::Invalid {
+                what: InvalidErrorKind::Deserialization,
+                context: std::format!(
+                    "Failed deserializing field '{}' ({}) into type '{}': {}",
+                    field_name,
+                    json,
+                    std::any::type_name::<T>(),
+                    e
+                ),
+            }
+            .caused_by(e)
+            .with_field_path(path)
+        })
+    }
 
-            result
-        } else {
-            None
-        }
+    /// Deserialize a union field of the sample, such as `union`, into a
+    /// [`DdsUnion`](crate::DdsUnion).
+    ///
+    /// Unlike deserializing the union from its JSON, which probes its members,
+    /// its active member is selected by the discriminator of the union, from
+    /// the `field#` pseudo-field.
+    pub fn deserialize_union<T>(&self, field_name: &str) -> ConnectorResult<T>
+    where
+        T: crate::DdsUnion,
+    {
+        let member = self.get_string(&std::format!("{}#", field_name))?;
+        let member_field = std::format!("{}.{}", field_name, member);
+        let json = self.get_value_json(&member_field)?;
+
+        let mut deserializer = serde_json::Deserializer::from_str(&json);
+        let mut track = serde_path_to_error::Track::new();
+        T::deserialize_member(
+            &member,
+            serde_path_to_error::Deserializer::new(&mut deserializer, &mut track),
+        )
+        .and_then(|value| deserializer.end().map(|()| value))
+        .map_err(|e| {
+            let path = crate::json_path::from_serde_path(&member_field, &track.path());
+            ErrorKind::Invalid {
+                    what: InvalidErrorKind::Deserialization,
+                    context: std::format!(
+                        "Failed deserializing member '{}' of union '{}' ({}) into type '{}': {}",
+                        member,
+                        field_name,
+                        json,
+                        std::any::type_name::<T>(),
+                        e
+                    ),
+                }
+                .caused_by(e)
+                .with_field_path(path)
+            })
     }
 
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        let remaining = self.len();
-        (remaining, Some(remaining))
+    /// Deserialize the sample into a concrete type, getting each field
+    /// directly rather than through its JSON representation.
+    ///
+    /// The result is the same as with [`Sample::deserialize`], including
+    /// unset optional members as `None` and the exact values of 64-bit
+    /// integers, but the fields are read one by one with the typed getters,
+    /// avoiding building and parsing the JSON of the whole sample. Only the
+    /// 64-bit integers, maps and enums which aren't represented as numbers
+    /// are read as JSON. The type must be a struct.
+    pub fn deserialize_direct<T>(&self) -> ConnectorResult<T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        self.input.deserialize_direct(self.index)
     }
-}
 
-/// Allows [`SampleIterator`] to implement `len()`.
-impl ExactSizeIterator for SampleIterator<'_> {
-    fn len(&self) -> usize {
-        self.samples_len - self.index
+    /// Deserialize the sample, reporting its index in the error context on failure.
+    fn deserialize_with_index<T>(&self) -> ConnectorResult<T>
+    where
+        T: for<'de> serde::Deserialize<'de>,
+    {
+        self.deserialize().map_err(|e| {
+            ErrorKind::Invalid {
+                what: InvalidErrorKind::Deserialization,
+                context: std::format!(
+                    "Sample #{} could not be deserialized: {}",
+                    self.index,
+                    e
+                ),
+            }
+            .caused_by(e)
+        })
     }
-}
 
-/// Allows transforming a [`SampleIterator`] into a [`ValidSampleIterator`].
-impl<'a> SampleIterator<'a> {
-    /// Create a [`ValidSampleIterator`] which yields only valid samples,
-    /// out of this [`SampleIterator`].
-    pub fn valid_only(self) -> ValidSampleIterator<'a> {
-        ValidSampleIterator(self)
+    /// Get the sample state of the sample, from its `sample_state` info field.
+    pub fn sample_state(&self) -> ConnectorResult<SampleState> {
+        self.get_state("sample_state", SampleState::from_info)
     }
-}
 
-/// A specialized [`SampleIterator`] which returns only valid [`Sample`] elements.
-///
-/// ```rust
-#[doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/snippets/input/using_valid_sample_iterator.rs"))]
-/// ```
-pub struct ValidSampleIterator<'a>(SampleIterator<'a>);
+    /// Get the view state of the sample, from its `view_state` info field.
+    pub fn view_state(&self) -> ConnectorResult<ViewState> {
+        self.get_state("view_state", ViewState::from_info)
+    }
 
-impl<'a> Iterator for ValidSampleIterator<'a> {
-    type Item = Sample<'a>;
-    fn next(&mut self) -> Option<Self::Item> {
-        for sample in &mut self.0.by_ref() {
-            match sample.is_valid() {
-                Ok(true) => return Some(sample),
-                // Skip invalid samples and try the next one
-                other => {
-                    if let Err(e) = other {
-                        eprintln!(
-                            "Error checking sample validity, skipping sample: {}",
-                            e
-                        );
-                    }
-                    continue;
-                }
-            }
-        }
+    /// Get the instance state of the sample, from its `instance_state` info field.
+    ///
+    /// This is meaningful for samples without valid data too, such as those
+    /// notifying that an instance was disposed.
+    pub fn instance_state(&self) -> ConnectorResult<InstanceState> {
+        self.get_state("instance_state", InstanceState::from_info)
+    }
 
-        None // No more samples or error occurred
+    /// Retrieve and parse every info field of the sample into a [`SampleInfo`].
+    ///
+    /// Fails if any info field can't be retrieved or parsed. To leave such
+    /// fields out instead, use [`SampleIterator::with_info`].
+    pub fn info(&self) -> ConnectorResult<SampleInfo> {
+        Ok(SampleInfo {
+            valid_data: self.is_valid()?,
+            source_timestamp: self.get_timestamp("source_timestamp")?,
+            reception_timestamp: self.get_timestamp("reception_timestamp")?,
+            identity: self.identity()?,
+            related_identity: self.related_identity()?,
+            sample_state: self.sample_state()?,
+            view_state: self.view_state()?,
+            instance_state: self.instance_state()?,
+            sample_rank: self.sample_rank()?,
+            generation_rank: self.generation_rank()?,
+            absolute_generation_rank: self.absolute_generation_rank()?,
+            disposed_generation_count: self.disposed_generation_count()?,
+            no_writers_generation_count: self.no_writers_generation_count()?,
+        })
     }
 
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        // We can't know how many valid samples are left without iterating
-        (0, Some(self.0.len()))
+    /// Get the identity of the sample, from its `sample_identity` info field.
+    ///
+    /// It can be used as the related identity of a reply, in [`WriteParams`](crate::WriteParams).
+    pub fn identity(&self) -> ConnectorResult<SampleIdentity> {
+        self.get_identity("sample_identity")
     }
-}
+
+    /// Get the GUID of the writer of the sample, from its `sample_identity` info field.
+    ///
+    /// Wrap it in a [`Guid`] to display or compare it.
+    pub fn writer_guid(&self) -> ConnectorResult<[u8; 16]> {
+        self.identity().map(|identity| identity.writer_guid)
+    }
+
+    /// Get the identity of the sample this one is related to, from its
+    /// `related_sample_identity` info field.
+    ///
+    /// Returns `None` if the writer didn't set a related identity.
+    pub fn related_identity(&self) -> ConnectorResult<Option<SampleIdentity>> {
+        self.get_identity("related_sample_identity")
+            .map(|identity| {
+                Some(identity).filter(|identity| identity.writer_guid != [0; 16])
+            })
+    }
+
+    /// Returns whether this sample is a reply to the request with the given identity,
+    /// that is, whether its related sample identity matches it.
+    ///
+    /// See [`Output::reply_to`](crate::Output::reply_to) for the replier side.
+    pub fn is_reply_to(&self, identity: &SampleIdentity) -> ConnectorResult<bool> {
+        Ok(self.related_identity()?.as_ref() == Some(identity))
+    }
+
+    /// Get the handle of the instance of the sample, from its `instance_handle`
+    /// info field.
+    ///
+    /// Samples of the same instance have equal handles. Returns `None` if the
+    /// native library doesn't provide the info field, or the handle is nil.
+    pub fn instance_handle(&self) -> ConnectorResult<Option<Handle>> {
+        self.get_handle("instance_handle")
+    }
+
+    /// Get the handle of the matched publication which wrote the sample, from
+    /// its `publication_handle` info field.
+    ///
+    /// Samples from the same writer have equal handles. Returns `None` if the
+    /// native library doesn't provide the info field, or the handle is nil.
+    pub fn publication_handle(&self) -> ConnectorResult<Option<Handle>> {
+        self.get_handle("publication_handle")
+    }
+
+    /// Get the number of samples of the same instance which follow this one in
+    /// the samples cache, from its `sample_rank` info field.
+    ///
+    /// Returns `None` if the native library doesn't provide the info field.
+    pub fn sample_rank(&self) -> ConnectorResult<Option<i32>> {
+        self.get_count("sample_rank")
+    }
+
+    /// Get the number of generations of the instance between this sample and
+    /// the most recent sample of the instance in the samples cache, from its
+    /// `generation_rank` info field.
+    ///
+    /// Returns `None` if the native library doesn't provide the info field.
+    pub fn generation_rank(&self) -> ConnectorResult<Option<i32>> {
+        self.get_count("generation_rank")
+    }
+
+    /// Get the number of generations of the instance between this sample and
+    /// the most recent sample of the instance received, from its
+    /// `absolute_generation_rank` info field.
+    ///
+    /// Returns `None` if the native library doesn't provide the info field.
+    pub fn absolute_generation_rank(&self) -> ConnectorResult<Option<i32>> {
+        self.get_count("absolute_generation_rank")
+    }
+
+    /// Get the number of times the instance became alive after being disposed,
+    /// when the sample was received, from its `disposed_generation_count` info
+    /// field.
+    ///
+    /// Returns `None` if the native library doesn't provide the info field.
+    pub fn disposed_generation_count(&self) -> ConnectorResult<Option<i32>> {
+        self.get_count("disposed_generation_count")
+    }
+
+    /// Get the number of times the instance became alive after having no
+    /// writers, when the sample was received, from its
+    /// `no_writers_generation_count` info field.
+    ///
+    /// Returns `None` if the native library doesn't provide the info field.
+    pub fn no_writers_generation_count(&self) -> ConnectorResult<Option<i32>> {
+        self.get_count("no_writers_generation_count")
+    }
+
+    /// Get the source timestamp of the sample, in nanoseconds since the DDS epoch.
+    pub fn source_timestamp_nanos(&self) -> ConnectorResult<i64> {
+        self.get_timestamp("source_timestamp")
+    }
+
+    /// Get the reception timestamp of the sample, in nanoseconds since the DDS epoch.
+    pub fn reception_timestamp_nanos(&self) -> ConnectorResult<i64> {
+        self.get_timestamp("reception_timestamp")
+    }
+
+    /// Get the source timestamp of the sample, set by the writer.
+    pub fn source_timestamp(&self) -> ConnectorResult<std::time::SystemTime> {
+        system_time_from_nanos(self.source_timestamp_nanos()?)
+    }
+
+    /// Get the reception timestamp of the sample, set by the reader.
+    pub fn reception_timestamp(&self) -> ConnectorResult<std::time::SystemTime> {
+        system_time_from_nanos(self.reception_timestamp_nanos()?)
+    }
+
+    /// Returns whether the instance of the sample is alive.
+    pub fn is_alive(&self) -> ConnectorResult<bool> {
+        Ok(self.instance_state()? == InstanceState::Alive)
+    }
+
+    /// Returns whether the instance of the sample was disposed.
+    ///
+    /// This is typically notified with a sample without valid data.
+    pub fn is_disposed(&self) -> ConnectorResult<bool> {
+        Ok(self.instance_state()? == InstanceState::NotAliveDisposed)
+    }
+
+    /// Returns whether the instance of the sample was unregistered by every writer.
+    ///
+    /// This is typically notified with a sample without valid data.
+    pub fn is_unregistered(&self) -> ConnectorResult<bool> {
+        Ok(self.instance_state()? == InstanceState::NotAliveNoWriters)
+    }
+
+    /// Returns whether the sample matches every state required by a [`SampleStateMask`].
+    fn matches_state(&self, mask: &SampleStateMask) -> ConnectorResult<bool> {
+        if let Some(state) = mask.sample_state
+            && self.sample_state()? != state
+        {
+            return Ok(false);
+        }
+
+        if let Some(state) = mask.view_state
+            && self.view_state()? != state
+        {
+            return Ok(false);
+        }
+
+        if let Some(state) = mask.instance_state
+            && self.instance_state()? != state
+        {
+            return Ok(false);
+        }
+
+        Ok(true)
+    }
+
+    /// Get and parse one of the timestamp info fields of the sample, in nanoseconds.
+    fn get_timestamp(&self, field_name: &str) -> ConnectorResult<i64> {
+        let json = self.get_info_json(field_name)?;
+        let value = json.trim().trim_matches('"');
+
+        value.parse().map_err(|_| {
+            ErrorKind::Invalid {
+                what: InvalidErrorKind::Conversion,
+                context: std::format!("Invalid {} value: {}", field_name, value),
+            }
+            .into()
+        })
+    }
+
+    /// Get and parse one of the identity info fields of the sample.
+    fn get_identity(&self, field_name: &str) -> ConnectorResult<SampleIdentity> {
+        let json = self.get_info_json(field_name)?;
+
+        parse_identity(&json).ok_or_else(|| {
+            ErrorKind::Invalid {
+                what: InvalidErrorKind::Conversion,
+                context: std::format!("Invalid {} value: {}", field_name, json),
+            }
+            .into()
+        })
+    }
+
+    /// Get and parse one of the rank or count info fields of the sample, if
+    /// provided.
+    fn get_count(&self, field_name: &str) -> ConnectorResult<Option<i32>> {
+        let value = match self.get_info(field_name) {
+            Ok(value) => value,
+            Err(e) if e.is_field_not_found() => return Ok(None),
+            Err(e) => return Err(e),
+        };
+
+        let count = match &value {
+            SelectedValue::Number(number)
+                if number.fract() == 0.0
+                    && (f64::from(i32::MIN)..=f64::from(i32::MAX)).contains(number) =>
+            {
+                Some(*number as i32)
+            }
+            SelectedValue::String(string) => string.trim().trim_matches('"').parse().ok(),
+            _ => None,
+        };
+
+        match count {
+            Some(count) => Ok(Some(count)),
+            None => ErrorKind::Invalid {
+                what: InvalidErrorKind::Conversion,
+                context: std::format!("Invalid {} value: {:?}", field_name, value),
+            }
+            .into_err(),
+        }
+    }
+
+    /// Get and parse one of the handle info fields of the sample, if provided.
+    fn get_handle(&self, field_name: &str) -> ConnectorResult<Option<Handle>> {
+        let json = match self.get_info_json(field_name) {
+            Ok(json) => json,
+            Err(e) if e.is_field_not_found() => return Ok(None),
+            Err(e) => return Err(e),
+        };
+
+        match parse_handle(&json) {
+            Some(handle) => Ok(Some(handle).filter(|handle| !handle.is_nil())),
+            None => ErrorKind::Invalid {
+                what: InvalidErrorKind::Conversion,
+                context: std::format!("Invalid {} value: {}", field_name, json),
+            }
+            .into_err(),
+        }
+    }
+
+    /// Get and parse one of the state info fields of the sample.
+    fn get_state<S>(
+        &self,
+        field_name: &str,
+        parse: impl FnOnce(&str) -> Option<S>,
+    ) -> ConnectorResult<S> {
+        let json = self.get_info_json(field_name)?;
+        let value = json.trim().trim_matches('"');
+
+        parse(value).ok_or_else(|| {
+            ErrorKind::Invalid {
+                what: InvalidErrorKind::Conversion,
+                context: std::format!("Unknown {} value: {}", field_name, value),
+            }
+            .into()
+        })
+    }
+
+    /// Get the JSON of the sample, borrowed from the native library.
+    pub(crate) fn get_as_json(&self) -> ConnectorResult<NativeString> {
+        self.input.get_json(self.index)
+    }
+}
+
+/// The error of failing to get the JSON of a sample to deserialize it as `T`.
+fn json_error<T>(e: ConnectorError) -> ConnectorError {
+    ErrorKind::Invalid {
+        what: InvalidErrorKind::Deserialization,
+        context: std::format!(
+            "Failed getting JSON for deserialization of type '{}': {}",
+            std::any::type_name::<T>(),
+            e
+        ),
+    }
+    .caused_by(e)
+}
+
+/// A `serde_json` error, along with the path of the value which caused it.
+pub(crate) type PathError = serde_path_to_error::Error<serde_json::Error>;
+
+/// Deserialize a JSON string, tracking the path of the value which fails to
+/// be deserialized.
+pub(crate) fn from_json_str<'a, T>(json: &'a str) -> Result<T, PathError>
+where
+    T: serde::Deserialize<'a>,
+{
+    let mut deserializer = serde_json::Deserializer::from_str(json);
+    let mut track = serde_path_to_error::Track::new();
+    T::deserialize(serde_path_to_error::Deserializer::new(
+        &mut deserializer,
+        &mut track,
+    ))
+    .and_then(|value| deserializer.end().map(|()| value))
+    .map_err(|e| serde_path_to_error::Error::new(track.path(), e))
+}
+
+/// The error of failing to deserialize the JSON of a sample as `T`.
+fn parse_error<T>(json: &str, e: PathError) -> ConnectorError {
+    let path = crate::json_path::from_serde_path("", e.path());
+    ErrorKind::Invalid {
+        what: InvalidErrorKind::Deserialization,
+        context: std::format!(
+            "Failed deserializing JSON ({}) into type '{}': {}",
+            json,
+            std::any::type_name::<T>(),
+            e
+        ),
+    }
+    .caused_by(e)
+    .with_field_path(path)
+}
+
+/// How strictly [`Sample::deserialize_with`] matches the data of a sample
+/// with the type it is deserialized into.
+///
+/// Every option is disabled by default, which deserializes the data as
+/// [`Sample::deserialize`] does.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DeserializeOptions {
+    /// Whether the members of a struct which the type doesn't have are errors.
+    pub(crate) deny_unknown_fields: bool,
+
+    /// Whether the members missing from a struct take the default value of
+    /// their type.
+    pub(crate) missing_as_default: bool,
+
+    /// Whether strings holding a number can be deserialized as that number.
+    pub(crate) numeric_strings: bool,
+}
+
+impl DeserializeOptions {
+    /// The default options, the same as [`Sample::deserialize`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fail if a struct of the sample has a member which the type doesn't,
+    /// naming the member, rather than ignoring it.
+    ///
+    /// This is the same as `#[serde(deny_unknown_fields)]` on every struct.
+    pub fn deny_unknown_fields(mut self, deny: bool) -> Self {
+        self.deny_unknown_fields = deny;
+        self
+    }
+
+    /// Set the fields of the type missing from a struct of the sample, such as
+    /// unset optional members, to their default value rather than failing.
+    ///
+    /// The default value is `0`, `false`, an empty string or sequence, `None`,
+    /// the first variant of an enum, or a struct of default values, the same
+    /// as `Default` for the types deriving it.
+    pub fn missing_as_default(mut self, default: bool) -> Self {
+        self.missing_as_default = default;
+        self
+    }
+
+    /// Accept strings holding a number, such as `"42"`, for numeric fields.
+    pub fn numeric_strings(mut self, coerce: bool) -> Self {
+        self.numeric_strings = coerce;
+        self
+    }
+}
+
+/// Turn the error of accessing an unset optional member into `None`.
+fn unset_optional_as_none<T>(result: ConnectorResult<T>) -> ConnectorResult<Option<T>> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(e) if e.is_unset_optional() => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// An [`Iterator`] which returns individual [`Sample`] elements.
+///
+/// ```rust
+#[doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/snippets/input/using_sample_iterator.rs"))]
+/// ```
+pub struct SampleIterator<'a> {
+    /// The current index in the iteration, from the front.
+    index: usize,
+
+    /// The index past the last sample to yield, from the back.
+    samples_len: usize,
+
+    /// A reference to the parent [`Input`] object.
+    input: &'a Input,
+}
+
+/// Implements the core iteration logic for [`SampleIterator`].
+impl<'a> Iterator for SampleIterator<'a> {
+    type Item = Sample<'a>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index < self.samples_len {
+            let result = Some(Sample::new(self.index, self.input));
+            self.index += 1;
+
+            result
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.len();
+        (remaining, Some(remaining))
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+}
+
+/// Allows [`SampleIterator`] to implement `len()`.
+impl ExactSizeIterator for SampleIterator<'_> {
+    fn len(&self) -> usize {
+        self.samples_len - self.index
+    }
+}
+
+/// Allows iterating a [`SampleIterator`] from the newest samples.
+impl DoubleEndedIterator for SampleIterator<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.index < self.samples_len {
+            self.samples_len -= 1;
+
+            Some(Sample::new(self.samples_len, self.input))
+        } else {
+            None
+        }
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        // Skip the samples without constructing them
+        self.samples_len = self.samples_len.saturating_sub(n).max(self.index);
+        self.next_back()
+    }
+}
+
+/// Allows transforming a [`SampleIterator`] into more specialized iterators.
+impl<'a> SampleIterator<'a> {
+    /// Create a [`ValidSampleIterator`] which yields only valid samples,
+    /// out of this [`SampleIterator`].
+    ///
+    /// Errors checking the validity of a sample are yielded to the caller.
+    pub fn valid_only(self) -> ValidSampleIterator<'a> {
+        ValidSampleIterator(self)
+    }
+
+    /// Create a [`LossyValidSampleIterator`] which yields only valid samples,
+    /// out of this [`SampleIterator`].
+    ///
+    /// Samples whose validity can't be checked are skipped.
+    pub fn valid_only_lossy(self) -> LossyValidSampleIterator<'a> {
+        LossyValidSampleIterator(self.valid_only())
+    }
+
+    /// Create a [`CountedValidSampleIterator`] which yields only valid samples,
+    /// out of this [`SampleIterator`].
+    ///
+    /// The validity of every remaining sample is checked upfront, so the
+    /// returned iterator knows its exact length. Errors checking the validity
+    /// of a sample are returned here instead of during the iteration.
+    pub fn valid_only_counted(self) -> ConnectorResult<CountedValidSampleIterator<'a>> {
+        let validity = self.input.validity_bitmap(self.index..self.samples_len)?;
+        let remaining = validity.iter().filter(|valid| **valid).count();
+
+        Ok(CountedValidSampleIterator {
+            offset: self.index,
+            inner: self,
+            validity,
+            remaining,
+        })
+    }
+
+    /// Create a [`StateFilteredSampleIterator`] which yields only the samples
+    /// matching a [`SampleStateMask`], out of this [`SampleIterator`].
+    ///
+    /// Samples without valid data are yielded too if they match, since their
+    /// states are still meaningful (e.g. a disposed instance). Chain
+    /// [`StateFilteredSampleIterator::valid_only`] to skip them.
+    pub fn filter_state(self, mask: SampleStateMask) -> StateFilteredSampleIterator<'a> {
+        StateFilteredSampleIterator {
+            inner: self,
+            mask: Some(mask),
+            valid_only: false,
+        }
+    }
+
+    /// Create a [`StateFilteredSampleIterator`] which yields only the samples
+    /// whose view state is [`ViewState::New`].
+    pub fn new_only(self) -> StateFilteredSampleIterator<'a> {
+        self.filter_state(SampleStateMask::any().with_view_state(ViewState::New))
+    }
+
+    /// Create a [`StateFilteredSampleIterator`] which yields only the samples
+    /// whose instance state is [`InstanceState::Alive`].
+    pub fn alive_only(self) -> StateFilteredSampleIterator<'a> {
+        self.filter_state(
+            SampleStateMask::any().with_instance_state(InstanceState::Alive),
+        )
+    }
+
+    /// Create a [`SampleWithInfoIterator`] which yields every sample along with
+    /// its [`LenientSampleInfo`], out of this [`SampleIterator`].
+    pub fn with_info(self) -> SampleWithInfoIterator<'a> {
+        SampleWithInfoIterator(self)
+    }
+}
+
+/// Whether a [`Sample`] has been read before.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SampleState {
+    /// The sample has already been read (`READ`).
+    Read,
+    /// The sample has not been read before (`NOT_READ`).
+    NotRead,
+}
+
+impl SampleState {
+    /// Parse the value of the `sample_state` info field.
+    fn from_info(value: &str) -> Option<Self> {
+        match value {
+            "READ" => Some(SampleState::Read),
+            "NOT_READ" => Some(SampleState::NotRead),
+            _ => None,
+        }
+    }
+}
+
+/// Whether the instance of a [`Sample`] has been seen before.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ViewState {
+    /// This is the first sample of the instance, or the first since it was
+    /// alive again (`NEW`).
+    New,
+    /// Samples of the instance were seen before (`NOT_NEW`).
+    NotNew,
+}
+
+impl ViewState {
+    /// Parse the value of the `view_state` info field.
+    fn from_info(value: &str) -> Option<Self> {
+        match value {
+            "NEW" => Some(ViewState::New),
+            "NOT_NEW" => Some(ViewState::NotNew),
+            _ => None,
+        }
+    }
+}
+
+/// The liveliness of the instance of a [`Sample`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InstanceState {
+    /// The instance has live writers (`ALIVE`).
+    Alive,
+    /// The instance was disposed by a writer (`NOT_ALIVE_DISPOSED`).
+    NotAliveDisposed,
+    /// The instance has no live writers (`NOT_ALIVE_NO_WRITERS`).
+    NotAliveNoWriters,
+}
+
+impl InstanceState {
+    /// Parse the value of the `instance_state` info field.
+    fn from_info(value: &str) -> Option<Self> {
+        match value {
+            "ALIVE" => Some(InstanceState::Alive),
+            "NOT_ALIVE_DISPOSED" => Some(InstanceState::NotAliveDisposed),
+            "NOT_ALIVE_NO_WRITERS" => Some(InstanceState::NotAliveNoWriters),
+            _ => None,
+        }
+    }
+}
+
+/// The states a [`Sample`] must be in to be yielded by a [`StateFilteredSampleIterator`].
+///
+/// States left as `None` match any value.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SampleStateMask {
+    /// The required sample state, if any.
+    pub sample_state: Option<SampleState>,
+
+    /// The required view state, if any.
+    pub view_state: Option<ViewState>,
+
+    /// The required instance state, if any.
+    pub instance_state: Option<InstanceState>,
+}
+
+impl SampleStateMask {
+    /// Create a [`SampleStateMask`] which matches every sample.
+    pub fn any() -> Self {
+        Self::default()
+    }
+
+    /// Require a specific sample state.
+    pub fn with_sample_state(mut self, state: SampleState) -> Self {
+        self.sample_state = Some(state);
+        self
+    }
+
+    /// Require a specific view state.
+    pub fn with_view_state(mut self, state: ViewState) -> Self {
+        self.view_state = Some(state);
+        self
+    }
+
+    /// Require a specific instance state.
+    pub fn with_instance_state(mut self, state: InstanceState) -> Self {
+        self.instance_state = Some(state);
+        self
+    }
+
+    /// Combine the states required by two masks, so that samples must match
+    /// both, or `None` if they require different values of the same state,
+    /// which no sample can match.
+    fn merge(self, other: SampleStateMask) -> Option<Self> {
+        /// Combine a state required by both masks.
+        fn both<T: PartialEq>(first: Option<T>, second: Option<T>) -> Option<Option<T>> {
+            match (first, second) {
+                (Some(first), Some(second)) => (first == second).then_some(Some(first)),
+                (first, second) => Some(first.or(second)),
+            }
+        }
+
+        Some(SampleStateMask {
+            sample_state: both(self.sample_state, other.sample_state)?,
+            view_state: both(self.view_state, other.view_state)?,
+            instance_state: both(self.instance_state, other.instance_state)?,
+        })
+    }
+}
+
+/// The identity of a sample, as found in its `sample_identity` and
+/// `related_sample_identity` info fields.
+///
+/// It's the same type used to set the identities in [`WriteParams`](crate::WriteParams),
+/// so the identity of a received sample can be reused directly.
+pub type SampleIdentity = WriteParamsIdentity;
+
+/// The GUID of a DDS entity, such as the writer of a [`Sample`].
+///
+/// It's displayed, and parsed from, 16 colon-separated hexadecimal bytes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Guid(pub [u8; 16]);
+
+impl From<[u8; 16]> for Guid {
+    fn from(bytes: [u8; 16]) -> Self {
+        Guid(bytes)
+    }
+}
+
+impl From<Guid> for [u8; 16] {
+    fn from(guid: Guid) -> Self {
+        guid.0
+    }
+}
+
+/// Display the [`Guid`] as colon-separated hexadecimal bytes.
+impl std::fmt::Display for Guid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, byte) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ":")?;
+            }
+            write!(f, "{:02x}", byte)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for Guid {
+    type Err = ConnectorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || -> ConnectorError {
+            ErrorKind::Invalid {
+                what: InvalidErrorKind::Conversion,
+                context: std::format!("Invalid GUID: {}", s),
+            }
+            .into()
+        };
+
+        let bytes: Vec<u8> = s
+            .split(':')
+            .map(|byte| match byte.len() {
+                1 | 2 => u8::from_str_radix(byte, 16).map_err(|_| invalid()),
+                _ => Err(invalid()),
+            })
+            .collect::<Result<_, _>>()?;
+
+        bytes.try_into().map(Guid).map_err(|_| invalid())
+    }
+}
+
+/// An opaque handle from the info of a [`Sample`], identifying its instance or
+/// the publication which wrote it.
+///
+/// Handles of the same instance or publication compare equal. It's displayed
+/// as its bytes in hexadecimal.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Handle(Vec<u8>);
+
+impl Handle {
+    /// Get the bytes of the handle.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Returns whether this is the nil handle, with every byte set to zero.
+    fn is_nil(&self) -> bool {
+        self.0.iter().all(|&byte| byte == 0)
+    }
+}
+
+/// Display the [`Handle`] as hexadecimal bytes.
+impl std::fmt::Display for Handle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for byte in &self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The metadata of a [`Sample`], retrieved and parsed from its info fields.
+///
+/// Created with [`Sample::info`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SampleInfo {
+    /// Whether the sample contains valid data.
+    pub valid_data: bool,
+
+    /// The source timestamp of the sample, in nanoseconds.
+    pub source_timestamp: i64,
+
+    /// The reception timestamp of the sample, in nanoseconds.
+    pub reception_timestamp: i64,
+
+    /// The identity of the sample.
+    pub identity: SampleIdentity,
+
+    /// The identity of the sample this one is related to, in request-reply
+    /// communications, if any.
+    pub related_identity: Option<SampleIdentity>,
+
+    /// The sample state of the sample.
+    pub sample_state: SampleState,
+
+    /// The view state of the sample.
+    pub view_state: ViewState,
+
+    /// The instance state of the sample.
+    pub instance_state: InstanceState,
+
+    /// The number of samples of the same instance which follow this one in
+    /// the samples cache, if provided by the native library.
+    pub sample_rank: Option<i32>,
+
+    /// The number of generations of the instance between this sample and the
+    /// most recent sample of the instance in the samples cache, if provided by
+    /// the native library.
+    pub generation_rank: Option<i32>,
+
+    /// The number of generations of the instance between this sample and the
+    /// most recent sample of the instance received, if provided by the native
+    /// library.
+    pub absolute_generation_rank: Option<i32>,
+
+    /// The number of times the instance became alive after being disposed,
+    /// if provided by the native library.
+    pub disposed_generation_count: Option<i32>,
+
+    /// The number of times the instance became alive after having no writers,
+    /// if provided by the native library.
+    pub no_writers_generation_count: Option<i32>,
+}
+
+/// The metadata of a [`Sample`], like a [`SampleInfo`], but leaving the info
+/// fields which can't be retrieved or parsed as `None`.
+///
+/// Created with [`SampleIterator::with_info`], so that a single unexpected
+/// info field doesn't fail the iteration.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LenientSampleInfo {
+    /// Whether the sample contains valid data.
+    pub valid_data: Option<bool>,
+
+    /// The source timestamp of the sample, in nanoseconds.
+    pub source_timestamp: Option<i64>,
+
+    /// The reception timestamp of the sample, in nanoseconds.
+    pub reception_timestamp: Option<i64>,
+
+    /// The identity of the sample.
+    pub identity: Option<SampleIdentity>,
+
+    /// The identity of the sample this one is related to, in request-reply
+    /// communications, if any.
+    pub related_identity: Option<SampleIdentity>,
+
+    /// The sample state of the sample.
+    pub sample_state: Option<SampleState>,
+
+    /// The view state of the sample.
+    pub view_state: Option<ViewState>,
+
+    /// The instance state of the sample.
+    pub instance_state: Option<InstanceState>,
+
+    /// The number of samples of the same instance which follow this one in
+    /// the samples cache.
+    pub sample_rank: Option<i32>,
+
+    /// The number of generations of the instance between this sample and the
+    /// most recent sample of the instance in the samples cache.
+    pub generation_rank: Option<i32>,
+
+    /// The number of generations of the instance between this sample and the
+    /// most recent sample of the instance received.
+    pub absolute_generation_rank: Option<i32>,
+
+    /// The number of times the instance became alive after being disposed.
+    pub disposed_generation_count: Option<i32>,
+
+    /// The number of times the instance became alive after having no writers.
+    pub no_writers_generation_count: Option<i32>,
+}
+
+impl LenientSampleInfo {
+    /// Retrieve and parse every info field of a [`Sample`], leaving the fields
+    /// which fail as `None`.
+    fn from_sample(sample: &Sample<'_>) -> Self {
+        LenientSampleInfo {
+            valid_data: sample.is_valid().ok(),
+            source_timestamp: sample.get_timestamp("source_timestamp").ok(),
+            reception_timestamp: sample.get_timestamp("reception_timestamp").ok(),
+            identity: sample.identity().ok(),
+            related_identity: sample.related_identity().ok().flatten(),
+            sample_state: sample.sample_state().ok(),
+            view_state: sample.view_state().ok(),
+            instance_state: sample.instance_state().ok(),
+            sample_rank: sample.sample_rank().ok().flatten(),
+            generation_rank: sample.generation_rank().ok().flatten(),
+            absolute_generation_rank: sample.absolute_generation_rank().ok().flatten(),
+            disposed_generation_count: sample.disposed_generation_count().ok().flatten(),
+            no_writers_generation_count: sample
+                .no_writers_generation_count()
+                .ok()
+                .flatten(),
+        }
+    }
+}
+
+/// Allows comparing a [`SampleInfo`] with a [`LenientSampleInfo`].
+impl From<SampleInfo> for LenientSampleInfo {
+    fn from(info: SampleInfo) -> Self {
+        LenientSampleInfo {
+            valid_data: Some(info.valid_data),
+            source_timestamp: Some(info.source_timestamp),
+            reception_timestamp: Some(info.reception_timestamp),
+            identity: Some(info.identity),
+            related_identity: info.related_identity,
+            sample_state: Some(info.sample_state),
+            view_state: Some(info.view_state),
+            instance_state: Some(info.instance_state),
+            sample_rank: info.sample_rank,
+            generation_rank: info.generation_rank,
+            absolute_generation_rank: info.absolute_generation_rank,
+            disposed_generation_count: info.disposed_generation_count,
+            no_writers_generation_count: info.no_writers_generation_count,
+        }
+    }
+}
+
+/// Convert a DDS timestamp, in nanoseconds, into a [`std::time::SystemTime`].
+///
+/// The DDS epoch is the same as the UNIX epoch.
+fn system_time_from_nanos(nanos: i64) -> ConnectorResult<std::time::SystemTime> {
+    let offset = std::time::Duration::from_nanos(nanos.unsigned_abs());
+    let time = if nanos >= 0 {
+        std::time::UNIX_EPOCH.checked_add(offset)
+    } else {
+        std::time::UNIX_EPOCH.checked_sub(offset)
+    };
+
+    time.ok_or_else(|| {
+        ErrorKind::Invalid {
+            what: InvalidErrorKind::Conversion,
+            context: std::format!("Timestamp out of range: {} ns", nanos),
+        }
+        .into()
+    })
+}
+
+/// Parse the JSON of an identity info field.
+///
+/// The `writer_guid` may be a list of 16 bytes or an object with such a list as
+/// `value`, and the `sequence_number` may be an integer, a numeric string or an
+/// object with `high` and `low` words.
+fn parse_identity(json: &str) -> Option<SampleIdentity> {
+    use serde_json::Value;
+
+    let value = parse_info_json(json)?;
+
+    let guid = match value.get("writer_guid")? {
+        Value::Object(guid) => guid.get("value")?,
+        guid => guid,
+    };
+    let guid: Vec<u8> = guid
+        .as_array()?
+        .iter()
+        .map(|byte| byte.as_u64().and_then(|byte| u8::try_from(byte).ok()))
+        .collect::<Option<_>>()?;
+
+    let sequence_number = match value.get("sequence_number")? {
+        Value::Number(n) => n.as_i64()?,
+        Value::String(s) => s.trim().parse().ok()?,
+        Value::Object(sn) => {
+            let high = i32::try_from(sn.get("high")?.as_i64()?).ok()?;
+            let low = u32::try_from(sn.get("low")?.as_u64()?).ok()?;
+            (i64::from(high) << 32) | i64::from(low)
+        }
+        _ => return None,
+    };
+
+    Some(SampleIdentity {
+        writer_guid: guid.try_into().ok()?,
+        sequence_number,
+    })
+}
+
+/// Parse the JSON of a handle info field.
+///
+/// The bytes may be a list, or nested within objects as their `value` or
+/// `key_hash`, such as `{ "key_hash": { "value": [...] } }`.
+fn parse_handle(json: &str) -> Option<Handle> {
+    use serde_json::Value;
+
+    let value = parse_info_json(json)?;
+    let mut bytes = &value;
+    while let Value::Object(members) = bytes {
+        bytes = members.get("value").or_else(|| members.get("key_hash"))?;
+    }
+
+    bytes
+        .as_array()?
+        .iter()
+        .map(|byte| byte.as_u64().and_then(|byte| u8::try_from(byte).ok()))
+        .collect::<Option<_>>()
+        .map(Handle)
+}
+
+/// Parse the JSON of an info field, which may come wrapped in a JSON string.
+fn parse_info_json(json: &str) -> Option<serde_json::Value> {
+    match serde_json::from_str(json).ok()? {
+        serde_json::Value::String(inner) => serde_json::from_str(&inner).ok(),
+        value => Some(value),
+    }
+}
+
+/// The info fields which a [`Sample`] may have, probed by
+/// [`Sample::info_field_names`] and copied into every [`OwnedSample`].
+///
+/// Those which the native library doesn't provide are skipped.
+const INFO_FIELDS: [&str; 15] = [
+    "valid_data",
+    "source_timestamp",
+    "reception_timestamp",
+    "sample_identity",
+    "related_sample_identity",
+    "sample_state",
+    "view_state",
+    "instance_state",
+    "instance_handle",
+    "publication_handle",
+    "sample_rank",
+    "generation_rank",
+    "absolute_generation_rank",
+    "disposed_generation_count",
+    "no_writers_generation_count",
+];
+
+/// An owned copy of a [`Sample`], decoupled from its [`Input`].
+///
+/// Created with [`Input::snapshot`], it copies the data and info fields of the
+/// sample at snapshot time, so it remains usable after the [`Input`] reads or
+/// takes new samples, and can be sent to other threads.
+#[derive(Debug, Clone)]
+pub struct OwnedSample {
+    /// The sample data, or `Null` for samples without valid data.
+    data: serde_json::Value,
+
+    /// The info fields of the sample which could be retrieved.
+    info: std::collections::HashMap<String, SelectedValue>,
+}
+
+/// Display the [`OwnedSample`] as a JSON string.
+impl std::fmt::Display for OwnedSample {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.data)
+    }
+}
+
+impl OwnedSample {
+    /// Copy the data and info fields of a [`Sample`].
+    fn from_sample(sample: &Sample<'_>) -> ConnectorResult<Self> {
+        let valid = sample.is_valid()?;
+        let data = if valid {
+            serde_json::from_str(&sample.get_as_json()?).map_err(|e| {
+                ErrorKind::Invalid {
+                    what: InvalidErrorKind::Conversion,
+                    context: std::format!("Sample JSON could not be parsed: {}", e),
+                }
+            })?
+        } else {
+            serde_json::Value::Null
+        };
+
+        let mut info: std::collections::HashMap<_, _> = INFO_FIELDS
+            .into_iter()
+            .filter_map(|name| {
+                sample
+                    .get_info(name)
+                    .ok()
+                    .map(|value| (name.to_string(), value))
+            })
+            .collect();
+        info.insert("valid_data".to_string(), SelectedValue::Boolean(valid));
+
+        Ok(OwnedSample { data, info })
+    }
+
+    /// Returns whether the sample contains valid data.
+    pub fn is_valid(&self) -> bool {
+        matches!(
+            self.info.get("valid_data"),
+            Some(SelectedValue::Boolean(true))
+        )
+    }
+
+    /// Get the value of an info field of the sample.
+    pub fn get_info(&self, field_name: &str) -> ConnectorResult<SelectedValue> {
+        self.info
+            .get(field_name)
+            .cloned()
+            .ok_or_else(|| ErrorKind::field_not_found_error(field_name).into())
+    }
+
+    /// Get the value of a boolean field of the sample.
+    pub fn get_boolean(&self, field_name: &str) -> ConnectorResult<bool> {
+        let value = self.get_json_value(field_name)?;
+        value
+            .as_bool()
+            .ok_or_else(|| Self::conversion_error(field_name, "boolean", value))
+    }
+
+    /// Get the value of a string field of the sample.
+    pub fn get_string(&self, field_name: &str) -> ConnectorResult<String> {
+        match self.get_json_value(field_name)? {
+            serde_json::Value::String(s) => Ok(s.clone()),
+            other => Ok(other.to_string()),
+        }
+    }
+
+    /// Get the value of a numeric field of the sample.
+    pub fn get_number(&self, field_name: &str) -> ConnectorResult<f64> {
+        let value = self.get_json_value(field_name)?;
+        value
+            .as_f64()
+            .ok_or_else(|| Self::conversion_error(field_name, "number", value))
+    }
+
+    /// Get the value of a field of the sample, whatever its type.
+    pub fn get_value(&self, field_name: &str) -> ConnectorResult<SelectedValue> {
+        match self.get_json_value(field_name)? {
+            serde_json::Value::Null => Ok(SelectedValue::Null),
+            serde_json::Value::Bool(b) => Ok(SelectedValue::Boolean(*b)),
+            serde_json::Value::String(s) => Ok(SelectedValue::String(s.clone())),
+            value => match value.as_f64() {
+                Some(n) => Ok(SelectedValue::Number(n)),
+                None => Ok(SelectedValue::Json(value.clone())),
+            },
+        }
+    }
+
+    /// Get the value of a field of the sample as a JSON string.
+    pub fn get_value_json(&self, field_name: &str) -> ConnectorResult<String> {
+        self.get_json_value(field_name)
+            .map(|value| value.to_string())
+    }
+
+    /// Deserialize the sample into a typed struct using Serde.
+    pub fn deserialize<T>(&self) -> ConnectorResult<T>
+    where
+        T: for<'de> serde::Deserialize<'de>,
+    {
+        serde_path_to_error::deserialize(&self.data).map_err(|e| {
+            let path = crate::json_path::from_serde_path("", e.path());
+            ErrorKind::Invalid {
+                what: InvalidErrorKind::Deserialization,
+                context: std::format!(
+                    "Failed deserializing JSON ({}) into type '{}': {}",
+                    self.data,
+                    std::any::type_name::<T>(),
+                    e
+                ),
+            }
+            .caused_by(e)
+            .with_field_path(path)
+        })
+    }
+
+    /// Resolve a field of the sample data.
+    fn get_json_value(&self, field_name: &str) -> ConnectorResult<&serde_json::Value> {
+        crate::json_path::resolve(&self.data, field_name)
+            .ok_or_else(|| ErrorKind::field_not_found_error(field_name).into())
+    }
+
+    /// Build an error for a field which doesn't hold the expected type.
+    fn conversion_error(
+        field_name: &str,
+        expected: &str,
+        value: &serde_json::Value,
+    ) -> ConnectorError {
+        ErrorKind::Invalid {
+            what: InvalidErrorKind::Conversion,
+            context: std::format!(
+                "Field '{}' is not a {}: {}",
+                field_name,
+                expected,
+                value
+            ),
+        }
+        .into()
+    }
+}
+
+/// A specialized [`SampleIterator`] which returns each [`Sample`] along with its [`LenientSampleInfo`].
+///
+/// Info fields which can't be retrieved or parsed are left as `None`, rather
+/// than failing the iteration.
+pub struct SampleWithInfoIterator<'a>(SampleIterator<'a>);
+
+impl<'a> Iterator for SampleWithInfoIterator<'a> {
+    type Item = (Sample<'a>, LenientSampleInfo);
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|sample| {
+            let info = LenientSampleInfo::from_sample(&sample);
+            (sample, info)
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+/// Allows [`SampleWithInfoIterator`] to implement `len()`.
+impl ExactSizeIterator for SampleWithInfoIterator<'_> {}
+
+/// Allows iterating a [`SampleWithInfoIterator`] from the newest samples.
+impl DoubleEndedIterator for SampleWithInfoIterator<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(|sample| {
+            let info = LenientSampleInfo::from_sample(&sample);
+            (sample, info)
+        })
+    }
+}
+
+/// A specialized [`SampleIterator`] which returns only valid [`Sample`] elements.
+///
+/// ```rust
+#[doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/snippets/input/using_valid_sample_iterator.rs"))]
+/// ```
+pub struct ValidSampleIterator<'a>(SampleIterator<'a>);
+
+impl<'a> Iterator for ValidSampleIterator<'a> {
+    type Item = ConnectorResult<Sample<'a>>;
+    fn next(&mut self) -> Option<Self::Item> {
+        for sample in &mut self.0.by_ref() {
+            match sample.is_valid() {
+                Ok(true) => return Some(Ok(sample)),
+                // Skip invalid samples and try the next one
+                Ok(false) => continue,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+
+        None // No more samples
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // We can't know how many valid samples are left without iterating
+        (0, Some(self.0.len()))
+    }
+}
+
+/// A specialized [`SampleIterator`] which returns only valid [`Sample`] elements,
+/// whose amount is known in advance.
+///
+/// Created with [`SampleIterator::valid_only_counted`].
+pub struct CountedValidSampleIterator<'a> {
+    /// The iterator over every sample.
+    inner: SampleIterator<'a>,
+
+    /// The index of the first sample in `validity`.
+    offset: usize,
+
+    /// The validity of every sample remaining in `inner` when created.
+    validity: Vec<bool>,
+
+    /// The number of valid samples not yet yielded.
+    remaining: usize,
+}
+
+impl<'a> Iterator for CountedValidSampleIterator<'a> {
+    type Item = Sample<'a>;
+    fn next(&mut self) -> Option<Self::Item> {
+        for sample in self.inner.by_ref() {
+            if self.validity[sample.index - self.offset] {
+                self.remaining -= 1;
+                return Some(sample);
+            }
+        }
+
+        None // No more samples
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+/// Allows iterating a [`CountedValidSampleIterator`] from the newest samples.
+impl DoubleEndedIterator for CountedValidSampleIterator<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        while let Some(sample) = self.inner.next_back() {
+            if self.validity[sample.index - self.offset] {
+                self.remaining -= 1;
+                return Some(sample);
+            }
+        }
+
+        None // No more samples
+    }
+}
+
+/// Allows [`CountedValidSampleIterator`] to implement `len()`.
+impl ExactSizeIterator for CountedValidSampleIterator<'_> {
+    fn len(&self) -> usize {
+        self.remaining
+    }
+}
+
+/// Allows iterating a [`ValidSampleIterator`] from the newest samples.
+impl DoubleEndedIterator for ValidSampleIterator<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        while let Some(sample) = self.0.next_back() {
+            match sample.is_valid() {
+                Ok(true) => return Some(Ok(sample)),
+                // Skip invalid samples and try the previous one
+                Ok(false) => continue,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+
+        None // No more samples
+    }
+}
+
+/// A specialized [`SampleIterator`] which returns only valid [`Sample`] elements,
+/// skipping those whose validity can't be checked.
+///
+/// Unlike [`ValidSampleIterator`], errors checking the validity of a sample are
+/// only reported to `stderr`, so the iteration may silently yield fewer samples.
+pub struct LossyValidSampleIterator<'a>(ValidSampleIterator<'a>);
+
+impl<'a> Iterator for LossyValidSampleIterator<'a> {
+    type Item = Sample<'a>;
+    fn next(&mut self) -> Option<Self::Item> {
+        for result in &mut self.0.by_ref() {
+            match result {
+                Ok(sample) => return Some(sample),
+                Err(e) => {
+                    eprintln!("Error checking sample validity, skipping sample: {}", e);
+                }
+            }
+        }
+
+        None // No more samples
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+/// Allows iterating a [`LossyValidSampleIterator`] from the newest samples.
+impl DoubleEndedIterator for LossyValidSampleIterator<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        while let Some(result) = self.0.next_back() {
+            match result {
+                Ok(sample) => return Some(sample),
+                Err(e) => {
+                    eprintln!("Error checking sample validity, skipping sample: {}", e);
+                }
+            }
+        }
+
+        None // No more samples
+    }
+}
+
+/// Allows restricting a [`ValidSampleIterator`] by the state of the samples.
+impl<'a> ValidSampleIterator<'a> {
+    /// Create a [`StateFilteredSampleIterator`] which yields only the valid
+    /// samples matching a [`SampleStateMask`].
+    pub fn filter_state(self, mask: SampleStateMask) -> StateFilteredSampleIterator<'a> {
+        self.0.filter_state(mask).valid_only()
+    }
+
+    /// Create a [`StateFilteredSampleIterator`] which yields only the valid
+    /// samples whose view state is [`ViewState::New`].
+    pub fn new_only(self) -> StateFilteredSampleIterator<'a> {
+        self.0.new_only().valid_only()
+    }
+
+    /// Create a [`StateFilteredSampleIterator`] which yields only the valid
+    /// samples whose instance state is [`InstanceState::Alive`].
+    pub fn alive_only(self) -> StateFilteredSampleIterator<'a> {
+        self.0.alive_only().valid_only()
+    }
+}
+
+/// A specialized [`SampleIterator`] which returns only the [`Sample`] elements
+/// matching a [`SampleStateMask`].
+///
+/// Created with [`SampleIterator::filter_state`] and related methods. Errors
+/// retrieving the states of a sample are yielded to the caller.
+pub struct StateFilteredSampleIterator<'a> {
+    /// The iterator over every sample.
+    inner: SampleIterator<'a>,
+
+    /// The states the samples must be in, or `None` if the masks combined
+    /// conflict, so that no sample matches.
+    mask: Option<SampleStateMask>,
+
+    /// Whether samples without valid data are skipped.
+    valid_only: bool,
+}
+
+impl<'a> StateFilteredSampleIterator<'a> {
+    /// Also require the samples to match another [`SampleStateMask`].
+    ///
+    /// If both masks require different values of the same state, no sample
+    /// matches.
+    pub fn filter_state(mut self, mask: SampleStateMask) -> Self {
+        self.mask = self.mask.and_then(|current| current.merge(mask));
+        self
+    }
+
+    /// Also require the view state of the samples to be [`ViewState::New`].
+    pub fn new_only(self) -> Self {
+        self.filter_state(SampleStateMask::any().with_view_state(ViewState::New))
+    }
+
+    /// Also require the instance state of the samples to be [`InstanceState::Alive`].
+    pub fn alive_only(self) -> Self {
+        self.filter_state(
+            SampleStateMask::any().with_instance_state(InstanceState::Alive),
+        )
+    }
+
+    /// Also skip the samples without valid data.
+    pub fn valid_only(mut self) -> Self {
+        self.valid_only = true;
+        self
+    }
+
+    /// Returns whether a sample should be yielded.
+    fn accepts(&self, sample: &Sample<'_>) -> ConnectorResult<bool> {
+        let Some(mask) = &self.mask else {
+            return Ok(false);
+        };
+
+        if self.valid_only && !sample.is_valid()? {
+            return Ok(false);
+        }
+
+        sample.matches_state(mask)
+    }
+}
+
+impl<'a> Iterator for StateFilteredSampleIterator<'a> {
+    type Item = ConnectorResult<Sample<'a>>;
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(sample) = self.inner.next() {
+            match self.accepts(&sample) {
+                Ok(true) => return Some(Ok(sample)),
+                // Skip non-matching samples and try the next one
+                Ok(false) => continue,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+
+        None // No more samples
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // We can't know how many samples match without iterating
+        (0, Some(self.inner.len()))
+    }
+}
+
+/// Allows iterating a [`StateFilteredSampleIterator`] from the newest samples.
+impl DoubleEndedIterator for StateFilteredSampleIterator<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        while let Some(sample) = self.inner.next_back() {
+            match self.accepts(&sample) {
+                Ok(true) => return Some(Ok(sample)),
+                // Skip non-matching samples and try the previous one
+                Ok(false) => continue,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+
+        None // No more samples
+    }
+}
 
 /// An interface to read data from a DDS `Topic`.
 ///
-/// Created with [`Connector::get_input`], an [`Input`] represents a DDS
+/// Created with [`Connector::get_input`](crate::Connector::get_input), an [`Input`] represents a DDS
 /// `DataReader` associated with a specific `Topic` within a `Participant`.
 ///
 /// The main operations are [`Input::read`] and [`Input::take`], which move data
 /// from the underlying `DataReader` into the [`Input`], at which point you can
-/// access [`Sample`] by means of a [`SampleIterator`].
+/// access [`Sample`] by means of [`Input::samples`] or [`Input::valid_samples`].
 ///
 /// ```rust
 #[doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/snippets/input/using_input.rs"))]
 /// ```
+///
+/// An [`Input`] is the single owner of its `DataReader` and of the samples it
+/// holds, so it does not implement [`Clone`]: a second handle could otherwise
+/// replace the samples that a [`Sample`] obtained from the first one refers to.
+/// Move the [`Input`] or borrow it instead:
+///
+/// ```rust,compile_fail,E0599
+/// # fn alias(input: rtiddsconnector::Input) {
+/// let other = input.clone(); // Error: `Input` is not `Clone`
+/// # }
+/// ```
+///
+/// The ownership of the `DataReader` is released exactly once, when the
+/// [`Input`] is dropped.
+#[derive(Debug)]
+pub struct Input {
+    /// The name of the [`Input`] as known to the parent [`Connector`](crate::Connector).
+    name: Arc<str>,
+
+    /// The name, converted once for the native library, which needs it on every call.
+    c_name: CString,
+
+    /// A shared reference to the state of the parent [`Connector`](crate::Connector) object.
+    parent: Arc<ConnectorInner>,
+}
+
+/// Allows obtaining a [`SampleIterator`] from an [`Input`] in `for` loops.
+///
+/// Prefer [`Input::samples`], which reports failures to access the sample cache.
+impl<'a> IntoIterator for &'a Input {
+    type Item = Sample<'a>;
+    type IntoIter = SampleIterator<'a>;
+
+    /// Create an `Iterator` over an `Input`'s samples cache.
+    /// If the sample cache can't be accessed, the error is logged
+    /// and the iterator yields no samples.
+    fn into_iter(self) -> Self::IntoIter {
+        self.samples().unwrap_or_else(|e| {
+            eprintln!(
+                "Error accessing the samples cache, assuming no samples: {}",
+                e
+            );
+            SampleIterator {
+                index: 0,
+                samples_len: 0,
+                input: self,
+            }
+        })
+    }
+}
+
+/// Ensures that the [`Input`] is freed to the parent [`Connector`](crate::Connector)
+impl Drop for Input {
+    fn drop(&mut self) {
+        if let Err(e) = self.parent.release_input(&self.name) {
+            eprintln!(
+                "Warning: Failed to release Input '{}' on drop: {}",
+                self.name, e
+            );
+        }
+    }
+}
+
+/// A guard over the samples obtained by [`Input::read_guard`] or [`Input::take_guard`].
+///
+/// The guard dereferences to the [`Input`], giving access to its samples, and
+/// returns the loan on the samples when dropped. Use [`SamplesGuard::finish`]
+/// to return the loan explicitly and check for errors.
+#[derive(Debug)]
+pub struct SamplesGuard<'a> {
+    /// The [`Input`] whose samples are guarded.
+    input: &'a mut Input,
+
+    /// Whether the loan has already been returned.
+    returned: bool,
+}
+
+impl SamplesGuard<'_> {
+    /// Return the loan on the guarded samples, reporting any error.
+    pub fn finish(mut self) -> ConnectorFallible {
+        self.return_loan()
+    }
+
+    /// Return the loan on the guarded samples, only once.
+    fn return_loan(&mut self) -> ConnectorFallible {
+        if self.returned {
+            Ok(())
+        } else {
+            self.returned = true;
+            self.input.return_loan()
+        }
+    }
+}
+
+/// Gives access to the guarded [`Input`] and its samples.
+impl std::ops::Deref for SamplesGuard<'_> {
+    type Target = Input;
+
+    fn deref(&self) -> &Self::Target {
+        self.input
+    }
+}
+
+/// Allows iterating over the samples of a [`SamplesGuard`] in `for` loops.
+impl<'b> IntoIterator for &'b SamplesGuard<'_> {
+    type Item = Sample<'b>;
+    type IntoIter = SampleIterator<'b>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        (&**self).into_iter()
+    }
+}
+
+/// Ensures that the loan on the guarded samples is returned.
+impl Drop for SamplesGuard<'_> {
+    fn drop(&mut self) {
+        if let Err(e) = self.return_loan() {
+            eprintln!(
+                "Warning: Failed to return loan of Input '{}' on drop: {}",
+                self.input.name, e
+            );
+        }
+    }
+}
+
+/// Convert a count reported by the native library, which is a double, such
+/// as a sample count or a sequence length, described by `what`.
+///
+/// Counts which aren't a non-negative integer, or which are too large to have
+/// been represented exactly, are rejected instead of being cast silently.
+pub(crate) fn count_from_native(what: &str, count: f64) -> ConnectorResult<usize> {
+    if count.is_nan() || count < 0.0 || count.fract() != 0.0 {
+        ErrorKind::assertion_failed_error(format!("Invalid {}: {}", what, count))
+            .into_err()
+    } else if count > crate::output::MAX_EXACT_INTEGER as f64 || count > usize::MAX as f64
+    {
+        ErrorKind::assertion_failed_error(format!(
+            "The {} is out of range: {}",
+            what, count
+        ))
+        .into_err()
+    } else {
+        Ok(count as usize)
+    }
+}
+
+/// A [`Stream`](futures_core::Stream) of the data of the valid samples of an
+/// [`Input`], deserialized into `T`.
+///
+/// Created with [`Input::into_stream`].
+#[cfg(feature = "async")]
+#[derive(Debug)]
+pub struct SampleStream<T> {
+    /// The data forwarded by the thread which owns the [`Input`]
+    receiver: tokio::sync::mpsc::Receiver<ConnectorResult<T>>,
+}
+
+#[cfg(feature = "async")]
+impl<T> SampleStream<T> {
+    /// The number of samples forwarded ahead of the consumer of the stream.
+    const CAPACITY: usize = 64;
+}
+
+#[cfg(feature = "async")]
+impl<T> futures_core::Stream for SampleStream<T> {
+    type Item = ConnectorResult<T>;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+/// A handle to the thread created by [`Input::on_data`].
+///
+/// Dropping the handle stops the thread, waiting for it to release its
+/// [`Input`]. The thread ends within one chunk of its wait, 100 ms, after the
+/// callbacks in progress.
+#[derive(Debug)]
+pub struct ListenerHandle {
+    /// Set to stop the thread
+    stop: Arc<std::sync::atomic::AtomicBool>,
+
+    /// The messages of the panics caught in the callback
+    panics: Arc<std::sync::Mutex<Vec<String>>>,
+
+    /// The thread, until it's joined
+    thread: Option<std::thread::JoinHandle<ConnectorFallible>>,
+}
+
+impl ListenerHandle {
+    /// Returns whether the thread has ended, either because of an error or
+    /// because [`ListenerHandle::stop`] was called.
+    pub fn is_finished(&self) -> bool {
+        self.thread
+            .as_ref()
+            .is_none_or(std::thread::JoinHandle::is_finished)
+    }
+
+    /// Take the messages of the panics caught in the callback since the last call.
+    ///
+    /// A panic doesn't stop the thread, which keeps invoking the callback for
+    /// the next samples.
+    pub fn take_panics(&self) -> Vec<String> {
+        std::mem::take(
+            &mut self
+                .panics
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner),
+        )
+    }
+
+    /// Stop the thread and wait for it, returning the error which ended it,
+    /// if any.
+    pub fn stop(mut self) -> ConnectorFallible {
+        self.join()
+    }
+
+    /// Stop the thread and wait for it.
+    fn join(&mut self) -> ConnectorFallible {
+        self.stop.store(true, std::sync::atomic::Ordering::Release);
+
+        match self.thread.take().map(std::thread::JoinHandle::join) {
+            None | Some(Ok(Ok(()))) => Ok(()),
+            Some(Ok(Err(e))) => Err(e),
+            Some(Err(payload)) => ErrorKind::assertion_failed_error(format!(
+                "The listener thread panicked: {}",
+                panic_message(payload.as_ref())
+            ))
+            .into_err(),
+        }
+    }
+}
+
+/// Stops the thread and waits for it to release its [`Input`].
+impl Drop for ListenerHandle {
+    fn drop(&mut self) {
+        let _ = self.join();
+    }
+}
+
+/// The message of a panic, if it has one.
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Box<dyn Any>".to_string()
+    }
+}
+
+/// An [`Input`] which reads a single Rust type `T`, obtained with
+/// [`Input::typed`].
+///
+/// The data of every valid sample is deserialized into `T`, so that samples
+/// can't be deserialized into another type by accident:
+///
+/// ```rust
+/// # #[derive(serde::Deserialize)]
+/// # struct Shape { color: String, x: i32, y: i32, shapesize: i32 }
+/// # fn subscribe(input: rtiddsconnector::Input) -> rtiddsconnector::ConnectorFallible {
+/// let mut input = input.typed::<Shape>();
+/// input.as_untyped().wait()?;
+/// for shape in input.take()? {
+///     println!("{}: ({}, {})", shape.color, shape.x, shape.y);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+///
+/// The [`Input`] remains available with [`TypedInput::as_untyped`], for the
+/// operations which don't depend on the type, such as waiting for data.
 #[derive(Debug)]
-pub struct Input<'a> {
-    /// The name of the [`Input`] as known to the parent [`Connector`].
-    name: String,
+pub struct TypedInput<T> {
+    /// The untyped input the samples are read from
+    input: Input,
+
+    /// The type of the data, which is only ever produced
+    _type: std::marker::PhantomData<fn() -> T>,
+}
+
+impl<T: serde::de::DeserializeOwned> TypedInput<T> {
+    /// Take the samples available and deserialize every valid one.
+    ///
+    /// See [`Input::take_deserialized`].
+    pub fn take(&mut self) -> ConnectorResult<Vec<T>> {
+        self.input.take_deserialized()
+    }
 
-    /// A reference to the parent [`Connector`] object.
-    parent: &'a Connector,
-}
+    /// Iterate over the valid samples of the received sample cache,
+    /// deserializing each of them.
+    ///
+    /// If the sample cache can't be accessed, its error is the only item.
+    pub fn iter(&self) -> impl Iterator<Item = ConnectorResult<T>> + '_ {
+        let (samples, error) = match self.input.valid_samples() {
+            Ok(samples) => (Some(samples), None),
+            Err(e) => (None, Some(Err(e))),
+        };
 
-/// Allows obtaining a [`SampleIterator`] from an [`Input`].
-impl<'a> IntoIterator for &'a Input<'a> {
-    type Item = Sample<'a>;
-    type IntoIter = SampleIterator<'a>;
+        samples
+            .into_iter()
+            .flatten()
+            .map(|sample| sample?.deserialize_with_index())
+            .chain(error)
+    }
 
-    /// Create an `Iterator` over an `Input`'s samples cache.
-    /// Note that the iterator will not consume the `Input`, but it
-    /// will take an immutable borrow on it, preventing the
-    /// sample cache from being modified by calls to [`Input::take()`]
-    /// or [`Input::read()`].
-    fn into_iter(self) -> Self::IntoIter {
-        SampleIterator {
-            index: 0,
-            samples_len: self.get_count().unwrap_or(0), // On error, assume 0 samples
-            input: self,
-        }
+    /// Get the underlying [`Input`].
+    pub fn as_untyped(&self) -> &Input {
+        &self.input
     }
-}
 
-/// Ensures that the [`Input`] is freed to the parent [`Connector`]
-impl<'a> Drop for Input<'a> {
-    fn drop(&mut self) {
-        if let Err(e) = self.parent.release_input(&self.name) {
-            eprintln!(
-                "Warning: Failed to release Input '{}' on drop: {}",
-                self.name, e
-            );
-        }
+    /// Get the underlying [`Input`] mutably, to read or take samples without
+    /// deserializing them.
+    pub fn as_untyped_mut(&mut self) -> &mut Input {
+        &mut self.input
+    }
+
+    /// Turn back into the underlying [`Input`].
+    pub fn into_untyped(self) -> Input {
+        self.input
     }
 }
 
@@ -285,10 +2566,15 @@ enum ReadOrTake {
     Take,
 }
 
-impl<'a> Input<'a> {
-    pub(crate) fn new(name: &str, connector: &'a Connector) -> Input<'a> {
+impl Input {
+    pub(crate) fn new(
+        name: Arc<str>,
+        c_name: CString,
+        connector: Arc<ConnectorInner>,
+    ) -> Input {
         Input {
-            name: name.to_string(),
+            name,
+            c_name,
             parent: connector,
         }
     }
@@ -300,7 +2586,9 @@ impl<'a> Input<'a> {
     /// still be available for accesse until they are pushed out of
     /// the `DataReader`'s cache for other reasons (i.e. Quality of
     /// Service parameters, such as History or Resource Limits).
-    pub fn read(&mut self) -> ConnectorFallible {
+    ///
+    /// Returns the number of samples in the received sample cache.
+    pub fn read(&mut self) -> ConnectorResult<usize> {
         self.impl_read_or_take(ReadOrTake::Read)
     }
 
@@ -309,32 +2597,57 @@ impl<'a> Input<'a> {
     /// This samples will be discard by the [`Input`] next time either
     /// [`Input::take()`] or [`Input::read()`] are called, and they
     /// will never be available for access again.
-    pub fn take(&mut self) -> ConnectorFallible {
+    ///
+    /// Returns the number of samples in the received sample cache.
+    pub fn take(&mut self) -> ConnectorResult<usize> {
         self.impl_read_or_take(ReadOrTake::Take)
     }
 
-    fn impl_read_or_take(&mut self, operation: ReadOrTake) -> ConnectorFallible {
+    fn impl_read_or_take(&mut self, operation: ReadOrTake) -> ConnectorResult<usize> {
+        // The sample cache belongs to this Input alone, and `&mut self` already
+        // excludes any access to its samples, so the native connector is only
+        // shared with the operations on other entities.
         let result = {
-            let native_mut = self.parent.native_mut()?;
+            let native = self.parent.native_ref()?;
             match operation {
-                ReadOrTake::Read => native_mut.read(&self.name),
-                ReadOrTake::Take => native_mut.take(&self.name),
+                ReadOrTake::Read => native.read(&self.c_name),
+                ReadOrTake::Take => native.take(&self.c_name),
             }
         };
 
-        if let Err(e) = result
-            && !e.is_native_error_code(crate::ffi::ReturnCode::NoData)
-        {
-            Err(e)
-        } else {
-            Ok(())
+        match result {
+            Ok(()) => self.len(),
+            Err(e) if e.is_native_error_code(crate::ffi::ReturnCode::NoData) => Ok(0),
+            Err(e) => Err(e),
         }
     }
 
+    /// Read samples like [`Input::read()`], returning a [`SamplesGuard`]
+    /// which returns the loan on them when dropped.
+    pub fn read_guard(&mut self) -> ConnectorResult<SamplesGuard<'_>> {
+        self.read()?;
+
+        Ok(SamplesGuard {
+            input: self,
+            returned: false,
+        })
+    }
+
+    /// Take samples like [`Input::take()`], returning a [`SamplesGuard`]
+    /// which returns the loan on them when dropped.
+    pub fn take_guard(&mut self) -> ConnectorResult<SamplesGuard<'_>> {
+        self.take()?;
+
+        Ok(SamplesGuard {
+            input: self,
+            returned: false,
+        })
+    }
+
     /// Return the loan on the samples previously taken
     /// from the underlying `DataReader`'s cache.
     pub fn return_loan(&mut self) -> ConnectorFallible {
-        self.parent.native_mut()?.return_loan(&self.name)
+        self.parent.native_ref()?.return_loan(&self.c_name)
     }
 
     /// Wait indefinitely for data to be available on an `Input`.
@@ -345,17 +2658,363 @@ impl<'a> Input<'a> {
     /// Wait for data to be available on an `Input`, or
     /// for a specified timeout to expire.
     pub fn wait_with_timeout(&self, timeout: std::time::Duration) -> ConnectorFallible {
-        self.impl_wait_for_data(Some(
-            // Durations cannot be negative
-            timeout.as_millis().try_into().unwrap_or(i32::MAX),
-        ))
+        self.impl_wait_for_data(Some(timeout))
     }
 
-    fn impl_wait_for_data(&self, timeout_ms: Option<i32>) -> ConnectorFallible {
-        self.parent
-            .native_ref()?
-            .get_input(&self.name)?
-            .wait_for_data(timeout_ms)
+    /// Wait for data to be available on an `Input`, or
+    /// for a specified timeout to expire, without blocking the async runtime.
+    ///
+    /// The native wait runs on a blocking thread of the Tokio runtime, and
+    /// fails like [`Input::wait_with_timeout`], or waits indefinitely like
+    /// [`Input::wait`] if no timeout is given.
+    ///
+    /// Dropping the future cancels the wait: the blocking thread stops within
+    /// one chunk of the wait, 100 ms, and holds no lock afterwards.
+    #[cfg(feature = "async")]
+    pub async fn wait_async(
+        &self,
+        timeout: Option<std::time::Duration>,
+    ) -> ConnectorFallible {
+        let parent = Arc::clone(&self.parent);
+        let c_name = self.c_name.clone();
+
+        crate::connector::spawn_blocking(move |cancelled| {
+            Self::wait_for_data_on(&parent, &c_name, timeout, Some(cancelled))
+        })
+        .await
+    }
+
+    /// Wait for data to be available on an `Input`, until the timeout
+    /// expires, indefinitely if no timeout is given, or until the
+    /// [`CancelToken`](crate::CancelToken) is cancelled.
+    ///
+    /// A cancelled wait returns an error for which
+    /// [`ConnectorError::is_cancelled`] returns `true`.
+    pub fn wait_cancellable(
+        &self,
+        timeout: Option<std::time::Duration>,
+        cancel: &crate::CancelToken,
+    ) -> ConnectorFallible {
+        self.parent.cancellable_wait(timeout, cancel, |chunk_ms| {
+            let reader = self.parent.native_ref()?.get_input(&self.c_name)?;
+            reader.wait_for_data(Some(chunk_ms))
+        })
+    }
+
+    fn impl_wait_for_data(
+        &self,
+        timeout: Option<std::time::Duration>,
+    ) -> ConnectorFallible {
+        Self::wait_for_data_on(&self.parent, &self.c_name, timeout, None)
+    }
+
+    /// Wait for data on the named `Input` of a connector.
+    ///
+    /// The connector lock is only held to look up the `DataReader`, not while
+    /// waiting, so that waiting doesn't block other threads.
+    fn wait_for_data_on(
+        parent: &ConnectorInner,
+        c_name: &std::ffi::CStr,
+        timeout: Option<std::time::Duration>,
+        cancelled: Option<&std::sync::atomic::AtomicBool>,
+    ) -> ConnectorFallible {
+        parent.interruptible_wait(timeout, cancelled, |chunk_ms| {
+            let reader = parent.native_ref()?.get_input(c_name)?;
+            reader.wait_for_data(Some(chunk_ms))
+        })
+    }
+
+    /// Take the samples available and return the data of the first valid one.
+    ///
+    /// The data is returned by value, so the [`Input`] is not borrowed afterwards.
+    /// Returns `None` if no valid sample was taken.
+    pub fn take_one(&mut self) -> ConnectorResult<Option<serde_json::Value>> {
+        self.take()?;
+
+        self.valid_samples()?
+            .next()
+            .map(|sample| sample?.deserialize::<serde_json::Value>())
+            .transpose()
+    }
+
+    /// Take the samples available and deserialize every valid one into `T`.
+    ///
+    /// Fails on the first sample that can't be deserialized, reporting its index.
+    pub fn take_deserialized<T>(&mut self) -> ConnectorResult<Vec<T>>
+    where
+        T: for<'de> serde::Deserialize<'de>,
+    {
+        self.take()?;
+
+        self.valid_samples()?
+            .map(|sample| sample?.deserialize_with_index())
+            .collect()
+    }
+
+    /// Check that a Rust type matches the DDS type of the [`Input`], returning
+    /// every mismatch rather than failing on the first.
+    ///
+    /// The first valid sample of the received sample cache is deserialized
+    /// into `T`, reporting the fields which aren't members of the sample and
+    /// those whose values are of the wrong kind. Fields which are `Option`s
+    /// may be missing, while unset optional members modeled without an
+    /// `Option` are reported as of the wrong kind.
+    ///
+    /// A deserialization error is returned if the cache holds no valid sample,
+    /// so data must have been read or taken first.
+    pub fn check_type<T>(&self) -> ConnectorResult<Vec<crate::TypeMismatch>>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let sample = self.valid_samples()?.next().transpose()?.ok_or_else(|| {
+            ConnectorError::from(ErrorKind::Invalid {
+                what: InvalidErrorKind::Deserialization,
+                context: std::format!(
+                    "No valid sample to check type '{}' against, read or take data first",
+                    std::any::type_name::<T>()
+                ),
+            })
+        })?;
+
+        let json = sample.get_as_json()?;
+        let value = serde_json::from_str(&json).map_err(|e| {
+            ErrorKind::Invalid {
+                what: InvalidErrorKind::Conversion,
+                context: std::format!("Sample JSON could not be parsed: {}", e),
+            }
+            .caused_by(e)
+        })?;
+
+        Ok(crate::value_deserializer::check_value::<T>(value)
+            .into_iter()
+            .map(|mismatch| match mismatch {
+                crate::TypeMismatch::MissingMember { field }
+                    if sample.has_field(&field).unwrap_or(false) =>
+                {
+                    crate::TypeMismatch::WrongKind {
+                        field,
+                        reason: "the member is optional and unset, expected an Option"
+                            .to_string(),
+                    }
+                }
+                mismatch => mismatch,
+            })
+            .collect())
+    }
+
+    /// Turn the [`Input`] into a [`TypedInput`], which deserializes every
+    /// sample into `T`.
+    pub fn typed<T>(self) -> TypedInput<T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        TypedInput {
+            input: self,
+            _type: std::marker::PhantomData,
+        }
+    }
+
+    /// Take the samples available and deserialize every valid one into `T`,
+    /// skipping those that can't be deserialized.
+    ///
+    /// The errors of the skipped samples are returned along with the data.
+    pub fn take_deserialized_lossy<T>(
+        &mut self,
+    ) -> ConnectorResult<(Vec<T>, Vec<ConnectorError>)>
+    where
+        T: for<'de> serde::Deserialize<'de>,
+    {
+        self.take()?;
+
+        let mut data = Vec::new();
+        let mut errors = Vec::new();
+        for sample in self.valid_samples()? {
+            match sample.and_then(|sample| sample.deserialize_with_index()) {
+                Ok(value) => data.push(value),
+                Err(e) => errors.push(e),
+            }
+        }
+
+        Ok((data, errors))
+    }
+
+    /// Turn the [`Input`] into a [`SampleStream`] of the data of its valid
+    /// samples, deserialized into `T`.
+    ///
+    /// The [`Input`] is moved to a blocking thread of the Tokio runtime, which
+    /// must be running, where it waits for data up to `poll_timeout` at a time,
+    /// takes it and forwards the data to the stream. No sample is lost: the
+    /// thread waits for the stream to be polled while the stream is full.
+    ///
+    /// Samples which can't be deserialized are yielded as errors. Any other
+    /// error, such as a wait interrupted with [`Connector::wake_handle`](crate::Connector::wake_handle),
+    /// is yielded and ends the stream. Once the stream is dropped, the thread
+    /// stops and releases the [`Input`] after its current wait.
+    #[cfg(feature = "async")]
+    pub fn into_stream<T>(mut self, poll_timeout: std::time::Duration) -> SampleStream<T>
+    where
+        T: serde::de::DeserializeOwned + Send + 'static,
+    {
+        let (sender, receiver) = tokio::sync::mpsc::channel(SampleStream::<T>::CAPACITY);
+
+        tokio::task::spawn_blocking(move || {
+            while !sender.is_closed() {
+                match self.wait_and_take(Some(poll_timeout)) {
+                    Ok(_) => {}
+                    Err(e) if e.is_timeout() => continue,
+                    Err(e) => {
+                        let _ = sender.blocking_send(Err(e));
+                        return;
+                    }
+                }
+
+                let samples = match self.valid_samples() {
+                    Ok(samples) => samples,
+                    Err(e) => {
+                        let _ = sender.blocking_send(Err(e));
+                        return;
+                    }
+                };
+                for sample in samples {
+                    let data = sample.and_then(|sample| sample.deserialize_with_index());
+                    if sender.blocking_send(data).is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+
+        SampleStream { receiver }
+    }
+
+    /// Move the [`Input`] to a new thread which forwards the data of its valid
+    /// samples, deserialized into `T`, to a channel of the given capacity.
+    ///
+    /// The thread waits for data, takes it and sends it to the returned
+    /// [`Receiver`](std::sync::mpsc::Receiver), blocking while the channel is
+    /// full, so no sample is lost. Samples which can't be deserialized are
+    /// sent as errors.
+    ///
+    /// The thread stops once the receiver is dropped, the next time it has
+    /// data to send, and then releases the [`Input`]. It also stops on any
+    /// other error, such as a wait interrupted with
+    /// [`Connector::wake_handle`](crate::Connector::wake_handle), which is
+    /// returned through the [`JoinHandle`](std::thread::JoinHandle).
+    pub fn spawn_into_channel<T>(
+        mut self,
+        capacity: usize,
+    ) -> (
+        std::thread::JoinHandle<ConnectorFallible>,
+        std::sync::mpsc::Receiver<ConnectorResult<T>>,
+    )
+    where
+        T: serde::de::DeserializeOwned + Send + 'static,
+    {
+        let (sender, receiver) = std::sync::mpsc::sync_channel(capacity);
+
+        let handle = std::thread::spawn(move || {
+            loop {
+                self.wait_and_take(None)?;
+
+                for sample in self.valid_samples()? {
+                    let data = sample.and_then(|sample| sample.deserialize_with_index());
+                    if sender.send(data).is_err() {
+                        return Ok(());
+                    }
+                }
+            }
+        });
+
+        (handle, receiver)
+    }
+
+    /// Move the [`Input`] to a new thread which invokes `callback` with every
+    /// valid sample it takes.
+    ///
+    /// The thread waits for data up to 100 ms at a time, so that dropping the
+    /// returned [`ListenerHandle`] stops it promptly. A panic in the callback
+    /// is caught and reported by [`ListenerHandle::take_panics`], without
+    /// stopping the thread. Any other error, such as a wait interrupted with
+    /// [`Connector::wake_handle`](crate::Connector::wake_handle), ends the
+    /// thread and is returned by [`ListenerHandle::stop`].
+    pub fn on_data(
+        mut self,
+        mut callback: impl FnMut(&Sample) + Send + 'static,
+    ) -> ListenerHandle {
+        let stop = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let panics = Arc::new(std::sync::Mutex::new(Vec::new()));
+
+        let thread = {
+            let stop = Arc::clone(&stop);
+            let panics = Arc::clone(&panics);
+            std::thread::spawn(move || {
+                while !stop.load(std::sync::atomic::Ordering::Acquire) {
+                    match self.wait_and_take(Some(crate::connector::WAIT_CHUNK)) {
+                        Ok(_) => {}
+                        Err(e) if e.is_timeout() => continue,
+                        Err(e) => return Err(e),
+                    }
+
+                    for sample in self.valid_samples()? {
+                        let sample = sample?;
+                        let result = std::panic::catch_unwind(
+                            std::panic::AssertUnwindSafe(|| callback(&sample)),
+                        );
+                        if let Err(payload) = result {
+                            panics
+                                .lock()
+                                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                                .push(panic_message(payload.as_ref()));
+                        }
+                    }
+                }
+
+                Ok(())
+            })
+        };
+
+        ListenerHandle {
+            stop,
+            panics,
+            thread: Some(thread),
+        }
+    }
+
+    /// Wait for data to be available on an `Input`, indefinitely if no timeout
+    /// is given, and then take it.
+    ///
+    /// Returns the number of samples in the received sample cache, which may be
+    /// `0` if the data was consumed before it could be taken.
+    pub fn wait_and_take(
+        &mut self,
+        timeout: Option<std::time::Duration>,
+    ) -> ConnectorResult<usize> {
+        match timeout {
+            Some(timeout) => self.wait_with_timeout(timeout)?,
+            None => self.wait()?,
+        };
+
+        self.take()
+    }
+
+    /// Wait for data to be available on an `Input` and take it, retrying
+    /// until at least one sample is taken or the timeout expires.
+    ///
+    /// A timeout error is returned if no sample could be taken in time.
+    pub fn wait_and_take_until_timeout(
+        &mut self,
+        timeout: std::time::Duration,
+    ) -> ConnectorResult<usize> {
+        let deadline = std::time::Instant::now() + timeout;
+
+        loop {
+            let remaining = deadline.saturating_duration_since(std::time::Instant::now());
+            match self.wait_and_take(Some(remaining))? {
+                0 if remaining.is_zero() => return ErrorKind::timeout_error().into_err(),
+                0 => continue,
+                count => return Ok(count),
+            }
+        }
     }
 
     /// Wait indefinitely for a publication to be matched
@@ -369,49 +3028,235 @@ impl<'a> Input<'a> {
         &self,
         timeout: std::time::Duration,
     ) -> ConnectorResult<i32> {
-        self.impl_wait_for_publications(Some(
-            // Durations cannot be negative
-            timeout.as_millis().try_into().unwrap_or(i32::MAX),
-        ))
+        self.impl_wait_for_publications(Some(timeout))
+    }
+
+    /// Wait for the publications matched with this `Input` to change,
+    /// indefinitely if no timeout is given.
+    ///
+    /// Unlike [`Input::wait_for_publications`], the returned [`MatchEvent`]
+    /// tells matches apart from unmatches and includes the number of
+    /// publications currently matched.
+    pub fn wait_for_publication_event(
+        &self,
+        timeout: Option<std::time::Duration>,
+    ) -> ConnectorResult<MatchEvent> {
+        let change = match timeout {
+            Some(timeout) => self.wait_for_publications_with_timeout(timeout)?,
+            None => self.wait_for_publications()?,
+        };
+
+        Ok(MatchEvent::new(change, &self.matched_publications()?))
+    }
+
+    /// Wait for the publications matched with this `Input` to change, or for
+    /// a specified timeout to expire, without blocking the async runtime.
+    ///
+    /// This is the async counterpart of [`Input::wait_for_publications_with_timeout`],
+    /// waiting indefinitely if no timeout is given. Dropping the future
+    /// cancels the wait like [`Input::wait_async`].
+    #[cfg(feature = "async")]
+    pub async fn wait_for_publications_async(
+        &self,
+        timeout: Option<std::time::Duration>,
+    ) -> ConnectorResult<i32> {
+        let parent = Arc::clone(&self.parent);
+        let c_name = self.c_name.clone();
+
+        crate::connector::spawn_blocking(move |cancelled| {
+            parent.interruptible_wait(timeout, Some(cancelled), |chunk_ms| {
+                let reader = parent.native_ref()?.get_input(&c_name)?;
+                reader.wait_for_matched_publication(Some(chunk_ms))
+            })
+        })
+        .await
+    }
+
+    /// Wait for the publications matched with this `Input` to change, until
+    /// the timeout expires, indefinitely if no timeout is given, or until the
+    /// [`CancelToken`](crate::CancelToken) is cancelled.
+    pub fn wait_for_publications_cancellable(
+        &self,
+        timeout: Option<std::time::Duration>,
+        cancel: &crate::CancelToken,
+    ) -> ConnectorResult<i32> {
+        self.parent.cancellable_wait(timeout, cancel, |chunk_ms| {
+            let reader = self.parent.native_ref()?.get_input(&self.c_name)?;
+            reader.wait_for_matched_publication(Some(chunk_ms))
+        })
     }
 
     fn impl_wait_for_publications(
         &self,
-        timeout_ms: Option<i32>,
+        timeout: Option<std::time::Duration>,
     ) -> ConnectorResult<i32> {
+        self.parent.interruptible_wait(timeout, None, |chunk_ms| {
+            let reader = self.parent.native_ref()?.get_input(&self.c_name)?;
+            reader.wait_for_matched_publication(Some(chunk_ms))
+        })
+    }
+
+    /// Create a [`SampleIterator`] over the [`Input`]'s received sample cache.
+    ///
+    /// The iterator takes an immutable borrow on the [`Input`], preventing
+    /// the sample cache from being modified by calls to [`Input::take()`]
+    /// or [`Input::read()`] while iterating.
+    pub fn samples(&self) -> ConnectorResult<SampleIterator<'_>> {
+        Ok(SampleIterator {
+            index: 0,
+            samples_len: self.len()?,
+            input: self,
+        })
+    }
+
+    /// Copy every sample in the [`Input`]'s received sample cache into an [`OwnedSample`].
+    pub fn snapshot(&self) -> ConnectorResult<Vec<OwnedSample>> {
+        self.samples()?
+            .map(|sample| OwnedSample::from_sample(&sample))
+            .collect()
+    }
+
+    /// Create a [`ValidSampleIterator`] over the [`Input`]'s received sample cache.
+    pub fn valid_samples(&self) -> ConnectorResult<ValidSampleIterator<'_>> {
+        self.samples().map(SampleIterator::valid_only)
+    }
+
+    /// Access the [`Sample`] at a given position of the [`Input`]'s received sample cache.
+    ///
+    /// An invalid argument error is returned if the index is out of range.
+    pub fn sample_at(&self, index: usize) -> ConnectorResult<Sample<'_>> {
+        let len = self.len()?;
+        if index < len {
+            Ok(Sample::new(index, self))
+        } else {
+            ErrorKind::invalid_argument_error(format!(
+                "Sample index {} is out of range, the Input holds {} samples",
+                index, len
+            ))
+            .into_err()
+        }
+    }
+
+    /// Get a pointer to the native sample at a given position of the [`Input`]'s
+    /// received sample cache, for use with the *Connext DDS* C API.
+    ///
+    /// The pointer is valid until the [`Input`] reads or takes samples again,
+    /// which the borrow of the returned [`NativePointer`](crate::NativePointer)
+    /// prevents. An invalid argument error is returned if the index is out of
+    /// range.
+    #[cfg(feature = "unsafe-interop")]
+    pub fn native_sample_ptr(
+        &self,
+        index: usize,
+    ) -> ConnectorResult<crate::NativePointer<'_>> {
+        self.sample_at(index)?;
+
         self.parent
             .native_ref()?
-            .get_input(&self.name)?
-            .wait_for_matched_publication(timeout_ms)
+            .get_native_sample(&self.c_name, index)
+            .map(crate::ffi::FfiSample::into_native_pointer)
+    }
+
+    /// Get a pointer to the native `DDS_DataReader` of the [`Input`], for use
+    /// with the *Connext DDS* C API.
+    ///
+    /// The returned [`NativeEntityGuard`](crate::NativeEntityGuard) holds the
+    /// connector lock, and the pointer is only valid while it's alive.
+    #[cfg(feature = "unsafe-interop")]
+    pub fn native_datareader_ptr(&self) -> ConnectorResult<crate::NativeEntityGuard<'_>> {
+        let native = self.parent.native_mut()?;
+        let reader = native.get_input(&self.c_name)?.as_native_ptr();
+
+        Ok(crate::NativeEntityGuard::new(native, reader))
+    }
+
+    /// Access the most recent [`Sample`] of the [`Input`]'s received sample cache, if any.
+    pub fn last_sample(&self) -> ConnectorResult<Option<Sample<'_>>> {
+        Ok(self
+            .len()?
+            .checked_sub(1)
+            .map(|index| Sample::new(index, self)))
     }
 
-    /// Access the size of the `Input`'s received sample cache.
-    fn get_count(&self) -> ConnectorResult<usize> {
+    /// Get the number of samples in the [`Input`]'s received sample cache.
+    pub fn len(&self) -> ConnectorResult<usize> {
         self.parent
             .native_ref()?
-            .get_sample_count(&self.name)
-            .map(|res| res as usize)
+            .get_sample_count(&self.c_name)
+            .and_then(|count| count_from_native("sample count", count))
+    }
+
+    /// Check whether the [`Input`]'s received sample cache is empty.
+    pub fn is_empty(&self) -> ConnectorResult<bool> {
+        self.len().map(|len| len == 0)
     }
 
     /// Access a numeric field in a received sample.
     fn get_number(&self, index: usize, field_name: &str) -> ConnectorResult<f64> {
-        self.parent
-            .native_ref()?
-            .get_number_from_sample(&self.name, index, field_name)
+        let result = self.parent.native_ref()?.get_number_from_sample(
+            &self.c_name,
+            index,
+            field_name,
+        );
+        result.map_err(|e| self.field_error(index, field_name, Some("number"), e))
     }
 
     /// Access a boolean field in a received sample.
     fn get_boolean(&self, index: usize, field_name: &str) -> ConnectorResult<bool> {
-        self.parent
-            .native_ref()?
-            .get_boolean_from_sample(&self.name, index, field_name)
+        let result = self.parent.native_ref()?.get_boolean_from_sample(
+            &self.c_name,
+            index,
+            field_name,
+        );
+        result.map_err(|e| self.field_error(index, field_name, Some("boolean"), e))
     }
 
     /// Access a string field in a received sample.
-    fn get_string(&self, index: usize, field_name: &str) -> ConnectorResult<String> {
-        self.parent
-            .native_ref()?
-            .get_string_from_sample(&self.name, index, field_name)
+    fn get_string(
+        &self,
+        index: usize,
+        field_name: &str,
+    ) -> ConnectorResult<NativeString> {
+        let result = self.parent.native_ref()?.get_string_from_sample(
+            &self.c_name,
+            index,
+            field_name,
+        );
+        result.map_err(|e| self.field_error(index, field_name, Some("string"), e))
+    }
+
+    /// Classify the native error of accessing a field of a received sample,
+    /// using the JSON of the sample: a malformed path, an index out of bounds,
+    /// or a field holding another type than the `expected` one.
+    fn field_error(
+        &self,
+        index: usize,
+        field_name: &str,
+        expected: Option<&'static str>,
+        error: ConnectorError,
+    ) -> ConnectorError {
+        if !error.is_native_error() {
+            return error;
+        }
+
+        let sample: Option<serde_json::Value> = self
+            .get_json(index)
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok());
+        let error = error.into_path_error(field_name, sample.as_ref());
+        match (
+            expected,
+            sample
+                .as_ref()
+                .and_then(|sample| crate::json_path::resolve(sample, field_name)),
+        ) {
+            (Some(expected), Some(value)) => error.into_type_mismatch(
+                field_name,
+                expected,
+                crate::json_path::type_name(value),
+            ),
+            _ => error,
+        }
     }
 
     /// Access a variant-type field in a received sample.
@@ -422,47 +3267,222 @@ impl<'a> Input<'a> {
     ) -> ConnectorResult<SelectedValue> {
         self.parent
             .native_ref()?
-            .get_from_sample(&self.name, index, field_name)
+            .get_from_sample(&self.c_name, index, field_name)
+    }
+
+    /// Access several variant-type fields in a received sample, holding the
+    /// connector lock once for all of them.
+    fn get_fields(
+        &self,
+        index: usize,
+        field_names: &[&str],
+    ) -> ConnectorResult<Vec<ConnectorResult<SelectedValue>>> {
+        let native = self.parent.native_ref()?;
+
+        Ok(field_names
+            .iter()
+            .map(|field_name| native.get_from_sample(&self.c_name, index, field_name))
+            .collect())
     }
 
     /// Access a field (as JSON) in a received sample.
-    fn get_field_json(&self, index: usize, field_name: &str) -> ConnectorResult<String> {
+    fn get_field_json(
+        &self,
+        index: usize,
+        field_name: &str,
+    ) -> ConnectorResult<NativeString> {
         self.parent
             .native_ref()?
-            .get_json_member(&self.name, index, field_name)
+            .get_json_member(&self.c_name, index, field_name)
     }
 
     /// Access a variant-type field in a received sample's info.
     fn get_info(&self, index: usize, field_name: &str) -> ConnectorResult<SelectedValue> {
         self.parent
             .native_ref()?
-            .get_from_info(&self.name, index, field_name)
+            .get_from_info(&self.c_name, index, field_name)
     }
 
     /// Access a received sample's info field as JSON.
     fn get_info_json(&self, index: usize, field_name: &str) -> ConnectorResult<String> {
         self.parent
             .native_ref()?
-            .get_json_from_infos(&self.name, index, field_name)
+            .get_json_from_infos(&self.c_name, index, field_name)
+    }
+
+    /// Deserialize a received sample field by field.
+    fn deserialize_direct<T>(&self, index: usize) -> ConnectorResult<T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let native = self.parent.native_ref()?;
+
+        crate::field_deserializer::deserialize_from(&native, &self.c_name, index)
     }
 
-    /// Access a received sample as JSON string.
-    fn get_json(&self, index: usize) -> ConnectorResult<String> {
-        self.parent.native_ref()?.get_json_sample(&self.name, index)
+    /// Access a received sample as JSON string, without copying it.
+    fn get_json(&self, index: usize) -> ConnectorResult<NativeString> {
+        self.parent
+            .native_ref()?
+            .get_json_sample(&self.c_name, index)
     }
 
     /// Check whether a received sample contains valid data.
     fn is_valid(&self, index: usize) -> ConnectorResult<bool> {
-        self.parent
-            .native_ref()?
-            .get_boolean_from_infos(&self.name, index, "valid_data")
+        self.parent.native_ref()?.get_boolean_from_infos(
+            &self.c_name,
+            index,
+            "valid_data",
+        )
+    }
+
+    /// Check the validity of a range of samples while holding the native lock once.
+    fn validity_bitmap(
+        &self,
+        indices: std::ops::Range<usize>,
+    ) -> ConnectorResult<Vec<bool>> {
+        let native = self.parent.native_ref()?;
+
+        indices
+            .map(|index| native.get_boolean_from_infos(&self.c_name, index, "valid_data"))
+            .collect()
     }
 
     /// Display the list of publications currently matched.
     pub fn display_matched_publications(&self) -> ConnectorResult<String> {
         self.parent
             .native_ref()?
-            .get_input(&self.name)?
+            .get_input(&self.c_name)?
             .get_matched_publications()
     }
+
+    /// Get the number of publications currently matched, without waiting.
+    ///
+    /// It may be called while another thread waits on this `Input`, since it
+    /// only reads the list of matched publications.
+    pub fn matched_publication_count(&self) -> ConnectorResult<usize> {
+        self.matched_publications().map(|matched| matched.len())
+    }
+
+    /// Whether at least one publication is currently matched, without waiting.
+    pub fn is_matched(&self) -> ConnectorResult<bool> {
+        self.matched_publication_count().map(|count| count > 0)
+    }
+
+    /// Get the list of publications currently matched, parsed from the JSON
+    /// returned by [`Input::display_matched_publications`].
+    pub fn matched_publications(&self) -> ConnectorResult<Vec<EndpointInfo>> {
+        EndpointInfo::parse_list(&self.display_matched_publications()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        InstanceState, SampleState, SampleStateMask, ViewState, count_from_native,
+        parse_handle,
+    };
+    use crate::{
+        ConnectorError,
+        result::{ErrorKind, InvalidErrorKind},
+    };
+
+    #[test]
+    fn test_state_mask_merge() {
+        let new = SampleStateMask::any().with_view_state(ViewState::New);
+        let alive = SampleStateMask::any().with_instance_state(InstanceState::Alive);
+
+        assert_eq!(
+            Some(
+                SampleStateMask::any()
+                    .with_view_state(ViewState::New)
+                    .with_instance_state(InstanceState::Alive)
+            ),
+            new.merge(alive)
+        );
+        assert_eq!(Some(new), new.merge(new));
+        assert_eq!(Some(new), SampleStateMask::any().merge(new));
+        assert_eq!(
+            None,
+            new.merge(SampleStateMask::any().with_view_state(ViewState::NotNew))
+        );
+        assert_eq!(
+            None,
+            SampleStateMask::any()
+                .with_sample_state(SampleState::Read)
+                .merge(SampleStateMask::any().with_sample_state(SampleState::NotRead))
+        );
+    }
+
+    #[test]
+    fn test_count_from_native() {
+        for (count, expected) in [(0.0, 0), (42.0, 42), (2f64.powi(53), 1 << 53)] {
+            assert!(
+                matches!(count_from_native("count", count), Ok(len) if len == expected),
+                "Expected {} to be converted into {}",
+                count,
+                expected
+            );
+        }
+
+        for invalid in [
+            f64::NAN,
+            -1.0,
+            -0.5,
+            1.5,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            2f64.powi(53) + 2.0,
+            2f64.powi(64),
+        ] {
+            let result = count_from_native("count", invalid);
+            assert!(
+                matches!(
+                    result.as_ref().map_err(ConnectorError::kind),
+                    Err(ErrorKind::Invalid {
+                        what: InvalidErrorKind::Assertion,
+                        ..
+                    })
+                ),
+                "Expected an assertion error for {}, got {:?}",
+                invalid,
+                result
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_handle() {
+        let bytes: Vec<u8> = (1..=16).collect();
+        for json in [
+            "[1,2,3,4,5,6,7,8,9,10,11,12,13,14,15,16]",
+            r#"{"value":[1,2,3,4,5,6,7,8,9,10,11,12,13,14,15,16]}"#,
+            r#"{"key_hash":{"value":[1,2,3,4,5,6,7,8,9,10,11,12,13,14,15,16]},"isValid":1}"#,
+            r#""[1,2,3,4,5,6,7,8,9,10,11,12,13,14,15,16]""#,
+        ] {
+            let handle = parse_handle(json);
+            assert!(
+                matches!(&handle, Some(handle) if handle.as_bytes() == bytes),
+                "Expected {} to be parsed, got {:?}",
+                json,
+                handle
+            );
+        }
+
+        assert!(matches!(
+            parse_handle("[0,0,0,0]"),
+            Some(handle) if handle.is_nil()
+        ));
+        assert!(matches!(
+            parse_handle("[1,2,255]"),
+            Some(handle) if handle.to_string() == "0102ff"
+        ));
+        for invalid in ["", "42", "[256]", r#"{"other":[1]}"#] {
+            assert!(
+                parse_handle(invalid).is_none(),
+                "{} should be invalid",
+                invalid
+            );
+        }
+    }
 }
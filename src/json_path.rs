@@ -0,0 +1,292 @@
+/*******************************************************************************
+ * (c) 2025 Copyright, Real-Time Innovations.  All rights reserved.            *
+ * No duplications, whole or partial, manual or electronic, may be made        *
+ * without express written permission.  Any such copies, or revisions thereof, *
+ * must display this notice unaltered.                                         *
+ * This code contains trade secrets of Real-Time Innovations, Inc.             *
+ *******************************************************************************/
+
+#![doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/docs/json_path.md"))]
+
+use serde_json::Value;
+
+/// A single step of a field path.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum PathSegment<'a> {
+    /// Access a member of a structure or union by name.
+    Member(&'a str),
+
+    /// Access an element of an array or sequence by position.
+    Index(usize),
+}
+
+/// Split a field path into its segments, or `None` if the path is malformed.
+///
+/// Multi-dimensional indexes (`[i,j]`) are expanded into consecutive [`PathSegment::Index`].
+pub(crate) fn parse(path: &str) -> Option<Vec<PathSegment<'_>>> {
+    let mut segments = Vec::new();
+
+    for part in path.split('.') {
+        let (name, mut rest) = match part.find('[') {
+            Some(position) => part.split_at(position),
+            None => (part, ""),
+        };
+
+        if name.is_empty() {
+            return None;
+        }
+        segments.push(PathSegment::Member(name));
+
+        while !rest.is_empty() {
+            let end = rest.find(']')?;
+            for index in rest.get(1..end)?.split(',') {
+                segments.push(PathSegment::Index(index.trim().parse().ok()?));
+            }
+            rest = &rest[end + 1..];
+
+            if !rest.is_empty() && !rest.starts_with('[') {
+                return None;
+            }
+        }
+    }
+
+    Some(segments)
+}
+
+/// A buffer holding a field name in the field-name syntax of RTI Connector,
+/// such as `simple.long_field` or `long_matrix[1,2]`, which can be extended
+/// with members and indexes.
+///
+/// It's the single place where field names are built, for the serializer,
+/// the deserializer and [`FieldPath`](crate::FieldPath).
+pub(crate) trait FieldNameBuf {
+    /// The length of the field name.
+    fn len(&self) -> usize;
+
+    /// Shorten the field name to `len`.
+    fn truncate(&mut self, len: usize);
+
+    /// Append text to the field name.
+    fn push_str(&mut self, text: &str);
+
+    /// Append the decimal representation of an index to the field name.
+    fn push_number(&mut self, number: usize);
+
+    /// Append a member name to the field name, returning the previous length.
+    fn push_member(&mut self, name: &str) -> usize {
+        let len = self.len();
+        if len > 0 {
+            self.push_str(".");
+        }
+        self.push_str(name);
+        len
+    }
+
+    /// Append an index to the field name, returning the previous length.
+    ///
+    /// With `flatten`, the field name must end with the index of an array
+    /// element, and nested arrays are indexed as multi-dimensional arrays,
+    /// `[i,j]`.
+    fn push_index(&mut self, index: usize, flatten: bool) -> usize {
+        let len = self.len();
+        if flatten {
+            self.truncate(len - 1);
+            self.push_str(",");
+        } else {
+            self.push_str("[");
+        }
+        self.push_number(index);
+        self.push_str("]");
+        len
+    }
+
+    /// Remove the index appended by [`FieldNameBuf::push_index`].
+    fn pop_index(&mut self, len: usize, flatten: bool) {
+        if flatten {
+            self.truncate(len - 1);
+            self.push_str("]");
+        } else {
+            self.truncate(len);
+        }
+    }
+}
+
+impl FieldNameBuf for Vec<u8> {
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    fn truncate(&mut self, len: usize) {
+        Vec::truncate(self, len)
+    }
+
+    fn push_str(&mut self, text: &str) {
+        self.extend_from_slice(text.as_bytes());
+    }
+
+    fn push_number(&mut self, number: usize) {
+        let _ = std::io::Write::write_fmt(self, format_args!("{}", number));
+    }
+}
+
+impl FieldNameBuf for String {
+    fn len(&self) -> usize {
+        String::len(self)
+    }
+
+    fn truncate(&mut self, len: usize) {
+        String::truncate(self, len)
+    }
+
+    fn push_str(&mut self, text: &str) {
+        String::push_str(self, text);
+    }
+
+    fn push_number(&mut self, number: usize) {
+        let _ = std::fmt::Write::write_fmt(self, format_args!("{}", number));
+    }
+}
+
+/// Resolve a field path against a JSON value, or `None` if it doesn't exist.
+pub(crate) fn resolve<'v>(value: &'v Value, path: &str) -> Option<&'v Value> {
+    resolve_segments(value, parse(path)?)
+}
+
+/// Resolve the structure holding the last member of a field path against a
+/// JSON value, or `None` if it doesn't exist or the path ends with an index.
+pub(crate) fn resolve_parent<'v>(value: &'v Value, path: &str) -> Option<&'v Value> {
+    let mut segments = parse(path)?;
+    match segments.pop()? {
+        PathSegment::Member(_) => {
+            resolve_segments(value, segments).filter(|parent| parent.is_object())
+        }
+        PathSegment::Index(_) => None,
+    }
+}
+
+/// Resolve the segments of a field path against a JSON value.
+fn resolve_segments<'v>(
+    value: &'v Value,
+    segments: Vec<PathSegment<'_>>,
+) -> Option<&'v Value> {
+    segments
+        .into_iter()
+        .try_fold(value, |current, segment| match segment {
+            PathSegment::Member(name) => current.get(name),
+            PathSegment::Index(index) => current.get(index),
+        })
+}
+
+/// Find the first index of a field path past the end of its array or sequence
+/// in a JSON value, or `None` if there isn't one or the path doesn't exist.
+pub(crate) fn out_of_bounds(value: &Value, path: &str) -> Option<usize> {
+    let mut current = value;
+
+    for segment in parse(path)? {
+        current = match segment {
+            PathSegment::Member(name) => current.get(name)?,
+            PathSegment::Index(index) => {
+                let elements = current.as_array()?;
+                if index >= elements.len() {
+                    return Some(index);
+                }
+                &elements[index]
+            }
+        };
+    }
+
+    None
+}
+
+/// Describe the type of a JSON value, for error messages.
+pub(crate) fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "sequence or array",
+        Value::Object(_) => "structure",
+    }
+}
+
+/// Wrap a JSON value into the nested objects described by a field path, so that
+/// it can be set as a partial JSON instance.
+///
+/// Returns `None` if the path is malformed or accesses elements by position,
+/// since those can't be expressed as a partial JSON object.
+pub(crate) fn fragment(path: &str, value: Value) -> Option<Value> {
+    parse(path)?
+        .into_iter()
+        .rev()
+        .try_fold(value, |inner, segment| match segment {
+            PathSegment::Member(name) => Some(Value::Object(serde_json::Map::from_iter(
+                [(name.to_string(), inner)],
+            ))),
+            PathSegment::Index(_) => None,
+        })
+}
+
+/// Turn the path of a value reported by `serde_path_to_error` into a field
+/// path within the field `parent`, or `None` if it is the whole value.
+///
+/// Enum variants aren't members, so they are skipped, and the path stops at
+/// any segment which can't be named, such as a non-string map key.
+pub(crate) fn from_serde_path(
+    parent: &str,
+    path: &serde_path_to_error::Path,
+) -> Option<String> {
+    use serde_path_to_error::Segment;
+
+    let mut field = parent.to_string();
+    for segment in path {
+        match segment {
+            Segment::Map { key } if field.is_empty() => field.push_str(key),
+            Segment::Map { key } => {
+                field.push('.');
+                field.push_str(key);
+            }
+            Segment::Seq { index } => field.push_str(&std::format!("[{}]", index)),
+            Segment::Enum { .. } => {}
+            Segment::Unknown => break,
+        }
+    }
+
+    (!field.is_empty()).then_some(field)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FieldNameBuf;
+    use crate::FieldPath;
+
+    #[test]
+    fn test_field_name_buf() {
+        let mut field = Vec::new();
+        field.push_member("long_matrix");
+        let row = field.push_index(1, false);
+        let column = field.push_index(2, true);
+        assert_eq!(b"long_matrix[1,2]", field.as_slice());
+
+        field.pop_index(column, true);
+        assert_eq!(b"long_matrix[1]", field.as_slice());
+        field.pop_index(row, false);
+        field.push_member("simple");
+        assert_eq!(b"long_matrix.simple", field.as_slice());
+
+        // The derive support builds the same field names
+        let path = FieldPath::default()
+            .member("long_matrix")
+            .array_element(1)
+            .array_element(2);
+        assert_eq!("long_matrix[1,2]", path.as_str());
+        assert_eq!(
+            "double_sequence[3].value",
+            FieldPath::default()
+                .member("double_sequence")
+                .element(3)
+                .member("value")
+                .as_str()
+        );
+    }
+}
@@ -16,17 +16,54 @@
     clippy::panic
 )]
 
-pub use connector::{Connector, SelectedValue};
+#[cfg(feature = "async")]
+pub use connector::DataHandler;
+pub use connector::{
+    CancelToken, Connector, EndpointInfo, MatchEvent, SelectedValue, WakeHandle,
+};
+pub use dds_data::{DdsData, DdsField, FieldPath};
+pub use dds_enum::DdsEnum;
+pub use dds_time::DdsTime;
+pub use dds_union::DdsUnion;
 pub use ffi::GlobalsDropGuard;
-pub use input::{Input, Sample, SampleIterator, ValidSampleIterator};
-pub use output::{Instance, Output, WriteParams, WriteParamsAction, WriteParamsIdentity};
-pub use result::{ConnectorError, ConnectorFallible, ConnectorResult};
+pub use ffi::ReturnCode;
+#[cfg(feature = "unsafe-interop")]
+pub use ffi::{NativeEntityGuard, NativePointer};
+#[cfg(feature = "async")]
+pub use input::SampleStream;
+pub use input::{
+    CountedValidSampleIterator, DeserializeOptions, Guid, Handle, Input, InstanceState,
+    LenientSampleInfo, ListenerHandle, LossyValidSampleIterator, OwnedSample, Sample,
+    SampleIdentity, SampleInfo, SampleIterator, SampleState, SampleStateMask,
+    SampleWithInfoIterator, SamplesGuard, StateFilteredSampleIterator, TypedInput,
+    ValidSampleIterator, ViewState,
+};
+pub use output::{
+    AcknowledgmentStatus, Instance, OptionalBehavior, Output, PersistentInstance,
+    TypedOutput, WriteParams, WriteParamsAction, WriteParamsIdentity,
+};
+pub use result::{
+    BusyErrorKind, ConnectorError, ConnectorFallible, ConnectorResult, ErrorKind,
+    InvalidErrorKind, NotFoundErrorKind,
+};
+#[cfg(feature = "derive")]
+pub use rtiddsconnector_derive::{DdsData, DdsEnum};
+pub use type_check::TypeMismatch;
 
 mod connector;
+mod dds_data;
+pub mod dds_enum;
+mod dds_time;
+pub mod dds_union;
 mod ffi;
+mod field_deserializer;
+mod field_serializer;
 mod input;
+mod json_path;
 mod output;
 mod result;
+mod type_check;
+mod value_deserializer;
 
 #[cfg(doc)]
 pub mod guide {
@@ -9,16 +9,188 @@
 #![doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/docs/output.md"))]
 
 use crate::{
-    Connector, ConnectorFallible, ConnectorResult, SelectedValue,
+    ConnectorError, ConnectorFallible, ConnectorResult, EndpointInfo, MatchEvent,
+    SelectedValue,
+    connector::ConnectorInner,
+    input::PathError,
     result::{ErrorKind, InvalidErrorKind},
 };
+use std::{
+    ffi::CString,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+/// The largest integer magnitude that a `f64` represents exactly, 2^53.
+pub(crate) const MAX_EXACT_INTEGER: u64 = 1 << f64::MANTISSA_DIGITS;
+
+/// The number of characters of a JSON payload included in error messages.
+const MAX_PAYLOAD_IN_ERROR: usize = 256;
+
+/// Convert a [`SystemTime`](std::time::SystemTime) into nanoseconds since the epoch.
+fn nanos_from_system_time(time: std::time::SystemTime) -> ConnectorResult<i64> {
+    let nanos = match time.duration_since(std::time::UNIX_EPOCH) {
+        Ok(offset) => i64::try_from(offset.as_nanos()).ok(),
+        Err(e) => i64::try_from(e.duration().as_nanos())
+            .ok()
+            .and_then(i64::checked_neg),
+    };
+
+    nanos.ok_or_else(|| {
+        ErrorKind::Invalid {
+            what: InvalidErrorKind::Conversion,
+            context: std::format!("Time out of range for a source timestamp: {:?}", time),
+        }
+        .into()
+    })
+}
+
+/// Remove the members set to `null` from the objects within a JSON value.
+fn remove_null_members(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(members) => {
+            members.retain(|_, member| !member.is_null());
+            members.values_mut().for_each(remove_null_members);
+        }
+        serde_json::Value::Array(elements) => {
+            elements.iter_mut().for_each(remove_null_members);
+        }
+        _ => {}
+    }
+}
+
+/// Merge `patch` into `target` as a JSON merge patch: objects are merged
+/// member by member, members set to `null` are removed, and any other value
+/// replaces the target.
+fn merge_patch(target: &mut serde_json::Value, patch: &serde_json::Value) {
+    let serde_json::Value::Object(patch) = patch else {
+        *target = patch.clone();
+        return;
+    };
+    if !target.is_object() {
+        *target = serde_json::Value::Object(serde_json::Map::new());
+    }
+
+    if let serde_json::Value::Object(members) = target {
+        for (name, value) in patch {
+            if value.is_null() {
+                members.remove(name);
+            } else {
+                let member = members
+                    .entry(name.clone())
+                    .or_insert(serde_json::Value::Null);
+                merge_patch(member, value);
+            }
+        }
+    }
+}
+
+/// Check that a JSON value to set as a whole instance is an object.
+fn expect_object(value: &serde_json::Value) -> ConnectorFallible {
+    if value.is_object() {
+        return Ok(());
+    }
+
+    Err(ErrorKind::invalid_argument_error(std::format!(
+        "Expected a JSON object to set as the instance, found a {}: {}",
+        crate::json_path::type_name(value),
+        truncate_payload(&value.to_string())
+    ))
+    .into())
+}
+
+/// Check whether a JSON value contains any `null`.
+fn contains_null(value: &serde_json::Value) -> bool {
+    match value {
+        serde_json::Value::Null => true,
+        serde_json::Value::Object(members) => members.values().any(contains_null),
+        serde_json::Value::Array(elements) => elements.iter().any(contains_null),
+        _ => false,
+    }
+}
+
+/// Serialize `data` as a JSON string, tracking the path of the value which
+/// fails to be serialized.
+fn to_json_string<T>(data: &T) -> Result<String, PathError>
+where
+    T: serde::Serialize + ?Sized,
+{
+    let mut json = Vec::new();
+    serde_path_to_error::serialize(data, &mut serde_json::Serializer::new(&mut json))?;
+    // serde_json only writes valid UTF-8
+    Ok(String::from_utf8(json)
+        .unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned()))
+}
+
+/// Serialize `data` as a JSON value, tracking the path of the value which
+/// fails to be serialized.
+fn to_json_value<T>(data: &T) -> Result<serde_json::Value, PathError>
+where
+    T: serde::Serialize + ?Sized,
+{
+    serde_path_to_error::serialize(data, serde_json::value::Serializer)
+}
+
+/// The error of failing to serialize `T`, as a whole or for `field`.
+fn serialize_error<T: ?Sized>(field: &str, e: PathError) -> ConnectorError {
+    let context = if field.is_empty() {
+        std::format!(
+            "Type '{}' could not be serialized: {}",
+            std::any::type_name::<T>(),
+            e
+        )
+    } else {
+        std::format!(
+            "Type '{}' could not be serialized for field '{}': {}",
+            std::any::type_name::<T>(),
+            field,
+            e
+        )
+    };
+    let path = crate::json_path::from_serde_path(field, e.path());
+
+    ErrorKind::Invalid {
+        what: InvalidErrorKind::Serialization,
+        context,
+    }
+    .caused_by(e)
+    .with_field_path(path)
+}
+
+/// Shorten a JSON payload to be included in an error message.
+fn truncate_payload(json: &str) -> std::borrow::Cow<'_, str> {
+    match json.char_indices().nth(MAX_PAYLOAD_IN_ERROR) {
+        Some((end, _)) => {
+            std::format!("{}... ({} bytes)", &json[..end], json.len()).into()
+        }
+        None => json.into(),
+    }
+}
 
 /// An interface to modify the data held by a given [`Output`] instance.
 ///
 /// ```rust
 #[doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/snippets/output/using_instance.rs"))]
 /// ```
-pub struct Instance<'a>(&'a Output<'a>);
+///
+/// An [`Instance`] borrows its [`Output`], while the operations which write or
+/// clear the sample require exclusive access to it. An [`Instance`] can't be
+/// held across [`Output::write`], which is rejected at compile time:
+///
+/// ```rust,compile_fail,E0502
+/// # fn stale(mut output: rtiddsconnector::Output) -> rtiddsconnector::ConnectorFallible {
+/// let mut instance = output.instance();
+/// instance.set_number("x", 1.0)?;
+/// output.write()?; // Error: `output` is still borrowed by `instance`
+/// instance.set_number("x", 2.0)?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Use [`Output::persistent_instance`] to keep an instance across writes.
+pub struct Instance<'a>(&'a Output);
 
 /// Display the [`Instance`] as a JSON string.
 impl std::fmt::Display for Instance<'_> {
@@ -36,7 +208,7 @@ impl Instance<'_> {
         self.0
             .parent
             .native_mut()?
-            .clear_member(&self.0.name, field)
+            .clear_member(&self.0.c_name, field)
     }
 
     /// Set the entire instance from a JSON string.
@@ -44,39 +216,355 @@ impl Instance<'_> {
         self.0
             .parent
             .native_mut()?
-            .set_json_instance(&self.0.name, json_value)
+            .set_json_instance(&self.0.c_name, json_value)
     }
 
     /// Set a specific field of the underlying sample.
+    ///
+    /// NaN and infinite numbers are rejected, unless allowed with
+    /// [`Output::allow_non_finite_numbers`]. [`SelectedValue::Null`] clears
+    /// the field, like [`Instance::clear`], and [`SelectedValue::Json`] sets
+    /// the members present in the JSON, like [`Instance::serialize_field`].
     pub fn set_value(&mut self, field: &str, value: SelectedValue) -> ConnectorFallible {
+        self.set_value_ref(field, &value)
+    }
+
+    /// Set a specific field of the underlying sample from a borrowed value.
+    ///
+    /// The same as [`Instance::set_value`], without taking ownership of the
+    /// value, so that one value, such as a large string, can be set into
+    /// several fields without cloning it.
+    pub fn set_value_ref(
+        &mut self,
+        field: &str,
+        value: &SelectedValue,
+    ) -> ConnectorFallible {
+        match value {
+            SelectedValue::Number(number) => self.check_finite(field, *number)?,
+            SelectedValue::String(string) => return self.set_string(field, string),
+            SelectedValue::Null => return self.clear(field),
+            SelectedValue::Boolean(_) | SelectedValue::Json(_) => {}
+        }
+
         self.0
             .parent
             .native_mut()?
-            .set_into_samples(&self.0.name, field, value)
+            .set_into_samples(&self.0.c_name, field, value)
+    }
+
+    /// Set a specific field of the underlying sample from any value convertible
+    /// into a [`SelectedValue`], such as `10`, `"BLUE"` or `true`.
+    ///
+    /// Integers which a number may not represent exactly, such as [`i64`] or
+    /// [`u64`], are only accepted by [`Instance::try_set`].
+    pub fn set<V>(&mut self, field: &str, value: V) -> ConnectorFallible
+    where
+        V: Into<SelectedValue>,
+    {
+        self.set_value(field, value.into())
+    }
+
+    /// Set a specific field of the underlying sample from any value which may
+    /// be converted into a [`SelectedValue`], failing if the conversion does.
+    pub fn try_set<V>(&mut self, field: &str, value: V) -> ConnectorFallible
+    where
+        V: TryInto<SelectedValue>,
+        crate::ConnectorError: From<V::Error>,
+    {
+        self.set_value(field, value.try_into()?)
     }
 
     /// Set a numeric field of the underlying sample.
+    ///
+    /// NaN and infinite numbers are rejected, unless allowed with
+    /// [`Output::allow_non_finite_numbers`].
+    ///
+    /// The native library converts the numbers set into integer members
+    /// without reporting any error. With [`Output::check_integer_conversions`],
+    /// a conversion error is returned instead if the field is an integer member
+    /// which can't hold the value exactly, because it has a fractional part or
+    /// is out of its range, and the field holds the value as converted by the
+    /// native library. Use [`Instance::set_integer`] to set integers beyond
+    /// 2^53 without loss of precision.
     pub fn set_number(&mut self, field: &str, value: f64) -> ConnectorFallible {
-        self.0
-            .parent
-            .native_mut()?
-            .set_number_into_samples(&self.0.name, field, value)
+        self.check_finite(field, value)?;
+
+        let result = self.0.parent.native_mut()?.set_number_into_samples(
+            &self.0.c_name,
+            field,
+            value,
+        );
+        result.map_err(|e| self.field_error(field, "number", e))?;
+
+        // Only the values which an integer member may not hold are checked
+        if self.0.check_integers
+            && value.is_finite()
+            && (value.fract() != 0.0 || value.abs() > MAX_EXACT_INTEGER as f64)
+        {
+            self.check_exact(field, value)?;
+        }
+        Ok(())
+    }
+
+    /// Set a DDS `Time_t`-style struct field, such as `timestamp`, from a
+    /// [`SystemTime`](std::time::SystemTime), through its `sec` and `nanosec`
+    /// members.
+    ///
+    /// An error is returned if the time doesn't fit in a [`DdsTime`](crate::DdsTime).
+    pub fn set_time(
+        &mut self,
+        field: &str,
+        time: std::time::SystemTime,
+    ) -> ConnectorFallible {
+        let time = crate::DdsTime::try_from(time)?;
+
+        self.set_i32(&std::format!("{}.sec", field), time.sec)?;
+        self.set_u32(&std::format!("{}.nanosec", field), time.nanosec)
     }
 
     /// Set a boolean field of the underlying sample.
     pub fn set_boolean(&mut self, field: &str, value: bool) -> ConnectorFallible {
-        self.0
-            .parent
-            .native_mut()?
-            .set_boolean_into_samples(&self.0.name, field, value)
+        let result = self.0.parent.native_mut()?.set_boolean_into_samples(
+            &self.0.c_name,
+            field,
+            value,
+        );
+        result.map_err(|e| self.field_error(field, "boolean", e))
     }
 
     /// Set a string field of the underlying sample.
     pub fn set_string(&mut self, field: &str, value: &str) -> ConnectorFallible {
-        self.0
-            .parent
-            .native_mut()?
-            .set_string_into_samples(&self.0.name, field, value)
+        let value = match value.find('\0') {
+            Some(offset) if self.0.truncate_at_nul => &value[..offset],
+            _ => value,
+        };
+
+        let result = self.0.parent.native_mut()?.set_string_into_samples(
+            &self.0.c_name,
+            field,
+            value,
+        );
+        result.map_err(|e| self.field_error(field, "string", e))
+    }
+
+    /// Set a numeric field of the underlying sample from an `i32`.
+    pub fn set_i32(&mut self, field: &str, value: i32) -> ConnectorFallible {
+        self.set_number(field, f64::from(value))
+    }
+
+    /// Set a numeric field of the underlying sample from an `u32`.
+    pub fn set_u32(&mut self, field: &str, value: u32) -> ConnectorFallible {
+        self.set_number(field, f64::from(value))
+    }
+
+    /// Set a numeric field of the underlying sample from an `i16`.
+    pub fn set_i16(&mut self, field: &str, value: i16) -> ConnectorFallible {
+        self.set_number(field, f64::from(value))
+    }
+
+    /// Set a numeric field of the underlying sample from an `u16`.
+    pub fn set_u16(&mut self, field: &str, value: u16) -> ConnectorFallible {
+        self.set_number(field, f64::from(value))
+    }
+
+    /// Set a numeric field of the underlying sample from an `u8`.
+    pub fn set_u8(&mut self, field: &str, value: u8) -> ConnectorFallible {
+        self.set_number(field, f64::from(value))
+    }
+
+    /// Set a signed integer field of the underlying sample, without loss of precision.
+    ///
+    /// Values which can't be represented exactly as a `f64` are set from their
+    /// decimal representation, so `int64` fields can hold any value.
+    pub fn set_i64(&mut self, field: &str, value: i64) -> ConnectorFallible {
+        self.set_integer(field, value)
+    }
+
+    /// Set an unsigned integer field of the underlying sample, without loss of precision.
+    ///
+    /// Values which can't be represented exactly as a `f64` are set from their
+    /// decimal representation, so `uint64` fields can hold any value.
+    pub fn set_u64(&mut self, field: &str, value: u64) -> ConnectorFallible {
+        self.set_integer(field, value)
+    }
+
+    /// Set an integer field of the underlying sample from any integer type,
+    /// without loss of precision.
+    ///
+    /// Unlike [`Instance::set_number`], values beyond 2^53, which can't be
+    /// represented exactly as a `f64`, are set from their decimal
+    /// representation, so 64-bit integer fields can hold any value.
+    pub fn set_integer<I>(&mut self, field: &str, value: I) -> ConnectorFallible
+    where
+        I: Into<i128>,
+    {
+        let value: i128 = value.into();
+        if value.unsigned_abs() <= u128::from(MAX_EXACT_INTEGER) {
+            self.set_number(field, value as f64)
+        } else {
+            self.set_string(field, &value.to_string())
+        }
+    }
+
+    /// Set the value of a member of a union field, making it the active member.
+    pub fn set_union(
+        &mut self,
+        field: &str,
+        member: &str,
+        value: SelectedValue,
+    ) -> ConnectorFallible {
+        self.set_value(&std::format!("{}.{}", field, member), value)
+    }
+
+    /// Set an enumeration field of the underlying sample by the name of its enumerator.
+    ///
+    /// Numeric values, such as `"1"`, are accepted too. The field must be a
+    /// member path such as `simple.enum_field`, without element indexes.
+    pub fn set_enum_string(&mut self, field: &str, value: &str) -> ConnectorFallible {
+        match value.trim().parse::<i32>() {
+            Ok(number) => self.set_number(field, f64::from(number)),
+            Err(_) => self.set_member_json(field, serde_json::Value::from(value)),
+        }
+    }
+
+    /// Set a byte sequence or array field of the underlying sample.
+    ///
+    /// The whole field is set at once, which is much faster than setting each
+    /// element individually. The field must be a member path such as
+    /// `payload` or `inner.payload`, without element indexes.
+    pub fn set_bytes(&mut self, field: &str, value: &[u8]) -> ConnectorFallible {
+        self.set_member_json(field, serde_json::Value::from(value))
+    }
+
+    /// Set a sequence or array field of the underlying sample, serializing each element.
+    ///
+    /// The whole field is set at once. The field must be a member path such as
+    /// `double_sequence`, without element indexes.
+    pub fn set_sequence<T>(&mut self, field: &str, value: &[T]) -> ConnectorFallible
+    where
+        T: serde::Serialize,
+    {
+        crate::field_serializer::check_finite(field, value)?;
+
+        let elements = value
+            .iter()
+            .enumerate()
+            .map(|(index, element)| {
+                serde_json::to_value(element).map_err(|e| ErrorKind::Invalid {
+                    what: InvalidErrorKind::Conversion,
+                    context: std::format!(
+                        "Element #{} for field '{}' could not be serialized: {}",
+                        index,
+                        field,
+                        e
+                    ),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        self.set_member_json(field, serde_json::Value::Array(elements))
+            .map_err(|e| {
+                if e.is_field_not_found() {
+                    return e;
+                }
+
+                ErrorKind::Invalid {
+                    what: InvalidErrorKind::Conversion,
+                    context: std::format!(
+                        "Sequence of {} elements could not be set into field '{}': {}",
+                        value.len(),
+                        field,
+                        e
+                    ),
+                }
+                .into()
+            })
+    }
+
+    /// Set a field of the underlying sample from a JSON value, by setting a
+    /// partial JSON instance which leaves the rest of the fields unmodified.
+    fn set_member_json(
+        &mut self,
+        field: &str,
+        value: serde_json::Value,
+    ) -> ConnectorFallible {
+        self.0.parent.native_mut()?.set_into_samples(
+            &self.0.c_name,
+            field,
+            &SelectedValue::Json(value),
+        )
+    }
+
+    /// Classify the native error of setting a value into a field, using the
+    /// JSON of the instance: a malformed path, an index out of bounds, or a
+    /// field holding another type than the one `found` in the value.
+    fn field_error(
+        &self,
+        field: &str,
+        found: &'static str,
+        error: ConnectorError,
+    ) -> ConnectorError {
+        if !error.is_native_error() {
+            return error;
+        }
+
+        let instance = self
+            .get_as_json()
+            .ok()
+            .and_then(|json| serde_json::from_str::<serde_json::Value>(&json).ok());
+        let error = error.into_path_error(field, instance.as_ref());
+        match instance
+            .as_ref()
+            .and_then(|instance| crate::json_path::resolve(instance, field))
+        {
+            Some(value) => {
+                error.into_type_mismatch(field, crate::json_path::type_name(value), found)
+            }
+            None => error,
+        }
+    }
+
+    /// Check that an integer member holds the number just set into it exactly.
+    ///
+    /// The native library converts the numbers set into integer members without
+    /// reporting any error, so the value is read back from the instance.
+    fn check_exact(&self, field: &str, value: f64) -> ConnectorFallible {
+        let json = self.get_as_json()?;
+        let instance: serde_json::Value =
+            serde_json::from_str(&json).map_err(|e| ErrorKind::Invalid {
+                what: InvalidErrorKind::Conversion,
+                context: std::format!("Instance JSON could not be parsed: {}", e),
+            })?;
+
+        let stored = crate::json_path::resolve(&instance, field).and_then(|stored| {
+            stored
+                .as_i64()
+                .map(|stored| stored as f64)
+                .or_else(|| stored.as_u64().map(|stored| stored as f64))
+        });
+
+        match stored {
+            Some(stored) if stored != value => ErrorKind::Invalid {
+                what: InvalidErrorKind::Conversion,
+                context: std::format!(
+                    "Field '{}' is an integer which can't hold {} exactly, it was set to {}",
+                    field,
+                    value,
+                    stored
+                ),
+            }
+            .into_err(),
+            _ => Ok(()),
+        }
+    }
+
+    /// Reject NaN and infinite numbers, unless the [`Output`] allows them.
+    fn check_finite(&self, field: &str, value: f64) -> ConnectorFallible {
+        if value.is_finite() || self.0.allow_non_finite {
+            Ok(())
+        } else {
+            ErrorKind::non_finite_number_error(field, value).into_err()
+        }
     }
 
     /// Set the instance data from a typed struct using Serde serialization.
@@ -84,6 +572,14 @@ impl Instance<'_> {
     /// This method allows you to work with strongly-typed data structures
     /// instead of setting fields individually.
     ///
+    /// Members missing from the serialized data, such as optional members
+    /// skipped when `None`, keep their previous value. Use
+    /// [`Instance::serialize_with`] to unset them instead.
+    ///
+    /// JSON can't represent NaN and infinite numbers, so they are rejected
+    /// with an error naming their field. Use [`Instance::serialize_direct`]
+    /// with [`Output::allow_non_finite_numbers`] to set them.
+    ///
     /// # Example
     /// ```rust
     #[doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/snippets/output/using_serialization.rs"))]
@@ -92,37 +588,173 @@ impl Instance<'_> {
     where
         T: serde::Serialize,
     {
-        let json = serde_json::to_string(data).map_err(|e| ErrorKind::Invalid {
-            what: InvalidErrorKind::Serialization,
-            context: std::format!(
-                "Type '{}' could not be serialized: {}",
-                std::any::type_name::<T>(),
-                e
-            ),
-        })?;
+        let json = to_json_string(data).map_err(|e| serialize_error::<T>("", e))?;
+        // Non-finite numbers are serialized as `null`
+        if json.contains("null") {
+            crate::field_serializer::check_finite("", data)?;
+        }
 
-        self.set_as_json(&json).map_err(|e| ErrorKind::Invalid {
-            what: InvalidErrorKind::Serialization,
-            context: std::format!(
-                "Failed setting JSON serialied field ({}) of type '{}': {}",
-                std::any::type_name::<T>(),
-                json,
-                e
-            ),
+        self.set_as_json(&json).map_err(|e| {
+            ErrorKind::Invalid {
+                what: InvalidErrorKind::Serialization,
+                context: std::format!(
+                    "Failed setting JSON serialied field ({}) of type '{}': {}",
+                    std::any::type_name::<T>(),
+                    json,
+                    e
+                ),
+            }
+            .caused_by(e)
         })?;
 
         Ok(())
     }
 
+    /// Set the instance data from a typed struct, setting each field directly
+    /// instead of going through a JSON representation.
+    ///
+    /// The result is the same as with [`Instance::serialize`], but it avoids
+    /// formatting and parsing JSON, which is faster for large types, and sets
+    /// 64-bit integers without loss of precision. Sequences are replaced, and
+    /// `None` optional members which aren't skipped are unset.
+    ///
+    /// NaN and infinite numbers are rejected, unless allowed with
+    /// [`Output::allow_non_finite_numbers`].
+    pub fn serialize_direct<T>(&mut self, data: &T) -> ConnectorFallible
+    where
+        T: serde::Serialize,
+    {
+        let native = self.0.parent.native_mut()?;
+
+        crate::field_serializer::serialize_into(
+            &native,
+            &self.0.c_name,
+            data,
+            self.0.allow_non_finite,
+        )
+    }
+
+    /// Set the instance data from a typed struct, choosing how to handle the
+    /// members which are missing from its serialized form.
+    ///
+    /// With [`OptionalBehavior::ClearMissing`], the optional members which are
+    /// `None` (whether skipped or serialized as `null`) are left unset, even
+    /// if they were set in the instance before. [`Instance::serialize`] keeps
+    /// their previous value instead.
+    pub fn serialize_with<T>(
+        &mut self,
+        data: &T,
+        optionals: OptionalBehavior,
+    ) -> ConnectorFallible
+    where
+        T: serde::Serialize,
+    {
+        match optionals {
+            OptionalBehavior::KeepMissing => self.serialize(data),
+            OptionalBehavior::ClearMissing => {
+                let mut value =
+                    to_json_value(data).map_err(|e| serialize_error::<T>("", e))?;
+                // Non-finite numbers are serialized as `null`, and mustn't be unset
+                if contains_null(&value) {
+                    crate::field_serializer::check_finite("", data)?;
+                }
+                remove_null_members(&mut value);
+
+                self.0.parent.native_mut()?.clear(&self.0.c_name)?;
+                self.set_as_json(&value.to_string())
+            }
+        }
+    }
+
+    /// Returns whether a field, such as `simple.long_field`, is present in the instance.
+    ///
+    /// This inspects the JSON of the instance, so unlike [`Sample::has_field`](crate::Sample::has_field),
+    /// unset optional members are reported as missing.
+    pub fn has_field(&self, field_name: &str) -> ConnectorResult<bool> {
+        let json = self.get_as_json()?;
+        let value: serde_json::Value =
+            serde_json::from_str(&json).map_err(|e| ErrorKind::Invalid {
+                what: InvalidErrorKind::Conversion,
+                context: std::format!("Instance JSON could not be parsed: {}", e),
+            })?;
+
+        Ok(crate::json_path::resolve(&value, field_name).is_some())
+    }
+
+    /// Set a single field of the instance, such as a nested structure, from a
+    /// typed value using Serde serialization.
+    ///
+    /// The rest of the fields are left unmodified. The field must be a member
+    /// path such as `simple` or `simple.string_field`, without element indexes.
+    pub fn serialize_field<T>(&mut self, field: &str, data: &T) -> ConnectorFallible
+    where
+        T: serde::Serialize,
+    {
+        let value = to_json_value(data).map_err(|e| serialize_error::<T>(field, e))?;
+        // Non-finite numbers are serialized as `null`
+        if contains_null(&value) {
+            crate::field_serializer::check_finite(field, data)?;
+        }
+
+        self.set_member_json(field, value)
+    }
+
+    /// Set the instance from a JSON value, such as one forwarded by a gateway.
+    ///
+    /// The value must be an object with the members of the type. As with
+    /// [`Instance::set_as_json`], members missing from it keep their previous
+    /// value.
+    pub fn set_from_value(&mut self, value: &serde_json::Value) -> ConnectorFallible {
+        expect_object(value)?;
+
+        self.set_as_json(&value.to_string())
+    }
+
+    /// Merge a JSON value into the instance, changing only the members present
+    /// in it.
+    ///
+    /// Nested objects are merged member by member and `null` unsets a member,
+    /// while any other value replaces the previous one, sequences included,
+    /// as in a JSON merge patch (RFC 7396). If the merged value can't be set,
+    /// the instance is restored to its previous value.
+    pub fn merge_value(&mut self, value: &serde_json::Value) -> ConnectorFallible {
+        expect_object(value)?;
+
+        let previous = self.get_as_json()?;
+        let mut instance: serde_json::Value =
+            serde_json::from_str(&previous).map_err(|e| ErrorKind::Invalid {
+                what: InvalidErrorKind::Conversion,
+                context: std::format!("Instance JSON could not be parsed: {}", e),
+            })?;
+        merge_patch(&mut instance, value);
+
+        self.0.parent.native_mut()?.clear(&self.0.c_name)?;
+        let result = self.set_as_json(&instance.to_string());
+        if result.is_err() {
+            // The previous value was read from the instance, so it can be set back
+            let _ = self
+                .0
+                .parent
+                .native_mut()
+                .and_then(|native| native.clear(&self.0.c_name))
+                .and_then(|_| self.set_as_json(&previous));
+        }
+
+        result
+    }
+
     /// Get the entire instance as a JSON string.
     pub(crate) fn get_as_json(&self) -> ConnectorResult<String> {
-        self.0.parent.native_ref()?.get_json_instance(&self.0.name)
+        self.0
+            .parent
+            .native_ref()?
+            .get_json_instance(&self.0.c_name)
     }
 }
 
 /// An interface to write data to a DDS `Topic`.
 ///
-/// Created with [`Connector::get_output`], an [`Output`] represents a DDS
+/// Created with [`Connector::get_output`](crate::Connector::get_output), an [`Output`] represents a DDS
 /// `DataWriter` associated with a specific `Topic` within a `Participant`.
 ///
 /// The main functionality of an [`Output`] is to provide access to an
@@ -133,15 +765,31 @@ impl Instance<'_> {
 #[doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/snippets/output/using_output.rs"))]
 /// ```
 #[derive(Debug)]
-pub struct Output<'a> {
-    /// The name of the output as known to the parent [`Connector`].
-    pub(crate) name: String,
+pub struct Output {
+    /// The name of the output as known to the parent [`Connector`](crate::Connector).
+    pub(crate) name: Arc<str>,
+
+    /// The name, converted once for the native library, which needs it on every call.
+    pub(crate) c_name: CString,
 
-    /// A reference to the parent [`Connector`].
-    pub(crate) parent: &'a Connector,
+    /// A shared reference to the state of the parent [`Connector`](crate::Connector).
+    pub(crate) parent: Arc<ConnectorInner>,
+
+    /// The number of samples written since all of them were last known to be
+    /// acknowledged.
+    written_since_acknowledged: AtomicU64,
+
+    /// Whether NaN and infinite numbers can be set into the instance.
+    allow_non_finite: bool,
+
+    /// Whether strings set into the instance are truncated at their first NUL byte.
+    truncate_at_nul: bool,
+
+    /// Whether the numbers set into integer members are checked to be held exactly.
+    check_integers: bool,
 }
 
-impl<'a> Drop for Output<'a> {
+impl Drop for Output {
     fn drop(&mut self) {
         if let Err(e) = self.parent.release_output(&self.name) {
             eprintln!(
@@ -152,6 +800,158 @@ impl<'a> Drop for Output<'a> {
     }
 }
 
+/// An instance of the data held by an [`Output`] which remains usable across
+/// writes, obtained with [`Output::persistent_instance`].
+///
+/// Writing doesn't clear the underlying sample, so the values of its fields
+/// carry over to the next write until they are set again. This allows
+/// publishers to only update the fields which changed between writes:
+///
+/// ```rust
+/// # fn publish(output: &mut rtiddsconnector::Output) -> rtiddsconnector::ConnectorFallible {
+/// let mut persistent = output.persistent_instance();
+/// persistent.instance().set_string("color", "BLUE")?;
+/// for x in 0..10 {
+///     persistent.instance().set_number("x", x.into())?;
+///     persistent.write()?; // `color` is still "BLUE"
+/// }
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`PersistentInstance::clear_members`] consumes the instance, and the
+/// [`Output`] remains exclusively borrowed while the instance exists, so it
+/// can't be used once the sample has been cleared:
+///
+/// ```rust,compile_fail,E0382
+/// # fn cleared(output: &mut rtiddsconnector::Output) -> rtiddsconnector::ConnectorFallible {
+/// let mut persistent = output.persistent_instance();
+/// persistent.clear_members()?;
+/// persistent.write()?; // Error: `persistent` was moved by `clear_members`
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct PersistentInstance<'a>(&'a mut Output);
+
+impl PersistentInstance<'_> {
+    /// Get an [`Instance`] to modify the fields of the sample.
+    pub fn instance(&mut self) -> Instance<'_> {
+        self.0.instance()
+    }
+
+    /// Write the sample, keeping the values of its fields for the next write.
+    ///
+    /// See [`Output::write`].
+    pub fn write(&mut self) -> ConnectorFallible {
+        self.0.write()
+    }
+
+    /// Write the sample with specific parameters, keeping the values of its
+    /// fields for the next write.
+    ///
+    /// See [`Output::write_with_params`].
+    pub fn write_with_params(&mut self, params: &WriteParams) -> ConnectorFallible {
+        self.0.write_with_params(params)
+    }
+
+    /// Clear all fields of the sample, releasing the instance.
+    ///
+    /// See [`Output::clear_members`].
+    pub fn clear_members(self) -> ConnectorFallible {
+        self.0.clear_members()
+    }
+}
+
+/// An [`Output`] which writes a single Rust type `T`, obtained with
+/// [`Output::typed`].
+///
+/// Every sample is written from a `T` with [`Output::write_typed`], so that
+/// data of another type can't be written to the same [`Output`] by accident:
+///
+/// ```rust
+/// # #[derive(serde::Serialize)]
+/// # struct Shape { color: String, x: i32, y: i32, shapesize: i32 }
+/// # fn publish(output: rtiddsconnector::Output) -> rtiddsconnector::ConnectorFallible {
+/// let mut output = output.typed::<Shape>();
+/// output.write(&Shape { color: "BLUE".to_string(), x: 1, y: 2, shapesize: 30 })?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// The [`Output`] remains available with [`TypedOutput::as_untyped`], for
+/// the operations which don't depend on the type, such as waiting for
+/// subscriptions.
+#[derive(Debug)]
+pub struct TypedOutput<T> {
+    /// The untyped output the data is written with
+    output: Output,
+
+    /// The type of the data, which is only ever borrowed
+    _type: std::marker::PhantomData<fn(&T)>,
+}
+
+impl<T: serde::Serialize> TypedOutput<T> {
+    /// Clear the instance, set it from `data` and write it.
+    ///
+    /// See [`Output::write_typed`].
+    pub fn write(&mut self, data: &T) -> ConnectorFallible {
+        self.output.write_typed(data)
+    }
+
+    /// Clear the instance, set it from `data` and write it with specific
+    /// parameters.
+    ///
+    /// See [`Output::write_typed_with_params`].
+    pub fn write_with_params(
+        &mut self,
+        data: &T,
+        params: &WriteParams,
+    ) -> ConnectorFallible {
+        self.output.write_typed_with_params(data, params)
+    }
+
+    /// Get the underlying [`Output`].
+    pub fn as_untyped(&self) -> &Output {
+        &self.output
+    }
+
+    /// Get the underlying [`Output`] mutably, to write data other than `T`.
+    pub fn as_untyped_mut(&mut self) -> &mut Output {
+        &mut self.output
+    }
+
+    /// Turn back into the underlying [`Output`].
+    pub fn into_untyped(self) -> Output {
+        self.output
+    }
+}
+
+/// The outcome of [`Output::wait_for_acknowledgments_detailed`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AcknowledgmentStatus {
+    /// Whether all the samples written were acknowledged before the timeout.
+    pub acknowledged: bool,
+
+    /// The number of samples written by the [`Output`] since all of them were
+    /// last known to be acknowledged, when the wait started.
+    ///
+    /// When `acknowledged` is `false`, this is an upper bound of the number of
+    /// unacknowledged samples. When it is `0`, there was nothing outstanding.
+    pub written_since_acknowledged: u64,
+}
+
+/// How [`Instance::serialize_with`] handles the members missing from the serialized data.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OptionalBehavior {
+    /// Keep the previous value of missing members, as [`Instance::serialize`] does.
+    #[default]
+    KeepMissing,
+
+    /// Clear the instance first, so that missing optional members are unset.
+    ClearMissing,
+}
+
 /// Action to perform when writing a sample.
 #[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -168,13 +968,69 @@ pub enum WriteParamsAction {
 }
 
 /// Identity of a written sample.
-#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct WriteParamsIdentity {
     /// The GUID of the writer as a list of 16 bytes.
     pub writer_guid: [u8; 16],
 
     /// The sequence number of the sample.
-    pub sequence_number: u64,
+    ///
+    /// DDS sequence numbers are signed 64-bit integers. Valid values are
+    /// non-negative, or [`WriteParamsIdentity::SEQUENCE_NUMBER_UNKNOWN`].
+    pub sequence_number: i64,
+}
+
+impl WriteParamsIdentity {
+    /// The sentinel sequence number which lets the `DataWriter` assign one.
+    pub const SEQUENCE_NUMBER_UNKNOWN: i64 = -1;
+
+    /// The largest sequence number that can be written.
+    ///
+    /// The native library handles numbers as doubles, so larger sequence
+    /// numbers would not be written exactly.
+    pub const MAX_SEQUENCE_NUMBER: i64 = MAX_EXACT_INTEGER as i64;
+
+    /// Create an identity from a writer GUID and a sequence number.
+    pub fn new(writer_guid: [u8; 16], sequence_number: i64) -> Self {
+        WriteParamsIdentity {
+            writer_guid,
+            sequence_number,
+        }
+    }
+
+    /// Create an identity from an unsigned sequence number, as used by
+    /// previous versions of this type.
+    #[deprecated(note = "sequence numbers are signed, use `WriteParamsIdentity::new`")]
+    pub fn from_unsigned(
+        writer_guid: [u8; 16],
+        sequence_number: u64,
+    ) -> ConnectorResult<Self> {
+        let sequence_number = i64::try_from(sequence_number).map_err(|_| {
+            ErrorKind::invalid_argument_error(std::format!(
+                "Sequence number {} is out of range",
+                sequence_number
+            ))
+        })?;
+
+        Ok(Self::new(writer_guid, sequence_number))
+    }
+
+    /// Check that the sequence number is within the range that can be written.
+    fn validate(&self, name: &str) -> ConnectorFallible {
+        let sequence_number = self.sequence_number;
+        if sequence_number == Self::SEQUENCE_NUMBER_UNKNOWN
+            || (0..=Self::MAX_SEQUENCE_NUMBER).contains(&sequence_number)
+        {
+            Ok(())
+        } else {
+            ErrorKind::invalid_argument_error(std::format!(
+                "Sequence number {} of the {} is out of range",
+                sequence_number,
+                name
+            ))
+            .into_err()
+        }
+    }
 }
 
 /// Parameters for writing a sample.
@@ -228,6 +1084,42 @@ impl WriteParams {
         self
     }
 
+    /// Check that the parameters can be written, as done by [`Output::write_with_params`].
+    ///
+    /// This rejects sequence numbers out of range, and related sample identities
+    /// with an all-zero writer GUID, which can't be matched to any sample.
+    pub fn validate(&self) -> ConnectorFallible {
+        if let Some(identity) = &self.identity {
+            identity.validate("identity")?;
+        }
+
+        if let Some(related) = &self.related_sample_identity {
+            related.validate("related sample identity")?;
+
+            if related.writer_guid == [0; 16] {
+                return ErrorKind::invalid_argument_error(
+                    "The related sample identity has an all-zero writer GUID",
+                )
+                .into_err();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Set the source timestamp from a [`SystemTime`](std::time::SystemTime).
+    ///
+    /// Fails if the time can't be represented as nanoseconds since the epoch in
+    /// an `i64`, which covers the years 1677 to 2262.
+    pub fn with_source_time(self, time: std::time::SystemTime) -> ConnectorResult<Self> {
+        Ok(self.with_source_timestamp(nanos_from_system_time(time)?))
+    }
+
+    /// Set the source timestamp to the current time.
+    pub fn with_source_timestamp_now(self) -> ConnectorResult<Self> {
+        self.with_source_time(std::time::SystemTime::now())
+    }
+
     /// Set the writer identity.
     pub fn with_identity(mut self, identity: WriteParamsIdentity) -> Self {
         self.identity = Some(identity);
@@ -244,40 +1136,437 @@ impl WriteParams {
     }
 }
 
-impl<'a> Output<'a> {
-    pub(crate) fn new(name: &str, connector: &'a Connector) -> Output<'a> {
+impl Output {
+    pub(crate) fn new(
+        name: Arc<str>,
+        c_name: CString,
+        connector: Arc<ConnectorInner>,
+    ) -> Output {
         Output {
-            name: name.to_string(),
+            name,
+            c_name,
             parent: connector,
+            written_since_acknowledged: AtomicU64::new(0),
+            allow_non_finite: false,
+            truncate_at_nul: false,
+            check_integers: false,
         }
     }
 
     /// Get an [`Instance`] of the data held by this [`Output`].
-    pub fn instance(&'a self) -> Instance<'a> {
+    pub fn instance(&self) -> Instance<'_> {
         Instance(self)
     }
 
+    /// Get a pointer to the native sample of the [`Output`]'s instance, for use
+    /// with the *Connext DDS* C API.
+    ///
+    /// The pointer is valid while the [`Output`] is borrowed by the returned
+    /// [`NativePointer`](crate::NativePointer), which prevents writing it.
+    /// Members modified through the pointer are written like the ones set
+    /// through the [`Instance`].
+    #[cfg(feature = "unsafe-interop")]
+    pub fn native_instance_ptr(&self) -> ConnectorResult<crate::NativePointer<'_>> {
+        self.parent
+            .native_ref()?
+            .get_native_instance(&self.c_name)
+            .map(crate::ffi::FfiSample::into_native_pointer)
+    }
+
+    /// Get a pointer to the native `DDS_DataWriter` of the [`Output`], for use
+    /// with the *Connext DDS* C API.
+    ///
+    /// The returned [`NativeEntityGuard`](crate::NativeEntityGuard) holds the
+    /// connector lock, and the pointer is only valid while it's alive.
+    #[cfg(feature = "unsafe-interop")]
+    pub fn native_datawriter_ptr(&self) -> ConnectorResult<crate::NativeEntityGuard<'_>> {
+        let native = self.parent.native_mut()?;
+        let writer = native.get_output(&self.c_name)?.as_native_ptr();
+
+        Ok(crate::NativeEntityGuard::new(native, writer))
+    }
+
+    /// Allow setting NaN and infinite numbers into the instance.
+    ///
+    /// By default, [`Instance::set_number`], [`Instance::set_value`] and
+    /// [`Instance::serialize_direct`] reject them, since not every data
+    /// representation supports them. Allow them if the IEEE special values are
+    /// meaningful to the applications reading the data. They can never be set
+    /// through JSON, for example with [`Instance::serialize`].
+    pub fn allow_non_finite_numbers(&mut self, allow: bool) {
+        self.allow_non_finite = allow;
+    }
+
+    /// Check that the integer members of the instance hold exactly the numbers
+    /// set into them with [`Instance::set_number`].
+    ///
+    /// The native library silently converts numbers with a fractional part, or
+    /// out of the range of the member, so the value is read back from the
+    /// instance whenever such a number is set. This is disabled by default,
+    /// since it makes setting those numbers, such as into floating-point
+    /// members, much slower.
+    pub fn check_integer_conversions(&mut self, check: bool) {
+        self.check_integers = check;
+    }
+
+    /// Truncate the strings set into the instance at their first NUL byte.
+    ///
+    /// The native library can't set strings which contain NUL bytes, so by
+    /// default, [`Instance::set_string`] and [`Instance::set_value`] reject
+    /// them with an error naming the field and the offset of the byte. When
+    /// truncation is enabled, they silently set the part of the string before
+    /// it instead, so check the strings beforehand if the loss of data must be
+    /// detected.
+    pub fn truncate_strings_at_nul(&mut self, truncate: bool) {
+        self.truncate_at_nul = truncate;
+    }
+
+    /// Get a [`PersistentInstance`] of the data held by this [`Output`], which
+    /// can be kept across writes.
+    ///
+    /// Unlike an [`Instance`], which must be obtained again after each write,
+    /// the persistent instance borrows the [`Output`] exclusively and writes
+    /// the sample itself. The values of the fields carry over between writes.
+    pub fn persistent_instance(&mut self) -> PersistentInstance<'_> {
+        PersistentInstance(self)
+    }
+
+    /// Modify the data held by this [`Output`] within a closure.
+    ///
+    /// The [`Instance`] given to `f` can't escape the closure, so it can't be
+    /// held across a later [`Output::write`]. Errors returned by `f` are
+    /// propagated unchanged.
+    pub fn with_instance<F>(&mut self, f: F) -> ConnectorFallible
+    where
+        F: FnOnce(&mut Instance<'_>) -> ConnectorFallible,
+    {
+        f(&mut self.instance())
+    }
+
+    /// Modify the data held by this [`Output`] within a closure, then write it.
+    ///
+    /// The sample is only written if `f` succeeds; otherwise its error is
+    /// returned unchanged. Since the [`Output`] is borrowed exclusively for the
+    /// whole operation, no other modification can happen between setting the
+    /// fields and writing them. Failures to write are reported as
+    /// [`Output::write`] does.
+    pub fn write_with<F>(&mut self, f: F) -> ConnectorFallible
+    where
+        F: FnOnce(&mut Instance<'_>) -> ConnectorFallible,
+    {
+        self.with_instance(f)?;
+        self.write()
+    }
+
     /// Clear all fields of the underlying sample.
     pub fn clear_members(&mut self) -> ConnectorFallible {
-        self.parent.native_mut()?.clear(&self.name)
+        self.parent.native_mut()?.clear(&self.c_name)
     }
 
     /// Write the output sample using the underlying `DataWriter`.
     pub fn write(&mut self) -> ConnectorFallible {
-        self.parent.native_mut()?.write(&self.name)
+        self.parent.native_mut()?.write(&self.c_name)?;
+        self.count_written(1);
+        Ok(())
     }
 
     /// Write the output sample with specific parameters.
+    ///
+    /// The parameters are checked with [`WriteParams::validate`] first. If the
+    /// native library rejects them, an invalid argument error including the
+    /// parameters is returned.
     pub fn write_with_params(&mut self, params: &WriteParams) -> ConnectorFallible {
-        let params_json =
-            serde_json::to_string(params).map_err(|e| ErrorKind::Invalid {
+        let params_json = Self::params_json(params)?;
+
+        self.parent
+            .native_mut()?
+            .write_with_params(&self.c_name, &params_json)
+            .map_err(|e| self.rejected_params_error(e, &params_json))?;
+        self.count_written(1);
+        Ok(())
+    }
+
+    /// Account for samples written, which are pending acknowledgment.
+    fn count_written(&self, count: u64) {
+        self.written_since_acknowledged
+            .fetch_add(count, Ordering::Relaxed);
+    }
+
+    /// Validate the parameters and serialize them for the native library.
+    fn params_json(params: &WriteParams) -> ConnectorResult<String> {
+        params.validate()?;
+
+        serde_json::to_string(params).map_err(|e| {
+            ErrorKind::Invalid {
                 what: crate::result::InvalidErrorKind::Serialization,
                 context: std::format!("WriteParams could not be serialized: {}", e),
+            }
+            .caused_by(e)
+        })
+    }
+
+    /// Report a generic native failure to write with parameters as an invalid argument.
+    fn rejected_params_error(
+        &self,
+        e: ConnectorError,
+        params_json: &str,
+    ) -> ConnectorError {
+        if !e.is_native_error_code(crate::ffi::ReturnCode::Error) {
+            return e;
+        }
+
+        ErrorKind::invalid_argument_error(std::format!(
+            "Output '{}' rejected the WriteParams {}: {} ({})",
+            self.name,
+            params_json,
+            e,
+            e.last_error_message()
+                .unwrap_or("no native error message")
+                .trim(),
+        ))
+        .into()
+    }
+
+    /// Serialize and write each of the items, returning how many were written.
+    ///
+    /// Each item replaces the contents of the instance, as with
+    /// [`Output::write_typed`], but the native connector is locked only once
+    /// for the whole batch. Writing stops at the first item which can't be
+    /// serialized or written, and the error reports its index, which is also
+    /// the number of items written before it. The error keeps its kind, so
+    /// [`ConnectorError::is_timeout`] can still be checked, for example.
+    pub fn write_all<I>(&mut self, items: I) -> ConnectorResult<usize>
+    where
+        I: IntoIterator,
+        I::Item: serde::Serialize,
+    {
+        self.write_all_impl(items, None)
+    }
+
+    /// Serialize and write each of the items with specific parameters,
+    /// returning how many were written.
+    ///
+    /// The same parameters are used for every sample. See [`Output::write_all`]
+    /// and [`Output::write_with_params`].
+    pub fn write_all_with_params<I>(
+        &mut self,
+        items: I,
+        params: &WriteParams,
+    ) -> ConnectorResult<usize>
+    where
+        I: IntoIterator,
+        I::Item: serde::Serialize,
+    {
+        self.write_all_impl(items, Some(params))
+    }
+
+    fn write_all_impl<I>(
+        &mut self,
+        items: I,
+        params: Option<&WriteParams>,
+    ) -> ConnectorResult<usize>
+    where
+        I: IntoIterator,
+        I::Item: serde::Serialize,
+    {
+        let params_json = params.map(Self::params_json).transpose()?;
+        let native = self.parent.native_mut()?;
+
+        let mut written = 0;
+        for item in items {
+            let json =
+                to_json_string(&item).map_err(|e| serialize_error::<I::Item>("", e));
+
+            json.and_then(|json| {
+                // Non-finite numbers are serialized as `null`
+                if json.contains("null") {
+                    crate::field_serializer::check_finite("", &item)?;
+                }
+                native.clear(&self.c_name)?;
+                native.set_json_instance(&self.c_name, &json)
+            })
+            .and_then(|()| match &params_json {
+                Some(params_json) => native
+                    .write_with_params(&self.c_name, params_json)
+                    .map_err(|e| self.rejected_params_error(e, params_json)),
+                None => native.write(&self.c_name),
+            })
+            .map_err(|e| {
+                e.with_context(std::format!(
+                    "Item #{} could not be written by Output '{}'",
+                    written,
+                    self.name
+                ))
             })?;
 
+            written += 1;
+            self.count_written(1);
+        }
+
+        Ok(written)
+    }
+
+    /// Clear the instance, set it from a typed struct and write it, in one operation.
+    ///
+    /// This is equivalent to calling [`Output::clear_members`],
+    /// [`Instance::serialize`] and [`Output::write`]. If `data` can't be
+    /// serialized, a serialization error is returned and nothing is written;
+    /// failures to write are reported as [`Output::write`] does.
+    pub fn write_typed<T>(&mut self, data: &T) -> ConnectorFallible
+    where
+        T: serde::Serialize,
+    {
+        self.set_typed(data)?;
+        self.write()
+    }
+
+    /// Same as [`Output::write_typed`], writing with specific parameters.
+    pub fn write_typed_with_params<T>(
+        &mut self,
+        data: &T,
+        params: &WriteParams,
+    ) -> ConnectorFallible
+    where
+        T: serde::Serialize,
+    {
+        self.set_typed(data)?;
+        self.write_with_params(params)
+    }
+
+    /// Check that a Rust type matches the DDS type of the [`Output`], returning
+    /// every mismatch rather than failing on the first.
+    ///
+    /// `T::default()` is serialized and each of its fields is set into the
+    /// instance, reporting the fields which aren't members of the DDS type
+    /// and those whose values are of the wrong kind. The instance is cleared
+    /// afterwards, discarding anything set into it before. Sequences are only
+    /// checked for the elements of their default value, usually none.
+    ///
+    /// If clearing the instance fails, its error is returned instead of the
+    /// mismatches, with their number as context, since the instance may still
+    /// hold the values of `T::default()`.
+    pub fn check_type<T>(&mut self) -> ConnectorResult<Vec<crate::TypeMismatch>>
+    where
+        T: serde::Serialize + Default,
+    {
+        let value = serde_json::to_value(T::default()).map_err(|e| {
+            ErrorKind::Invalid {
+                what: InvalidErrorKind::Serialization,
+                context: std::format!(
+                    "Type '{}' could not be serialized: {}",
+                    std::any::type_name::<T>(),
+                    e
+                ),
+            }
+            .caused_by(e)
+        })?;
+
+        let mut mismatches = Vec::new();
+        crate::type_check::check_instance(
+            &mut self.instance(),
+            &value,
+            &crate::FieldPath::default(),
+            &mut mismatches,
+        );
         self.parent
-            .native_mut()?
-            .write_with_params(&self.name, &params_json)
+            .native_mut()
+            .and_then(|native| native.clear(&self.c_name))
+            .map_err(|e| {
+                e.with_context(std::format!(
+                    "clearing the instance after finding {} type mismatches",
+                    mismatches.len()
+                ))
+            })?;
+
+        Ok(mismatches)
+    }
+
+    /// Turn the [`Output`] into a [`TypedOutput`], which only writes data of
+    /// type `T`.
+    pub fn typed<T>(self) -> TypedOutput<T>
+    where
+        T: serde::Serialize,
+    {
+        TypedOutput {
+            output: self,
+            _type: std::marker::PhantomData,
+        }
+    }
+
+    /// Clear the instance, set it from a JSON string and write it, in one operation.
+    ///
+    /// This is equivalent to calling [`Output::clear_members`],
+    /// [`Instance::set_as_json`] and [`Output::write`], and avoids parsing the
+    /// payload on the Rust side. If the native library rejects the payload, an
+    /// invalid argument error including the beginning of the payload is returned
+    /// and nothing is written.
+    pub fn write_json(&mut self, json: &str) -> ConnectorFallible {
+        self.set_json(json)?;
+        self.write()
+    }
+
+    /// Same as [`Output::write_json`], writing with specific parameters.
+    pub fn write_json_with_params(
+        &mut self,
+        json: &str,
+        params: &WriteParams,
+    ) -> ConnectorFallible {
+        self.set_json(json)?;
+        self.write_with_params(params)
+    }
+
+    /// Replace the contents of the instance with a JSON string.
+    fn set_json(&mut self, json: &str) -> ConnectorFallible {
+        self.clear_members()?;
+        self.instance().set_as_json(json).map_err(|e| {
+            if e.is_field_not_found() {
+                return e;
+            }
+
+            ErrorKind::invalid_argument_error(std::format!(
+                "JSON payload '{}' could not be set into Output '{}': {} ({})",
+                truncate_payload(json),
+                self.name,
+                e,
+                e.last_error_message()
+                    .unwrap_or("no native error message")
+                    .trim(),
+            ))
+            .into()
+        })
+    }
+
+    /// Replace the contents of the instance with a typed struct.
+    fn set_typed<T>(&mut self, data: &T) -> ConnectorFallible
+    where
+        T: serde::Serialize,
+    {
+        self.clear_members()?;
+        self.instance().serialize(data)
+    }
+
+    /// Write the output sample as a reply to a request, in request-reply communications.
+    ///
+    /// The identity of `request` is set as the related sample identity of the
+    /// written sample, replacing the one in `params`, if any. The requester can
+    /// match replies with [`Sample::is_reply_to`](crate::Sample::is_reply_to).
+    pub fn reply_to(
+        &mut self,
+        request: &crate::Sample<'_>,
+        params: WriteParams,
+    ) -> ConnectorFallible {
+        let params = params.with_related_sample_identity(request.identity()?);
+        self.write_with_params(&params)
+    }
+
+    /// Write the output sample with an explicit source timestamp.
+    ///
+    /// See [`WriteParams::with_source_time`] for the range of valid times.
+    pub fn write_with_timestamp(
+        &mut self,
+        time: std::time::SystemTime,
+    ) -> ConnectorFallible {
+        self.write_with_params(&WriteParams::write().with_source_time(time)?)
     }
 
     /// Wait until all previously written samples have been acknowledged, indefinitely.
@@ -287,18 +1576,70 @@ impl<'a> Output<'a> {
 
     /// Wait until all previously written samples have been acknowledged, or until the timeout expires.
     pub fn wait_with_timeout(&self, timeout: std::time::Duration) -> ConnectorFallible {
-        self.impl_wait(Some(
-            // Durations cannot be negative
-            timeout.as_millis().try_into().unwrap_or(i32::MAX),
-        ))
+        self.impl_wait(Some(timeout))
+    }
+
+    /// Wait until all previously written samples have been acknowledged, or
+    /// until the timeout expires, reporting the outcome instead of a timeout
+    /// error.
+    ///
+    /// The returned [`AcknowledgmentStatus`] tells whether every sample was
+    /// acknowledged and how many samples had been written since they last
+    /// were. The native library doesn't report how many samples remain
+    /// unacknowledged after a timeout, so this count is an upper bound of
+    /// them. Other failures, including an interrupted wait, are returned as
+    /// errors.
+    pub fn wait_for_acknowledgments_detailed(
+        &self,
+        timeout: std::time::Duration,
+    ) -> ConnectorResult<AcknowledgmentStatus> {
+        let written_since_acknowledged =
+            self.written_since_acknowledged.load(Ordering::Relaxed);
+
+        let acknowledged = match self.wait_with_timeout(timeout) {
+            Ok(()) => true,
+            Err(e) if e.is_timeout() => false,
+            Err(e) => return Err(e),
+        };
+
+        Ok(AcknowledgmentStatus {
+            acknowledged,
+            written_since_acknowledged,
+        })
+    }
+
+    /// Wait until all previously written samples have been acknowledged,
+    /// until the timeout expires, indefinitely if no timeout is given, or
+    /// until the [`CancelToken`](crate::CancelToken) is cancelled.
+    ///
+    /// A cancelled wait returns an error for which
+    /// [`ConnectorError::is_cancelled`](crate::ConnectorError::is_cancelled)
+    /// returns `true`.
+    pub fn wait_cancellable(
+        &self,
+        timeout: Option<std::time::Duration>,
+        cancel: &crate::CancelToken,
+    ) -> ConnectorFallible {
+        self.parent.cancellable_wait(timeout, cancel, |chunk_ms| {
+            let writer = self.parent.native_ref()?.get_output(&self.c_name)?;
+            writer.wait_for_acknowledgments(Some(chunk_ms))
+        })?;
+        self.written_since_acknowledged.store(0, Ordering::Relaxed);
+        Ok(())
     }
 
     /// Implementation of wait functionality.
-    fn impl_wait(&self, timeout_ms: Option<i32>) -> ConnectorFallible {
-        self.parent
-            .native_ref()?
-            .get_output(&self.name)?
-            .wait_for_acknowledgments(timeout_ms)
+    fn impl_wait(&self, timeout: Option<std::time::Duration>) -> ConnectorFallible {
+        // No sample can be written while waiting, since writing requires
+        // exclusive access to the output
+        self.parent.interruptible_wait(timeout, None, |chunk_ms| {
+            self.parent
+                .native_ref()?
+                .get_output(&self.c_name)?
+                .wait_for_acknowledgments(Some(chunk_ms))
+        })?;
+        self.written_since_acknowledged.store(0, Ordering::Relaxed);
+        Ok(())
     }
 
     /// Wait until a subscription is matched, indefinitely.
@@ -311,28 +1652,126 @@ impl<'a> Output<'a> {
         &self,
         timeout: std::time::Duration,
     ) -> ConnectorResult<i32> {
-        self.impl_wait_for_subscriptions(Some(
-            // Durations cannot be negative
-            timeout.as_millis().try_into().unwrap_or(i32::MAX),
-        ))
+        self.impl_wait_for_subscriptions(Some(timeout))
+    }
+
+    /// Wait for the subscriptions matched with this `Output` to change,
+    /// indefinitely if no timeout is given.
+    ///
+    /// Unlike [`Output::wait_for_subscriptions`], the returned [`MatchEvent`]
+    /// tells matches apart from unmatches and includes the number of
+    /// subscriptions currently matched.
+    pub fn wait_for_subscription_event(
+        &self,
+        timeout: Option<std::time::Duration>,
+    ) -> ConnectorResult<MatchEvent> {
+        let change = match timeout {
+            Some(timeout) => self.wait_for_subscriptions_with_timeout(timeout)?,
+            None => self.wait_for_subscriptions()?,
+        };
+
+        Ok(MatchEvent::new(change, &self.matched_subscriptions()?))
+    }
+
+    /// Wait until all previously written samples have been acknowledged, or
+    /// until the timeout expires, without blocking the async runtime.
+    ///
+    /// This is the async counterpart of [`Output::wait_with_timeout`], waiting
+    /// indefinitely if no timeout is given. The native wait runs on a blocking
+    /// thread of the Tokio runtime, and dropping the future cancels it: the
+    /// thread stops within one chunk of the wait, 100 ms, and holds no lock
+    /// afterwards.
+    #[cfg(feature = "async")]
+    pub async fn wait_async(
+        &self,
+        timeout: Option<std::time::Duration>,
+    ) -> ConnectorFallible {
+        let parent = Arc::clone(&self.parent);
+        let c_name = self.c_name.clone();
+
+        crate::connector::spawn_blocking(move |cancelled| {
+            parent.interruptible_wait(timeout, Some(cancelled), |chunk_ms| {
+                let writer = parent.native_ref()?.get_output(&c_name)?;
+                writer.wait_for_acknowledgments(Some(chunk_ms))
+            })
+        })
+        .await?;
+        self.written_since_acknowledged.store(0, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Wait for the subscriptions matched with this `Output` to change, or
+    /// until the timeout expires, without blocking the async runtime.
+    ///
+    /// This is the async counterpart of [`Output::wait_for_subscriptions_with_timeout`],
+    /// waiting indefinitely if no timeout is given. Dropping the future
+    /// cancels the wait like [`Output::wait_async`].
+    #[cfg(feature = "async")]
+    pub async fn wait_for_subscriptions_async(
+        &self,
+        timeout: Option<std::time::Duration>,
+    ) -> ConnectorResult<i32> {
+        let parent = Arc::clone(&self.parent);
+        let c_name = self.c_name.clone();
+
+        crate::connector::spawn_blocking(move |cancelled| {
+            parent.interruptible_wait(timeout, Some(cancelled), |chunk_ms| {
+                let writer = parent.native_ref()?.get_output(&c_name)?;
+                writer.wait_for_matched_subscription(Some(chunk_ms))
+            })
+        })
+        .await
+    }
+
+    /// Wait for the subscriptions matched with this `Output` to change, until
+    /// the timeout expires, indefinitely if no timeout is given, or until the
+    /// [`CancelToken`](crate::CancelToken) is cancelled.
+    pub fn wait_for_subscriptions_cancellable(
+        &self,
+        timeout: Option<std::time::Duration>,
+        cancel: &crate::CancelToken,
+    ) -> ConnectorResult<i32> {
+        self.parent.cancellable_wait(timeout, cancel, |chunk_ms| {
+            let writer = self.parent.native_ref()?.get_output(&self.c_name)?;
+            writer.wait_for_matched_subscription(Some(chunk_ms))
+        })
     }
 
     /// Implementation of wait for subscriptions functionality.
     fn impl_wait_for_subscriptions(
         &self,
-        timeout_ms: Option<i32>,
+        timeout: Option<std::time::Duration>,
     ) -> ConnectorResult<i32> {
-        self.parent
-            .native_ref()?
-            .get_output(&self.name)?
-            .wait_for_matched_subscription(timeout_ms)
+        self.parent.interruptible_wait(timeout, None, |chunk_ms| {
+            let writer = self.parent.native_ref()?.get_output(&self.c_name)?;
+            writer.wait_for_matched_subscription(Some(chunk_ms))
+        })
     }
 
     /// Display the matched subscriptions as a JSON string.
     pub fn display_matched_subscriptions(&self) -> ConnectorResult<String> {
         self.parent
             .native_ref()?
-            .get_output(&self.name)?
+            .get_output(&self.c_name)?
             .get_matched_subscriptions()
     }
+
+    /// Get the number of subscriptions currently matched, without waiting.
+    ///
+    /// It may be called while another thread waits on this `Output`, since it
+    /// only reads the list of matched subscriptions.
+    pub fn matched_subscription_count(&self) -> ConnectorResult<usize> {
+        self.matched_subscriptions().map(|matched| matched.len())
+    }
+
+    /// Whether at least one subscription is currently matched, without waiting.
+    pub fn is_matched(&self) -> ConnectorResult<bool> {
+        self.matched_subscription_count().map(|count| count > 0)
+    }
+
+    /// Get the matched subscriptions, parsed from the JSON returned by
+    /// [`Output::display_matched_subscriptions`].
+    pub fn matched_subscriptions(&self) -> ConnectorResult<Vec<EndpointInfo>> {
+        EndpointInfo::parse_list(&self.display_matched_subscriptions()?)
+    }
 }
@@ -15,20 +15,52 @@ pub type ConnectorResult<T> = std::result::Result<T, ConnectorError>;
 pub type ConnectorFallible = ConnectorResult<()>;
 
 /// An error returned by Connector operations
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ConnectorError {
     /// The kind of error that occurred
     pub(crate) kind: ErrorKind,
     /// The last error message from the native library, if any
-    last_error_message: Option<String>,
+    last_error_message: Option<Box<str>>,
+    /// What was being done when the error occurred, if known
+    context: Option<Box<str>>,
+    /// The underlying error, such as a `serde_json` error, if any
+    source: Option<std::sync::Arc<dyn std::error::Error + Send + Sync>>,
+    /// The path of the field which failed to be serialized or deserialized, if known
+    field_path: Option<Box<str>>,
 }
 
 impl ConnectorError {
+    /// Get the kind of the error, to branch on its category and read its details.
+    ///
+    /// ```rust
+    /// use rtiddsconnector::{ConnectorError, ErrorKind};
+    ///
+    /// fn should_retry(error: &ConnectorError) -> bool {
+    ///     match error.kind() {
+    ///         ErrorKind::Timeout | ErrorKind::Busy { .. } => true,
+    ///         _ => false,
+    ///     }
+    /// }
+    /// ```
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+
     /// Check if the error is a timeout error
     pub fn is_timeout(&self) -> bool {
         matches!(self.kind, ErrorKind::Timeout)
     }
 
+    /// Check if the error is due to a wait interrupted by a [`WakeHandle`][crate::WakeHandle]
+    pub fn is_interrupted(&self) -> bool {
+        matches!(self.kind, ErrorKind::Interrupted)
+    }
+
+    /// Check if the error is due to a wait cancelled by a [`CancelToken`][crate::CancelToken]
+    pub fn is_cancelled(&self) -> bool {
+        matches!(self.kind, ErrorKind::Cancelled)
+    }
+
     /// Check if the error is a not found entity error
     pub fn is_entity_not_found(&self) -> bool {
         matches!(
@@ -51,19 +83,206 @@ impl ConnectorError {
         )
     }
 
+    /// Check if the error is caused by a busy entity or lock
+    pub fn is_busy(&self) -> bool {
+        matches!(self.kind, ErrorKind::Busy { .. })
+    }
+
+    /// Check if the error is caused by serializing or deserializing data
+    pub fn is_serialization_error(&self) -> bool {
+        matches!(
+            self.kind,
+            ErrorKind::Invalid {
+                what: InvalidErrorKind::Serialization | InvalidErrorKind::Deserialization,
+                ..
+            }
+        )
+    }
+
+    /// Check if retrying the operation which failed may succeed.
+    ///
+    /// | Kind                                                    | Retryable |
+    /// |---------------------------------------------------------|-----------|
+    /// | [`ErrorKind::Timeout`]                                  | yes       |
+    /// | [`ErrorKind::Busy`]                                     | yes       |
+    /// | [`ErrorKind::Native`] with a `Timeout` or `NoData` code | yes       |
+    /// | [`ErrorKind::Native`] with any other code               | no        |
+    /// | [`ErrorKind::NotFound`]                                 | no        |
+    /// | [`ErrorKind::Invalid`]                                  | no        |
+    /// | Any other kind                                          | no        |
+    ///
+    /// Interrupted and cancelled waits aren't retryable, since they were ended
+    /// on purpose.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self.kind,
+            ErrorKind::Timeout
+                | ErrorKind::Busy { .. }
+                | ErrorKind::Native {
+                    code: crate::ffi::ReturnCode::Timeout
+                        | crate::ffi::ReturnCode::NoData
+                }
+        )
+    }
+
+    /// Check if the error is caused by accessing a field as a type it doesn't hold
+    pub fn is_type_mismatch(&self) -> bool {
+        matches!(self.kind, ErrorKind::TypeMismatch { .. })
+    }
+
+    /// Check if the error is caused by indexing past the end of an array or sequence
+    pub fn is_out_of_bounds(&self) -> bool {
+        matches!(self.kind, ErrorKind::IndexOutOfBounds { .. })
+    }
+
     /// Check if the error is a native error
     pub fn is_native_error(&self) -> bool {
         matches!(self.kind, ErrorKind::Native { .. })
     }
 
+    /// Get the return code of a native error, or `None` for any other kind of error
+    pub fn native_code(&self) -> Option<crate::ReturnCode> {
+        match self.kind {
+            ErrorKind::Native { code } => Some(code),
+            _ => None,
+        }
+    }
+
     pub(crate) fn is_native_error_code(&self, code: crate::ffi::ReturnCode) -> bool {
         matches!(self.kind, ErrorKind::Native { code: c } if c == code)
     }
 
+    /// Check if the error was caused by accessing an optional member which isn't set.
+    ///
+    /// The native library reports those accesses with a `NoData` return code.
+    pub(crate) fn is_unset_optional(&self) -> bool {
+        self.is_native_error_code(crate::ffi::ReturnCode::NoData)
+    }
+
+    /// Get the path of the field which caused the error, such as
+    /// `simple.enum_field` or `double_sequence[2]`, if known.
+    ///
+    /// This is the field whose value failed to be serialized or deserialized,
+    /// including when a whole type was, or the field of a
+    /// [`ErrorKind::TypeMismatch`], [`ErrorKind::IndexOutOfBounds`] or field
+    /// [`ErrorKind::NotFound`] error.
+    ///
+    /// ```rust
+    /// use rtiddsconnector::{ConnectorResult, Sample};
+    ///
+    /// #[derive(serde::Deserialize)]
+    /// struct Shape { color: String, x: i32, y: i32 }
+    ///
+    /// fn shape(sample: &Sample) -> ConnectorResult<Shape> {
+    ///     sample.deserialize().inspect_err(|e| {
+    ///         if let Some(field) = e.field_path() {
+    ///             eprintln!("Field '{}' doesn't match the Shape type", field);
+    ///         }
+    ///     })
+    /// }
+    /// ```
+    pub fn field_path(&self) -> Option<&str> {
+        if let Some(field_path) = &self.field_path {
+            return Some(field_path);
+        }
+
+        match &self.kind {
+            ErrorKind::IndexOutOfBounds { field, .. }
+            | ErrorKind::TypeMismatch { field, .. }
+            | ErrorKind::NotFound {
+                what: NotFoundErrorKind::Field,
+                name: field,
+            } => Some(field),
+            _ => self
+                .source
+                .as_deref()
+                .and_then(|source| {
+                    (source as &(dyn std::error::Error + 'static))
+                        .downcast_ref::<ConnectorError>()
+                })
+                .and_then(ConnectorError::field_path),
+        }
+    }
+
+    /// Record the path of the field which caused the error, unless it is
+    /// already known.
+    pub(crate) fn with_field_path(mut self, field_path: Option<String>) -> Self {
+        if self.field_path.is_none() {
+            self.field_path = field_path.map(String::into_boxed_str);
+        }
+        self
+    }
+
     /// Get the last error message from the native library, if any
     pub fn last_error_message(&self) -> Option<&str> {
         self.last_error_message.as_deref()
     }
+
+    /// Turn a native error from accessing a field into a [`ErrorKind::TypeMismatch`],
+    /// keeping its native message, if the expected and found types differ.
+    ///
+    /// The native library reports these failures with a generic error code,
+    /// so the types are found out by the caller.
+    pub(crate) fn into_type_mismatch(
+        mut self,
+        field: &str,
+        expected: &'static str,
+        found: &'static str,
+    ) -> Self {
+        if self.is_native_error() && !self.is_unset_optional() && expected != found {
+            self.kind = ErrorKind::TypeMismatch {
+                field: field.to_string(),
+                expected,
+                found,
+            };
+        }
+        self
+    }
+
+    /// Turn a native error from accessing a field into an invalid argument error
+    /// if its path is malformed, such as with negative or non-numeric indexes,
+    /// or into a [`ErrorKind::IndexOutOfBounds`] if it indexes past the end of
+    /// an array or sequence in `value`, the JSON of the sample or instance.
+    ///
+    /// The native message is kept in both cases.
+    pub(crate) fn into_path_error(
+        mut self,
+        field: &str,
+        value: Option<&serde_json::Value>,
+    ) -> Self {
+        if !self.is_native_error() || self.is_unset_optional() {
+            return self;
+        }
+
+        if crate::json_path::parse(field).is_none() {
+            self.kind = ErrorKind::invalid_argument_error(format!(
+                "Malformed field path '{}', indexes must be non-negative integers",
+                field
+            ));
+        } else if let Some(index) =
+            value.and_then(|value| crate::json_path::out_of_bounds(value, field))
+        {
+            self.kind = ErrorKind::IndexOutOfBounds {
+                field: field.to_string(),
+                index,
+            };
+        }
+        self
+    }
+
+    /// Describe what was being done when the error occurred, keeping its kind.
+    ///
+    /// The context is shown before the error message, after any context
+    /// added previously.
+    pub(crate) fn with_context(mut self, context: impl Into<String>) -> Self {
+        let context = context.into();
+        let context = match self.context.take() {
+            Some(inner) => std::format!("{}: {}", context, inner),
+            None => context,
+        };
+        self.context = Some(context.into_boxed_str());
+        self
+    }
 }
 
 impl<T> From<ConnectorError> for ConnectorResult<T> {
@@ -95,10 +314,14 @@ fn invalid_field_error_from_message(message: &str) -> Option<&str> {
     }
 }
 
-impl From<ErrorKind> for ConnectorError {
-    fn from(kind: ErrorKind) -> Self {
-        // Only fetch error message for errors that come from native code
-        let last_error_message = crate::Connector::get_last_error_message();
+impl ConnectorError {
+    /// Create an error for a failure reported by the native library.
+    ///
+    /// The last error message is fetched here, so this must be called right after
+    /// the failing FFI call and while still holding the lock that guarded it.
+    pub(crate) fn from_native(kind: ErrorKind) -> Self {
+        let last_error_message =
+            crate::Connector::get_last_error_message().map(String::into_boxed_str);
 
         // Special case for transforming error messages about missing fields
         if let Some(message) = &last_error_message
@@ -107,20 +330,97 @@ impl From<ErrorKind> for ConnectorError {
             Self {
                 kind: ErrorKind::field_not_found_error(field_name),
                 last_error_message,
+                context: None,
+                source: None,
+                field_path: None,
             }
         } else {
             Self {
                 kind,
                 last_error_message,
+                context: None,
+                source: None,
+                field_path: None,
             }
         }
     }
 }
 
-impl std::error::Error for ConnectorError {}
+/// Errors created from an [`ErrorKind`] originate on the Rust side, with no native message.
+impl From<ErrorKind> for ConnectorError {
+    fn from(kind: ErrorKind) -> Self {
+        Self {
+            kind,
+            last_error_message: None,
+            context: None,
+            source: None,
+            field_path: None,
+        }
+    }
+}
+
+/// Allows infallible conversions to be used where a [`ConnectorError`] is expected.
+impl From<std::convert::Infallible> for ConnectorError {
+    fn from(never: std::convert::Infallible) -> Self {
+        match never {}
+    }
+}
+
+impl std::error::Error for ConnectorError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.source
+            .as_deref()
+            .map(|source| source as &(dyn std::error::Error + 'static))
+    }
+}
+
+/// Allows returning a [`ConnectorError`] where an [`std::io::Error`] is expected.
+///
+/// Timeouts become [`TimedOut`](std::io::ErrorKind::TimedOut), missing entities
+/// or fields [`NotFound`](std::io::ErrorKind::NotFound), and busy entities or
+/// locks [`WouldBlock`](std::io::ErrorKind::WouldBlock); any other error becomes
+/// [`Other`](std::io::ErrorKind::Other). The original error can be retrieved
+/// with [`std::io::Error::get_ref`] or [`std::io::Error::into_inner`].
+impl From<ConnectorError> for std::io::Error {
+    fn from(error: ConnectorError) -> Self {
+        let kind = match error.kind {
+            ErrorKind::Timeout => std::io::ErrorKind::TimedOut,
+            ErrorKind::NotFound { .. } => std::io::ErrorKind::NotFound,
+            ErrorKind::Busy { .. } => std::io::ErrorKind::WouldBlock,
+            _ => std::io::ErrorKind::Other,
+        };
+        std::io::Error::new(kind, error)
+    }
+}
+
+/// Allows using [`ConnectorError`] as the error of the serializers of this crate.
+impl serde::ser::Error for ConnectorError {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        ErrorKind::Invalid {
+            what: InvalidErrorKind::Serialization,
+            context: msg.to_string(),
+        }
+        .into()
+    }
+}
+
+/// Allows using [`ConnectorError`] as the error of the deserializers of this crate.
+impl serde::de::Error for ConnectorError {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        ErrorKind::Invalid {
+            what: InvalidErrorKind::Deserialization,
+            context: msg.to_string(),
+        }
+        .into()
+    }
+}
 
 impl std::fmt::Display for ConnectorError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(context) = &self.context {
+            write!(f, "{}: ", context)?;
+        }
+
         match &self.kind {
             ErrorKind::Native { code } => {
                 write!(f, "Native error with code '{}'", code)
@@ -156,9 +456,31 @@ impl std::fmt::Display for ConnectorError {
                 BusyErrorKind::Lock => write!(f, "Lock is busy: {}", reason),
             },
 
+            ErrorKind::IndexOutOfBounds { field, index } => {
+                write!(f, "Index {} is out of bounds in field '{}'", index, field)
+            }
+
+            ErrorKind::TypeMismatch {
+                field,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Type mismatch for field '{}': expected a {}, found a {}",
+                field, expected, found
+            ),
+
             ErrorKind::Timeout => {
                 write!(f, "Operation timed out")
             }
+
+            ErrorKind::Interrupted => {
+                write!(f, "Operation was interrupted")
+            }
+
+            ErrorKind::Cancelled => {
+                write!(f, "Operation was cancelled")
+            }
         }?;
 
         if let Some(msg) = &self.last_error_message {
@@ -170,7 +492,11 @@ impl std::fmt::Display for ConnectorError {
 }
 
 /// An enumeration of possible errors returned by Connector operations
-#[derive(Debug)]
+///
+/// Obtained with [`ConnectorError::kind`]. More kinds may be added in the
+/// future, so matches on an [`ErrorKind`] need a wildcard arm.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub enum ErrorKind {
     /// Some error occurred in the Native libraries
     Native {
@@ -202,12 +528,39 @@ pub enum ErrorKind {
         context: String,
     },
 
+    /// A field indexed past the end of an array or sequence
+    IndexOutOfBounds {
+        /// The path of the field
+        field: String,
+        /// The index which is out of bounds
+        index: usize,
+    },
+
+    /// A field was accessed as a type it doesn't hold, such as getting a
+    /// string field as a boolean, or setting a string into a numeric field
+    TypeMismatch {
+        /// The name of the field
+        field: String,
+        /// The type which was expected: the type requested when getting a
+        /// field, or the type of the field when setting it
+        expected: &'static str,
+        /// The type which was found instead
+        found: &'static str,
+    },
+
     /// Operation timed out
     Timeout,
+
+    /// Operation was interrupted by a [`WakeHandle`][crate::WakeHandle]
+    Interrupted,
+
+    /// Operation was cancelled by a [`CancelToken`][crate::CancelToken]
+    Cancelled,
 }
 
 /// What type of thing was not found
 #[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub enum NotFoundErrorKind {
     /// An entity (Input, Output, Connector) was not found
     Entity,
@@ -217,6 +570,7 @@ pub enum NotFoundErrorKind {
 
 /// What type of invalid input was encountered
 #[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub enum InvalidErrorKind {
     /// An argument passed to a function was invalid
     Argument,
@@ -232,6 +586,7 @@ pub enum InvalidErrorKind {
 
 /// What type of resource is busy
 #[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub enum BusyErrorKind {
     /// An entity is busy (e.g., has outstanding loans)
     Entity,
@@ -241,41 +596,103 @@ pub enum BusyErrorKind {
 
 impl ErrorKind {
     /// Helper to create an InvalidArgument error
-    pub fn invalid_argument_error(context: impl Into<String>) -> Self {
+    pub(crate) fn invalid_argument_error(context: impl Into<String>) -> Self {
         Self::Invalid {
             what: InvalidErrorKind::Argument,
             context: context.into(),
         }
     }
 
-    pub fn entity_busy_error(context: impl Into<String>) -> Self {
+    pub(crate) fn entity_busy_error(context: impl Into<String>) -> Self {
         Self::Busy {
             resource: BusyErrorKind::Entity,
             context: context.into(),
         }
     }
 
-    pub fn lock_poisoned_error(context: impl Into<String>) -> Self {
+    pub(crate) fn lock_poisoned_error(context: impl Into<String>) -> Self {
         Self::Busy {
             resource: BusyErrorKind::Lock,
             context: context.into(),
         }
     }
 
-    pub fn invalid_string_conversion_error() -> Self {
+    pub(crate) fn invalid_string_conversion_error() -> Self {
         Self::Invalid {
             what: InvalidErrorKind::Conversion,
             context: "string conversion failed".into(),
         }
     }
 
+    /// Helper to create a Conversion error for an integer a [f64] can't represent exactly
+    pub(crate) fn inexact_number_error(value: impl std::fmt::Display) -> Self {
+        Self::Invalid {
+            what: InvalidErrorKind::Conversion,
+            context: format!("{} can't be represented exactly as a number", value),
+        }
+    }
+
+    /// Helper to create an InvalidArgument error for a NaN or infinite number set into a field
+    pub(crate) fn non_finite_number_error(
+        field: impl std::fmt::Display,
+        value: f64,
+    ) -> Self {
+        Self::invalid_argument_error(format!(
+            "Field '{}' can't be set to the non-finite number {}",
+            field, value
+        ))
+    }
+
+    /// Helper to create a Conversion error for a string passed to the native library
+    /// which contains a NUL byte
+    pub(crate) fn nul_byte_error(what: impl std::fmt::Display, offset: usize) -> Self {
+        Self::Invalid {
+            what: InvalidErrorKind::Conversion,
+            context: format!("{} contains a NUL byte at offset {}", what, offset),
+        }
+    }
+
+    /// Helper to create a Conversion error for a [crate::SelectedValue] of another variant
+    /// than the one expected
+    pub(crate) fn selected_value_mismatch_error(
+        expected: &str,
+        actual: &crate::SelectedValue,
+    ) -> Self {
+        Self::Invalid {
+            what: InvalidErrorKind::Conversion,
+            context: format!("Expected a {} value, found {:?}", expected, actual),
+        }
+    }
+
     /// Helper to create a TimeoutError
-    pub fn timeout_error() -> Self {
+    pub(crate) fn timeout_error() -> Self {
         Self::Timeout
     }
 
+    /// Helper to create an Interrupted error
+    pub(crate) fn interrupted_error() -> Self {
+        Self::Interrupted
+    }
+
+    /// Helper to create a Cancelled error
+    pub(crate) fn cancelled_error() -> Self {
+        Self::Cancelled
+    }
+
+    /// Helper to create an error caused by another one, such as a `serde_json`
+    /// error, which is kept as its [`source`](std::error::Error::source).
+    pub(crate) fn caused_by(
+        self,
+        source: impl std::error::Error + Send + Sync + 'static,
+    ) -> ConnectorError {
+        ConnectorError {
+            source: Some(std::sync::Arc::new(source)),
+            ..self.into()
+        }
+    }
+
     /// Helper to create an EntityNotFound error
-    pub fn entity_not_found_error(entity_name: impl Into<String>) -> Self {
+    pub(crate) fn entity_not_found_error(entity_name: impl Into<String>) -> Self {
         Self::NotFound {
             what: NotFoundErrorKind::Entity,
             name: entity_name.into(),
@@ -283,7 +700,7 @@ impl ErrorKind {
     }
 
     /// Helper to create a FieldNotFound error
-    pub fn field_not_found_error(field_name: impl Into<String>) -> Self {
+    pub(crate) fn field_not_found_error(field_name: impl Into<String>) -> Self {
         Self::NotFound {
             what: NotFoundErrorKind::Field,
             name: field_name.into(),
@@ -291,12 +708,12 @@ impl ErrorKind {
     }
 
     /// Helper to create an [`Native`][ErrorKind::Native] variant from a FFI return code
-    pub fn native_error(code: crate::ffi::ReturnCode) -> Self {
+    pub(crate) fn native_error(code: crate::ffi::ReturnCode) -> Self {
         Self::Native { code }
     }
 
     /// Helper to create an [`InvalidErrorKind::Assertion`] error
-    pub fn assertion_failed_error(context: impl Into<String>) -> Self {
+    pub(crate) fn assertion_failed_error(context: impl Into<String>) -> Self {
         Self::Invalid {
             what: InvalidErrorKind::Assertion,
             context: context.into(),
@@ -304,7 +721,7 @@ impl ErrorKind {
     }
 
     /// Turn this error into a [Err] variant of [ConnectorResult]
-    pub fn into_err<R>(self) -> ConnectorResult<R> {
+    pub(crate) fn into_err<R>(self) -> ConnectorResult<R> {
         ConnectorError::from(self).into()
     }
 }
@@ -0,0 +1,114 @@
+/*******************************************************************************
+ * (c) 2025 Copyright, Real-Time Innovations.  All rights reserved.            *
+ * No duplications, whole or partial, manual or electronic, may be made        *
+ * without express written permission.  Any such copies, or revisions thereof, *
+ * must display this notice unaltered.                                         *
+ * This code contains trade secrets of Real-Time Innovations, Inc.             *
+ *******************************************************************************/
+
+//! Checks of Rust types against the DDS type of an [`Output`](crate::Output)
+//! or [`Input`](crate::Input), with `check_type`.
+
+use crate::{ConnectorError, FieldPath, Instance};
+use serde_json::Value;
+
+/// A difference between a Rust type and the DDS type of an entity, found by
+/// [`Output::check_type`](crate::Output::check_type) or
+/// [`Input::check_type`](crate::Input::check_type).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TypeMismatch {
+    /// The Rust type has a field which the DDS type doesn't have, for
+    /// example because it is misspelled.
+    MissingMember {
+        /// The path of the field, such as `simple.long_field`.
+        field: String,
+    },
+
+    /// The Rust type has a field whose kind of value doesn't match the
+    /// member of the DDS type, such as a string for a numeric member.
+    WrongKind {
+        /// The path of the field, such as `simple.long_field`, or an empty
+        /// string for the whole type.
+        field: String,
+
+        /// Why the value doesn't match.
+        reason: String,
+    },
+}
+
+impl TypeMismatch {
+    /// The path of the field which doesn't match.
+    pub fn field(&self) -> &str {
+        match self {
+            TypeMismatch::MissingMember { field }
+            | TypeMismatch::WrongKind { field, .. } => field,
+        }
+    }
+
+    /// A mismatch for a field, from the error of setting it.
+    fn from_error(field: &FieldPath, error: ConnectorError) -> Self {
+        if error.is_field_not_found() {
+            TypeMismatch::MissingMember {
+                field: field.to_string(),
+            }
+        } else {
+            TypeMismatch::WrongKind {
+                field: field.to_string(),
+                reason: error.to_string(),
+            }
+        }
+    }
+}
+
+/// Display the [`TypeMismatch`] as a message naming the field.
+impl std::fmt::Display for TypeMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TypeMismatch::MissingMember { field } => {
+                write!(f, "Field '{}' is not a member of the DDS type", field)
+            }
+            TypeMismatch::WrongKind { field, reason } => {
+                write!(
+                    f,
+                    "Field '{}' doesn't match the DDS type: {}",
+                    field, reason
+                )
+            }
+        }
+    }
+}
+
+/// Set every field of `value` into the instance one by one, collecting the
+/// mismatches rather than stopping at the first.
+///
+/// Members of structs are set individually, while sequences and arrays are set
+/// as a whole. Fields which are `null`, such as optional members set to
+/// `None`, are cleared.
+pub(crate) fn check_instance(
+    instance: &mut Instance,
+    value: &Value,
+    path: &FieldPath,
+    mismatches: &mut Vec<TypeMismatch>,
+) {
+    let result = match value {
+        Value::Object(members) if path.as_str().is_empty() || !members.is_empty() => {
+            for (name, member) in members {
+                check_instance(instance, member, &path.member(name), mismatches);
+            }
+            return;
+        }
+        Value::Null => instance.clear(path.as_str()),
+        Value::Bool(boolean) => instance.set_boolean(path.as_str(), *boolean),
+        Value::String(string) => instance.set_string(path.as_str(), string),
+        Value::Number(number) => {
+            instance.set_number(path.as_str(), number.as_f64().unwrap_or_default())
+        }
+        Value::Array(_) | Value::Object(_) => {
+            instance.serialize_field(path.as_str(), value)
+        }
+    };
+
+    if let Err(e) = result {
+        mismatches.push(TypeMismatch::from_error(path, e));
+    }
+}
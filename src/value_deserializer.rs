@@ -0,0 +1,615 @@
+/*******************************************************************************
+ * (c) 2025 Copyright, Real-Time Innovations.  All rights reserved.            *
+ * No duplications, whole or partial, manual or electronic, may be made        *
+ * without express written permission.  Any such copies, or revisions thereof, *
+ * must display this notice unaltered.                                         *
+ * This code contains trade secrets of Real-Time Innovations, Inc.             *
+ *******************************************************************************/
+
+//! A Serde deserializer of the JSON value of a sample which applies the
+//! [`DeserializeOptions`] of [`crate::Sample::deserialize_with`].
+//!
+//! It wraps [`serde_json::Value`], to which every value is handed once the
+//! options have been applied, so that the data is deserialized exactly as
+//! [`crate::Sample::deserialize`] does otherwise.
+//!
+//! It also checks a type against a sample for [`crate::Input::check_type`],
+//! reporting every member missing from the sample and every value of the
+//! wrong kind, deserializing them as their default value to carry on.
+
+use crate::{DeserializeOptions, FieldPath, TypeMismatch};
+use serde::de::{self, DeserializeOwned, DeserializeSeed, Visitor};
+use serde_json::{Error, Value};
+use std::cell::RefCell;
+
+/// Deserialize `value` into `T`, applying the options and tracking the path
+/// of the value which fails to be deserialized.
+pub(crate) fn deserialize_value<T>(
+    value: Value,
+    options: &DeserializeOptions,
+) -> Result<T, crate::input::PathError>
+where
+    T: DeserializeOwned,
+{
+    serde_path_to_error::deserialize(ValueDeserializer {
+        value: Some(value),
+        context: Context {
+            options,
+            mismatches: None,
+        },
+        path: None,
+        missing: false,
+    })
+}
+
+/// Check that `value` can be deserialized into `T`, returning the mismatches.
+///
+/// Values which can't be deserialized for other reasons, such as numbers out
+/// of the range of their type, are reported as a mismatch of the whole sample.
+pub(crate) fn check_value<T>(value: Value) -> Vec<TypeMismatch>
+where
+    T: DeserializeOwned,
+{
+    let mismatches = RefCell::new(Vec::new());
+    let result = T::deserialize(ValueDeserializer {
+        value: Some(value),
+        context: Context {
+            options: &DeserializeOptions::default(),
+            mismatches: Some(&mismatches),
+        },
+        path: Some(FieldPath::default()),
+        missing: false,
+    });
+
+    let mut mismatches = mismatches.into_inner();
+    if let Err(e) = result {
+        mismatches.push(TypeMismatch::WrongKind {
+            field: String::new(),
+            reason: e.to_string(),
+        });
+    }
+
+    mismatches
+}
+
+/// The state shared by the deserializers of every value.
+#[derive(Clone, Copy)]
+struct Context<'a> {
+    options: &'a DeserializeOptions,
+
+    /// The mismatches found so far, when checking a type.
+    mismatches: Option<&'a RefCell<Vec<TypeMismatch>>>,
+}
+
+impl Context<'_> {
+    /// Report a mismatch, if checking a type.
+    fn report(&self, mismatch: impl FnOnce() -> TypeMismatch) {
+        if let Some(mismatches) = self.mismatches {
+            mismatches.borrow_mut().push(mismatch());
+        }
+    }
+
+    /// Whether the members missing from a struct take their default value.
+    fn missing_as_default(&self) -> bool {
+        self.options.missing_as_default || self.mismatches.is_some()
+    }
+}
+
+/// The deserializer of a value, or of a member missing from the sample.
+struct ValueDeserializer<'a> {
+    /// The value, or `None` if the member is missing and is deserialized as
+    /// the default value of its type.
+    value: Option<Value>,
+
+    context: Context<'a>,
+
+    /// The path of the value, only kept when checking a type.
+    path: Option<FieldPath>,
+
+    /// Whether the value is a member missing from a struct of the sample.
+    missing: bool,
+}
+
+impl ValueDeserializer<'_> {
+    /// When checking a type, report a member missing from the sample, unless
+    /// it is deserialized as an `Option`.
+    fn report_missing(&mut self) {
+        if let Some(path) = &self.path
+            && self.missing
+        {
+            self.context.report(|| TypeMismatch::MissingMember {
+                field: path.to_string(),
+            });
+            self.missing = false;
+        }
+    }
+
+    /// When checking a type, report a value which isn't of the expected kind
+    /// and deserialize the default value instead.
+    fn expect(mut self, expected: &str, is_kind: fn(&Value) -> bool) -> Self {
+        self.report_missing();
+        if let (Some(path), Some(value)) = (&self.path, &self.value)
+            && !is_kind(value)
+        {
+            self.context.report(|| TypeMismatch::WrongKind {
+                field: path.to_string(),
+                reason: std::format!("expected {}, found {}", expected, kind_of(value)),
+            });
+            self.value = None;
+        }
+
+        self
+    }
+}
+
+/// The kind of a JSON value, for the mismatches.
+fn kind_of(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "a sequence",
+        Value::Object(_) => "a struct",
+    }
+}
+
+/// Convert a string holding a number into that number, if enabled.
+fn coerce_number(value: Option<Value>, options: &DeserializeOptions) -> Option<Value> {
+    match value {
+        Some(Value::String(string)) if options.numeric_strings => {
+            match string.trim().parse::<serde_json::Number>() {
+                Ok(number) => Some(Value::Number(number)),
+                Err(_) => Some(Value::String(string)),
+            }
+        }
+        value => value,
+    }
+}
+
+/// The deserializer of each of the elements of a sequence.
+fn elements<'a>(
+    elements: impl IntoIterator<Item = Option<Value>>,
+    context: Context<'a>,
+    path: Option<FieldPath>,
+) -> SeqDeserializer<'a> {
+    SeqDeserializer {
+        elements: elements.into_iter().collect::<Vec<_>>().into_iter(),
+        index: 0,
+        context,
+        path,
+    }
+}
+
+/// The deserializer of each of the entries of a map or enum.
+fn entries<'a>(
+    entries: impl IntoIterator<Item = (String, Option<Value>)>,
+    context: Context<'a>,
+    path: Option<FieldPath>,
+) -> MapDeserializer<'a> {
+    MapDeserializer {
+        entries: entries.into_iter().collect::<Vec<_>>().into_iter(),
+        value: None,
+        context,
+        path,
+        report_missing: false,
+    }
+}
+
+/// The deserializer of the members of a struct, checking them against the
+/// fields of the type and adding the missing ones, as enabled.
+///
+/// The missing members are reported if the struct isn't missing itself.
+fn members<'a>(
+    members: Option<serde_json::Map<String, Value>>,
+    fields: &'static [&'static str],
+    context: Context<'a>,
+    path: Option<FieldPath>,
+) -> Result<MapDeserializer<'a>, Error> {
+    let report_missing = members.is_some();
+    let members = members.unwrap_or_default();
+
+    let unknown = context
+        .options
+        .deny_unknown_fields
+        .then(|| members.keys().find(|key| !fields.contains(&key.as_str())))
+        .flatten();
+    if let Some(unknown) = unknown {
+        return Err(de::Error::unknown_field(unknown, fields));
+    }
+
+    let missing: Vec<_> = if context.missing_as_default() {
+        fields
+            .iter()
+            .filter(|field| !members.contains_key(**field))
+            .map(|field| (field.to_string(), None))
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    Ok(MapDeserializer {
+        report_missing,
+        ..entries(
+            members
+                .into_iter()
+                .map(|(key, value)| (key, Some(value)))
+                .chain(missing),
+            context,
+            path,
+        )
+    })
+}
+
+/// Deserialize a number, converting it from a string if enabled, or the
+/// default value of its type if it is missing.
+macro_rules! deserialize_number {
+    ($($method:ident => $visit:ident),* $(,)?) => {
+        $(
+            fn $method<V>(mut self, visitor: V) -> Result<V::Value, Error>
+            where
+                V: Visitor<'de>,
+            {
+                self.value = coerce_number(self.value, self.context.options);
+                match self.expect("a number", Value::is_number).value {
+                    None => visitor.$visit(Default::default()),
+                    Some(value) => value.$method(visitor),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for ValueDeserializer<'_> {
+    type Error = Error;
+
+    fn deserialize_any<V>(mut self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.report_missing();
+        match self.value {
+            None => visitor.visit_unit(),
+            Some(Value::Array(values)) => visitor.visit_seq(elements(
+                values.into_iter().map(Some),
+                self.context,
+                self.path,
+            )),
+            Some(Value::Object(values)) => visitor.visit_map(entries(
+                values.into_iter().map(|(key, value)| (key, Some(value))),
+                self.context,
+                self.path,
+            )),
+            Some(value) => value.deserialize_any(visitor),
+        }
+    }
+
+    deserialize_number! {
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+    }
+
+    fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.expect("a boolean", Value::is_boolean).value {
+            None => visitor.visit_bool(false),
+            Some(value) => value.deserialize_bool(visitor),
+        }
+    }
+
+    fn deserialize_char<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.expect("a string", Value::is_string).value {
+            None => visitor.visit_char(char::default()),
+            Some(value) => value.deserialize_char(visitor),
+        }
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.expect("a string", Value::is_string).value {
+            None => visitor.visit_str(""),
+            Some(value) => value.deserialize_string(visitor),
+        }
+    }
+
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_byte_buf(visitor)
+    }
+
+    fn deserialize_byte_buf<V>(mut self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.report_missing();
+        match self.value {
+            None => visitor.visit_bytes(&[]),
+            Some(value) => value.deserialize_byte_buf(visitor),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            None | Some(Value::Null) => visitor.visit_none(),
+            Some(_) => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_unit<V>(mut self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.report_missing();
+        match self.value {
+            None => visitor.visit_unit(),
+            Some(value) => value.deserialize_unit(visitor),
+        }
+    }
+
+    fn deserialize_unit_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        let this = self.expect("a sequence", Value::is_array);
+        match this.value {
+            None => visitor.visit_seq(elements([], this.context, this.path)),
+            Some(Value::Array(values)) => visitor.visit_seq(elements(
+                values.into_iter().map(Some),
+                this.context,
+                this.path,
+            )),
+            Some(value) => value.deserialize_seq(visitor),
+        }
+    }
+
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        let this = self.expect("a sequence", Value::is_array);
+        match this.value {
+            // Arrays hold as many default elements as their length
+            None => visitor.visit_seq(elements(
+                std::iter::repeat_n(None, len),
+                this.context,
+                this.path,
+            )),
+            Some(_) => this.deserialize_seq(visitor),
+        }
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        let this = self.expect("a struct", Value::is_object);
+        match this.value {
+            None => visitor.visit_map(entries([], this.context, this.path)),
+            Some(Value::Object(_)) => this.deserialize_any(visitor),
+            Some(value) => value.deserialize_map(visitor),
+        }
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        let this = self.expect("a struct", Value::is_object);
+        match this.value {
+            None => visitor.visit_map(members(None, fields, this.context, this.path)?),
+            Some(Value::Object(values)) => {
+                visitor.visit_map(members(Some(values), fields, this.context, this.path)?)
+            }
+            Some(value) => value.deserialize_struct(name, fields, visitor),
+        }
+    }
+
+    fn deserialize_enum<V>(
+        mut self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.report_missing();
+        let variant = match self.value {
+            // The first variant, holding its default value if any
+            None => match variants.first() {
+                Some(variant) => {
+                    entries([(variant.to_string(), None)], self.context, self.path)
+                }
+                None => {
+                    return Err(de::Error::custom(format!(
+                        "enum {} has no variants",
+                        name
+                    )));
+                }
+            },
+            Some(Value::Object(values)) => entries(
+                values.into_iter().map(|(key, value)| (key, Some(value))),
+                self.context,
+                self.path,
+            ),
+            Some(value) => return value.deserialize_enum(name, variants, visitor),
+        };
+
+        visitor.visit_enum(de::value::MapAccessDeserializer::new(variant))
+    }
+
+    fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+}
+
+/// Access to the elements of a sequence.
+struct SeqDeserializer<'a> {
+    elements: std::vec::IntoIter<Option<Value>>,
+
+    /// The index of the next element.
+    index: usize,
+
+    context: Context<'a>,
+    path: Option<FieldPath>,
+}
+
+impl<'de> de::SeqAccess<'de> for SeqDeserializer<'_> {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        let Some(value) = self.elements.next() else {
+            return Ok(None);
+        };
+        let path = self.path.as_ref().map(|path| path.element(self.index));
+        self.index += 1;
+
+        seed.deserialize(ValueDeserializer {
+            value,
+            context: self.context,
+            path,
+            missing: false,
+        })
+        .map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.elements.len())
+    }
+}
+
+/// Access to the entries of a map or struct.
+struct MapDeserializer<'a> {
+    entries: std::vec::IntoIter<(String, Option<Value>)>,
+
+    /// The value of the entry whose key was just deserialized, and its path.
+    value: Option<(Option<Value>, Option<FieldPath>)>,
+
+    context: Context<'a>,
+    path: Option<FieldPath>,
+
+    /// Whether the entries without a value are members missing from a
+    /// struct of the sample.
+    report_missing: bool,
+}
+
+impl<'de> de::MapAccess<'de> for MapDeserializer<'_> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        match self.entries.next() {
+            Some((key, value)) => {
+                let path = self.path.as_ref().map(|path| path.member(&key));
+                self.value = Some((value, path));
+                seed.deserialize(de::value::StringDeserializer::<Error>::new(key))
+                    .map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let (value, path) = self
+            .value
+            .take()
+            .ok_or_else(|| de::Error::custom("value requested before its key"))?;
+
+        seed.deserialize(ValueDeserializer {
+            missing: self.report_missing && value.is_none(),
+            value,
+            context: self.context,
+            path,
+        })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
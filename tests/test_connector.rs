@@ -11,9 +11,9 @@ extern crate assert_matches;
 
 mod test_utils;
 
-use rtiddsconnector::{self, Connector, SelectedValue};
-use std::path::Path;
-use test_utils::TestContextBuilder;
+use rtiddsconnector::{self, Connector, ConnectorResult, SelectedValue};
+use std::{path::Path, time::Duration};
+use test_utils::{TEST_TIMEOUT, TestContextBuilder};
 
 /// Helper function to check if path exists
 fn path_exists(path: &str) -> bool {
@@ -133,6 +133,112 @@ fn test_connector_advanced_features() {
     );
 }
 
+#[test]
+fn test_wake_handle_interrupts_wait_for_data() {
+    let context = TestContextBuilder::simple_input_only()
+        .build()
+        .expect("Failed to create test context");
+
+    let wake_handle = context.connector.wake_handle();
+    let waker = std::thread::spawn(move || {
+        std::thread::sleep(std::time::Duration::from_millis(200));
+        wake_handle.trigger();
+    });
+
+    let start = std::time::Instant::now();
+    assert_matches!(
+        context.connector.wait_for_data(),
+        Err(e) if e.is_interrupted(),
+        "An infinite wait for data should be interrupted by the wake handle"
+    );
+    assert!(
+        start.elapsed() < TEST_TIMEOUT,
+        "Interrupted wait should return promptly"
+    );
+
+    waker.join().expect("Waker thread panicked");
+}
+
+#[test]
+fn test_wake_handle_interrupts_wait_for_publications() {
+    let context = TestContextBuilder::simple_input_only()
+        .build()
+        .expect("Failed to create test context");
+    let input = context
+        .connector
+        .get_input("TestSubscriber::TestReader")
+        .expect("Failed to get valid Input");
+
+    let wake_handle = context.connector.wake_handle();
+    let waker = std::thread::spawn(move || {
+        std::thread::sleep(std::time::Duration::from_millis(200));
+        wake_handle.trigger();
+    });
+
+    let start = std::time::Instant::now();
+    assert_matches!(
+        input.wait_for_publications(),
+        Err(e) if e.is_interrupted(),
+        "An infinite wait for publications should be interrupted by the wake handle"
+    );
+    assert!(
+        start.elapsed() < TEST_TIMEOUT,
+        "Interrupted wait should return promptly"
+    );
+
+    waker.join().expect("Waker thread panicked");
+}
+
+#[test]
+fn test_connector_validate_config() {
+    let _globals = rtiddsconnector::GlobalsDropGuard;
+    let config_file =
+        concat!(env!("CARGO_MANIFEST_DIR"), "/tests/resources", "/Test.xml");
+
+    assert_matches!(
+        Connector::validate_config(
+            "TestDomainParticipantLibrary::SimpleParticipant",
+            config_file
+        ),
+        Ok(()),
+        "A valid configuration should be validated"
+    );
+
+    assert_matches!(
+        Connector::validate_config("InvalidLibrary::InvalidParticipant", config_file),
+        Err(e) if e.is_entity_not_found()
+            && e.to_string().contains("InvalidLibrary::InvalidParticipant")
+            && e.to_string().contains(config_file),
+        "An invalid profile should be reported along with its file"
+    );
+
+    assert_matches!(
+        Connector::validate_config(
+            "TestDomainParticipantLibrary::SimpleParticipant",
+            "invalid/path/to/xml"
+        ),
+        Err(e) if e.to_string().contains("invalid/path/to/xml")
+            && e.to_string().contains("TestDomainParticipantLibrary::SimpleParticipant"),
+        "An invalid file should be reported along with the profile"
+    );
+
+    // When `_globals` goes out of scope, no participant must be left behind
+}
+
+#[test]
+fn test_connector_domain_id() {
+    for builder in [TestContextBuilder::simple(), TestContextBuilder::complex()] {
+        let context = builder.build().expect("Failed to create test context");
+
+        // The domain id is defined by the DOMAIN_ID variable in Test.xml
+        assert_matches!(context.connector.domain_id(), Ok(61));
+        assert!(
+            format!("{:?}", context.connector).contains("domain_id: 61"),
+            "Debug output should include the domain id"
+        );
+    }
+}
+
 #[test]
 fn test_connector_versions() {
     use regex::Regex;
@@ -165,6 +271,361 @@ fn test_connector_versions() {
     );
 }
 
+#[test]
+fn test_entities_outlive_connector() {
+    let (connector, _globals) = TestContextBuilder::simple()
+        .build()
+        .expect("Failed to create test context")
+        .into_parts();
+
+    let mut input = connector
+        .get_input("TestSubscriber::TestReader")
+        .expect("Failed to get input");
+    let mut output = connector
+        .get_output("TestPublisher::TestWriter")
+        .expect("Failed to get output");
+
+    // Entities keep the native connector alive once the Connector is dropped
+    drop(connector);
+
+    assert_matches!(
+        output.wait_for_subscriptions_with_timeout(TEST_TIMEOUT),
+        Ok(count) if count >= 1,
+        "Output should still discover the input"
+    );
+    output
+        .instance()
+        .set_number("long_field", 42.0)
+        .expect("Failed to set long_field");
+    output.write().expect("Failed to write data");
+
+    // Entities are owned and can be moved into other threads
+    let input = std::thread::spawn(move || {
+        input
+            .wait_with_timeout(TEST_TIMEOUT)
+            .expect("Failed to wait for data");
+        input.take().expect("Failed to take data");
+        input
+    })
+    .join()
+    .expect("Reader thread panicked");
+
+    let sample = input.into_iter().next().expect("Expected a sample");
+    assert_matches!(sample.get_number("long_field"), Ok(42.0));
+
+    // The native connector is deleted after the last entity is dropped
+    drop(output);
+    drop(input);
+}
+
+#[test]
+fn test_cloned_connector_across_threads() {
+    use std::sync::{Arc, Barrier};
+
+    /// Own an entity until every thread has checked exclusivity of its own.
+    fn hold_entity<T: std::fmt::Debug>(
+        barrier: &Barrier,
+        acquire: impl Fn() -> ConnectorResult<T>,
+    ) {
+        let entity = acquire().expect("Failed to acquire entity");
+        barrier.wait();
+        assert_matches!(acquire(), Err(_), "Entity should be busy while owned");
+        barrier.wait();
+        drop(entity);
+    }
+
+    let (connector, _globals) = TestContextBuilder::multiple_entities()
+        .build()
+        .expect("Failed to create test context")
+        .into_parts();
+    let barrier = Arc::new(Barrier::new(4));
+
+    let threads = Vec::from_iter((0..4).map(|i| {
+        let connector = connector.clone();
+        let barrier = Arc::clone(&barrier);
+        std::thread::spawn(move || match i {
+            0 => hold_entity(&barrier, || {
+                connector.get_input("TestSubscriber::SimpleReader")
+            }),
+            1 => hold_entity(&barrier, || {
+                connector.get_input("TestSubscriber::ComplexReader")
+            }),
+            2 => hold_entity(&barrier, || {
+                connector.get_output("TestPublisher::SimpleWriter")
+            }),
+            _ => hold_entity(&barrier, || {
+                connector.get_output("TestPublisher::ComplexWriter")
+            }),
+        })
+    }));
+
+    // The native connector must survive the original handle
+    drop(connector);
+
+    for thread in threads {
+        thread.join().expect("Worker thread panicked");
+    }
+}
+
+#[test]
+fn test_entity_released_by_panicking_thread() {
+    let (connector, _globals) = TestContextBuilder::simple_input_only()
+        .build()
+        .expect("Failed to create test context")
+        .into_parts();
+
+    let thread = {
+        let connector = connector.clone();
+        std::thread::spawn(move || {
+            let _input = connector
+                .get_input("TestSubscriber::TestReader")
+                .expect("Failed to get valid Input");
+            panic!("Panicking while holding the Input");
+        })
+    };
+    assert_matches!(thread.join(), Err(_), "The thread should have panicked");
+
+    // The Input was released when dropped during unwinding
+    assert_matches!(
+        connector.get_input("TestSubscriber::TestReader"),
+        Ok(_),
+        "The Input should be available again"
+    );
+}
+
+#[test]
+fn test_take_output_with_timeout() {
+    let context = TestContextBuilder::simple_output_only()
+        .build()
+        .expect("Failed to create test context");
+    let connector = &context.connector;
+
+    let output = connector
+        .take_output_with_timeout("TestPublisher::TestWriter", Duration::from_millis(50))
+        .expect("A free Output should be acquired without waiting");
+
+    let thread = {
+        let connector = connector.clone();
+        std::thread::spawn(move || {
+            connector
+                .take_output_with_timeout(
+                    "TestPublisher::TestWriter",
+                    Duration::from_millis(50),
+                )
+                .map(|_| ())
+        })
+    };
+    assert_matches!(
+        thread.join().expect("The thread should not panic"),
+        Err(e) if e.is_timeout(),
+        "The Output is still owned after the timeout"
+    );
+
+    drop(output);
+    assert_matches!(
+        connector.get_output("TestPublisher::TestWriter"),
+        Ok(_),
+        "A timed out thread should leave the Output available"
+    );
+}
+
+#[test]
+fn test_take_output_is_fair() {
+    const THREADS: usize = 8;
+    const DURATION: Duration = Duration::from_secs(2);
+
+    let context = TestContextBuilder::simple_output_only()
+        .build()
+        .expect("Failed to create test context");
+    let connector = &context.connector;
+    let barrier = std::sync::Barrier::new(THREADS);
+
+    let acquisitions: Vec<usize> = std::thread::scope(|scope| {
+        let threads: Vec<_> = (0..THREADS)
+            .map(|_| {
+                scope.spawn(|| {
+                    barrier.wait();
+                    let start = std::time::Instant::now();
+                    let mut acquisitions = 0;
+                    while start.elapsed() < DURATION {
+                        let _output = connector
+                            .take_output("TestPublisher::TestWriter")
+                            .expect("Failed to take Output");
+                        acquisitions += 1;
+                    }
+                    acquisitions
+                })
+            })
+            .collect();
+
+        threads
+            .into_iter()
+            .map(|thread| thread.join().expect("The thread should not panic"))
+            .collect()
+    });
+
+    assert!(
+        acquisitions.iter().all(|count| *count > 0),
+        "Every thread should acquire the Output, got {:?}",
+        acquisitions
+    );
+}
+
+#[test]
+fn test_error_is_retryable() {
+    use rtiddsconnector::{
+        BusyErrorKind, ConnectorError, ErrorKind, InvalidErrorKind, NotFoundErrorKind,
+        ReturnCode,
+    };
+
+    let invalid = |what| ErrorKind::Invalid {
+        what,
+        context: "context".to_string(),
+    };
+    let cases = [
+        (ErrorKind::Timeout, true),
+        (ErrorKind::Interrupted, false),
+        (ErrorKind::Cancelled, false),
+        (
+            ErrorKind::Busy {
+                resource: BusyErrorKind::Entity,
+                context: "context".to_string(),
+            },
+            true,
+        ),
+        (
+            ErrorKind::Busy {
+                resource: BusyErrorKind::Lock,
+                context: "context".to_string(),
+            },
+            true,
+        ),
+        (
+            ErrorKind::Native {
+                code: ReturnCode::Timeout,
+            },
+            true,
+        ),
+        (
+            ErrorKind::Native {
+                code: ReturnCode::NoData,
+            },
+            true,
+        ),
+        (
+            ErrorKind::Native {
+                code: ReturnCode::Error,
+            },
+            false,
+        ),
+        (
+            ErrorKind::Native {
+                code: ReturnCode::IllegalOperation,
+            },
+            false,
+        ),
+        (
+            ErrorKind::NotFound {
+                what: NotFoundErrorKind::Entity,
+                name: "name".to_string(),
+            },
+            false,
+        ),
+        (
+            ErrorKind::NotFound {
+                what: NotFoundErrorKind::Field,
+                name: "name".to_string(),
+            },
+            false,
+        ),
+        (invalid(InvalidErrorKind::Argument), false),
+        (invalid(InvalidErrorKind::Conversion), false),
+        (invalid(InvalidErrorKind::Serialization), false),
+        (invalid(InvalidErrorKind::Deserialization), false),
+        (
+            ErrorKind::IndexOutOfBounds {
+                field: "sequence[3]".to_string(),
+                index: 3,
+            },
+            false,
+        ),
+    ];
+
+    for (kind, retryable) in cases {
+        let error = ConnectorError::from(kind);
+        assert_eq!(error.is_retryable(), retryable, "{:?}", error);
+        assert_eq!(
+            error.is_busy(),
+            matches!(error.kind(), ErrorKind::Busy { .. }),
+            "{:?}",
+            error
+        );
+        assert_eq!(
+            error.is_serialization_error(),
+            matches!(
+                error.kind(),
+                ErrorKind::Invalid {
+                    what: InvalidErrorKind::Serialization
+                        | InvalidErrorKind::Deserialization,
+                    ..
+                }
+            ),
+            "{:?}",
+            error
+        );
+    }
+}
+
+#[test]
+fn test_error_into_io_error() {
+    use rtiddsconnector::{BusyErrorKind, ConnectorError, ErrorKind, ReturnCode};
+
+    let cases = [
+        (ErrorKind::Timeout, std::io::ErrorKind::TimedOut),
+        (
+            ErrorKind::NotFound {
+                what: rtiddsconnector::NotFoundErrorKind::Field,
+                name: "name".to_string(),
+            },
+            std::io::ErrorKind::NotFound,
+        ),
+        (
+            ErrorKind::Busy {
+                resource: BusyErrorKind::Lock,
+                context: "context".to_string(),
+            },
+            std::io::ErrorKind::WouldBlock,
+        ),
+        (
+            ErrorKind::Native {
+                code: ReturnCode::Error,
+            },
+            std::io::ErrorKind::Other,
+        ),
+    ];
+
+    for (kind, io_kind) in cases {
+        let error = ConnectorError::from(kind);
+        let io_error = std::io::Error::from(error.clone());
+        assert_eq!(io_error.kind(), io_kind, "{:?}", error);
+        assert_matches!(
+            io_error
+                .get_ref()
+                .and_then(|inner| inner.downcast_ref::<ConnectorError>()),
+            Some(inner) if inner.kind() == error.kind()
+        );
+    }
+
+    assert_eq!(
+        ConnectorError::from(ErrorKind::Native {
+            code: ReturnCode::NoData
+        })
+        .native_code(),
+        Some(ReturnCode::NoData)
+    );
+    assert_eq!(ConnectorError::from(ErrorKind::Timeout).native_code(), None);
+}
+
 #[test]
 fn test_selected_value_conversions() {
     // Test SelectedValue enum variants that might be returned by sample methods
@@ -187,8 +648,344 @@ fn test_selected_value_conversions() {
     assert_eq!(from_string, SelectedValue::String("test".to_string()));
     assert_eq!(from_str, SelectedValue::String("test".to_string()));
 
+    // Primitives which a number represents exactly
+    assert_eq!(SelectedValue::from(-8_i8), SelectedValue::Number(-8.0));
+    assert_eq!(SelectedValue::from(16_u16), SelectedValue::Number(16.0));
+    assert_eq!(
+        SelectedValue::from(i32::MIN),
+        SelectedValue::Number(-2147483648.0)
+    );
+    assert_eq!(
+        SelectedValue::from(u32::MAX),
+        SelectedValue::Number(4294967295.0)
+    );
+    assert_eq!(SelectedValue::from(0.5_f32), SelectedValue::Number(0.5));
+    assert_eq!(
+        SelectedValue::from(&"test".to_string()),
+        SelectedValue::String("test".to_string())
+    );
+    assert_eq!(
+        SelectedValue::from('x'),
+        SelectedValue::String("x".to_string())
+    );
+
+    // 64-bit integers are only converted if they are represented exactly
+    assert_matches!(
+        SelectedValue::try_from(-(1_i64 << 53)),
+        Ok(SelectedValue::Number(n)) if n == -9007199254740992.0
+    );
+    assert_matches!(SelectedValue::try_from((1_i64 << 53) + 1), Err(_));
+    assert_matches!(SelectedValue::try_from(1_u64 << 53), Ok(_));
+    assert_matches!(SelectedValue::try_from(u64::MAX), Err(_));
+    assert_matches!(
+        SelectedValue::try_from(7_usize),
+        Ok(SelectedValue::Number(7.0))
+    );
+    assert_matches!(SelectedValue::try_from(usize::MAX), Err(_));
+
+    // Borrowed and owned strings
+    assert_eq!(
+        SelectedValue::from(std::borrow::Cow::Borrowed("test")),
+        SelectedValue::String("test".to_string())
+    );
+    assert_eq!(
+        SelectedValue::from(std::borrow::Cow::<str>::Owned("test".to_string())),
+        SelectedValue::String("test".to_string())
+    );
+
+    // Integer literals convert without a suffix
+    let from_literal: SelectedValue = 10.into();
+    assert_eq!(from_literal, SelectedValue::Number(10.0));
+
     // Test Clone and Debug
     let cloned = number_val.clone();
     assert_eq!(cloned, number_val);
     let _debug_str = format!("{:?}", number_val);
 }
+
+#[test]
+fn test_selected_value_serde_and_display() {
+    let values = [
+        SelectedValue::Number(42.0),
+        SelectedValue::Number(-0.0),
+        SelectedValue::Number(0.1),
+        SelectedValue::Number(-1e300),
+        SelectedValue::Boolean(false),
+        SelectedValue::String("".to_string()),
+        SelectedValue::String("42".to_string()),
+        SelectedValue::String("true".to_string()),
+        SelectedValue::Null,
+    ];
+
+    // JSON round trip, keeping strings which look like numbers as strings
+    for value in &values {
+        let json = serde_json::to_string(value).expect("Failed to serialize");
+        let parsed: SelectedValue =
+            serde_json::from_str(&json).expect("Failed to deserialize");
+        assert_eq!(&parsed, value, "Round trip through {}", json);
+    }
+    assert_eq!(
+        serde_json::to_string(&SelectedValue::String("42".to_string())).unwrap(),
+        r#""42""#
+    );
+    assert_eq!(
+        serde_json::from_str::<SelectedValue>("7").unwrap(),
+        SelectedValue::Number(7.0)
+    );
+    let negative_zero: SelectedValue = serde_json::from_str("-0.0").unwrap();
+    assert_matches!(negative_zero, SelectedValue::Number(n) if n == 0.0 && n.is_sign_negative());
+
+    // Display
+    assert_eq!(SelectedValue::Number(42.0).to_string(), "42");
+    assert_eq!(SelectedValue::Number(2.5).to_string(), "2.5");
+    assert_eq!(SelectedValue::Number(-0.0).to_string(), "-0");
+    assert_eq!(SelectedValue::Boolean(true).to_string(), "true");
+    assert_eq!(
+        SelectedValue::String("BLUE".to_string()).to_string(),
+        "BLUE"
+    );
+    assert_eq!(SelectedValue::Null.to_string(), "null");
+
+    // FromStr: booleans, then finite numbers, then strings
+    let parse = |s: &str| s.parse::<SelectedValue>().unwrap();
+    assert_eq!(parse("true"), SelectedValue::Boolean(true));
+    assert_eq!(parse("False"), SelectedValue::String("False".to_string()));
+    assert_eq!(parse("42"), SelectedValue::Number(42.0));
+    assert_eq!(parse("-1.5e3"), SelectedValue::Number(-1500.0));
+    assert_eq!(parse("NaN"), SelectedValue::String("NaN".to_string()));
+    assert_eq!(parse("BLUE"), SelectedValue::String("BLUE".to_string()));
+    assert_matches!(parse("-0"), SelectedValue::Number(n) if n.is_sign_negative());
+
+    // Display and FromStr round trip for numbers and booleans
+    for value in &values[..5] {
+        assert_eq!(&parse(&value.to_string()), value);
+    }
+}
+
+#[test]
+fn test_selected_value_try_into() {
+    let number = SelectedValue::Number(42.0);
+    let boolean = SelectedValue::Boolean(true);
+    let string = SelectedValue::String("42".to_string());
+
+    // Non-consuming inspection
+    assert_eq!(number.as_number(), Some(42.0));
+    assert_eq!(boolean.as_boolean(), Some(true));
+    assert_eq!(string.as_str(), Some("42"));
+    assert_eq!(number.as_str(), None);
+    assert_eq!(string.as_number(), None);
+    assert!(SelectedValue::Null.is_null());
+
+    assert_matches!(f64::try_from(number.clone()), Ok(42.0));
+    assert_matches!(bool::try_from(boolean.clone()), Ok(true));
+    assert_matches!(String::try_from(string.clone()).as_deref(), Ok("42"));
+    assert_matches!(i64::try_from(number.clone()), Ok(42));
+    assert_matches!(i32::try_from(number.clone()), Ok(42));
+    assert_matches!(u32::try_from(number.clone()), Ok(42));
+
+    // No cross-conversions, naming the expected and actual variants
+    assert_matches!(
+        f64::try_from(boolean.clone()),
+        Err(e) if e.to_string().contains("number") && e.to_string().contains("Boolean")
+    );
+    assert_matches!(f64::try_from(string.clone()), Err(_));
+    assert_matches!(bool::try_from(number.clone()), Err(_));
+    assert_matches!(String::try_from(number.clone()), Err(_));
+    assert_matches!(i64::try_from(SelectedValue::Null), Err(_));
+
+    // Integers must be held exactly
+    assert_matches!(i32::try_from(SelectedValue::Number(0.5)), Err(_));
+    assert_matches!(u32::try_from(SelectedValue::Number(-1.0)), Err(_));
+    assert_matches!(
+        u32::try_from(SelectedValue::Number(4294967295.0)),
+        Ok(u32::MAX)
+    );
+    assert_matches!(u32::try_from(SelectedValue::Number(4294967296.0)), Err(_));
+    assert_matches!(
+        i32::try_from(SelectedValue::Number(-2147483648.0)),
+        Ok(i32::MIN)
+    );
+    assert_matches!(i64::try_from(SelectedValue::Number(9.3e18)), Err(_));
+    assert_matches!(i64::try_from(SelectedValue::Number(f64::NAN)), Err(_));
+}
+
+#[test]
+fn test_selected_value_conversions_compile_fail() {
+    // 64-bit integers can only be converted through the fallible TryFrom
+    trybuild::TestCases::new().compile_fail("tests/ui/selected_value_*.rs");
+}
+
+#[cfg(not(feature = "unsafe-interop"))]
+#[test]
+fn test_native_pointers_require_feature() {
+    trybuild::TestCases::new().compile_fail("tests/ui/unsafe_interop_*.rs");
+}
+
+#[cfg(feature = "unsafe-interop")]
+#[test]
+fn test_native_entity_pointers() {
+    let mut context = TestContextBuilder::simple()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation");
+    let input = entities
+        .input
+        .expect("This test expects an available input");
+    let output = entities
+        .output
+        .expect("This test expects an available output");
+
+    {
+        let participant = context
+            .connector
+            .native_participant_ptr()
+            .expect("Failed to get the native participant");
+        assert!(!participant.as_ptr().is_null());
+    }
+    {
+        let reader = input
+            .native_datareader_ptr()
+            .expect("Failed to get the native DataReader");
+        assert!(!reader.as_ptr().is_null());
+    }
+    {
+        let writer = output
+            .native_datawriter_ptr()
+            .expect("Failed to get the native DataWriter");
+        assert!(!writer.as_ptr().is_null());
+    }
+
+    // The connector can be used again once the guards are dropped
+    assert_matches!(output.matched_subscription_count(), Ok(_));
+}
+
+#[cfg(feature = "unsafe-interop")]
+#[test]
+fn test_native_participant_ptr_same_config() {
+    // Both connectors are created from the same configuration name
+    let first = TestContextBuilder::simple()
+        .build()
+        .expect("Failed to create the first test context");
+    let second = TestContextBuilder::simple()
+        .build()
+        .expect("Failed to create the second test context");
+
+    let first_participant = first
+        .connector
+        .native_participant_ptr()
+        .expect("Failed to get the first native participant")
+        .as_ptr();
+    let second_participant = second
+        .connector
+        .native_participant_ptr()
+        .expect("Failed to get the second native participant")
+        .as_ptr();
+
+    assert_ne!(
+        first_participant, second_participant,
+        "Each connector should expose its own participant"
+    );
+
+    // Dropping one connector must not affect the other
+    drop(first);
+    assert_matches!(second.connector.native_participant_ptr(), Ok(p) if p.as_ptr() == second_participant);
+}
+
+#[cfg(feature = "async")]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_dispatch_async() -> rtiddsconnector::ConnectorFallible {
+    use rtiddsconnector::{CancelToken, DataHandler};
+    use std::{
+        collections::HashMap,
+        sync::{
+            Arc,
+            atomic::{AtomicBool, AtomicUsize, Ordering},
+        },
+    };
+
+    const SAMPLES: usize = 5;
+
+    /// A handler counting the samples, which fails if it runs concurrently with itself
+    fn counter(count: &Arc<AtomicUsize>) -> DataHandler {
+        let count = Arc::clone(count);
+        let running = Arc::new(AtomicBool::new(false));
+        Box::new(move |sample| {
+            let count = Arc::clone(&count);
+            let running = Arc::clone(&running);
+            Box::pin(async move {
+                assert!(sample.is_valid());
+                assert!(
+                    !running.swap(true, Ordering::SeqCst),
+                    "The handlers of an input should run serially"
+                );
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                running.store(false, Ordering::SeqCst);
+                count.fetch_add(1, Ordering::SeqCst);
+            })
+        })
+    }
+
+    let (connector, _globals) = TestContextBuilder::multiple_entities()
+        .build()?
+        .into_parts();
+    let mut writers = [
+        connector.get_output("TestPublisher::SimpleWriter")?,
+        connector.get_output("TestPublisher::ComplexWriter")?,
+    ];
+    for writer in &writers {
+        assert_matches!(writer.wait_for_subscriptions_with_timeout(TEST_TIMEOUT), Ok(n) if n >= 1);
+    }
+
+    let simple_count = Arc::new(AtomicUsize::new(0));
+    let complex_count = Arc::new(AtomicUsize::new(0));
+    let handlers = HashMap::from([
+        (
+            "TestSubscriber::SimpleReader".to_string(),
+            counter(&simple_count),
+        ),
+        (
+            "TestSubscriber::ComplexReader".to_string(),
+            counter(&complex_count),
+        ),
+    ]);
+
+    let cancel = CancelToken::new();
+    let dispatch = {
+        let connector = connector.clone();
+        let cancel = cancel.clone();
+        tokio::spawn(async move { connector.dispatch_async(handlers, &cancel).await })
+    };
+
+    for _ in 0..SAMPLES {
+        for writer in &mut writers {
+            writer.write()?;
+        }
+    }
+
+    let deadline = tokio::time::Instant::now() + TEST_TIMEOUT;
+    while simple_count.load(Ordering::SeqCst) < SAMPLES
+        || complex_count.load(Ordering::SeqCst) < SAMPLES
+    {
+        assert!(
+            tokio::time::Instant::now() < deadline,
+            "Timed out waiting for the handlers"
+        );
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
+    cancel.cancel();
+    assert_matches!(
+        tokio::time::timeout(Duration::from_secs(1), dispatch).await,
+        Ok(Ok(Ok(())))
+    );
+    assert_eq!(SAMPLES, simple_count.load(Ordering::SeqCst));
+    assert_eq!(SAMPLES, complex_count.load(Ordering::SeqCst));
+
+    // Dispatching released the inputs
+    connector.get_input("TestSubscriber::SimpleReader")?;
+    connector.get_input("TestSubscriber::ComplexReader")?;
+
+    Ok(())
+}
@@ -3,7 +3,7 @@ mod test_utils;
 #[macro_use]
 extern crate assert_matches;
 
-use rtiddsconnector::SelectedValue;
+use rtiddsconnector::{ConnectorError, ErrorKind, InvalidErrorKind, SelectedValue};
 
 use test_utils::TEST_TIMEOUT;
 
@@ -28,12 +28,17 @@ fn test_display_instance_and_sample() {
         input
             .wait_with_timeout(TEST_TIMEOUT)
             .expect("Failed to wait for data");
-        input.read().expect("Failed to read data");
+        assert_eq!(1, input.read().expect("Failed to read data"));
 
         (input, output)
     };
 
-    let iter = input.into_iter().valid_only();
+    assert_matches!(input.len(), Ok(1), "Input should hold the written sample");
+    assert_matches!(input.is_empty(), Ok(false), "Input should not be empty");
+
+    let iter = input
+        .valid_samples()
+        .expect("Failed to access the samples cache");
     let (_, upper_hint) = iter.size_hint();
     assert_eq!(
         1,
@@ -42,7 +47,11 @@ fn test_display_instance_and_sample() {
     );
 
     let instance = output.instance();
-    let sample = iter.take(1).next().expect("Expected a sample");
+    let sample = iter
+        .take(1)
+        .next()
+        .expect("Expected a sample")
+        .expect("Failed to check sample validity");
 
     assert_eq!(
         format!("{}", instance),
@@ -103,6 +112,7 @@ fn test_setget_value() {
     );
 
     for s in iter.take(1) {
+        let s = s.expect("Failed to check sample validity");
         // Verify data using type-variant accessors
         assert_eq!(
             SelectedValue::Number(10_f64),
@@ -185,6 +195,7 @@ fn test_setget_number() {
     );
 
     for s in iter.take(1) {
+        let s = s.expect("Failed to check sample validity");
         // Verify data using type-specific accessors
         assert_eq!(
             10_f64,
@@ -215,6 +226,67 @@ fn test_setget_number() {
 }
 
 // it('getBoolean should return a boolean', () => {
+#[test]
+fn test_generic_set() {
+    let mut context = test_utils::TestContextBuilder::simple()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
+
+    let string = "Hello".to_string();
+    let mut instance = output.instance();
+    instance
+        .set("long_field", 10)
+        .expect("Failed to set an i32");
+    instance
+        .set("double_field", 0.25_f32)
+        .expect("Failed to set an f32");
+    instance
+        .set("boolean_field", true)
+        .expect("Failed to set a bool");
+    instance
+        .set("string_field", &string)
+        .expect("Failed to set a &String");
+    instance
+        .set("enum_field", 2_u8)
+        .expect("Failed to set a u8");
+
+    // Fallible conversions are only accepted by try_set
+    instance
+        .try_set("long_field", 42_i64)
+        .expect("Failed to set an exact i64");
+    assert_matches!(instance.try_set("long_field", i64::MAX), Err(_));
+    assert_matches!(instance.try_set("long_field", u64::MAX), Err(_));
+    instance
+        .try_set("string_field", "World")
+        .expect("Failed to set a &str with try_set");
+
+    output.write().expect("Failed to write data");
+    input
+        .wait_with_timeout(TEST_TIMEOUT)
+        .expect("Failed to wait for data");
+    input.take().expect("Failed to take data");
+
+    let sample = input
+        .last_sample()
+        .expect("Failed to access samples")
+        .expect("Expected a sample");
+    assert_matches!(sample.get_number("long_field"), Ok(42.0));
+    assert_matches!(sample.get_number("double_field"), Ok(0.25));
+    assert_matches!(sample.get_boolean("boolean_field"), Ok(true));
+    assert_matches!(sample.get_string("string_field"), Ok(value) if value == "World");
+    assert_matches!(sample.get_number("enum_field"), Ok(2.0));
+}
+
 #[test]
 fn test_setget_boolean() {
     let mut context = test_utils::TestContextBuilder::simple()
@@ -261,6 +333,7 @@ fn test_setget_boolean() {
     );
 
     for s in iter.take(1) {
+        let s = s.expect("Failed to check sample validity");
         // Verify data using type-specific accessors
         assert!(
             s.get_boolean("boolean_field")
@@ -327,6 +400,7 @@ fn test_setget_string() {
     );
 
     for s in iter.take(1) {
+        let s = s.expect("Failed to check sample validity");
         // Verify data using type-specific accessors
         assert_eq!(
             "Hello",
@@ -348,6 +422,20 @@ fn test_setget_string() {
             s.get_string("enum_field") // Implicit conversion
                 .expect("Failed 'get_string' operation on 'enum_field'")
         );
+
+        // The buffer's contents are replaced, keeping its allocation
+        let mut buffer = String::with_capacity(64);
+        buffer.push_str("previous contents");
+        let capacity = buffer.capacity();
+        s.get_string_into("string_field", &mut buffer)
+            .expect("Failed 'get_string_into' operation on 'string_field'");
+        assert_eq!("Hello", buffer);
+        assert_eq!(capacity, buffer.capacity());
+        assert_matches!(
+            s.get_string_into("non_existent_field", &mut buffer),
+            Err(e) if e.is_field_not_found()
+        );
+        assert_eq!("Hello", buffer, "A failed read leaves the buffer untouched");
     }
 }
 
@@ -397,6 +485,11 @@ fn test_setget_invalid_field_or_value() {
                 Err(e) if e.is_field_not_found(),
                 "Unexpected OK when setting non-existent field using 'set_value'"
             );
+            assert_matches!(
+                instance.set_string("long_field", "not a number"),
+                Err(e) if e.is_type_mismatch(),
+                "Unexpected OK when setting a string into 'long_field'"
+            );
         }
         output.write().expect("Failed to write data");
 
@@ -418,6 +511,7 @@ fn test_setget_invalid_field_or_value() {
     );
 
     for s in iter.take(1) {
+        let s = s.expect("Failed to check sample validity");
         // Verify that valid fields are still unset (default values)
         assert_eq!(
             0.0,
@@ -458,6 +552,11 @@ fn test_setget_invalid_field_or_value() {
             Err(e) if e.is_field_not_found(),
             "Expected error for non-existent field"
         );
+        assert_matches!(
+            s.get_boolean("string_field"),
+            Err(e) if e.is_type_mismatch(),
+            "Expected a type mismatch getting 'string_field' as a boolean"
+        );
     }
 }
 
@@ -496,7 +595,11 @@ fn test_get_info_fields() {
         "Expected only one sample"
     );
 
-    let sample = iter.take(1).next().expect("Expected a sample");
+    let sample = iter
+        .take(1)
+        .next()
+        .expect("Expected a sample")
+        .expect("Failed to check sample validity");
 
     // Test that unknown fields return an error
     assert_matches!(
@@ -510,6 +613,36 @@ fn test_get_info_fields() {
         "Expected error for unknown info field with get_info_json"
     );
 
+    // The provided info fields include at least the documented ones
+    let names = sample
+        .info_field_names()
+        .expect("Failed to get the info field names");
+    for name in [
+        "valid_data",
+        "source_timestamp",
+        "reception_timestamp",
+        "sample_identity",
+        "related_sample_identity",
+        "sample_state",
+        "view_state",
+        "instance_state",
+    ] {
+        assert!(
+            names.iter().any(|provided| provided == name),
+            "Expected '{}' in the info field names {:?}",
+            name,
+            names
+        );
+    }
+    for name in &names {
+        assert_matches!(
+            sample.get_info(name),
+            Ok(_),
+            "Expected the '{}' info field to be accessible",
+            name
+        );
+    }
+
     // Test valid_data field
     assert_matches!(
         sample.get_info("valid_data"),
@@ -529,9 +662,9 @@ fn test_get_info_fields() {
     let json = sample
         .get_info_json("source_timestamp")
         .expect("Expected 'source_timestamp' to be available via get_info_json");
-    assert_matches!(
-        selected,
-        SelectedValue::String(value) if value == json,
+    assert_eq!(
+        Some(json.as_str()),
+        selected.as_str(),
         "Expected 'source_timestamp' to be a string"
     );
 
@@ -542,9 +675,9 @@ fn test_get_info_fields() {
     let json = sample
         .get_info_json("reception_timestamp")
         .expect("Expected 'reception_timestamp' to be available via get_info_json");
-    assert_matches!(
-        selected,
-        SelectedValue::String(value) if value == json,
+    assert_eq!(
+        Some(json.as_str()),
+        selected.as_str(),
         "Expected 'reception_timestamp' to be a string"
     );
 
@@ -555,9 +688,9 @@ fn test_get_info_fields() {
     let json = sample
         .get_info_json("sample_identity")
         .expect("Expected 'sample_identity' to be available via get_info_json");
-    assert_matches!(
-        selected,
-        SelectedValue::String(value) if value == json,
+    assert_eq!(
+        Some(json.as_str()),
+        selected.as_str(),
         "Expected 'sample_identity' to be a string"
     );
 
@@ -568,9 +701,9 @@ fn test_get_info_fields() {
     let json = sample
         .get_info_json("related_sample_identity")
         .expect("Expected 'related_sample_identity' to be available via get_info_json");
-    assert_matches!(
-        selected,
-        SelectedValue::String(value) if value == json,
+    assert_eq!(
+        Some(json.as_str()),
+        selected.as_str(),
         "Expected 'related_sample_identity' to be a string"
     );
 
@@ -581,9 +714,9 @@ fn test_get_info_fields() {
     let json = sample
         .get_info_json("sample_state")
         .expect("Expected 'sample_state' to be available via get_info_json");
-    assert_matches!(
-        selected,
-        SelectedValue::String(value) if value == json,
+    assert_eq!(
+        Some(json.as_str()),
+        selected.as_str(),
         "Expected 'sample_state' to be a string"
     );
 
@@ -594,9 +727,9 @@ fn test_get_info_fields() {
     let json = sample
         .get_info_json("view_state")
         .expect("Expected 'view_state' to be available via get_info_json");
-    assert_matches!(
-        selected,
-        SelectedValue::String(value) if value == json,
+    assert_eq!(
+        Some(json.as_str()),
+        selected.as_str(),
         "Expected 'view_state' to be a string"
     );
 
@@ -607,439 +740,445 @@ fn test_get_info_fields() {
     let json = sample
         .get_info_json("instance_state")
         .expect("Expected 'instance_state' to be available via get_info_json");
-    assert_matches!(
-        selected,
-        SelectedValue::String(value) if value == json,
+    assert_eq!(
+        Some(json.as_str()),
+        selected.as_str(),
         "Expected 'instance_state' to be a string"
     );
 }
 
-// it('getString requires a valid index', () => {
-// it('getBoolean requires a valid index', () => {
-// it('getValue requires a valid index', () => {
 #[test]
-#[ignore = "index access is internal to the Input and not yet exposed"]
-fn test_setget_by_index() {}
+fn test_samples_with_info() {
+    use rtiddsconnector::{InstanceState, SampleState, ViewState};
 
-// it('access a value nested within a struct', () => {
-#[test]
-fn test_output_access_a_value_nested_within_a_struct() {
-    let mut context = test_utils::TestContextBuilder::complex()
+    let mut context = test_utils::TestContextBuilder::simple()
         .build()
         .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
 
-    let input = {
-        let entities = context
-            .test_entities()
-            .expect("Error in test entities creation")
-            .ensure_discovery();
-        let mut output = entities
-            .output
-            .expect("This test expects an available output");
-        let mut input = entities
-            .input
-            .expect("This test expects an available input");
-
-        {
-            let mut instance = output.instance();
-            instance
-                .set_number("simple.long_field", 10_f64)
-                .expect("Failed to set simple.long_field");
-            instance
-                .set_number("simple.double_field", 123.45)
-                .expect("Failed to set simple.double_field");
-            instance
-                .set_string("simple.string_field", "Hello")
-                .expect("Failed to set simple.string_field");
-            instance
-                .set_boolean("simple.boolean_field", true)
-                .expect("Failed to set simple.boolean_field");
-        }
-        output.write().expect("Failed to write data");
-
-        input
-            .wait_with_timeout(TEST_TIMEOUT)
-            .expect("Failed to wait for data");
-        input.read().expect("Failed to read data");
+    output.write().expect("Failed to write data");
+    input
+        .wait_with_timeout(TEST_TIMEOUT)
+        .expect("Failed to wait for data");
+    input.take().expect("Failed to take data");
 
-        input
+    let (sample, info) = input
+        .samples()
+        .expect("Failed to access the samples cache")
+        .with_info()
+        .next()
+        .expect("Expected a sample");
+
+    assert_matches!(sample.is_valid(), Ok(true));
+    assert_eq!(Some(true), info.valid_data);
+    let (Some(source_timestamp), Some(reception_timestamp), Some(identity)) = (
+        info.source_timestamp,
+        info.reception_timestamp,
+        info.identity.clone(),
+    ) else {
+        panic!(
+            "Expected the timestamps and identity of the sample: {:?}",
+            info
+        );
     };
+    assert!(
+        source_timestamp <= reception_timestamp,
+        "The sample should be received after being sent"
+    );
+    assert_eq!(None, info.related_identity, "No related sample was set");
+    assert_eq!(Some(InstanceState::Alive), info.instance_state);
+    assert_eq!(Some(ViewState::New), info.view_state);
+    assert_eq!(Some(SampleState::NotRead), info.sample_state);
 
-    let iter = input.into_iter().valid_only();
-    let (_, upper_hint) = iter.size_hint();
-
+    // The parsed values must match the raw info fields
+    let raw_timestamp = sample
+        .get_info_json("source_timestamp")
+        .expect("Failed to get source_timestamp");
     assert_eq!(
-        1,
-        upper_hint.expect("Our implementation always yields a upper hint"),
-        "Expected only one sample"
+        raw_timestamp.trim_matches('"').parse::<i64>().ok(),
+        Some(source_timestamp)
+    );
+    let raw_identity: serde_json::Value = serde_json::from_str(
+        &sample
+            .get_info_json("sample_identity")
+            .expect("Failed to get sample_identity"),
+    )
+    .expect("Failed to parse sample_identity");
+    assert_eq!(
+        raw_identity["writer_guid"],
+        serde_json::json!(identity.writer_guid)
+    );
+    assert_eq!(
+        raw_identity["sequence_number"],
+        serde_json::json!(identity.sequence_number)
+    );
+    assert_matches!(
+        sample.info(),
+        Ok(same) if rtiddsconnector::LenientSampleInfo::from(same.clone()) == info
     );
 
-    for s in iter.take(1) {
-        // Verify data using type-variant accessors
-        assert_eq!(
-            SelectedValue::Number(10_f64),
-            s.get_value("simple.long_field")
-                .expect("Failed 'get_value' operation on 'simple.long_field'")
-        );
-        assert_eq!(
-            SelectedValue::Number(123.45),
-            s.get_value("simple.double_field")
-                .expect("Failed 'get_value' operation on 'simple.double_field'")
-        );
-        assert_eq!(
-            SelectedValue::String("Hello".to_string()),
-            s.get_value("simple.string_field")
-                .expect("Failed 'get_value' operation on 'simple.string_field'")
-        );
-        assert_eq!(
-            SelectedValue::Boolean(true),
-            s.get_value("simple.boolean_field")
-                .expect("Failed 'get_value' operation on 'simple.boolean_field'")
-        );
+    // Disposing produces an invalid sample, whose info is still available
+    output
+        .write_with_params(&rtiddsconnector::WriteParams::dispose())
+        .expect("Failed to write with dispose parameters");
+    input
+        .wait_with_timeout(TEST_TIMEOUT)
+        .expect("Failed to wait for data after dispose");
+    input.take().expect("Failed to take data after dispose");
 
-        // Verify data using type-specific accessors
-        assert_eq!(
-            10_f64,
-            s.get_number("simple.long_field")
-                .expect("Failed 'get_number' operation on 'simple.long_field'")
-        );
-        assert_eq!(
-            123.45,
-            s.get_number("simple.double_field")
-                .expect("Failed 'get_number' operation on 'simple.double_field'")
-        );
-        assert_eq!(
-            "Hello",
-            s.get_string("simple.string_field")
-                .expect("Failed 'get_string' operation on 'simple.string_field'")
-        );
-        assert!(
-            s.get_boolean("simple.boolean_field")
-                .expect("Failed 'get_boolean' operation on 'simple.boolean_field'")
-        );
-    }
-}
+    let (_, info) = input
+        .samples()
+        .expect("Failed to access the samples cache")
+        .with_info()
+        .next()
+        .expect("Expected a sample");
 
-fn combinatorial(range: std::ops::Range<usize>) -> impl Iterator<Item = (usize, usize)> {
-    (range)
-        .clone()
-        .flat_map(move |r| range.clone().map(move |c| (r, c)))
+    assert_eq!(Some(false), info.valid_data);
+    assert_eq!(Some(InstanceState::NotAliveDisposed), info.instance_state);
 }
 
-// it('access values and sizes of sequences and arrays', () => {
-// it('access values past the end of a sequence', () => {
-// it('attempt to access members with bad sequence syntax', () => {
-// it('attempt to access the negative member of a sequence', () => {
 #[test]
-fn test_setget_array_and_sequences() {
-    const MATRIX_SIZE: usize = 3;
-    const SEQUENCE_SIZE: usize = 3;
-
-    let mut context = test_utils::TestContextBuilder::complex()
+fn test_samples_guard() {
+    let mut context = test_utils::TestContextBuilder::simple()
         .build()
         .expect("Failed to create test context");
-    let input = {
-        let entities = context
-            .test_entities()
-            .expect("Error in test entities creation")
-            .ensure_discovery();
-        let mut output = entities
-            .output
-            .expect("This test expects an available output");
-        let mut input = entities
-            .input
-            .expect("This test expects an available input");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
 
-        {
-            let mut instance = output.instance();
-            for (row, col) in combinatorial(0..MATRIX_SIZE) {
-                let value = (row + col) as f64;
-                // Flat indexing
-                instance
-                    .set_number(&format!("long_matrix[{row},{col}]"), value)
-                    .expect("Failed to set long_matrix");
-                // Nested indexing
-                // TODO: Review nested indexing
-                // instance.set_number(&format!("long_matrix[{row}][{col}]"), value)?;
-            }
+    output.write().expect("Failed to write data");
+    input
+        .wait_with_timeout(TEST_TIMEOUT)
+        .expect("Failed to wait for data");
 
-            // Out of bounds access should be an error
-            assert_matches!(
-                instance.set_number(
-                    &format!("long_matrix[{MATRIX_SIZE},{MATRIX_SIZE}]"),
-                    0.0
-                ),
-                Err(_),
-                "Expected error for out-of-bounds access"
-            );
+    {
+        let guard = input.take_guard().expect("Failed to take data");
+        assert_matches!(guard.len(), Ok(1), "Guard should expose the taken sample");
+        assert_eq!(1, (&guard).into_iter().count());
+        assert_matches!(guard.finish(), Ok(()), "Returning the loan should succeed");
+    }
 
-            for index in 0..SEQUENCE_SIZE {
-                instance
-                    .set_number(
-                        &format!("double_sequence[{index}]"),
-                        123.45 * (index + 1) as f64,
-                    )
-                    .expect("Failed to set double_sequence");
-            }
-
-            // Out of bounds access should be an error
-            assert_matches!(
-                instance.set_number(&format!("double_sequence[{SEQUENCE_SIZE}]"), 0.0),
-                Err(_),
-                "Expected error for out-of-bounds access"
-            );
-        }
-        output.write().expect("Failed to write data");
-
-        input
-            .wait_with_timeout(TEST_TIMEOUT)
-            .expect("Failed to wait for data");
-        input.read().expect("Failed to read data");
+    output.write().expect("Failed to write data");
+    input
+        .wait_with_timeout(TEST_TIMEOUT)
+        .expect("Failed to wait for data");
 
-        input
-    };
+    {
+        let guard = input.read_guard().expect("Failed to read data");
+        assert_matches!(guard.len(), Ok(1), "Guard should expose the read sample");
+        // The loan is returned on drop
+    }
 
-    let iter = input.into_iter().valid_only();
-    let (_, upper_hint) = iter.size_hint();
+    // Returning the loan again is harmless
+    assert_matches!(input.return_loan(), Ok(()));
 
-    assert_eq!(
-        1,
-        upper_hint.expect("Our implementation always yields a upper hint"),
-        "Expected only one sample"
+    let guard = input.take_guard().expect("Failed to take data");
+    assert_matches!(
+        guard.is_empty(),
+        Ok(false),
+        "Read samples can still be taken"
     );
+    drop(guard);
 
-    for s in iter.take(1) {
-        for (row, col) in combinatorial(0..MATRIX_SIZE) {
-            let value = (row + col) as f64;
-            let flat_field = format!("long_matrix[{row},{col}]");
-            // TODO: Review nested indexing
-            // let nested_field = format!("long_matrix[{row}][{col}]");
-
-            // Verify data using type-variant accessors
-            assert_eq!(
-                value,
-                s.get_number(&flat_field)
-                    .expect("Failed 'get_number' operation on 'long_matrix[row,col]'")
-            );
-            // assert_eq!(
-            //     value,
-            //     s.get_number(&nested_field)
-            //         .expect("Failed 'get_number' operation on 'long_matrix[row][col]'")
-            // );
-
-            // Verify data using type-specific accessors
-            assert_eq!(
-                SelectedValue::Number(value),
-                s.get_value(&flat_field)
-                    .expect("Failed 'get_value' operation on 'long_matrix[row,col]'")
-            );
-            // assert_eq!(
-            //     SelectedValue::Number(value),
-            //     s.get_value(&nested_field)
-            //         .expect("Failed 'get_value' operation on 'long_matrix[row][col]'")
-            // );
-        }
-
-        // Out of bounds access should be an error
-        assert_matches!(
-            s.get_number("long_matrix[-1][-1]"),
-            Err(_),
-            "Expected error for out-of-bounds access (negative index)"
-        );
-        assert_matches!(
-            s.get_number("long_matrix[bad,index]"),
-            Err(_),
-            "Expected error for out-of-bounds access (non-numeric index)"
-        );
-        assert_matches!(
-            s.get_number(&format!("long_matrix[{MATRIX_SIZE},{MATRIX_SIZE}]")),
-            Err(_),
-            "Expected error for out-of-bounds access"
-        );
-
-        for index in 0..SEQUENCE_SIZE {
-            let value = 123.45 * (index + 1) as f64;
-            let field = format!("double_sequence[{index}]");
-
-            // Verify data using type-variant accessors
-            assert_eq!(
-                value,
-                s.get_number(&field)
-                    .expect("Failed 'get_number' operation on 'double_sequence[index]'")
-            );
-
-            // Verify data using type-specific accessors
-            assert_eq!(
-                SelectedValue::Number(value),
-                s.get_value(&field)
-                    .expect("Failed 'get_value' operation on 'double_sequence[index]'")
-            );
-        }
-
-        // Out of bounds access should be an error
-        assert_matches!(
-            s.get_number("double_sequence[-1]"),
-            Err(_),
-            "Expected error for out-of-bounds access (negative index)"
-        );
-        assert_matches!(
-            s.get_number("double_sequence[bad_index]"),
-            Err(_),
-            "Expected error for out-of-bounds access (negative index)"
-        );
-        assert_matches!(
-            s.get_number(&format!("double_sequence[{SEQUENCE_SIZE}]")),
-            Err(_),
-            "Expected error for out-of-bounds access"
-        );
-    }
+    let guard = input.take_guard().expect("Failed to take data");
+    assert_matches!(guard.is_empty(), Ok(true), "No samples should be left");
+    assert_matches!(guard.finish(), Ok(()));
 }
 
-// it('obtain the selected member of a union with # syntax', () => {
-// it('getNumber on unions', () => {
+// it('getString requires a valid index', () => {
+// it('getBoolean requires a valid index', () => {
+// it('getValue requires a valid index', () => {
 #[test]
-fn test_setget_unions() {
-    let mut context = test_utils::TestContextBuilder::complex()
+fn test_setget_by_index() {
+    let mut context = test_utils::TestContextBuilder::simple()
         .build()
         .expect("Failed to create test context");
-    let input = {
-        let entities = context
-            .test_entities()
-            .expect("Error in test entities creation")
-            .ensure_discovery();
-        let mut output = entities
-            .output
-            .expect("This test expects an available output");
-        let mut input = entities
-            .input
-            .expect("This test expects an available input");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
 
-        {
-            let mut instance = output.instance();
-            instance
-                .set_string("union.string", "Hello")
-                .expect("Failed to set union.string");
-        }
+    assert_matches!(
+        input.last_sample(),
+        Ok(None),
+        "Empty Input has no last sample"
+    );
+    assert_matches!(
+        input.sample_at(0),
+        Err(_),
+        "Empty Input should reject any index"
+    );
+
+    for value in [1_f64, 2_f64] {
         output
-            .write()
-            .expect("Failed to write data for 'union# == string'");
+            .instance()
+            .set_number("long_field", value)
+            .expect("Failed to set long_field");
+        output.write().expect("Failed to write data");
+    }
 
+    while input.len().expect("Failed to get sample count") < 2 {
         input
             .wait_with_timeout(TEST_TIMEOUT)
             .expect("Failed to wait for data");
         input.read().expect("Failed to read data");
+    }
 
-        {
-            let mut instance = output.instance();
-            instance
-                .set_number("union.number", 123.45)
-                .expect("Failed to set union.number");
-        }
-        output
-            .write()
-            .expect("Failed to write data for 'union# == number'");
+    let first = input.sample_at(0).expect("Index 0 should be valid");
+    assert_matches!(first.get_number("long_field"), Ok(1_f64));
+    assert_matches!(first.get_string("string_field"), Ok(_));
+    assert_matches!(first.get_boolean("boolean_field"), Ok(false));
+    assert_matches!(
+        first.get_value("long_field"),
+        Ok(SelectedValue::Number(1_f64))
+    );
 
-        input
-            .wait_with_timeout(TEST_TIMEOUT)
-            .expect("Failed to wait for data");
-        input.read().expect("Failed to read data");
+    let last = input
+        .last_sample()
+        .expect("Failed to get last sample")
+        .expect("Input should have a last sample");
+    assert_matches!(last.get_number("long_field"), Ok(2_f64));
 
-        {
-            let mut instance = output.instance();
-            instance
-                .set_boolean("union.boolean", true)
-                .expect("Failed to set union.boolean");
-        }
+    assert_matches!(
+        input.sample_at(2),
+        Err(e) if e.to_string().contains("out of range"),
+        "Out of range index should be rejected"
+    );
+}
+
+#[test]
+fn test_read_and_take_return_count() {
+    const SAMPLES: usize = 3;
+
+    let mut context = test_utils::TestContextBuilder::simple()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
+
+    assert_matches!(input.take(), Ok(0), "No samples should be available yet");
+
+    for value in 0..SAMPLES {
         output
-            .write()
-            .expect("Failed to write data for 'union# == boolean'");
+            .instance()
+            .set_number("long_field", value as f64)
+            .expect("Failed to set long_field");
+        output.write().expect("Failed to write data");
+    }
 
+    // Reading leaves the samples in the DataReader, so the count eventually reaches all of them
+    let deadline = std::time::Instant::now() + TEST_TIMEOUT;
+    while input.read().expect("Failed to read data") < SAMPLES {
+        assert!(
+            std::time::Instant::now() < deadline,
+            "Timed out waiting for all samples"
+        );
         input
             .wait_with_timeout(TEST_TIMEOUT)
             .expect("Failed to wait for data");
-        input.read().expect("Failed to read data");
+    }
 
-        input
-    };
+    assert_matches!(input.take(), Ok(SAMPLES), "Take should obtain all samples");
+    assert_matches!(
+        input.take(),
+        Ok(0),
+        "Taken samples should not be obtained again"
+    );
+}
 
-    let iter = input.into_iter().valid_only();
-    let (_, upper_hint) = iter.size_hint();
+#[test]
+fn test_wait_and_take() {
+    let mut context = test_utils::TestContextBuilder::simple()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
 
-    assert_eq!(
-        3,
-        upper_hint.expect("Our implementation always yields a upper hint"),
-        "Expected only three samples"
+    output.write().expect("Failed to write data");
+    assert_matches!(
+        input.wait_and_take(Some(TEST_TIMEOUT)),
+        Ok(1),
+        "wait_and_take should take the written sample"
     );
 
-    for s in iter.take(3) {
-        let discriminator = s
-            .get_string("union#")
-            .expect("Failed to get 'union#' value");
+    output.write().expect("Failed to write data");
+    assert_matches!(
+        input.wait_and_take_until_timeout(TEST_TIMEOUT),
+        Ok(1),
+        "wait_and_take_until_timeout should take the written sample"
+    );
+}
 
-        match discriminator.as_str() {
-            "string" => {
-                assert_eq!(
-                    "Hello",
-                    s.get_string("union.string")
-                        .expect("Failed 'get_string' operation on 'union.string'")
-                );
-                assert_matches!(
-                    s.get_value("union.number"),
-                    Err(_),
-                    "Expected error for inactive union member 'union.number'"
-                );
-                assert_matches!(
-                    s.get_value("union.boolean"),
-                    Err(_),
-                    "Expected error for inactive union member 'union.boolean'"
-                );
-            }
-            "number" => {
-                assert_eq!(
-                    123.45,
-                    s.get_number("union.number")
-                        .expect("Failed 'get_number' operation on 'union.number'")
-                );
-                assert_matches!(
-                    s.get_value("union.string"),
-                    Err(_),
-                    "Expected error for inactive union member 'union.string'"
-                );
-                assert_matches!(
-                    s.get_value("union.boolean"),
-                    Err(_),
-                    "Expected error for inactive union member 'union.boolean'"
-                );
-            }
-            "boolean" => {
-                assert!(
-                    s.get_boolean("union.boolean")
-                        .expect("Failed 'get_boolean' operation on 'union.boolean'")
-                );
-                assert_matches!(
-                    s.get_value("union.string"),
-                    Err(_),
-                    "Expected error for inactive union member 'union.string'"
-                );
-                assert_matches!(
-                    s.get_value("union.number"),
-                    Err(_),
-                    "Expected error for inactive union member 'union.number'"
-                );
-            }
-            _ => panic!("Unexpected union member"),
-        }
+#[test]
+fn test_take_one() {
+    let mut context = test_utils::TestContextBuilder::simple()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
+
+    assert_matches!(input.take_one(), Ok(None), "No sample should be available");
+
+    output
+        .instance()
+        .set_number("long_field", 5_f64)
+        .expect("Failed to set long_field");
+    output.write().expect("Failed to write data");
+    input
+        .wait_with_timeout(TEST_TIMEOUT)
+        .expect("Failed to wait for data");
+
+    let data = input
+        .take_one()
+        .expect("Failed to take one sample")
+        .expect("Expected a valid sample");
+    assert_eq!(5, data["long_field"], "Unexpected long_field value");
+
+    // Disposing only produces an invalid sample
+    output
+        .write_with_params(&rtiddsconnector::WriteParams::dispose())
+        .expect("Failed to write with dispose parameters");
+    input
+        .wait_with_timeout(TEST_TIMEOUT)
+        .expect("Failed to wait for data after dispose");
+
+    assert_matches!(
+        input.take_one(),
+        Ok(None),
+        "Invalid samples should not be returned"
+    );
+}
+
+#[test]
+fn test_take_deserialized() {
+    use test_utils::types::SimpleStruct;
+
+    /// A type which can't be deserialized from a SimpleStruct sample
+    #[derive(Debug, serde::Deserialize)]
+    #[allow(dead_code)]
+    struct MismatchedStruct {
+        missing_field: i32,
     }
+
+    let mut context = test_utils::TestContextBuilder::simple()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
+
+    let write_and_wait = |output: &mut rtiddsconnector::Output,
+                          input: &rtiddsconnector::Input| {
+        output.write().expect("Failed to write data");
+        input
+            .wait_with_timeout(TEST_TIMEOUT)
+            .expect("Failed to wait for data");
+    };
+
+    write_and_wait(&mut output, &input);
+    assert_matches!(
+        input.take_deserialized::<SimpleStruct>(),
+        Ok(data) if data.len() == 1,
+        "Expected one deserialized sample"
+    );
+
+    write_and_wait(&mut output, &input);
+    let error = input
+        .take_deserialized::<MismatchedStruct>()
+        .expect_err("Expected a deserialization error");
+    assert!(
+        error.to_string().contains("Sample #0"),
+        "Error should report the index of the offending sample"
+    );
+    assert_eq!(error.clone().to_string(), error.to_string());
+    assert!(
+        std::iter::successors(std::error::Error::source(&error), |e| e.source())
+            .any(|e| e.is::<serde_json::Error>()),
+        "The serde error should be kept as the source: {:?}",
+        error
+    );
+
+    write_and_wait(&mut output, &input);
+    assert_matches!(
+        input.take_deserialized_lossy::<MismatchedStruct>(),
+        Ok((data, errors)) if data.is_empty() && errors.len() == 1,
+        "Offending samples should be skipped and reported"
+    );
+
+    // Disposing only produces an invalid sample, which is skipped without error
+    output
+        .write_with_params(&rtiddsconnector::WriteParams::dispose())
+        .expect("Failed to write with dispose parameters");
+    input
+        .wait_with_timeout(TEST_TIMEOUT)
+        .expect("Failed to wait for data after dispose");
+    assert_matches!(
+        input.take_deserialized::<SimpleStruct>(),
+        Ok(data) if data.is_empty(),
+        "Invalid samples should be skipped"
+    );
 }
 
-// it('obtain an unset optional member', () => {
-// it('obtain an unset optional member as a string', () => {
-// it('obtain an unset optional complex member', () => {
+// it('access a value nested within a struct', () => {
 #[test]
-fn test_unset_optional_members() {
+fn test_output_access_a_value_nested_within_a_struct() {
     let mut context = test_utils::TestContextBuilder::complex()
         .build()
         .expect("Failed to create test context");
+
     let input = {
         let entities = context
             .test_entities()
@@ -1055,21 +1194,17 @@ fn test_unset_optional_members() {
         {
             let mut instance = output.instance();
             instance
-                .set_number("optional.long_field", 10_f64)
-                .expect("Failed to set optional.long_field");
-            instance
-                .set_number("optional.double_field", 123.45)
-                .expect("Failed to set optional.double_field");
+                .set_number("simple.long_field", 10_f64)
+                .expect("Failed to set simple.long_field");
             instance
-                .set_boolean("optional.boolean_field", true)
-                .expect("Failed to set optional.boolean_field");
-            // Explicitly clear optional fields
+                .set_number("simple.double_field", 123.45)
+                .expect("Failed to set simple.double_field");
             instance
-                .clear("optional.string_field")
-                .expect("Failed to clear optional.string_field");
+                .set_string("simple.string_field", "Hello")
+                .expect("Failed to set simple.string_field");
             instance
-                .clear("optional.enum_field")
-                .expect("Failed to clear optional.enum_field");
+                .set_boolean("simple.boolean_field", true)
+                .expect("Failed to set simple.boolean_field");
         }
         output.write().expect("Failed to write data");
 
@@ -1091,36 +1226,109 @@ fn test_unset_optional_members() {
     );
 
     for s in iter.take(1) {
-        // Verify that optional fields are unset (default values)
+        let s = s.expect("Failed to check sample validity");
+        // Verify data using type-variant accessors
+        assert_eq!(
+            SelectedValue::Number(10_f64),
+            s.get_value("simple.long_field")
+                .expect("Failed 'get_value' operation on 'simple.long_field'")
+        );
+        assert_eq!(
+            SelectedValue::Number(123.45),
+            s.get_value("simple.double_field")
+                .expect("Failed 'get_value' operation on 'simple.double_field'")
+        );
+        assert_eq!(
+            SelectedValue::String("Hello".to_string()),
+            s.get_value("simple.string_field")
+                .expect("Failed 'get_value' operation on 'simple.string_field'")
+        );
+        assert_eq!(
+            SelectedValue::Boolean(true),
+            s.get_value("simple.boolean_field")
+                .expect("Failed 'get_value' operation on 'simple.boolean_field'")
+        );
+
+        // Verify data using type-specific accessors
         assert_eq!(
             10_f64,
-            s.get_number("optional.long_field")
-                .expect("Failed 'get_number' operation on 'optional.long_field'")
+            s.get_number("simple.long_field")
+                .expect("Failed 'get_number' operation on 'simple.long_field'")
         );
         assert_eq!(
             123.45,
-            s.get_number("optional.double_field")
-                .expect("Failed 'get_number' operation on 'optional.double_field'")
+            s.get_number("simple.double_field")
+                .expect("Failed 'get_number' operation on 'simple.double_field'")
+        );
+        assert_eq!(
+            "Hello",
+            s.get_string("simple.string_field")
+                .expect("Failed 'get_string' operation on 'simple.string_field'")
         );
         assert!(
-            s.get_boolean("optional.boolean_field")
-                .expect("Failed 'get_boolean' operation on 'optional.boolean_field'")
+            s.get_boolean("simple.boolean_field")
+                .expect("Failed 'get_boolean' operation on 'simple.boolean_field'")
         );
-        assert_matches!(
-            s.get_string("optional.string_field"),
-            Err(_),
-            "Expected error for unset optional string field"
+
+        // Fetching several fields at once matches the individual getters
+        let fields = [
+            "simple.long_field",
+            "simple.double_field",
+            "simple.string_field",
+            "simple.boolean_field",
+            "simple",
+        ];
+        let individual: Vec<SelectedValue> = fields
+            .iter()
+            .map(|field| s.get_value(field).expect("Failed 'get_value' operation"))
+            .collect();
+        assert_matches!(individual[4], SelectedValue::Json(_));
+        assert_eq!(
+            individual,
+            s.get_fields(&fields)
+                .expect("Failed 'get_fields' operation")
         );
+
+        let map = s
+            .get_fields_map(&fields)
+            .expect("Failed 'get_fields_map' operation");
+        assert_eq!(fields.len(), map.len());
+        for (field, value) in fields.iter().zip(&individual) {
+            assert_eq!(Some(value), map.get(*field));
+        }
+
         assert_matches!(
-            s.get_value("optional.enum_field"),
-            Err(_),
-            "Expected error for unset optional enum field"
+            s.get_fields(&["simple.long_field", "non_existent_field"]),
+            Err(e) if e.is_field_not_found()
         );
     }
 }
 
+fn combinatorial(range: std::ops::Range<usize>) -> impl Iterator<Item = (usize, usize)> {
+    (range)
+        .clone()
+        .flat_map(move |r| range.clone().map(move |c| (r, c)))
+}
+
+fn is_invalid_argument(error: &ConnectorError) -> bool {
+    matches!(
+        error.kind(),
+        ErrorKind::Invalid {
+            what: InvalidErrorKind::Argument,
+            ..
+        }
+    )
+}
+
+// it('access values and sizes of sequences and arrays', () => {
+// it('access values past the end of a sequence', () => {
+// it('attempt to access members with bad sequence syntax', () => {
+// it('attempt to access the negative member of a sequence', () => {
 #[test]
-fn test_sample_get_json() {
+fn test_setget_array_and_sequences() {
+    const MATRIX_SIZE: usize = 3;
+    const SEQUENCE_SIZE: usize = 3;
+
     let mut context = test_utils::TestContextBuilder::complex()
         .build()
         .expect("Failed to create test context");
@@ -1136,94 +1344,3532 @@ fn test_sample_get_json() {
             .input
             .expect("This test expects an available input");
 
-        let mut instance = output.instance();
-        instance
-            .set_number("simple.long_field", 10_f64)
-            .expect("Failed to set field");
-        instance
-            .set_number("simple.double_field", 123.45)
-            .expect("Failed to set field");
-        instance
-            .set_number("simple.enum_field", 1.0)
-            .expect("Failed to set field");
-        instance
-            .set_string("simple.string_field", "Hello")
-            .expect("Failed to set field");
-        instance
-            .set_boolean("simple.boolean_field", true)
-            .expect("Failed to set field");
-        output.write().expect("Failed to write data");
+        {
+            let mut instance = output.instance();
+            for (row, col) in combinatorial(0..MATRIX_SIZE) {
+                let value = (row + col) as f64;
+                // Flat indexing
+                instance
+                    .set_number(&format!("long_matrix[{row},{col}]"), value)
+                    .expect("Failed to set long_matrix");
+                // Nested indexing
+                // TODO: Review nested indexing
+                // instance.set_number(&format!("long_matrix[{row}][{col}]"), value)?;
+            }
+
+            // Out of bounds access should be an error
+            assert_matches!(
+                instance.set_number(
+                    &format!("long_matrix[{MATRIX_SIZE},{MATRIX_SIZE}]"),
+                    0.0
+                ),
+                Err(e) if e.is_out_of_bounds(),
+                "Expected error for out-of-bounds access"
+            );
+
+            for index in 0..SEQUENCE_SIZE {
+                instance
+                    .set_number(
+                        &format!("double_sequence[{index}]"),
+                        123.45 * (index + 1) as f64,
+                    )
+                    .expect("Failed to set double_sequence");
+            }
+
+            // Out of bounds access should be an error
+            assert_matches!(
+                instance.set_number(&format!("double_sequence[{SEQUENCE_SIZE}]"), 0.0),
+                Err(e) if e.is_out_of_bounds(),
+                "Expected error for out-of-bounds access"
+            );
+        }
+        output.write().expect("Failed to write data");
+
+        input
+            .wait_with_timeout(TEST_TIMEOUT)
+            .expect("Failed to wait for data");
+        input.read().expect("Failed to read data");
+
+        input
+    };
+
+    let iter = input.into_iter().valid_only();
+    let (_, upper_hint) = iter.size_hint();
+
+    assert_eq!(
+        1,
+        upper_hint.expect("Our implementation always yields a upper hint"),
+        "Expected only one sample"
+    );
+
+    for s in iter.take(1) {
+        let s = s.expect("Failed to check sample validity");
+        for (row, col) in combinatorial(0..MATRIX_SIZE) {
+            let value = (row + col) as f64;
+            let flat_field = format!("long_matrix[{row},{col}]");
+            // TODO: Review nested indexing
+            // let nested_field = format!("long_matrix[{row}][{col}]");
+
+            // Verify data using type-variant accessors
+            assert_eq!(
+                value,
+                s.get_number(&flat_field)
+                    .expect("Failed 'get_number' operation on 'long_matrix[row,col]'")
+            );
+            // assert_eq!(
+            //     value,
+            //     s.get_number(&nested_field)
+            //         .expect("Failed 'get_number' operation on 'long_matrix[row][col]'")
+            // );
+
+            // Verify data using type-specific accessors
+            assert_eq!(
+                SelectedValue::Number(value),
+                s.get_value(&flat_field)
+                    .expect("Failed 'get_value' operation on 'long_matrix[row,col]'")
+            );
+            // assert_eq!(
+            //     SelectedValue::Number(value),
+            //     s.get_value(&nested_field)
+            //         .expect("Failed 'get_value' operation on 'long_matrix[row][col]'")
+            // );
+        }
+
+        // Out of bounds access should be an error
+        assert_matches!(
+            s.get_number("long_matrix[-1][-1]"),
+            Err(e) if is_invalid_argument(&e),
+            "Expected error for out-of-bounds access (negative index)"
+        );
+        assert_matches!(
+            s.get_number("long_matrix[bad,index]"),
+            Err(e) if is_invalid_argument(&e),
+            "Expected error for out-of-bounds access (non-numeric index)"
+        );
+        assert_matches!(
+            s.get_number(&format!("long_matrix[{MATRIX_SIZE},{MATRIX_SIZE}]")),
+            Err(e) if e.is_out_of_bounds(),
+            "Expected error for out-of-bounds access"
+        );
+
+        for index in 0..SEQUENCE_SIZE {
+            let value = 123.45 * (index + 1) as f64;
+            let field = format!("double_sequence[{index}]");
+
+            // Verify data using type-variant accessors
+            assert_eq!(
+                value,
+                s.get_number(&field)
+                    .expect("Failed 'get_number' operation on 'double_sequence[index]'")
+            );
+
+            // Verify data using type-specific accessors
+            assert_eq!(
+                SelectedValue::Number(value),
+                s.get_value(&field)
+                    .expect("Failed 'get_value' operation on 'double_sequence[index]'")
+            );
+        }
+
+        // Out of bounds access should be an error
+        assert_matches!(
+            s.get_number("double_sequence[-1]"),
+            Err(e) if is_invalid_argument(&e),
+            "Expected error for out-of-bounds access (negative index)"
+        );
+        assert_matches!(
+            s.get_number("double_sequence[bad_index]"),
+            Err(e) if is_invalid_argument(&e),
+            "Expected error for out-of-bounds access (non-numeric index)"
+        );
+        assert_matches!(
+            s.get_number(&format!("double_sequence[{SEQUENCE_SIZE}]")),
+            Err(e) if e.is_out_of_bounds(),
+            "Expected error for out-of-bounds access"
+        );
+    }
+}
+
+// it('obtain the selected member of a union with # syntax', () => {
+// it('getNumber on unions', () => {
+#[test]
+fn test_setget_unions() {
+    let mut context = test_utils::TestContextBuilder::complex()
+        .build()
+        .expect("Failed to create test context");
+    let input = {
+        let entities = context
+            .test_entities()
+            .expect("Error in test entities creation")
+            .ensure_discovery();
+        let mut output = entities
+            .output
+            .expect("This test expects an available output");
+        let mut input = entities
+            .input
+            .expect("This test expects an available input");
+
+        {
+            let mut instance = output.instance();
+            instance
+                .set_string("union.string", "Hello")
+                .expect("Failed to set union.string");
+        }
+        output
+            .write()
+            .expect("Failed to write data for 'union# == string'");
+
+        input
+            .wait_with_timeout(TEST_TIMEOUT)
+            .expect("Failed to wait for data");
+        input.read().expect("Failed to read data");
+
+        {
+            let mut instance = output.instance();
+            instance
+                .set_number("union.number", 123.45)
+                .expect("Failed to set union.number");
+        }
+        output
+            .write()
+            .expect("Failed to write data for 'union# == number'");
+
+        input
+            .wait_with_timeout(TEST_TIMEOUT)
+            .expect("Failed to wait for data");
+        input.read().expect("Failed to read data");
+
+        {
+            let mut instance = output.instance();
+            instance
+                .set_boolean("union.boolean", true)
+                .expect("Failed to set union.boolean");
+        }
+        output
+            .write()
+            .expect("Failed to write data for 'union# == boolean'");
+
+        input
+            .wait_with_timeout(TEST_TIMEOUT)
+            .expect("Failed to wait for data");
+        input.read().expect("Failed to read data");
+
+        input
+    };
+
+    let iter = input.into_iter().valid_only();
+    let (_, upper_hint) = iter.size_hint();
+
+    assert_eq!(
+        3,
+        upper_hint.expect("Our implementation always yields a upper hint"),
+        "Expected only three samples"
+    );
+
+    for s in iter.take(3) {
+        let s = s.expect("Failed to check sample validity");
+        let discriminator = s
+            .get_string("union#")
+            .expect("Failed to get 'union#' value");
+
+        match discriminator.as_str() {
+            "string" => {
+                assert_eq!(
+                    "Hello",
+                    s.get_string("union.string")
+                        .expect("Failed 'get_string' operation on 'union.string'")
+                );
+                assert_matches!(
+                    s.get_value("union.number"),
+                    Err(_),
+                    "Expected error for inactive union member 'union.number'"
+                );
+                assert_matches!(
+                    s.get_value("union.boolean"),
+                    Err(_),
+                    "Expected error for inactive union member 'union.boolean'"
+                );
+            }
+            "number" => {
+                assert_eq!(
+                    123.45,
+                    s.get_number("union.number")
+                        .expect("Failed 'get_number' operation on 'union.number'")
+                );
+                assert_matches!(
+                    s.get_value("union.string"),
+                    Err(_),
+                    "Expected error for inactive union member 'union.string'"
+                );
+                assert_matches!(
+                    s.get_value("union.boolean"),
+                    Err(_),
+                    "Expected error for inactive union member 'union.boolean'"
+                );
+            }
+            "boolean" => {
+                assert!(
+                    s.get_boolean("union.boolean")
+                        .expect("Failed 'get_boolean' operation on 'union.boolean'")
+                );
+                assert_matches!(
+                    s.get_value("union.string"),
+                    Err(_),
+                    "Expected error for inactive union member 'union.string'"
+                );
+                assert_matches!(
+                    s.get_value("union.number"),
+                    Err(_),
+                    "Expected error for inactive union member 'union.number'"
+                );
+            }
+            _ => panic!("Unexpected union member"),
+        }
+    }
+}
+
+// it('obtain an unset optional member', () => {
+// it('obtain an unset optional member as a string', () => {
+// it('obtain an unset optional complex member', () => {
+#[test]
+fn test_unset_optional_members() {
+    let mut context = test_utils::TestContextBuilder::complex()
+        .build()
+        .expect("Failed to create test context");
+    let input = {
+        let entities = context
+            .test_entities()
+            .expect("Error in test entities creation")
+            .ensure_discovery();
+        let mut output = entities
+            .output
+            .expect("This test expects an available output");
+        let mut input = entities
+            .input
+            .expect("This test expects an available input");
+
+        {
+            let mut instance = output.instance();
+            instance
+                .set_number("optional.long_field", 10_f64)
+                .expect("Failed to set optional.long_field");
+            instance
+                .set_number("optional.double_field", 123.45)
+                .expect("Failed to set optional.double_field");
+            instance
+                .set_boolean("optional.boolean_field", true)
+                .expect("Failed to set optional.boolean_field");
+            // Explicitly clear optional fields
+            instance
+                .clear("optional.string_field")
+                .expect("Failed to clear optional.string_field");
+            instance
+                .clear("optional.enum_field")
+                .expect("Failed to clear optional.enum_field");
+        }
+        output.write().expect("Failed to write data");
+
+        input
+            .wait_with_timeout(TEST_TIMEOUT)
+            .expect("Failed to wait for data");
+        input.read().expect("Failed to read data");
+
+        input
+    };
+
+    let iter = input.into_iter().valid_only();
+    let (_, upper_hint) = iter.size_hint();
+
+    assert_eq!(
+        1,
+        upper_hint.expect("Our implementation always yields a upper hint"),
+        "Expected only one sample"
+    );
+
+    for s in iter.take(1) {
+        let s = s.expect("Failed to check sample validity");
+        // Verify that optional fields are unset (default values)
+        assert_eq!(
+            10_f64,
+            s.get_number("optional.long_field")
+                .expect("Failed 'get_number' operation on 'optional.long_field'")
+        );
+        assert_eq!(
+            123.45,
+            s.get_number("optional.double_field")
+                .expect("Failed 'get_number' operation on 'optional.double_field'")
+        );
+        assert!(
+            s.get_boolean("optional.boolean_field")
+                .expect("Failed 'get_boolean' operation on 'optional.boolean_field'")
+        );
+        assert_matches!(
+            s.get_string("optional.string_field"),
+            Err(_),
+            "Expected error for unset optional string field"
+        );
+        assert_matches!(
+            s.get_value("optional.enum_field"),
+            Err(_),
+            "Expected error for unset optional enum field"
+        );
+    }
+}
+
+#[test]
+fn test_sample_get_json() {
+    let mut context = test_utils::TestContextBuilder::complex()
+        .build()
+        .expect("Failed to create test context");
+    let input = {
+        let entities = context
+            .test_entities()
+            .expect("Error in test entities creation")
+            .ensure_discovery();
+        let mut output = entities
+            .output
+            .expect("This test expects an available output");
+        let mut input = entities
+            .input
+            .expect("This test expects an available input");
+
+        let mut instance = output.instance();
+        instance
+            .set_number("simple.long_field", 10_f64)
+            .expect("Failed to set field");
+        instance
+            .set_number("simple.double_field", 123.45)
+            .expect("Failed to set field");
+        instance
+            .set_number("simple.enum_field", 1.0)
+            .expect("Failed to set field");
+        instance
+            .set_string("simple.string_field", "Hello")
+            .expect("Failed to set field");
+        instance
+            .set_boolean("simple.boolean_field", true)
+            .expect("Failed to set field");
+        output.write().expect("Failed to write data");
+
+        input
+            .wait_with_timeout(TEST_TIMEOUT)
+            .expect("Failed to wait for data");
+        input.read().expect("Failed to read data");
+
+        input
+    };
+
+    let iter = input.into_iter().valid_only();
+    let (_, upper_hint) = iter.size_hint();
+
+    assert_eq!(
+        1,
+        upper_hint.expect("Our implementation always yields a upper hint"),
+        "Expected only one sample"
+    );
+
+    let sample = iter
+        .take(1)
+        .next()
+        .expect("Expected a sample")
+        .expect("Failed to check sample validity");
+
+    let json = sample
+        .get_value_json("simple")
+        .expect("Failed 'get_value_json' operation on the sample");
+
+    // It's not trivial to build the expected JSON string manually, so we use
+    // serde_json to build it for us.
+    #[derive(serde::Serialize, serde::Deserialize)]
+    struct SimpleStruct {
+        long_field: i64,
+        double_field: f64,
+        enum_field: i32,
+        string_field: String,
+        boolean_field: bool,
+    }
+
+    let expected_json = {
+        let mut value = serde_json::to_value(SimpleStruct {
+            long_field: 10,
+            double_field: 123.45,
+            enum_field: 1,
+            string_field: "Hello".to_string(),
+            boolean_field: true,
+        })
+        .expect("Failed to serialize expected value to JSON Value");
+        value.sort_all_objects();
+        value.to_string()
+    };
+
+    let json = {
+        let mut value = serde_json::from_str::<serde_json::Value>(&json)
+            .expect("Failed to parse JSON representation from get_value_json");
+        value.sort_all_objects();
+        value.to_string()
+    };
+
+    assert_eq!(
+        expected_json, json,
+        "JSON representation does not match expected"
+    );
+}
+
+#[test]
+fn test_write_with_params() {
+    let mut context = test_utils::TestContextBuilder::complex()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
+
+    let mut instance = output.instance();
+    instance
+        .set_string("simple.string_field", "Hello")
+        .expect("Failed to set field");
+
+    output.write().expect("Failed to write with parameters");
+
+    input
+        .wait_with_timeout(TEST_TIMEOUT)
+        .expect("Failed to wait for data");
+
+    input.take().expect("Failed to read data");
+
+    let iter = input.into_iter().valid_only();
+    let (_, upper_hint) = iter.size_hint();
+    assert_eq!(
+        1,
+        upper_hint.expect("Our implementation always yields a upper hint"),
+        "Expected only one sample"
+    );
+
+    let sample = iter
+        .take(1)
+        .next()
+        .expect("Expected a sample")
+        .expect("Failed to check sample validity");
+
+    assert_eq!(
+        "Hello",
+        sample
+            .get_string("simple.string_field")
+            .expect("Failed to get 'simple.string_field' value")
+    );
+
+    output
+        .write_with_params(&rtiddsconnector::WriteParams::dispose())
+        .expect("Failed to write with dispose parameters");
+
+    input
+        .wait_with_timeout(TEST_TIMEOUT)
+        .expect("Failed to wait for data after dispose");
+
+    input.take().expect("Failed to read data after dispose");
+
+    let iter = input.into_iter();
+    assert_eq!(1, iter.len(), "Expected one (invalid) sample after dispose");
+
+    let iter = iter.valid_only();
+    let (_, upper_hint) = iter.size_hint();
+    assert_eq!(
+        1,
+        upper_hint.expect("Our implementation always yields a upper hint"),
+        "Expected only one sample after dispose"
+    );
+
+    assert_matches!(
+        iter.take(1).next(),
+        None,
+        "Expected instance to be disposed"
+    );
+}
+
+#[test]
+fn test_typed_serialize_and_deserialize() {
+    use test_utils::types::SimpleStruct;
+
+    let mut context = test_utils::TestContextBuilder::simple()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Failed to get test entities")
+        .ensure_discovery();
+
+    let mut output = entities
+        .output
+        .expect("Output should be available in test context");
+    let mut input = entities
+        .input
+        .expect("Input should be available in test context");
+
+    // Create a simple struct to serialize
+    let original_data = SimpleStruct {
+        long_field: 42,
+        double_field: 123.45,
+        boolean_field: true,
+        string_field: "Hello, DDS!".to_string(),
+        enum_field: test_utils::types::TestEnum::Green,
+    };
+
+    // Serialize and write the data
+    output
+        .instance()
+        .serialize(&original_data)
+        .expect("Failed to serialize data");
+
+    output.write().expect("Failed to write data");
+
+    // Wait for data to be available
+    input
+        .wait_with_timeout(TEST_TIMEOUT)
+        .expect("Failed to wait for data");
+
+    // Take the data and read it
+    input.take().expect("Failed to take samples");
+
+    // Get the first sample
+    let sample = input
+        .into_iter()
+        .valid_only()
+        .next()
+        .expect("Expected at least one valid sample")
+        .expect("Failed to check sample validity");
+
+    // Deserialize the data
+    let deserialized_data: SimpleStruct =
+        sample.deserialize().expect("Failed to deserialize data");
+
+    // Verify the deserialized data matches the original
+    assert_eq!(
+        original_data, deserialized_data,
+        "Deserialized data should match original"
+    );
+}
+
+#[test]
+fn test_serialize_clearing_missing_optionals() {
+    use rtiddsconnector::OptionalBehavior;
+    use test_utils::types::{ComplexStruct, OptionalStruct};
+
+    let mut context = test_utils::TestContextBuilder::complex()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
+
+    let with_optionals = ComplexStruct {
+        optional: OptionalStruct {
+            long_field: Some(10),
+            string_field: Some("Set".to_string()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let without_optionals = ComplexStruct::default();
+
+    let mut write_and_take = |data: &ComplexStruct, optionals: OptionalBehavior| {
+        output
+            .instance()
+            .serialize_with(data, optionals)
+            .expect("Failed to serialize data");
+        output.write().expect("Failed to write data");
+        input
+            .wait_with_timeout(TEST_TIMEOUT)
+            .expect("Failed to wait for data");
+        input.take().expect("Failed to take data");
+        let sample = input
+            .last_sample()
+            .expect("Failed to access samples")
+            .expect("Expected a sample");
+        (
+            sample.try_get_number("optional.long_field"),
+            sample.try_get_string("optional.string_field"),
+        )
+    };
+
+    assert_matches!(
+        write_and_take(&with_optionals, OptionalBehavior::ClearMissing),
+        (Ok(Some(10.0)), Ok(Some(value))) if value == "Set"
+    );
+
+    // By default, members skipped when serializing keep their previous value
+    assert_matches!(
+        write_and_take(&without_optionals, OptionalBehavior::KeepMissing),
+        (Ok(Some(10.0)), Ok(Some(value))) if value == "Set"
+    );
+
+    assert_matches!(
+        write_and_take(&without_optionals, OptionalBehavior::ClearMissing),
+        (Ok(None), Ok(None)),
+        "Optional members should be unset"
+    );
+}
+
+#[test]
+fn test_write_typed() {
+    use test_utils::types::{SimpleStruct, TestEnum};
+
+    let mut context = test_utils::TestContextBuilder::simple()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Failed to get test entities")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("Output should be available in test context");
+    let mut input = entities
+        .input
+        .expect("Input should be available in test context");
+
+    let first = SimpleStruct {
+        long_field: 1,
+        double_field: 1.5,
+        boolean_field: true,
+        string_field: "First".to_string(),
+        enum_field: TestEnum::Green,
+    };
+    let second = SimpleStruct {
+        long_field: 2,
+        string_field: "Second".to_string(),
+        ..Default::default()
+    };
+    const TIMESTAMP: i64 = 1_700_000_000_123_456_789;
+
+    output
+        .write_typed(&first)
+        .expect("Failed to write typed data");
+    input
+        .wait_with_timeout(TEST_TIMEOUT)
+        .expect("Failed to wait for data");
+    input.take().expect("Failed to take data");
+    let sample = input
+        .last_sample()
+        .expect("Failed to access samples")
+        .expect("Expected a sample");
+    assert_matches!(sample.deserialize::<SimpleStruct>(), Ok(data) if data == first);
+
+    // Serialization failures are reported as such, and nothing is written
+    let unserializable = std::collections::HashMap::from([(vec![1_u8], 1)]);
+    assert_matches!(
+        output.write_typed(&unserializable),
+        Err(e) if e.to_string().contains("could not be serialized")
+    );
+
+    output
+        .write_typed_with_params(
+            &second,
+            &rtiddsconnector::WriteParams::write().with_source_timestamp(TIMESTAMP),
+        )
+        .expect("Failed to write typed data with params");
+    input
+        .wait_with_timeout(TEST_TIMEOUT)
+        .expect("Failed to wait for data");
+    assert_matches!(input.take(), Ok(1), "Expected only the second sample");
+    let sample = input
+        .last_sample()
+        .expect("Failed to access samples")
+        .expect("Expected a sample");
+    assert_matches!(sample.deserialize::<SimpleStruct>(), Ok(data) if data == second);
+    assert_matches!(sample.source_timestamp_nanos(), Ok(TIMESTAMP));
+}
+
+#[test]
+fn test_typed_output_and_input() {
+    use test_utils::types::SimpleStruct;
+
+    let mut context = test_utils::TestContextBuilder::simple()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Failed to get test entities")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("Output should be available in test context")
+        .typed::<SimpleStruct>();
+    let mut input = entities
+        .input
+        .expect("Input should be available in test context")
+        .typed::<SimpleStruct>();
+
+    let data: Vec<SimpleStruct> = (1..=3)
+        .map(|long_field| SimpleStruct {
+            long_field,
+            string_field: format!("Sample {}", long_field),
+            ..Default::default()
+        })
+        .collect();
+    const TIMESTAMP: i64 = 1_700_000_000_123_456_789;
+
+    output.write(&data[0]).expect("Failed to write typed data");
+    input
+        .as_untyped()
+        .wait_with_timeout(TEST_TIMEOUT)
+        .expect("Failed to wait for data");
+    assert_matches!(input.take(), Ok(taken) if taken == data[..1]);
+
+    output
+        .write_with_params(
+            &data[1],
+            &rtiddsconnector::WriteParams::write().with_source_timestamp(TIMESTAMP),
+        )
+        .expect("Failed to write typed data with params");
+    output.write(&data[2]).expect("Failed to write typed data");
+    while input
+        .as_untyped()
+        .len()
+        .expect("Failed to get sample count")
+        < 2
+    {
+        input
+            .as_untyped()
+            .wait_with_timeout(TEST_TIMEOUT)
+            .expect("Failed to wait for data");
+        input.as_untyped_mut().read().expect("Failed to read data");
+    }
+
+    // Iterating deserializes the samples in the cache without taking them
+    assert_matches!(
+        input.iter().collect::<rtiddsconnector::ConnectorResult<Vec<_>>>(),
+        Ok(read) if read == data[1..]
+    );
+    assert_matches!(
+        input
+            .as_untyped()
+            .sample_at(0)
+            .and_then(|s| s.source_timestamp_nanos()),
+        Ok(TIMESTAMP)
+    );
+    assert_matches!(input.take(), Ok(taken) if taken == data[1..]);
+
+    // The untyped entities remain usable
+    let mut output = output.into_untyped();
+    output.write().expect("Failed to write untyped data");
+    assert_matches!(input.into_untyped().wait_with_timeout(TEST_TIMEOUT), Ok(()));
+}
+
+#[test]
+fn test_write_json() {
+    let mut context = test_utils::TestContextBuilder::complex()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
+
+    // Members not present in the payload are cleared beforehand
+    output
+        .instance()
+        .set_number("optional.double_field", 1.5)
+        .expect("Failed to set optional.double_field");
+
+    let payload = r#"{
+        "simple": {"long_field": 7, "string_field": "Nested", "enum_field": 2},
+        "optional": {"long_field": 3},
+        "long_matrix": [[1, 2, 3], [4, 5, 6], [7, 8, 9]],
+        "double_sequence": [0.5, 1.5]
+    }"#;
+    output.write_json(payload).expect("Failed to write JSON");
+    input
+        .wait_with_timeout(TEST_TIMEOUT)
+        .expect("Failed to wait for data");
+    input.take().expect("Failed to take data");
+
+    let sample = input
+        .last_sample()
+        .expect("Failed to access samples")
+        .expect("Expected a sample");
+    assert_matches!(sample.get_number("simple.long_field"), Ok(7.0));
+    assert_matches!(
+        sample.get_string("simple.string_field"),
+        Ok(value) if value == "Nested"
+    );
+    assert_matches!(sample.get_number("simple.enum_field"), Ok(2.0));
+    assert_matches!(sample.get_number("optional.long_field"), Ok(3.0));
+    assert_matches!(sample.try_get_number("optional.double_field"), Ok(None));
+    assert_matches!(sample.get_number("long_matrix[1,2]"), Ok(6.0));
+    assert_matches!(
+        sample.get_sequence::<f64>("double_sequence"),
+        Ok(values) if values == [0.5, 1.5]
+    );
+
+    // Rejected payloads are truncated in the error, and nothing is written
+    let malformed = format!(r#"{{"simple": {{"string_field": "{}""#, "x".repeat(1024));
+    assert_matches!(
+        output.write_json(&malformed),
+        Err(e) if e.to_string().contains("... (1054 bytes)")
+            && !e.to_string().contains(&malformed)
+    );
+    assert_matches!(
+        input.wait_with_timeout(std::time::Duration::from_millis(200)),
+        Err(e) if e.is_timeout(),
+        "The rejected payload should not be written"
+    );
+}
+
+#[test]
+fn test_error_messages_not_shared_between_threads() {
+    const ITERATIONS: usize = 100;
+
+    let (connector, _globals) = test_utils::TestContextBuilder::multiple_entities()
+        .build()
+        .expect("Failed to create test context")
+        .into_parts();
+
+    // Thread A triggers native field-not-found errors
+    let native_thread = {
+        let connector = connector.clone();
+        std::thread::spawn(move || {
+            let output = connector
+                .get_output("TestPublisher::SimpleWriter")
+                .expect("Failed to get output");
+            for _ in 0..ITERATIONS {
+                assert_matches!(
+                    output.instance().set_number("non_existent_field", 10_f64),
+                    Err(e) if e.is_field_not_found() && e.last_error_message().is_some(),
+                    "Native errors should carry the native error message"
+                );
+            }
+        })
+    };
+
+    // Thread B triggers Rust-side serialization errors
+    let rust_thread = {
+        let connector = connector.clone();
+        std::thread::spawn(move || {
+            // Maps with non-string keys cannot be serialized into JSON
+            let unserializable = std::collections::HashMap::from([(vec![1_u8], 1)]);
+            let output = connector
+                .get_output("TestPublisher::ComplexWriter")
+                .expect("Failed to get output");
+            for _ in 0..ITERATIONS {
+                assert_matches!(
+                    output.instance().serialize(&unserializable),
+                    Err(e) if e.last_error_message().is_none(),
+                    "Rust-side errors should never carry a native error message"
+                );
+            }
+        })
+    };
+
+    native_thread.join().expect("Native error thread panicked");
+    rust_thread.join().expect("Rust error thread panicked");
+}
+
+#[test]
+fn test_snapshot_outlives_take() {
+    use test_utils::types::SimpleStruct;
+
+    let mut context = test_utils::TestContextBuilder::simple()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
+
+    output
+        .instance()
+        .set_number("long_field", 7_f64)
+        .expect("Failed to set long_field");
+    output
+        .instance()
+        .set_string("string_field", "Hello")
+        .expect("Failed to set string_field");
+    output.write().expect("Failed to write data");
+    input
+        .wait_with_timeout(TEST_TIMEOUT)
+        .expect("Failed to wait for data");
+    input.take().expect("Failed to take data");
+
+    let snapshot = input.snapshot().expect("Failed to snapshot samples");
+    assert_eq!(
+        1,
+        snapshot.len(),
+        "Snapshot should contain the taken sample"
+    );
+
+    // Taking again empties the sample cache, but not the snapshot
+    assert_matches!(input.take(), Ok(0));
+
+    let sample = std::thread::spawn(move || snapshot.into_iter().next())
+        .join()
+        .expect("Thread panicked")
+        .expect("Snapshot should not be empty");
+
+    assert!(sample.is_valid(), "Snapshot sample should be valid");
+    assert_matches!(sample.get_number("long_field"), Ok(7.0));
+    assert_matches!(sample.get_string("string_field").as_deref(), Ok("Hello"));
+    assert_matches!(
+        sample.get_info("valid_data"),
+        Ok(rtiddsconnector::SelectedValue::Boolean(true))
+    );
+    assert_matches!(
+        sample.get_number("missing_field"),
+        Err(e) if e.is_field_not_found()
+    );
+
+    let data: SimpleStruct = sample.deserialize().expect("Failed to deserialize");
+    assert_eq!(7, data.long_field);
+}
+
+#[test]
+fn test_valid_only_yields_results() {
+    let mut context = test_utils::TestContextBuilder::simple()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
+
+    output.write().expect("Failed to write data");
+    output
+        .write_with_params(&rtiddsconnector::WriteParams::dispose())
+        .expect("Failed to write with dispose parameters");
+    input
+        .wait_with_timeout(TEST_TIMEOUT)
+        .expect("Failed to wait for data");
+
+    // Wait until both the data and the dispose have been received
+    let mut count = input.read().expect("Failed to read data");
+    while count < 2 {
+        input
+            .wait_with_timeout(TEST_TIMEOUT)
+            .expect("Failed to wait for the dispose");
+        count = input.read().expect("Failed to read data");
+    }
+
+    let results: Vec<_> = input
+        .samples()
+        .expect("Failed to access samples")
+        .valid_only()
+        .collect();
+    assert_eq!(1, results.len(), "Only the written sample should be valid");
+    assert!(results.iter().all(Result::is_ok), "No errors were expected");
+
+    let lossy = input
+        .samples()
+        .expect("Failed to access samples")
+        .valid_only_lossy();
+    assert_eq!(
+        1,
+        lossy.count(),
+        "Lossy iteration should yield the same samples"
+    );
+}
+
+#[test]
+fn test_iterate_samples_from_newest() {
+    let mut context = test_utils::TestContextBuilder::simple()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
+
+    for value in 1..=3 {
+        output
+            .instance()
+            .set_number("long_field", value as f64)
+            .expect("Failed to set long_field");
+        output.write().expect("Failed to write data");
+        input
+            .wait_with_timeout(TEST_TIMEOUT)
+            .expect("Failed to wait for data");
+        input.read().expect("Failed to read data");
+    }
+
+    let newest = |sample: Option<rtiddsconnector::Sample>| {
+        sample
+            .expect("Expected a sample")
+            .get_number("long_field")
+            .expect("Failed to get long_field")
+    };
+
+    let iter = input.samples().expect("Failed to access samples");
+    assert_eq!(3, iter.len(), "Expected three samples");
+    let values: Vec<f64> = iter
+        .rev()
+        .map(|sample| {
+            sample
+                .get_number("long_field")
+                .expect("Failed to get long_field")
+        })
+        .collect();
+    assert_eq!(
+        vec![3.0, 2.0, 1.0],
+        values,
+        "Expected the newest sample first"
+    );
+
+    let iter = input.samples().expect("Failed to access samples");
+    assert_eq!(3.0, newest(iter.last()));
+
+    let mut iter = input.samples().expect("Failed to access samples");
+    assert_eq!(2.0, newest(iter.nth_back(1)));
+    assert_eq!(1, iter.len(), "Only the oldest sample should remain");
+    assert_eq!(1.0, newest(iter.next_back()));
+    assert!(iter.next().is_none(), "The iterator should be exhausted");
+
+    let newest_valid = input
+        .samples()
+        .expect("Failed to access samples")
+        .valid_only()
+        .next_back()
+        .expect("Expected a valid sample")
+        .expect("Failed to check sample validity");
+    assert_eq!(3.0, newest(Some(newest_valid)));
+
+    let mut counted = input
+        .samples()
+        .expect("Failed to access samples")
+        .valid_only_counted()
+        .expect("Failed to check sample validity");
+    assert_eq!(3.0, newest(counted.next_back()));
+    assert_eq!(1.0, newest(counted.next()));
+    assert_eq!(1, counted.len());
+}
+
+#[test]
+fn test_state_filters() {
+    use rtiddsconnector::{InstanceState, SampleStateMask, ViewState};
+
+    let mut context = test_utils::TestContextBuilder::complex()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
+
+    output
+        .instance()
+        .set_string("simple.string_field", "Hello")
+        .expect("Failed to set field");
+    output.write().expect("Failed to write data");
+    input
+        .wait_with_timeout(TEST_TIMEOUT)
+        .expect("Failed to wait for data");
+    input.take().expect("Failed to take data");
+
+    fn samples(input: &rtiddsconnector::Input) -> rtiddsconnector::SampleIterator<'_> {
+        input.samples().expect("Failed to access samples")
+    }
+    let count_ok = |iter: rtiddsconnector::StateFilteredSampleIterator| {
+        iter.collect::<rtiddsconnector::ConnectorResult<Vec<_>>>()
+            .expect("Failed to check sample state")
+            .len()
+    };
+
+    let sample = samples(&input).next().expect("Expected a sample");
+    assert_matches!(sample.view_state(), Ok(ViewState::New));
+    assert_matches!(sample.instance_state(), Ok(InstanceState::Alive));
+
+    assert_eq!(1, count_ok(samples(&input).new_only()));
+    assert_eq!(1, count_ok(samples(&input).alive_only()));
+    assert_eq!(1, count_ok(samples(&input).valid_only().alive_only()));
+    assert_eq!(
+        1,
+        count_ok(samples(&input).alive_only().new_only().valid_only())
+    );
+    assert_eq!(
+        0,
+        count_ok(samples(&input).filter_state(
+            SampleStateMask::any().with_instance_state(InstanceState::NotAliveDisposed)
+        ))
+    );
+
+    // Chained masks must all match, so conflicting ones match no sample
+    let not_new = SampleStateMask::any().with_view_state(ViewState::NotNew);
+    assert_eq!(
+        0,
+        count_ok(samples(&input).filter_state(not_new).new_only()),
+        "Conflicting view states should match no sample"
+    );
+    assert_eq!(
+        0,
+        count_ok(samples(&input).new_only().filter_state(not_new)),
+        "Conflicting view states should match no sample in any order"
+    );
+    assert_eq!(
+        1,
+        count_ok(
+            samples(&input)
+                .filter_state(SampleStateMask::any().with_view_state(ViewState::New))
+                .new_only()
+        ),
+        "Agreeing view states should match"
+    );
+
+    output
+        .write_with_params(&rtiddsconnector::WriteParams::dispose())
+        .expect("Failed to write with dispose parameters");
+    input
+        .wait_with_timeout(TEST_TIMEOUT)
+        .expect("Failed to wait for data after dispose");
+    input.take().expect("Failed to take data after dispose");
+
+    let sample = samples(&input)
+        .next()
+        .expect("Expected a dispose notification");
+    assert_matches!(sample.is_valid(), Ok(false));
+    assert_matches!(sample.instance_state(), Ok(InstanceState::NotAliveDisposed));
+
+    assert_eq!(
+        0,
+        count_ok(samples(&input).alive_only()),
+        "Instance is not alive"
+    );
+
+    let disposed =
+        SampleStateMask::any().with_instance_state(InstanceState::NotAliveDisposed);
+    assert_eq!(
+        1,
+        count_ok(samples(&input).filter_state(disposed)),
+        "Invalid samples carry the instance state"
+    );
+    assert_eq!(
+        0,
+        count_ok(samples(&input).filter_state(disposed).valid_only()),
+        "Dispose notifications have no valid data"
+    );
+}
+
+#[test]
+fn test_instance_state_helpers() {
+    let mut context = test_utils::TestContextBuilder::complex()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
+
+    output
+        .instance()
+        .set_string("simple.string_field", "Hello")
+        .expect("Failed to set field");
+
+    let mut write_and_take = |params: Option<rtiddsconnector::WriteParams>| {
+        match params {
+            Some(params) => output.write_with_params(&params),
+            None => output.write(),
+        }
+        .expect("Failed to write data");
+        input
+            .wait_with_timeout(TEST_TIMEOUT)
+            .expect("Failed to wait for data");
+        input.take().expect("Failed to take data");
+        input
+            .last_sample()
+            .expect("Failed to access samples")
+            .map(|sample| {
+                (
+                    sample.is_alive().expect("Failed to check is_alive"),
+                    sample.is_disposed().expect("Failed to check is_disposed"),
+                    sample
+                        .is_unregistered()
+                        .expect("Failed to check is_unregistered"),
+                )
+            })
+            .expect("Expected a sample")
+    };
+
+    assert_eq!(
+        (true, false, false),
+        write_and_take(None),
+        "Written instance should be alive"
+    );
+    assert_eq!(
+        (false, true, false),
+        write_and_take(Some(rtiddsconnector::WriteParams::dispose())),
+        "Instance should be disposed"
+    );
+    assert_eq!(
+        (true, false, false),
+        write_and_take(None),
+        "Written instance should be alive again"
+    );
+    assert_eq!(
+        (false, false, true),
+        write_and_take(Some(rtiddsconnector::WriteParams::unregister())),
+        "Instance should be unregistered"
+    );
+}
+
+#[test]
+fn test_sample_handles() {
+    let mut context = test_utils::TestContextBuilder::simple()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
+
+    // Two samples of the instance with key 1, and one of the instance with key 2
+    for key in [1, 1, 2] {
+        output
+            .instance()
+            .set_number("long_field", key.into())
+            .expect("Failed to set key");
+        output.write().expect("Failed to write data");
+    }
+    while input.read().expect("Failed to read data") < 3 {
+        input
+            .wait_with_timeout(TEST_TIMEOUT)
+            .expect("Failed to wait for data");
+    }
+
+    let handles = input
+        .into_iter()
+        .map(|sample| {
+            (
+                sample
+                    .instance_handle()
+                    .expect("Failed to get the instance handle"),
+                sample
+                    .publication_handle()
+                    .expect("Failed to get the publication handle"),
+            )
+        })
+        .collect::<Vec<_>>();
+    let [(first, writer), (second, _), (third, _)] = handles.as_slice() else {
+        panic!("Expected three samples, got {:?}", handles);
+    };
+
+    // Handles are only checked if the native library provides them
+    if let Some(first) = first {
+        assert_eq!(Some(first), second.as_ref(), "Expected the same instance");
+        assert_ne!(Some(first), third.as_ref(), "Expected another instance");
+        assert_eq!(first.to_string().len(), first.as_bytes().len() * 2);
+    }
+    if writer.is_some() {
+        assert!(
+            handles.iter().all(|(_, handle)| handle == writer),
+            "Expected the same publication for every sample"
+        );
+    }
+}
+
+#[test]
+fn test_sample_ranks() {
+    let mut context = test_utils::TestContextBuilder::simple()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
+
+    // Two samples of the same instance, taken at once
+    output
+        .instance()
+        .set_number("long_field", 1.0)
+        .expect("Failed to set key");
+    output.write().expect("Failed to write data");
+    output.write().expect("Failed to write data");
+    while input.read().expect("Failed to read data") < 2 {
+        input
+            .wait_with_timeout(TEST_TIMEOUT)
+            .expect("Failed to wait for data");
+    }
+    assert_matches!(input.take(), Ok(2));
+
+    let ranks = input
+        .into_iter()
+        .map(|sample| {
+            let info = sample.info().expect("Failed to get the sample info");
+            assert_matches!(
+                sample.sample_rank(),
+                Ok(rank) if rank == info.sample_rank,
+                "Expected the same rank from the info"
+            );
+            (
+                info.sample_rank,
+                info.generation_rank,
+                info.disposed_generation_count,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    // Ranks are only checked if the native library provides them
+    let [
+        (older, older_generation, disposed),
+        (newer, newer_generation, _),
+    ] = ranks.as_slice()
+    else {
+        panic!("Expected two samples, got {:?}", ranks);
+    };
+    if older.is_some() {
+        assert_eq!(
+            (*older, *newer),
+            (Some(1), Some(0)),
+            "Expected the older sample to be followed by the newer one"
+        );
+    }
+    if older_generation.is_some() {
+        assert_eq!(
+            older_generation, newer_generation,
+            "Expected one generation"
+        );
+    }
+    assert_matches!(disposed, None | Some(0));
+}
+
+#[test]
+fn test_sample_timestamps() {
+    let mut context = test_utils::TestContextBuilder::simple()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
+
+    let before = std::time::SystemTime::now();
+    output.write().expect("Failed to write data");
+    input
+        .wait_with_timeout(TEST_TIMEOUT)
+        .expect("Failed to wait for data");
+    input.take().expect("Failed to take data");
+
+    let sample = input
+        .last_sample()
+        .expect("Failed to access samples")
+        .expect("Expected a sample");
+
+    let source = sample
+        .source_timestamp()
+        .expect("Failed to get source_timestamp");
+    let reception = sample
+        .reception_timestamp()
+        .expect("Failed to get reception_timestamp");
+    assert!(source <= reception, "Sample received before being sent");
+    assert!(
+        source + std::time::Duration::from_secs(1) >= before,
+        "Source timestamp should be close to the write time"
+    );
+
+    let source_nanos = sample
+        .source_timestamp_nanos()
+        .expect("Failed to get source_timestamp_nanos");
+    let since_epoch = source
+        .duration_since(std::time::UNIX_EPOCH)
+        .expect("Source timestamp should be after the epoch");
+    assert_eq!(source_nanos as u128, since_epoch.as_nanos());
+}
+
+#[test]
+fn test_write_with_source_time() {
+    use rtiddsconnector::WriteParams;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    let mut context = test_utils::TestContextBuilder::simple()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
+
+    let time = UNIX_EPOCH + Duration::new(1_700_000_000, 123_456_789);
+    output
+        .write_with_timestamp(time)
+        .expect("Failed to write with timestamp");
+    input
+        .wait_with_timeout(TEST_TIMEOUT)
+        .expect("Failed to wait for data");
+    input.take().expect("Failed to take data");
+
+    let sample = input
+        .last_sample()
+        .expect("Failed to access samples")
+        .expect("Expected a sample");
+    assert_matches!(sample.source_timestamp(), Ok(received) if received == time);
+
+    let params = WriteParams::write()
+        .with_source_time(time)
+        .expect("Failed to set source time");
+    assert_eq!(params.source_timestamp, Some(1_700_000_000_123_456_789));
+    assert_matches!(
+        WriteParams::write().with_source_time(UNIX_EPOCH - Duration::from_secs(1)),
+        Ok(params) if params.source_timestamp == Some(-1_000_000_000)
+    );
+
+    // Times past 2262 don't fit into nanoseconds in an i64
+    let far_future = UNIX_EPOCH + Duration::from_secs(1 << 40);
+    assert_matches!(WriteParams::write().with_source_time(far_future), Err(_));
+    assert_matches!(output.write_with_timestamp(far_future), Err(_));
+
+    let before = std::time::SystemTime::now();
+    let params = WriteParams::write()
+        .with_source_timestamp_now()
+        .expect("Failed to set source time");
+    let now = UNIX_EPOCH + Duration::from_nanos(params.source_timestamp.unwrap() as u64);
+    assert!(now >= before, "Source timestamp should be the current time");
+}
+
+#[test]
+fn test_sample_identity_round_trip() {
+    use rtiddsconnector::{WriteParams, WriteParamsIdentity};
+
+    let mut context = test_utils::TestContextBuilder::simple()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
+
+    let identity = WriteParamsIdentity {
+        writer_guid: std::array::from_fn(|i| i as u8 + 1),
+        sequence_number: 42,
+    };
+    output
+        .write_with_params(&WriteParams::write().with_identity(identity.clone()))
+        .expect("Failed to write with identity");
+    input
+        .wait_with_timeout(TEST_TIMEOUT)
+        .expect("Failed to wait for data");
+    input.take().expect("Failed to take data");
+
+    let request_identity = {
+        let sample = input
+            .last_sample()
+            .expect("Failed to access samples")
+            .expect("Expected a sample");
+
+        let received = sample.identity().expect("Failed to get identity");
+        assert_eq!(identity.writer_guid, received.writer_guid);
+        assert_eq!(identity.sequence_number, received.sequence_number);
+        assert_matches!(sample.related_identity(), Ok(None));
+
+        received
+    };
+
+    // Reply to the received sample, as in request-reply communications
+    output
+        .write_with_params(
+            &WriteParams::write().with_related_sample_identity(request_identity.clone()),
+        )
+        .expect("Failed to write with related identity");
+    input
+        .wait_with_timeout(TEST_TIMEOUT)
+        .expect("Failed to wait for data");
+    input.take().expect("Failed to take data");
+
+    let sample = input
+        .last_sample()
+        .expect("Failed to access samples")
+        .expect("Expected a sample");
+    assert_matches!(
+        sample.related_identity(),
+        Ok(Some(related)) if related == request_identity,
+        "Related identity should match the request"
+    );
+}
+
+#[test]
+fn test_request_reply() {
+    use rtiddsconnector::WriteParams;
+
+    let (connector, _globals) = test_utils::TestContextBuilder::multiple_entities()
+        .build()
+        .expect("Failed to create test context")
+        .into_parts();
+
+    // Requests are sent over the simple topic, and replies over the complex one
+    let mut requester = connector
+        .get_output("TestPublisher::SimpleWriter")
+        .expect("Failed to get request output");
+    let mut replier = connector
+        .get_input("TestSubscriber::SimpleReader")
+        .expect("Failed to get request input");
+    let mut reply_writer = connector
+        .get_output("TestPublisher::ComplexWriter")
+        .expect("Failed to get reply output");
+    let mut reply_reader = connector
+        .get_input("TestSubscriber::ComplexReader")
+        .expect("Failed to get reply input");
+    for output in [&requester, &reply_writer] {
+        output
+            .wait_for_subscriptions_with_timeout(TEST_TIMEOUT)
+            .expect("Failed to discover subscriptions");
+    }
+
+    for id in 1..=2 {
+        requester
+            .instance()
+            .set_i32("long_field", id)
+            .expect("Failed to set request");
+        requester.write().expect("Failed to write request");
+    }
+
+    // Reply to every request, echoing its id
+    let mut requests = Vec::new();
+    while requests.len() < 2 {
+        replier
+            .wait_with_timeout(TEST_TIMEOUT)
+            .expect("Failed to wait for requests");
+        replier.take().expect("Failed to take requests");
+        for request in replier.valid_samples().expect("Failed to access requests") {
+            let request = request.expect("Failed to check request validity");
+            let id = request.get_i32("long_field").expect("Failed to get id");
+            reply_writer
+                .instance()
+                .set_i32("simple.long_field", id)
+                .expect("Failed to set reply");
+            reply_writer
+                .reply_to(&request, WriteParams::write())
+                .expect("Failed to write reply");
+            requests.push((id, request.identity().expect("Failed to get identity")));
+        }
+    }
+
+    let mut replies = 0;
+    while replies < 2 {
+        reply_reader
+            .wait_with_timeout(TEST_TIMEOUT)
+            .expect("Failed to wait for replies");
+        reply_reader.take().expect("Failed to take replies");
+        for reply in reply_reader
+            .valid_samples()
+            .expect("Failed to access replies")
+        {
+            let reply = reply.expect("Failed to check reply validity");
+            let id = reply
+                .get_i32("simple.long_field")
+                .expect("Failed to get id");
+            for (request_id, identity) in &requests {
+                assert_matches!(
+                    reply.is_reply_to(identity),
+                    Ok(matches) if matches == (id == *request_id),
+                    "Reply {} should only match its own request",
+                    id
+                );
+            }
+            replies += 1;
+        }
+    }
+}
+
+#[test]
+fn test_write_params_validation() {
+    use rtiddsconnector::{WriteParams, WriteParamsIdentity};
+
+    let mut context = test_utils::TestContextBuilder::simple()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
+
+    let guid = std::array::from_fn(|i| 0xF0 | i as u8);
+    let identity =
+        WriteParamsIdentity::new(guid, WriteParamsIdentity::MAX_SEQUENCE_NUMBER);
+    let params = WriteParams::write().with_identity(identity.clone());
+    assert_matches!(params.validate(), Ok(()));
+    output
+        .write_with_params(&params)
+        .expect("Failed to write with the max sequence number");
+    input
+        .wait_with_timeout(TEST_TIMEOUT)
+        .expect("Failed to wait for data");
+    input.take().expect("Failed to take data");
+
+    let sample = input
+        .last_sample()
+        .expect("Failed to access samples")
+        .expect("Expected a sample");
+    assert_matches!(
+        sample.identity(),
+        Ok(received) if received == identity,
+        "The max sequence number should be written exactly"
+    );
+
+    // Invalid parameters are rejected before writing
+    for params in [
+        WriteParams::write().with_identity(WriteParamsIdentity::new(guid, -2)),
+        WriteParams::write().with_identity(WriteParamsIdentity::new(
+            guid,
+            WriteParamsIdentity::MAX_SEQUENCE_NUMBER + 1,
+        )),
+        WriteParams::write()
+            .with_related_sample_identity(WriteParamsIdentity::new([0; 16], 1)),
+    ] {
+        assert_matches!(params.validate(), Err(_), "{:?} should be invalid", params);
+        assert_matches!(
+            output.write_with_params(&params),
+            Err(e) if e.to_string().starts_with("Invalid argument"),
+            "{:?} should not be written",
+            params
+        );
+    }
+    assert_matches!(
+        input.wait_with_timeout(std::time::Duration::from_millis(200)),
+        Err(e) if e.is_timeout(),
+        "Invalid parameters should not be written"
+    );
+
+    #[allow(deprecated)]
+    {
+        assert_matches!(
+            WriteParamsIdentity::from_unsigned(guid, 42),
+            Ok(identity) if identity.sequence_number == 42
+        );
+        assert_matches!(WriteParamsIdentity::from_unsigned(guid, u64::MAX), Err(_));
+    }
+}
+
+#[test]
+fn test_sample_writer_guid() {
+    use rtiddsconnector::Guid;
+
+    let mut context = test_utils::TestContextBuilder::simple()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
+
+    let writer_guid = |input: &rtiddsconnector::Input| {
+        Guid::from(
+            input
+                .last_sample()
+                .expect("Failed to access samples")
+                .expect("Expected a sample")
+                .writer_guid()
+                .expect("Failed to get writer_guid"),
+        )
+    };
+
+    output.write().expect("Failed to write data");
+    input
+        .wait_with_timeout(TEST_TIMEOUT)
+        .expect("Failed to wait for data");
+    input.read().expect("Failed to read data");
+    let read_guid = writer_guid(&input);
+
+    output.write().expect("Failed to write data");
+    input
+        .wait_with_timeout(TEST_TIMEOUT)
+        .expect("Failed to wait for data");
+    input.take().expect("Failed to take data");
+    let taken_guid = writer_guid(&input);
+
+    assert_ne!(Guid::default(), read_guid, "Writer GUID should be known");
+    assert_eq!(read_guid, taken_guid, "Samples come from the same writer");
+
+    let displayed = read_guid.to_string();
+    assert_eq!(47, displayed.len(), "Expected 16 colon-separated bytes");
+    assert_matches!(displayed.parse::<Guid>(), Ok(parsed) if parsed == read_guid);
+    assert_matches!("01:02".parse::<Guid>(), Err(_));
+    assert_matches!("not a guid".parse::<Guid>(), Err(_));
+}
+
+#[test]
+fn test_try_get_optional_members() {
+    let mut context = test_utils::TestContextBuilder::complex()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
+
+    {
+        let mut instance = output.instance();
+        instance
+            .set_number("optional.long_field", 10_f64)
+            .expect("Failed to set optional.long_field");
+        instance
+            .set_boolean("optional.boolean_field", true)
+            .expect("Failed to set optional.boolean_field");
+        instance
+            .clear("optional.string_field")
+            .expect("Failed to clear optional.string_field");
+        instance
+            .clear("optional.enum_field")
+            .expect("Failed to clear optional.enum_field");
+    }
+    output.write().expect("Failed to write data");
+    input
+        .wait_with_timeout(TEST_TIMEOUT)
+        .expect("Failed to wait for data");
+    input.take().expect("Failed to take data");
+
+    let sample = input
+        .last_sample()
+        .expect("Failed to access samples")
+        .expect("Expected a sample");
+
+    // Set optional members
+    assert_matches!(sample.try_get_number("optional.long_field"), Ok(Some(10.0)));
+    assert_matches!(
+        sample.try_get_boolean("optional.boolean_field"),
+        Ok(Some(true))
+    );
+
+    // Unset optional members
+    assert_matches!(sample.try_get_string("optional.string_field"), Ok(None));
+    assert_matches!(sample.try_get_value("optional.enum_field"), Ok(None));
+
+    // Members missing from the type
+    assert_matches!(
+        sample.try_get_number("optional.missing_field"),
+        Err(e) if e.is_field_not_found(),
+        "Fields missing from the type should still be errors"
+    );
+    assert_matches!(
+        sample.try_get_string("missing_field"),
+        Err(e) if e.is_field_not_found()
+    );
+}
+
+#[test]
+fn test_has_field() {
+    let mut context = test_utils::TestContextBuilder::complex()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
+
+    {
+        let mut instance = output.instance();
+        instance
+            .set_number("optional.long_field", 10_f64)
+            .expect("Failed to set optional.long_field");
+        instance
+            .clear("optional.string_field")
+            .expect("Failed to clear optional.string_field");
+
+        assert_matches!(instance.has_field("simple.long_field"), Ok(true));
+        assert_matches!(instance.has_field("optional.long_field"), Ok(true));
+        assert_matches!(instance.has_field("missing_field"), Ok(false));
+    }
+    output.write().expect("Failed to write data");
+    input
+        .wait_with_timeout(TEST_TIMEOUT)
+        .expect("Failed to wait for data");
+    input.take().expect("Failed to take data");
+
+    let sample = input
+        .last_sample()
+        .expect("Failed to access samples")
+        .expect("Expected a sample");
+
+    assert_matches!(sample.has_field("simple"), Ok(true));
+    assert_matches!(sample.has_field("simple.long_field"), Ok(true));
+    assert_matches!(sample.has_field("optional.long_field"), Ok(true));
+    assert_matches!(
+        sample.has_field("optional.string_field"),
+        Ok(true),
+        "Unset optional members are part of the type"
+    );
+    assert_matches!(sample.has_field("simple.missing_field"), Ok(false));
+    assert_matches!(sample.has_field("missing_field"), Ok(false));
+    assert_matches!(
+        sample.has_field("simple.long_field.missing_field"),
+        Ok(false),
+        "Members of non-structure fields don't exist"
+    );
+    assert_matches!(sample.has_field("missing_parent.long_field"), Ok(false));
+}
+
+#[test]
+fn test_64_bit_integers() {
+    let mut context = test_utils::TestContextBuilder::integers()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
+
+    for (int64, uint64) in [
+        (i64::MAX, u64::MAX - 1),
+        (i64::MIN, u64::MAX),
+        ((1 << 53) + 1, (1 << 53) + 1),
+        (-42, 42),
+    ] {
+        {
+            let mut instance = output.instance();
+            instance
+                .set_i64("int64_field", int64)
+                .expect("Failed to set int64_field");
+            instance
+                .set_u64("uint64_field", uint64)
+                .expect("Failed to set uint64_field");
+        }
+        output.write().expect("Failed to write data");
+        input
+            .wait_with_timeout(TEST_TIMEOUT)
+            .expect("Failed to wait for data");
+        input.take().expect("Failed to take data");
+
+        let sample = input
+            .last_sample()
+            .expect("Failed to access samples")
+            .expect("Expected a sample");
+        assert_matches!(sample.get_i64("int64_field"), Ok(value) if value == int64);
+        assert_matches!(sample.get_u64("uint64_field"), Ok(value) if value == uint64);
+    }
+
+    let sample = input
+        .last_sample()
+        .expect("Failed to access samples")
+        .expect("Expected a sample");
+    assert_matches!(
+        sample.get_u64("int64_field"),
+        Err(_),
+        "A negative value is not an unsigned integer"
+    );
+}
+
+#[test]
+fn test_64_bit_integers_reject_strings() {
+    #[derive(Debug, serde::Deserialize)]
+    struct Simple {
+        #[allow(dead_code)]
+        string_field: i64,
+    }
+
+    #[derive(Debug, serde::Deserialize)]
+    struct Complex {
+        #[allow(dead_code)]
+        simple: Simple,
+    }
+
+    let mut context = test_utils::TestContextBuilder::complex()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
+
+    output
+        .instance()
+        .set_string("simple.string_field", "42")
+        .expect("Failed to set simple.string_field");
+    output.write().expect("Failed to write data");
+    input
+        .wait_with_timeout(TEST_TIMEOUT)
+        .expect("Failed to wait for data");
+    input.take().expect("Failed to take data");
+
+    let sample = input
+        .last_sample()
+        .expect("Failed to access samples")
+        .expect("Expected a sample");
+
+    for result in [
+        sample.get_i64("simple.string_field").map(drop),
+        sample.get_u64("simple.string_field").map(drop),
+    ] {
+        assert_matches!(
+            result.as_ref().map_err(|e| e.kind()),
+            Err(ErrorKind::Invalid {
+                what: InvalidErrorKind::Conversion,
+                ..
+            }),
+            "A string holding digits is not an integer"
+        );
+    }
+    assert_matches!(
+        sample.deserialize::<Complex>(),
+        Err(_),
+        "A string holding digits is not an integer"
+    );
+    assert_matches!(
+        sample.deserialize_direct::<Complex>(),
+        Err(_),
+        "Direct deserialization should reject it like the JSON path"
+    );
+}
+
+#[test]
+fn test_set_integer_precision() {
+    const MAX_EXACT: i64 = 1 << 53;
+
+    let mut context = test_utils::TestContextBuilder::integers()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
+
+    for int64 in [
+        MAX_EXACT - 1,
+        MAX_EXACT,
+        MAX_EXACT + 1,
+        -MAX_EXACT,
+        -MAX_EXACT - 1,
+        i64::MIN,
+    ] {
+        let uint64 = int64.unsigned_abs();
+        {
+            let mut instance = output.instance();
+            instance
+                .set_integer("int64_field", int64)
+                .expect("Failed to set int64_field");
+            instance
+                .set_integer("uint64_field", uint64)
+                .expect("Failed to set uint64_field");
+        }
+        output.write().expect("Failed to write data");
+        input
+            .wait_with_timeout(TEST_TIMEOUT)
+            .expect("Failed to wait for data");
+        input.take().expect("Failed to take data");
+
+        let sample = input
+            .last_sample()
+            .expect("Failed to access samples")
+            .expect("Expected a sample");
+        assert_matches!(sample.get_i64("int64_field"), Ok(value) if value == int64);
+        assert_matches!(sample.get_u64("uint64_field"), Ok(value) if value == uint64);
+    }
+
+    // Numbers which an integer member can't hold exactly are reported, on request
+    assert_matches!(output.instance().set_number("int32_field", 1.5), Ok(()));
+    output.check_integer_conversions(true);
+    let mut instance = output.instance();
+    assert_matches!(
+        instance.set_number("int32_field", 1.5),
+        Err(e) if e.to_string().contains("'int32_field'")
+    );
+    assert_matches!(
+        instance.set_number("int64_field", -0.25),
+        Err(e) if e.to_string().contains("'int64_field'")
+    );
+    assert_matches!(
+        instance.set_number("int32_field", (1_i64 << 40) as f64),
+        Err(_)
+    );
+    assert_matches!(
+        instance.set_number("int64_field", (1_i64 << 60) as f64),
+        Ok(())
+    );
+    assert_matches!(instance.set_number("int32_field", -42.0), Ok(()));
+}
+
+#[test]
+fn test_checked_small_integers() {
+    let mut context = test_utils::TestContextBuilder::integers()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
+
+    {
+        let mut instance = output.instance();
+        instance
+            .set_i32("int32_field", i32::MIN)
+            .expect("Failed to set int32_field");
+        instance
+            .set_u32("uint32_field", u32::MAX)
+            .expect("Failed to set uint32_field");
+        instance
+            .set_i16("int16_field", i16::MIN)
+            .expect("Failed to set int16_field");
+        instance
+            .set_u16("uint16_field", u16::MAX)
+            .expect("Failed to set uint16_field");
+        instance
+            .set_u8("uint8_field", u8::MAX)
+            .expect("Failed to set uint8_field");
+    }
+    output.write().expect("Failed to write data");
+    input
+        .wait_with_timeout(TEST_TIMEOUT)
+        .expect("Failed to wait for data");
+    input.take().expect("Failed to take data");
+
+    let sample = input
+        .last_sample()
+        .expect("Failed to access samples")
+        .expect("Expected a sample");
+
+    assert_matches!(sample.get_i32("int32_field"), Ok(i32::MIN));
+    assert_matches!(sample.get_u32("uint32_field"), Ok(u32::MAX));
+    assert_matches!(sample.get_i16("int16_field"), Ok(i16::MIN));
+    assert_matches!(sample.get_u16("uint16_field"), Ok(u16::MAX));
+    assert_matches!(sample.get_u8("uint8_field"), Ok(u8::MAX));
+
+    // Values out of range of the requested type
+    assert_matches!(
+        sample.get_i32("uint32_field"),
+        Err(e) if e.to_string().contains(&u32::MAX.to_string()),
+        "The offending value should be reported"
+    );
+    assert_matches!(sample.get_u32("int32_field"), Err(_));
+    assert_matches!(sample.get_u8("uint16_field"), Err(_));
+    assert_matches!(sample.get_i16("uint16_field"), Err(_));
+}
+
+#[test]
+fn test_bytes_round_trip() {
+    const PAYLOAD_SIZE: usize = 64 * 1024;
+
+    let mut context = test_utils::TestContextBuilder::integers()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
+
+    let payload: Vec<u8> = (0..PAYLOAD_SIZE).map(|i| (i % 251) as u8).collect();
+
+    output
+        .instance()
+        .set_bytes("octet_sequence", &payload)
+        .expect("Failed to set octet_sequence");
+    output.write().expect("Failed to write data");
+    input
+        .wait_with_timeout(TEST_TIMEOUT)
+        .expect("Failed to wait for data");
+    input.take().expect("Failed to take data");
+
+    let received = input
+        .last_sample()
+        .expect("Failed to access samples")
+        .expect("Expected a sample")
+        .get_bytes("octet_sequence")
+        .expect("Failed to get octet_sequence");
+
+    assert_eq!(payload, received, "Payload should round-trip unchanged");
+
+    assert_matches!(
+        output.instance().set_bytes("octet_sequence[0]", &payload),
+        Err(_),
+        "Element paths can't be set as a whole"
+    );
+}
+
+#[test]
+fn test_sequence_accessors() {
+    let mut context = test_utils::TestContextBuilder::complex()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
+
+    let doubles = [1.5, -2.25, 3.0];
+    let strings = ["one".to_string(), "two".to_string(), "three".to_string()];
+    {
+        let mut instance = output.instance();
+        instance
+            .set_sequence("double_sequence", &doubles)
+            .expect("Failed to set double_sequence");
+        instance
+            .set_sequence("string_array", &strings)
+            .expect("Failed to set string_array");
+
+        assert_matches!(
+            instance.set_sequence("double_sequence", &[0.0; 4]),
+            Err(_),
+            "The sequence holds at most 3 elements"
+        );
+    }
+    output.write().expect("Failed to write data");
+    input
+        .wait_with_timeout(TEST_TIMEOUT)
+        .expect("Failed to wait for data");
+    input.take().expect("Failed to take data");
+
+    let sample = input
+        .last_sample()
+        .expect("Failed to access samples")
+        .expect("Expected a sample");
+
+    assert_matches!(
+        sample.get_sequence::<f64>("double_sequence"),
+        Ok(values) if values == doubles
+    );
+    assert_matches!(
+        sample.get_sequence::<String>("string_array"),
+        Ok(values) if values == strings
+    );
+    assert_matches!(
+        sample.get_sequence::<f64>("string_array"),
+        Err(e) if e.to_string().contains("Element #0"),
+        "Type mismatches should name the element index"
+    );
+    assert_matches!(
+        sample.get_sequence::<f64>("simple.long_field"),
+        Err(_),
+        "Scalar fields are not sequences"
+    );
+}
+
+#[test]
+fn test_get_length() {
+    let mut context = test_utils::TestContextBuilder::complex()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
+
+    for length in [0, 2] {
+        output
+            .instance()
+            .set_sequence("double_sequence", &vec![1.0; length])
+            .expect("Failed to set double_sequence");
+        output.write().expect("Failed to write data");
+        input
+            .wait_with_timeout(TEST_TIMEOUT)
+            .expect("Failed to wait for data");
+        input.take().expect("Failed to take data");
+
+        let sample = input
+            .last_sample()
+            .expect("Failed to access samples")
+            .expect("Expected a sample");
+        assert_matches!(
+            sample.get_length("double_sequence"),
+            Ok(value) if value == length,
+            "Sequence length should vary per sample"
+        );
+        assert_matches!(sample.get_length("string_array"), Ok(3));
+        assert_matches!(
+            sample.get_length("simple.long_field"),
+            Err(_),
+            "Scalar fields have no length"
+        );
+        assert_matches!(
+            sample.get_length("missing_field"),
+            Err(e) if e.is_field_not_found()
+        );
+    }
+}
+
+#[test]
+fn test_enum_strings() {
+    let mut context = test_utils::TestContextBuilder::complex()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
+
+    let mut write_and_take = |label: &str| {
+        output
+            .instance()
+            .set_enum_string("simple.enum_field", label)?;
+        output.write().expect("Failed to write data");
+        input
+            .wait_with_timeout(TEST_TIMEOUT)
+            .expect("Failed to wait for data");
+        input.take().expect("Failed to take data");
+
+        let sample = input
+            .last_sample()
+            .expect("Failed to access samples")
+            .expect("Expected a sample");
+        let number = sample
+            .get_number("simple.enum_field")
+            .expect("Failed to get simple.enum_field");
+        let label = sample
+            .get_enum_string("simple.enum_field")
+            .expect("Failed to get simple.enum_field as a string");
+
+        rtiddsconnector::ConnectorResult::Ok((number, label))
+    };
+
+    // TestEnum { Red, Green, Blue }
+    let (number, label) = write_and_take("2").expect("Failed to set a numeric value");
+    assert_eq!(2.0, number);
+    assert!(
+        label == "Blue" || label == "2",
+        "Expected the enumerator name or its value, got '{}'",
+        label
+    );
+
+    // Setting by name depends on the JSON support of the native library
+    if let Ok((number, label)) = write_and_take("Green") {
+        assert_eq!(1.0, number);
+        assert!(
+            label == "Green" || label == "1",
+            "Unexpected label '{}'",
+            label
+        );
+    }
+
+    assert_matches!(
+        input
+            .last_sample()
+            .expect("Failed to access samples")
+            .expect("Expected a sample")
+            .get_enum_string("simple"),
+        Err(_),
+        "Structures are not enumerations"
+    );
+}
+
+#[test]
+fn test_union_helpers() {
+    let mut context = test_utils::TestContextBuilder::complex()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
+
+    // Switch the active member between writes
+    for (member, value) in [
+        ("string", SelectedValue::String("Hello".to_string())),
+        ("number", SelectedValue::Number(123.45)),
+        ("boolean", SelectedValue::Boolean(true)),
+    ] {
+        output
+            .instance()
+            .set_union("union", member, value.clone())
+            .expect("Failed to set union member");
+        output.write().expect("Failed to write data");
+        input
+            .wait_with_timeout(TEST_TIMEOUT)
+            .expect("Failed to wait for data");
+        input.take().expect("Failed to take data");
+
+        let sample = input
+            .last_sample()
+            .expect("Failed to access samples")
+            .expect("Expected a sample");
+        assert_matches!(
+            sample.get_union("union"),
+            Ok((active, received)) if active == member && received == value,
+            "Unexpected active member for '{}'",
+            member
+        );
+    }
+
+    let sample = input
+        .last_sample()
+        .expect("Failed to access samples")
+        .expect("Expected a sample");
+    assert_matches!(
+        sample.get_union("simple"),
+        Err(_),
+        "Structures are not unions"
+    );
+}
+
+#[test]
+fn test_dds_union() {
+    use rtiddsconnector::DdsUnion;
+    use test_utils::types::{ComplexStruct, TestUnion};
+
+    let mut context = test_utils::TestContextBuilder::complex()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
+
+    assert_eq!(TestUnion::MEMBERS, ["string", "number", "boolean"]);
+
+    for union in [
+        TestUnion::String("Hello".to_string()),
+        TestUnion::Number(123.45),
+        TestUnion::Boolean(true),
+    ] {
+        // Only the active member is serialized
+        let json = serde_json::to_value(&union).expect("Failed to serialize union");
+        assert_eq!(
+            json.as_object().map(|members| members.len()),
+            Some(1),
+            "Unexpected JSON for {:?}: {}",
+            union,
+            json
+        );
+        assert!(json.get(union.member_name()).is_some());
+
+        let data = ComplexStruct {
+            union: union.clone(),
+            ..Default::default()
+        };
+        output
+            .write_typed(&data)
+            .expect("Failed to write typed data");
+        input
+            .wait_with_timeout(TEST_TIMEOUT)
+            .expect("Failed to wait for data");
+        input.take().expect("Failed to take data");
+
+        let sample = input
+            .last_sample()
+            .expect("Failed to access samples")
+            .expect("Expected a sample");
+        assert_matches!(
+            sample.deserialize::<ComplexStruct>(),
+            Ok(received) if received.union == union
+        );
+        assert_matches!(
+            sample.deserialize_field::<TestUnion>("union"),
+            Ok(received) if received == union
+        );
+        // Selected by the discriminator
+        assert_matches!(
+            sample.deserialize_union::<TestUnion>("union"),
+            Ok(received) if received == union
+        );
+    }
+
+    // Null members and unknown keys are ignored, but one member must be set
+    assert_matches!(
+        serde_json::from_str::<TestUnion>(
+            r#"{"string": null, "other": 1, "number": 2.5}"#
+        ),
+        Ok(TestUnion::Number(2.5))
+    );
+    assert_matches!(serde_json::from_str::<TestUnion>("{}"), Err(_));
+    assert_matches!(
+        serde_json::from_str::<TestUnion>(r#"{"string": "a", "boolean": true}"#),
+        Err(_)
+    );
+
+    let sample = input
+        .last_sample()
+        .expect("Failed to access samples")
+        .expect("Expected a sample");
+    assert_matches!(
+        sample.deserialize_union::<TestUnion>("simple"),
+        Err(_),
+        "Structures are not unions"
+    );
+}
+
+#[test]
+fn test_per_field_serialization() {
+    use test_utils::types::{OptionalStruct, SimpleStruct, TestEnum};
+
+    let mut context = test_utils::TestContextBuilder::complex()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
+
+    let simple = SimpleStruct {
+        long_field: 42,
+        double_field: 4.5,
+        boolean_field: true,
+        string_field: "Hello".to_string(),
+        enum_field: TestEnum::Blue,
+    };
+    // Unset optional members are skipped when serializing
+    let optional = OptionalStruct {
+        long_field: Some(7),
+        string_field: Some("World".to_string()),
+        ..Default::default()
+    };
+
+    let mut instance = output.instance();
+    instance
+        .serialize_field("simple", &simple)
+        .expect("Failed to serialize 'simple'");
+    instance
+        .serialize_field("optional", &optional)
+        .expect("Failed to serialize 'optional'");
+    // Setting a nested member keeps the rest of the structure
+    instance
+        .serialize_field("simple.double_field", &8.25)
+        .expect("Failed to serialize 'simple.double_field'");
+    assert_matches!(
+        instance.serialize_field("long_matrix[0]", &[1, 2, 3]),
+        Err(_),
+        "Element indexes are not supported"
+    );
+
+    output.write().expect("Failed to write data");
+    input
+        .wait_with_timeout(TEST_TIMEOUT)
+        .expect("Failed to wait for data");
+    input.take().expect("Failed to take data");
+
+    let sample = input
+        .last_sample()
+        .expect("Failed to access samples")
+        .expect("Expected a sample");
+    assert_matches!(
+        sample.deserialize_field::<SimpleStruct>("simple"),
+        Ok(received) if received == SimpleStruct { double_field: 8.25, ..simple }
+    );
+    assert_matches!(
+        sample.deserialize_field::<OptionalStruct>("optional"),
+        Ok(received) if received == optional
+    );
+    assert_matches!(
+        sample.deserialize_field::<String>("simple.string_field"),
+        Ok(received) if received == "Hello"
+    );
+
+    // The same fields as JSON values
+    assert_matches!(
+        sample.get_member_value("simple.long_field"),
+        Ok(received) if received == 42
+    );
+    assert_matches!(
+        sample.get_member_value("optional"),
+        Ok(received) if received == serde_json::json!({ "long_field": 7, "string_field": "World" })
+    );
+    assert_matches!(
+        sample.get_member_value("non_existent_field"),
+        Err(e) if e.is_field_not_found()
+    );
+
+    // Errors include the path of the field
+    assert_matches!(
+        sample.deserialize_field::<SimpleStruct>("optional"),
+        Err(e) if e.to_string().contains("'optional'")
+    );
+    assert_matches!(
+        sample.deserialize_field::<SimpleStruct>("non_existent_field"),
+        Err(e) if e.is_field_not_found()
+    );
+}
+
+#[test]
+fn test_deserialize_with_options() {
+    use rtiddsconnector::DeserializeOptions;
+    use test_utils::types::{ComplexStruct, OptionalStruct, SimpleStruct, TestUnion};
+
+    /// The complex type, with a member which the DDS type doesn't have
+    #[derive(Debug, serde::Deserialize)]
+    #[allow(dead_code)]
+    struct WithExtraField {
+        simple: SimpleStruct,
+        optional: OptionalStruct,
+        union: TestUnion,
+        long_matrix: [[i32; 3]; 3],
+        string_array: [String; 3],
+        double_sequence: Vec<f64>,
+        extra_field: i32,
+    }
+
+    /// Only the optional members, as required ones
+    #[derive(Debug, serde::Deserialize)]
+    #[allow(dead_code)]
+    struct RequiredOptionals {
+        optional: SimpleStruct,
+    }
+
+    /// Only the simple members, with the string one as a number
+    #[derive(Debug, serde::Deserialize)]
+    struct NumericString {
+        simple: NumericSimple,
+    }
+
+    #[derive(Debug, serde::Deserialize)]
+    struct NumericSimple {
+        string_field: u32,
+    }
+
+    let mut context = test_utils::TestContextBuilder::complex()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
+
+    let data = ComplexStruct {
+        simple: SimpleStruct {
+            long_field: 1,
+            string_field: "42".to_string(),
+            ..Default::default()
+        },
+        optional: OptionalStruct {
+            long_field: Some(7),
+            ..Default::default()
+        },
+        union: TestUnion::Number(2.5),
+        ..Default::default()
+    };
+    output
+        .write_typed(&data)
+        .expect("Failed to write typed data");
+    input
+        .wait_with_timeout(TEST_TIMEOUT)
+        .expect("Failed to wait for data");
+    input.take().expect("Failed to take data");
+    let sample = input
+        .last_sample()
+        .expect("Failed to access samples")
+        .expect("Expected a sample");
+
+    // The default options deserialize as `deserialize` does
+    assert_matches!(
+        sample.deserialize_with::<ComplexStruct>(DeserializeOptions::default()),
+        Ok(received) if received == data
+    );
+    assert_eq!(
+        sample
+            .deserialize_with::<WithExtraField>(DeserializeOptions::default())
+            .map_err(|e| e.to_string())
+            .expect_err("The extra field should be missing"),
+        sample
+            .deserialize::<WithExtraField>()
+            .map_err(|e| e.to_string())
+            .expect_err("The extra field should be missing"),
+        "The default options should report the same error"
+    );
+
+    // Members which the type doesn't have
+    let strict = DeserializeOptions::new().deny_unknown_fields(true);
+    assert_matches!(
+        sample.deserialize_with::<ComplexStruct>(strict),
+        Ok(received) if received == data
+    );
+    assert_matches!(
+        sample.deserialize_with::<NumericString>(strict.numeric_strings(true)),
+        Err(e) if e.to_string().contains("unknown field"),
+        "Members missing from the type should be rejected"
+    );
+
+    // Members missing from the sample
+    assert_matches!(
+        sample.deserialize_with::<RequiredOptionals>(DeserializeOptions::new()),
+        Err(e) if e.to_string().contains("missing field")
+    );
+    let lenient = DeserializeOptions::new().missing_as_default(true);
+    assert_matches!(
+        sample.deserialize_with::<RequiredOptionals>(lenient),
+        Ok(RequiredOptionals { optional })
+            if optional == SimpleStruct { long_field: 7, ..Default::default() }
+    );
+    assert_matches!(
+        sample.deserialize_with::<WithExtraField>(lenient),
+        Ok(WithExtraField { extra_field: 0, .. })
+    );
+    assert_matches!(
+        sample.deserialize_with::<ComplexStruct>(lenient),
+        Ok(received) if received.optional == data.optional,
+        "Unset optional members should still be None"
+    );
+
+    // Strings holding numbers
+    assert_matches!(
+        sample.deserialize_with::<NumericString>(DeserializeOptions::new()),
+        Err(_)
+    );
+    assert_matches!(
+        sample.deserialize_with::<NumericString>(
+            DeserializeOptions::new().numeric_strings(true)
+        ),
+        Ok(NumericString {
+            simple: NumericSimple { string_field: 42 }
+        })
+    );
+    assert_matches!(
+        sample.deserialize_with::<ComplexStruct>(DeserializeOptions::new().numeric_strings(true)),
+        Ok(received) if received.simple.string_field == "42",
+        "String fields should keep numeric strings"
+    );
+}
+
+#[test]
+fn test_deserialize_borrowed() {
+    use std::borrow::Cow;
+    use test_utils::types::{LargeStringRef, LargeStringStruct};
+
+    let mut context = test_utils::TestContextBuilder::large_strings()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
+
+    let data = LargeStringStruct {
+        first: "plain".to_string(),
+        second: "with \"quotes\"".to_string(),
+        third: "x".repeat(1 << 16),
+    };
+    output
+        .write_typed(&data)
+        .expect("Failed to write typed data");
+    input
+        .wait_with_timeout(TEST_TIMEOUT)
+        .expect("Failed to wait for data");
+    input.take().expect("Failed to take data");
+    let sample = input
+        .last_sample()
+        .expect("Failed to access samples")
+        .expect("Expected a sample");
+
+    // Stale contents of the buffer are replaced
+    let mut buf = "stale".to_string();
+    let borrowed: LargeStringRef = sample
+        .deserialize_borrowed(&mut buf)
+        .expect("Failed to deserialize borrowed data");
+    assert_matches!(borrowed.first, Cow::Borrowed("plain"));
+    assert_matches!(
+        &borrowed.second,
+        Cow::Owned(second) if *second == data.second,
+        "Escaped strings can't be borrowed"
+    );
+    assert_eq!(borrowed.third, data.third);
+
+    // Same outcome as the owned path
+    let owned: LargeStringStruct = sample
+        .deserialize_borrowed(&mut buf)
+        .expect("Failed to deserialize owned data");
+    assert_eq!(owned, data);
+    assert_eq!(
+        owned,
+        sample
+            .deserialize::<LargeStringStruct>()
+            .expect("Failed to deserialize data")
+    );
+
+    // A borrowed `&str` can't hold an escaped string
+    #[derive(Debug, serde::Deserialize)]
+    #[allow(dead_code)]
+    struct EscapedRef<'a> {
+        second: &'a str,
+    }
+    assert_matches!(
+        sample.deserialize_borrowed::<EscapedRef>(&mut buf),
+        Err(e) if e.is_serialization_error()
+    );
+}
+
+#[test]
+fn test_enum_by_name() {
+    use rtiddsconnector::DdsEnum;
+    use test_utils::types::{SimpleStruct, TestEnum};
+
+    /// SimpleStruct, with its enum serialized by name
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct NamedSimpleStruct {
+        long_field: i32,
+        double_field: f64,
+        boolean_field: bool,
+        string_field: String,
+        #[serde(with = "rtiddsconnector::dds_enum::by_name")]
+        enum_field: TestEnum,
+    }
+
+    let mut context = test_utils::TestContextBuilder::simple()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Failed to get test entities")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("Output should be available in test context");
+    let mut input = entities
+        .input
+        .expect("Input should be available in test context");
+
+    let numeric = SimpleStruct {
+        long_field: 1,
+        enum_field: TestEnum::Green,
+        ..Default::default()
+    };
+    let named = NamedSimpleStruct {
+        long_field: 2,
+        double_field: 0.0,
+        boolean_field: false,
+        string_field: String::new(),
+        enum_field: TestEnum::Blue,
+    };
+    assert_matches!(
+        serde_json::to_value(&named),
+        Ok(json) if json["enum_field"] == "Blue"
+    );
+
+    // Written by value, read by name
+    output
+        .write_typed(&numeric)
+        .expect("Failed to write numeric data");
+    input
+        .wait_with_timeout(TEST_TIMEOUT)
+        .expect("Failed to wait for data");
+    assert_matches!(
+        input.take_deserialized::<NamedSimpleStruct>().as_deref(),
+        Ok([received]) if received.enum_field == TestEnum::Green
+    );
+
+    // Written by name, read by value
+    output
+        .write_typed(&named)
+        .expect("Failed to write named data");
+    input
+        .wait_with_timeout(TEST_TIMEOUT)
+        .expect("Failed to wait for data");
+    input.take().expect("Failed to take data");
+    let sample = input
+        .last_sample()
+        .expect("Failed to access samples")
+        .expect("Expected a sample");
+    assert_matches!(sample.get_number("enum_field"), Ok(2.0));
+    assert_matches!(
+        sample.deserialize::<SimpleStruct>(),
+        Ok(received) if received.enum_field == TestEnum::Blue
+    );
+    assert_matches!(sample.deserialize::<NamedSimpleStruct>(), Ok(received) if received == named);
+
+    // Unknown enumerators are rejected either way
+    assert_matches!(TestEnum::from_name("Yellow"), None);
+    assert_matches!(
+        serde_json::from_str::<NamedSimpleStruct>(
+            r#"{"long_field":0,"double_field":0,"boolean_field":false,"string_field":"","enum_field":7}"#
+        ),
+        Err(_)
+    );
+
+    #[cfg(feature = "derive")]
+    {
+        #[derive(Debug, Clone, Copy, PartialEq, rtiddsconnector::DdsEnum)]
+        enum DerivedEnum {
+            Red = 0,
+            #[dds(rename = "GREEN")]
+            Green = 1,
+        }
+
+        assert_eq!(DerivedEnum::Green.name(), "GREEN");
+        assert_eq!(DerivedEnum::from_name("GREEN"), Some(DerivedEnum::Green));
+        assert_eq!(DerivedEnum::from_value(0), Some(DerivedEnum::Red));
+        assert_eq!(DerivedEnum::Green.value(), 1);
+    }
+}
+
+#[test]
+fn test_check_type() {
+    use rtiddsconnector::TypeMismatch;
+    use test_utils::types::{SimpleStruct, TestEnum};
+
+    /// SimpleStruct, with a misspelled field
+    #[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+    #[allow(dead_code)]
+    struct MisspelledStruct {
+        long_field: i32,
+        double_feild: f64,
+        boolean_field: bool,
+        string_field: String,
+        enum_field: TestEnum,
+    }
+
+    /// SimpleStruct, with a field of the wrong kind
+    #[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+    #[allow(dead_code)]
+    struct WrongKindStruct {
+        long_field: String,
+    }
+
+    let mut context = test_utils::TestContextBuilder::simple()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Failed to get test entities")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("Output should be available in test context");
+    let mut input = entities
+        .input
+        .expect("Input should be available in test context");
+
+    let missing = vec![TypeMismatch::MissingMember {
+        field: "double_feild".to_string(),
+    }];
+
+    output
+        .instance()
+        .set_string("string_field", "Before")
+        .expect("Failed to set string_field");
+    assert_matches!(output.check_type::<SimpleStruct>(), Ok(mismatches) if mismatches.is_empty());
+    assert_matches!(output.check_type::<MisspelledStruct>(), Ok(mismatches) if mismatches == missing);
+    assert_matches!(
+        output.check_type::<WrongKindStruct>().as_deref(),
+        Ok([TypeMismatch::WrongKind { field, .. }]) if field == "long_field"
+    );
+    // The scratch instance is cleared afterwards
+    assert!(
+        !output.instance().to_string().contains("Before"),
+        "The instance should be cleared"
+    );
+
+    // Input checks need a sample
+    assert_matches!(input.check_type::<SimpleStruct>(), Err(e) if e.is_serialization_error());
+    output
+        .write_typed(&SimpleStruct {
+            string_field: "Hello".to_string(),
+            ..Default::default()
+        })
+        .expect("Failed to write data");
+    input
+        .wait_with_timeout(TEST_TIMEOUT)
+        .expect("Failed to wait for data");
+    input.take().expect("Failed to take data");
+
+    assert_matches!(input.check_type::<SimpleStruct>(), Ok(mismatches) if mismatches.is_empty());
+    assert_matches!(input.check_type::<MisspelledStruct>(), Ok(mismatches) if mismatches == missing);
+    assert_matches!(
+        input.check_type::<WrongKindStruct>().as_deref(),
+        Ok([TypeMismatch::WrongKind { field, .. }]) if field == "long_field"
+    );
+}
+
+#[test]
+fn test_deserialize_direct() {
+    use test_utils::types::{
+        ComplexStruct, OptionalStruct, SimpleStruct, TestEnum, TestUnion,
+    };
+
+    let mut context = test_utils::TestContextBuilder::complex()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
+
+    let data = ComplexStruct {
+        simple: SimpleStruct {
+            long_field: -42,
+            double_field: 2.5,
+            boolean_field: true,
+            string_field: "Hello".to_string(),
+            enum_field: TestEnum::Green,
+        },
+        optional: OptionalStruct {
+            long_field: Some(7),
+            enum_field: Some(TestEnum::Blue),
+            ..Default::default()
+        },
+        union: TestUnion::String("Selected".to_string()),
+        long_matrix: [[1, 2, 3], [4, 5, 6], [7, 8, 9]],
+        string_array: ["a".to_string(), "b".to_string(), "c".to_string()],
+        double_sequence: vec![0.5, 1.5, 2.5],
+    };
+
+    for union in [
+        data.union.clone(),
+        TestUnion::Number(123.45),
+        TestUnion::Boolean(true),
+    ] {
+        let data = ComplexStruct {
+            union,
+            ..data.clone()
+        };
+        output.clear_members().expect("Failed to clear members");
+        output
+            .instance()
+            .serialize(&data)
+            .expect("Failed to serialize data");
+        output.write().expect("Failed to write data");
+        input
+            .wait_with_timeout(TEST_TIMEOUT)
+            .expect("Failed to wait for data");
+        input.take().expect("Failed to take data");
+
+        let sample = input
+            .last_sample()
+            .expect("Failed to access samples")
+            .expect("Expected a sample");
+        let expected = sample
+            .deserialize::<ComplexStruct>()
+            .expect("Failed to deserialize sample");
+        assert_eq!(expected, data);
+        assert_matches!(
+            sample.deserialize_direct::<ComplexStruct>(),
+            Ok(received) if received == expected
+        );
+        assert_matches!(
+            sample.deserialize_direct::<SimpleStruct>(),
+            Err(e) if e.is_field_not_found()
+        );
+    }
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn test_derive_dds_data_matches_serialize() {
+    use rtiddsconnector::{DdsData, Input, Output};
+    use test_utils::types::{
+        ComplexStruct, OptionalStruct, SimpleStruct, TestEnum, TestUnion,
+    };
+
+    /// Write the data set by `set`, returning the JSON of the sample received
+    /// and the data retrieved from it with `DdsData::get_from`
+    fn round_trip<T: DdsData>(
+        output: &mut Output,
+        input: &mut Input,
+        set: impl FnOnce(&mut rtiddsconnector::Instance),
+    ) -> (serde_json::Value, T) {
+        output.clear_members().expect("Failed to clear members");
+        set(&mut output.instance());
+        output.write().expect("Failed to write data");
+        input
+            .wait_with_timeout(TEST_TIMEOUT)
+            .expect("Failed to wait for data");
+        input.take().expect("Failed to take data");
+
+        let sample = input
+            .last_sample()
+            .expect("Failed to access samples")
+            .expect("Expected a sample");
+        let json =
+            serde_json::from_str(&sample.to_string()).expect("Invalid sample JSON");
+        (json, T::get_from(&sample).expect("Failed to get data"))
+    }
+
+    let simple = SimpleStruct {
+        long_field: -42,
+        double_field: 2.5,
+        boolean_field: true,
+        string_field: "Hello, derive!".to_string(),
+        enum_field: TestEnum::Green,
+    };
+
+    let mut context = test_utils::TestContextBuilder::simple()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
+
+    let (expected, _) = round_trip::<SimpleStruct>(&mut output, &mut input, |instance| {
+        instance
+            .serialize(&simple)
+            .expect("Failed to serialize data")
+    });
+    let (json, received) =
+        round_trip::<SimpleStruct>(&mut output, &mut input, |instance| {
+            simple.set_into(instance).expect("Failed to set data")
+        });
+    assert_eq!(expected, json);
+    assert_eq!(simple, received);
+    drop((output, input, context));
+
+    let mut context = test_utils::TestContextBuilder::complex()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
+
+    let complex = ComplexStruct {
+        simple,
+        optional: OptionalStruct {
+            long_field: Some(7),
+            enum_field: Some(TestEnum::Blue),
+            ..Default::default()
+        },
+        union: TestUnion::String("Selected".to_string()),
+        long_matrix: [[1, 2, 3], [4, 5, 6], [7, 8, 9]],
+        string_array: ["a".to_string(), "b".to_string(), "c".to_string()],
+        double_sequence: vec![0.5, 1.5, 2.5],
+    };
+
+    for union in [
+        complex.union.clone(),
+        TestUnion::Number(123.45),
+        TestUnion::Boolean(true),
+    ] {
+        let data = ComplexStruct {
+            union,
+            ..complex.clone()
+        };
+
+        let (expected, _) =
+            round_trip::<ComplexStruct>(&mut output, &mut input, |instance| {
+                instance.serialize(&data).expect("Failed to serialize data")
+            });
+        let (json, received) =
+            round_trip::<ComplexStruct>(&mut output, &mut input, |instance| {
+                data.set_into(instance).expect("Failed to set data")
+            });
+        assert_eq!(expected, json);
+        assert_eq!(data, received);
+    }
+
+    // Unset optional members and shorter sequences replace the previous values
+    let data = ComplexStruct {
+        double_sequence: vec![1.0],
+        ..Default::default()
+    };
+    let (_, received) =
+        round_trip::<ComplexStruct>(&mut output, &mut input, |instance| {
+            complex.set_into(instance).expect("Failed to set data");
+            data.set_into(instance).expect("Failed to set data")
+        });
+    assert_eq!(data, received);
+}
+
+#[test]
+fn test_complex_members_as_json() {
+    use test_utils::types::{ComplexStruct, TestUnion};
+
+    let mut context = test_utils::TestContextBuilder::complex()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
+
+    let data = ComplexStruct {
+        union: TestUnion::Number(123.45),
+        long_matrix: [[1, 2, 3], [4, 5, 6], [7, 8, 9]],
+        ..Default::default()
+    };
+    output
+        .instance()
+        .serialize(&data)
+        .expect("Failed to serialize data");
+
+    // Complex members are set from JSON, leaving the other members untouched
+    output
+        .instance()
+        .set_value(
+            "simple",
+            SelectedValue::Json(
+                serde_json::json!({"long_field": 5, "string_field": "Json"}),
+            ),
+        )
+        .expect("Failed to set 'simple' from JSON");
+    assert_matches!(
+        output.instance().set_value(
+            "long_matrix[0]",
+            SelectedValue::Json(serde_json::json!([0, 0, 0]))
+        ),
+        Err(_),
+        "Element indexes can't be set from JSON"
+    );
+    output.write().expect("Failed to write data");
+    input
+        .wait_with_timeout(TEST_TIMEOUT)
+        .expect("Failed to wait for data");
+    input.take().expect("Failed to take data");
+
+    let sample = input
+        .last_sample()
+        .expect("Failed to access samples")
+        .expect("Expected a sample");
+    let received = sample
+        .get_value("simple")
+        .expect("Failed 'get_value' operation on 'simple'");
+    assert_eq!(
+        received.as_json().map(|v| &v["long_field"]),
+        Some(&serde_json::json!(5))
+    );
+    assert_eq!(
+        received.as_json().map(|v| &v["string_field"]),
+        Some(&serde_json::json!("Json"))
+    );
+    assert_matches!(
+        sample.get_value("long_matrix"),
+        Ok(SelectedValue::Json(value)) if value == serde_json::json!([[1, 2, 3], [4, 5, 6], [7, 8, 9]])
+    );
+    assert_matches!(
+        sample.get_value("long_matrix[1]"),
+        Ok(SelectedValue::Json(value)) if value == serde_json::json!([4, 5, 6])
+    );
+    assert_matches!(
+        sample.get_value("union"),
+        Ok(SelectedValue::Json(value)) if value == serde_json::json!({"number": 123.45})
+    );
+
+    // Scalars and missing members are unaffected
+    assert_eq!(
+        sample.get_value("simple.long_field").ok(),
+        Some(SelectedValue::Number(5.0))
+    );
+    assert_matches!(
+        sample.get_value("no_such_member"),
+        Err(e) if e.is_field_not_found()
+    );
+}
+
+#[test]
+fn test_deserialize_direct_64_bit_integers() {
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct Integers {
+        int64_field: i64,
+        uint64_field: u64,
+        int32_field: i32,
+    }
+
+    let mut context = test_utils::TestContextBuilder::integers()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
+
+    let data = Integers {
+        int64_field: i64::MIN + 1,
+        uint64_field: (1 << 53) + 1,
+        int32_field: i32::MIN,
+    };
+    {
+        let mut instance = output.instance();
+        instance
+            .set_i64("int64_field", data.int64_field)
+            .expect("Failed to set int64_field");
+        instance
+            .set_u64("uint64_field", data.uint64_field)
+            .expect("Failed to set uint64_field");
+        instance
+            .set_i32("int32_field", data.int32_field)
+            .expect("Failed to set int32_field");
+    }
+    output.write().expect("Failed to write data");
+    input
+        .wait_with_timeout(TEST_TIMEOUT)
+        .expect("Failed to wait for data");
+    input.take().expect("Failed to take data");
+
+    let sample = input
+        .last_sample()
+        .expect("Failed to access samples")
+        .expect("Expected a sample");
+    assert_matches!(
+        sample.deserialize_direct::<Integers>(),
+        Ok(received) if received == data
+    );
+}
+
+#[test]
+fn test_persistent_instance() {
+    use test_utils::types::{SimpleStruct, TestEnum};
+
+    let mut context = test_utils::TestContextBuilder::simple()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
 
+    let receive = |input: &mut rtiddsconnector::Input| -> SimpleStruct {
         input
             .wait_with_timeout(TEST_TIMEOUT)
             .expect("Failed to wait for data");
-        input.read().expect("Failed to read data");
-
+        input.take().expect("Failed to take data");
         input
+            .last_sample()
+            .expect("Failed to access samples")
+            .expect("Expected a sample")
+            .deserialize()
+            .expect("Failed to deserialize sample")
     };
 
-    let iter = input.into_iter().valid_only();
-    let (_, upper_hint) = iter.size_hint();
-
-    assert_eq!(
-        1,
-        upper_hint.expect("Our implementation always yields a upper hint"),
-        "Expected only one sample"
+    // By default, the instance is obtained again after each write, and the
+    // values of its fields also carry over
+    output
+        .instance()
+        .set_string("string_field", "Default")
+        .expect("Failed to set string_field");
+    output.write().expect("Failed to write data");
+    receive(&mut input);
+    output
+        .instance()
+        .set_i32("long_field", 1)
+        .expect("Failed to set long_field");
+    output.write().expect("Failed to write data");
+    assert_matches!(
+        receive(&mut input),
+        SimpleStruct { long_field: 1, ref string_field, .. } if string_field == "Default"
     );
 
-    let sample = iter.take(1).next().expect("Expected a sample");
-
-    let json = sample
-        .get_value_json("simple")
-        .expect("Failed 'get_value_json' operation on the sample");
-
-    // It's not trivial to build the expected JSON string manually, so we use
-    // serde_json to build it for us.
-    #[derive(serde::Serialize, serde::Deserialize)]
-    struct SimpleStruct {
-        long_field: i64,
-        double_field: f64,
-        enum_field: i32,
-        string_field: String,
-        boolean_field: bool,
+    let mut persistent = output.persistent_instance();
+    persistent
+        .instance()
+        .set_string("string_field", "Persistent")
+        .expect("Failed to set string_field");
+    for long_field in 2..5 {
+        persistent
+            .instance()
+            .set_i32("long_field", long_field)
+            .expect("Failed to set long_field");
+        persistent.write().expect("Failed to write data");
+
+        let received = receive(&mut input);
+        assert_eq!(received.long_field, long_field);
+        assert_eq!(received.string_field, "Persistent");
     }
 
-    let expected_json = {
-        let mut value = serde_json::to_value(SimpleStruct {
-            long_field: 10,
-            double_field: 123.45,
-            enum_field: 1,
-            string_field: "Hello".to_string(),
-            boolean_field: true,
-        })
-        .expect("Failed to serialize expected value to JSON Value");
-        value.sort_all_objects();
-        value.to_string()
-    };
-
-    let json = {
-        let mut value = serde_json::from_str::<serde_json::Value>(&json)
-            .expect("Failed to parse JSON representation from get_value_json");
-        value.sort_all_objects();
-        value.to_string()
-    };
-
+    // Clearing the members releases the persistent instance
+    persistent.clear_members().expect("Failed to clear members");
+    output.write().expect("Failed to write data");
     assert_eq!(
-        expected_json, json,
-        "JSON representation does not match expected"
+        receive(&mut input),
+        SimpleStruct {
+            enum_field: TestEnum::Red,
+            ..Default::default()
+        }
     );
 }
 
 #[test]
-fn test_write_with_params() {
-    let mut context = test_utils::TestContextBuilder::complex()
+fn test_write_with_closure() {
+    use test_utils::types::SimpleStruct;
+
+    let mut context = test_utils::TestContextBuilder::simple()
         .build()
         .expect("Failed to create test context");
     let entities = context
         .test_entities()
         .expect("Error in test entities creation")
         .ensure_discovery();
-
     let mut output = entities
         .output
         .expect("This test expects an available output");
@@ -1231,122 +4877,334 @@ fn test_write_with_params() {
         .input
         .expect("This test expects an available input");
 
-    let mut instance = output.instance();
-    instance
-        .set_string("simple.string_field", "Hello")
-        .expect("Failed to set field");
-
-    output.write().expect("Failed to write with parameters");
-
+    output
+        .with_instance(|instance| instance.set_string("string_field", "Scoped"))
+        .expect("Failed to set string_field");
+    output
+        .write_with(|instance| instance.set_i32("long_field", 1))
+        .expect("Failed to write data");
     input
         .wait_with_timeout(TEST_TIMEOUT)
         .expect("Failed to wait for data");
+    input.take().expect("Failed to take data");
+    let sample = input
+        .last_sample()
+        .expect("Failed to access samples")
+        .expect("Expected a sample");
+    assert_matches!(
+        sample.deserialize::<SimpleStruct>(),
+        Ok(SimpleStruct { long_field: 1, ref string_field, .. }) if string_field == "Scoped"
+    );
 
-    input.take().expect("Failed to read data");
-
-    let iter = input.into_iter().valid_only();
-    let (_, upper_hint) = iter.size_hint();
-    assert_eq!(
-        1,
-        upper_hint.expect("Our implementation always yields a upper hint"),
-        "Expected only one sample"
+    // Errors from the closure are returned unchanged, and nothing is written
+    assert_matches!(
+        output.write_with(|instance| instance.set_number("non_existent_field", 1.0)),
+        Err(e) if e.is_field_not_found()
+    );
+    let application_error = || {
+        <rtiddsconnector::ConnectorError as serde::ser::Error>::custom(
+            "Application error",
+        )
+    };
+    assert_matches!(
+        output.write_with(|_| Err(application_error())),
+        Err(e) if e.to_string() == application_error().to_string()
+    );
+    assert_matches!(
+        input.wait_with_timeout(std::time::Duration::from_millis(500)),
+        Err(e) if e.is_timeout()
     );
+}
+
+#[test]
+fn test_write_all() {
+    use test_utils::types::{SimpleStruct, TestEnum};
+
+    let mut context = test_utils::TestContextBuilder::simple()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
 
-    let sample = iter.take(1).next().expect("Expected a sample");
+    let receive_all = |input: &mut rtiddsconnector::Input, count: usize| {
+        let mut received = Vec::new();
+        while received.len() < count {
+            input
+                .wait_with_timeout(TEST_TIMEOUT)
+                .expect("Failed to wait for data");
+            input.take().expect("Failed to take data");
+            for sample in input.valid_samples().expect("Failed to access samples") {
+                received.push(
+                    sample
+                        .expect("Failed to access sample")
+                        .deserialize::<SimpleStruct>()
+                        .expect("Failed to deserialize sample"),
+                );
+            }
+        }
+        received
+    };
 
+    let items: Vec<SimpleStruct> = (0..100)
+        .map(|i| SimpleStruct {
+            long_field: i,
+            double_field: f64::from(i) * 0.5,
+            boolean_field: i % 2 == 0,
+            string_field: std::format!("Item {}", i),
+            enum_field: TestEnum::Blue,
+        })
+        .collect();
+    assert_matches!(output.write_all(&items), Ok(100));
+    assert_eq!(receive_all(&mut input, items.len()), items);
+
+    let params =
+        rtiddsconnector::WriteParams::write().with_source_timestamp(1_000_000_000);
+    assert_matches!(output.write_all_with_params(&items[..3], &params), Ok(3));
+    assert_eq!(receive_all(&mut input, 3), &items[..3]);
+
+    // Writing stops at the first failure, which reports the index of the item
+    let items = [
+        serde_json::json!({ "long_field": 1 }),
+        serde_json::json!({ "long_field": 2 }),
+        serde_json::json!({ "non_existent_field": 3 }),
+        serde_json::json!({ "long_field": 4 }),
+    ];
+    assert_matches!(
+        output.write_all(&items),
+        Err(e) if e.is_field_not_found() && e.to_string().contains("Item #2")
+    );
+    let received = receive_all(&mut input, 2);
     assert_eq!(
-        "Hello",
-        sample
-            .get_string("simple.string_field")
-            .expect("Failed to get 'simple.string_field' value")
+        received.iter().map(|s| s.long_field).collect::<Vec<_>>(),
+        [1, 2]
     );
+}
 
-    output
-        .write_with_params(&rtiddsconnector::WriteParams::dispose())
-        .expect("Failed to write with dispose parameters");
+#[test]
+fn test_time_fields() {
+    use rtiddsconnector::DdsTime;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-    input
-        .wait_with_timeout(TEST_TIMEOUT)
-        .expect("Failed to wait for data after dispose");
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct TimeStruct {
+        timestamp: DdsTime,
+    }
 
-    input.take().expect("Failed to read data after dispose");
+    let mut context = test_utils::TestContextBuilder::time()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
 
-    let iter = input.into_iter();
-    assert_eq!(1, iter.len(), "Expected one (invalid) sample after dispose");
+    let mut take_sample = |output: &mut rtiddsconnector::Output| {
+        output.write().expect("Failed to write data");
+        input
+            .wait_with_timeout(TEST_TIMEOUT)
+            .expect("Failed to wait for data");
+        input.take().expect("Failed to take data");
+        let sample = input
+            .last_sample()
+            .expect("Failed to access samples")
+            .expect("Expected a sample");
+        (
+            sample.get_time("timestamp"),
+            sample.deserialize::<TimeStruct>(),
+        )
+    };
 
-    let iter = iter.valid_only();
-    let (_, upper_hint) = iter.size_hint();
-    assert_eq!(
-        1,
-        upper_hint.expect("Our implementation always yields a upper hint"),
-        "Expected only one sample after dispose"
+    // Sub-second precision and the epoch itself round-trip exactly
+    for time in [
+        UNIX_EPOCH + Duration::new(1_700_000_000, 123_456_789),
+        UNIX_EPOCH,
+    ] {
+        output
+            .instance()
+            .set_time("timestamp", time)
+            .expect("Failed to set time");
+        let (received, deserialized) = take_sample(&mut output);
+        assert_matches!(received, Ok(received) if received == time);
+        assert_matches!(
+            deserialized,
+            Ok(TimeStruct { timestamp }) if SystemTime::try_from(timestamp).ok() == Some(time)
+        );
+    }
+
+    // Serialized as the `sec` and `nanosec` members
+    let data = TimeStruct {
+        timestamp: DdsTime {
+            sec: 42,
+            nanosec: 999_999_999,
+        },
+    };
+    output
+        .instance()
+        .serialize(&data)
+        .expect("Failed to serialize data");
+    let (received, deserialized) = take_sample(&mut output);
+    assert_matches!(
+        received,
+        Ok(received) if received == UNIX_EPOCH + Duration::new(42, 999_999_999)
     );
+    assert_matches!(deserialized, Ok(received) if received == data);
 
+    // Times which don't fit in the seconds of a DDS time are rejected
     assert_matches!(
-        iter.take(1).next(),
-        None,
-        "Expected instance to be disposed"
+        output.instance().set_time(
+            "timestamp",
+            UNIX_EPOCH + Duration::from_secs(u64::from(u32::MAX))
+        ),
+        Err(e) if matches!(
+            e.kind(),
+            ErrorKind::Invalid { what: InvalidErrorKind::Conversion, .. }
+        )
+    );
+    assert_matches!(
+        output
+            .instance()
+            .set_time("non_existent_field", UNIX_EPOCH),
+        Err(e) if e.is_field_not_found()
     );
 }
 
 #[test]
-fn test_typed_serialize_and_deserialize() {
-    use test_utils::types::SimpleStruct;
+fn test_error_field_paths() {
+    use test_utils::types::{ComplexStruct, SimpleStruct};
+
+    /// The complex type, with the enum of the simple member as a string
+    #[derive(Debug, serde::Deserialize)]
+    #[allow(dead_code)]
+    struct CorruptComplex {
+        simple: CorruptSimple,
+        double_sequence: Vec<bool>,
+    }
 
-    let mut context = test_utils::TestContextBuilder::simple()
+    #[derive(Debug, serde::Deserialize)]
+    #[allow(dead_code)]
+    struct CorruptSimple {
+        enum_field: String,
+    }
+
+    /// A value which always fails to be serialized
+    struct Unserializable;
+
+    impl serde::Serialize for Unserializable {
+        fn serialize<S: serde::Serializer>(&self, _: S) -> Result<S::Ok, S::Error> {
+            Err(serde::ser::Error::custom("unserializable value"))
+        }
+    }
+
+    #[derive(serde::Serialize)]
+    struct UnserializableComplex {
+        simple: UnserializableSimple,
+    }
+
+    #[derive(serde::Serialize)]
+    struct UnserializableSimple {
+        long_field: i32,
+        enum_field: Unserializable,
+    }
+
+    let mut context = test_utils::TestContextBuilder::complex()
         .build()
         .expect("Failed to create test context");
     let entities = context
         .test_entities()
-        .expect("Failed to get test entities")
+        .expect("Error in test entities creation")
         .ensure_discovery();
-
     let mut output = entities
         .output
-        .expect("Output should be available in test context");
+        .expect("This test expects an available output");
     let mut input = entities
         .input
-        .expect("Input should be available in test context");
+        .expect("This test expects an available input");
 
-    // Create a simple struct to serialize
-    let original_data = SimpleStruct {
-        long_field: 42,
-        double_field: 123.45,
-        boolean_field: true,
-        string_field: "Hello, DDS!".to_string(),
-        enum_field: test_utils::types::TestEnum::Green,
+    let data = ComplexStruct {
+        simple: SimpleStruct {
+            long_field: 1,
+            ..Default::default()
+        },
+        double_sequence: vec![1.5],
+        ..Default::default()
     };
-
-    // Serialize and write the data
     output
-        .instance()
-        .serialize(&original_data)
-        .expect("Failed to serialize data");
-
-    output.write().expect("Failed to write data");
-
-    // Wait for data to be available
+        .write_typed(&data)
+        .expect("Failed to write typed data");
     input
         .wait_with_timeout(TEST_TIMEOUT)
         .expect("Failed to wait for data");
-
-    // Take the data and read it
-    input.take().expect("Failed to take samples");
-
-    // Get the first sample
+    input.take().expect("Failed to take data");
     let sample = input
-        .into_iter()
-        .valid_only()
-        .next()
-        .expect("Expected at least one valid sample");
+        .last_sample()
+        .expect("Failed to access samples")
+        .expect("Expected a sample");
 
-    // Deserialize the data
-    let deserialized_data: SimpleStruct =
-        sample.deserialize().expect("Failed to deserialize data");
+    // Deserialization errors report the nested field which doesn't match
+    assert_matches!(
+        sample.deserialize::<CorruptComplex>(),
+        Err(e) if e.field_path() == Some("simple.enum_field")
+    );
+    assert_matches!(
+        sample.deserialize_with::<CorruptComplex>(rtiddsconnector::DeserializeOptions::new()),
+        Err(e) if e.field_path() == Some("simple.enum_field")
+    );
+    assert_matches!(
+        sample.deserialize_field::<CorruptSimple>("simple"),
+        Err(e) if e.field_path() == Some("simple.enum_field"),
+        "The path should include the field being deserialized"
+    );
+    assert_matches!(
+        sample.deserialize_field::<Vec<bool>>("double_sequence"),
+        Err(e) if e.field_path() == Some("double_sequence[0]")
+    );
+    assert_matches!(
+        sample.deserialize_field::<Vec<f64>>("double_sequence"),
+        Ok(sequence) if sequence == [1.5]
+    );
 
-    // Verify the deserialized data matches the original
-    assert_eq!(
-        original_data, deserialized_data,
-        "Deserialized data should match original"
+    // Serialization errors report the nested field which failed
+    let unserializable = UnserializableComplex {
+        simple: UnserializableSimple {
+            long_field: 2,
+            enum_field: Unserializable,
+        },
+    };
+    assert_matches!(
+        output.instance().serialize(&unserializable),
+        Err(e) if matches!(
+            e.kind(),
+            ErrorKind::Invalid { what: InvalidErrorKind::Serialization, .. }
+        ) && e.field_path() == Some("simple.enum_field")
+    );
+    assert_matches!(
+        output.write_typed(&unserializable),
+        Err(e) if e.field_path() == Some("simple.enum_field")
+    );
+    assert_matches!(
+        output
+            .instance()
+            .serialize_field("simple", &unserializable.simple),
+        Err(e) if e.field_path() == Some("simple.enum_field")
+    );
+
+    // Errors which aren't about a field have no path
+    assert_matches!(
+        output.instance().set_as_json("not json"),
+        Err(e) if e.field_path().is_none()
     );
 }
@@ -3,7 +3,7 @@ mod test_utils;
 #[macro_use]
 extern crate assert_matches;
 
-use test_utils::TestContextBuilder;
+use test_utils::{TEST_TIMEOUT, TestContextBuilder};
 
 //   it('Input object should not get instantiated for invalid DataReader', function () {
 //   it('Input object should get instantiated for valid ' +
@@ -33,7 +33,7 @@ fn test_input_creation_and_display() {
         .expect("Failed to get valid Input");
 
     assert_eq!(
-        r#"Input { name: "TestSubscriber::TestReader", parent: Connector { name: "TestDomainParticipantLibrary::SimpleParticipant" } }"#,
+        r#"Input { name: "TestSubscriber::TestReader", parent: Connector { name: "TestDomainParticipantLibrary::SimpleParticipant", domain_id: Some(61) } }"#,
         format!("{:?}", input),
     );
 
@@ -54,6 +54,55 @@ fn test_input_creation_and_display() {
     );
 }
 
+#[test]
+fn test_input_ownership_released_once() {
+    let context = TestContextBuilder::simple_input_only()
+        .build()
+        .expect("Failed to create test context");
+    let connector = &context.connector;
+    let clone = connector.clone();
+
+    let input = connector
+        .get_input("TestSubscriber::TestReader")
+        .expect("Failed to get valid Input");
+
+    // Failed attempts, from any connector handle, don't release the owner's hold
+    for _ in 0..2 {
+        assert_matches!(
+            connector.get_input("TestSubscriber::TestReader"),
+            Err(_),
+            "get_input should fail while the Input is owned"
+        );
+        assert_matches!(
+            clone.get_input("TestSubscriber::TestReader"),
+            Err(_),
+            "get_input should fail while the Input is owned through a clone"
+        );
+    }
+
+    // Moving the Input doesn't release it either
+    let moved = std::thread::spawn(move || input)
+        .join()
+        .expect("Thread panicked");
+    assert_matches!(
+        connector.get_input("TestSubscriber::TestReader"),
+        Err(_),
+        "get_input should fail while the moved Input is owned"
+    );
+
+    // Dropping releases it once, so the next owner is exclusive again
+    drop(moved);
+    let input = clone
+        .get_input("TestSubscriber::TestReader")
+        .expect("Failed to get Input after release");
+    assert_matches!(
+        connector.get_input("TestSubscriber::TestReader"),
+        Err(_),
+        "get_input should fail while the new Input is owned"
+    );
+    drop(input);
+}
+
 #[test]
 fn test_input_basic_operations_no_data() {
     // Test the basic Input API without requiring actual data
@@ -68,13 +117,13 @@ fn test_input_basic_operations_no_data() {
 
     assert_matches!(
         input.read(),
-        Ok(_),
-        "Read operation should succeed (may return 0 samples)"
+        Ok(0),
+        "Read operation should succeed and return 0 samples"
     );
     assert_matches!(
         input.take(),
-        Ok(_),
-        "Take operation should succeed (may return 0 samples)"
+        Ok(0),
+        "Take operation should succeed and return 0 samples"
     );
     assert_matches!(
         input.return_loan(),
@@ -97,6 +146,12 @@ fn test_input_basic_operations_no_data() {
             .expect("Failed to get matched publications")
     );
 
+    let matched = input
+        .matched_publications()
+        .expect("Failed to parse matched publications");
+    assert_eq!(1, matched.len());
+    assert_eq!(Some("TestWriter"), matched[0].name.as_deref());
+
     assert_matches!(
         input.wait_with_timeout(std::time::Duration::from_secs(1)),
         Err(_),
@@ -104,6 +159,217 @@ fn test_input_basic_operations_no_data() {
     );
 }
 
+#[cfg(feature = "unsafe-interop")]
+#[test]
+fn test_native_sample_ptr_out_of_range() {
+    let context = TestContextBuilder::simple_input_only()
+        .build()
+        .expect("Failed to create test context");
+    let input = context
+        .connector
+        .get_input("TestSubscriber::TestReader")
+        .expect("Failed to get valid Input");
+
+    assert_matches!(
+        input.native_sample_ptr(0),
+        Err(_),
+        "There is no sample to point to before reading"
+    );
+}
+
+#[test]
+fn test_input_wait_interrupted() {
+    let context = TestContextBuilder::simple_input_only()
+        .build()
+        .expect("Failed to create test context");
+    let input = context
+        .connector
+        .get_input("TestSubscriber::TestReader")
+        .expect("Failed to get valid Input");
+
+    let wake_handle = context.connector.wake_handle();
+    let waker = std::thread::spawn(move || {
+        std::thread::sleep(std::time::Duration::from_millis(200));
+        wake_handle.trigger();
+    });
+
+    let start = std::time::Instant::now();
+    assert_matches!(
+        input.wait(),
+        Err(e) if e.is_interrupted(),
+        "An infinite wait should be interrupted by the wake handle"
+    );
+    assert!(
+        start.elapsed() < TEST_TIMEOUT,
+        "Interrupted wait should return promptly"
+    );
+
+    waker.join().expect("Waker thread panicked");
+
+    // A trigger only affects the waits already in progress
+    assert_matches!(
+        input.wait_with_timeout(std::time::Duration::from_millis(200)),
+        Err(e) if e.is_timeout(),
+        "A later wait should time out normally"
+    );
+}
+
+#[test]
+fn test_input_wait_cancelled() {
+    use rtiddsconnector::CancelToken;
+    use std::time::Duration;
+
+    let context = TestContextBuilder::simple_input_only()
+        .build()
+        .expect("Failed to create test context");
+    let input = context
+        .connector
+        .get_input("TestSubscriber::TestReader")
+        .expect("Failed to get valid Input");
+
+    let cancel = CancelToken::new().with_granularity(Duration::from_millis(20));
+    let canceller = {
+        let cancel = cancel.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(200));
+            cancel.cancel();
+        })
+    };
+
+    let start = std::time::Instant::now();
+    assert_matches!(
+        input.wait_cancellable(None, &cancel),
+        Err(e) if e.is_cancelled(),
+        "An infinite wait should be cancelled by the token"
+    );
+    assert!(
+        start.elapsed() < Duration::from_millis(500),
+        "Cancelled wait should return within the granularity"
+    );
+
+    canceller.join().expect("Canceller thread panicked");
+
+    // Unlike a wake handle, a cancelled token also cancels later waits
+    assert_matches!(
+        input.wait_for_publications_cancellable(None, &cancel),
+        Err(e) if e.is_cancelled()
+    );
+    assert_matches!(
+        input.wait_cancellable(
+            Some(Duration::from_millis(200)),
+            &CancelToken::new()
+        ),
+        Err(e) if e.is_timeout(),
+        "A wait with another token should time out normally"
+    );
+}
+
+#[test]
+fn test_error_kind_retry_decision() {
+    use rtiddsconnector::{ConnectorError, ErrorKind, NotFoundErrorKind};
+
+    // Timeouts are worth retrying, missing entities or fields aren't
+    fn should_retry(error: &ConnectorError) -> bool {
+        match error.kind() {
+            ErrorKind::Timeout | ErrorKind::Interrupted | ErrorKind::Busy { .. } => true,
+            ErrorKind::NotFound { .. } | ErrorKind::Invalid { .. } => false,
+            _ => false,
+        }
+    }
+
+    let context = TestContextBuilder::simple_input_only()
+        .build()
+        .expect("Failed to create test context");
+    let input = context
+        .connector
+        .get_input("TestSubscriber::TestReader")
+        .expect("Failed to get valid Input");
+
+    let mut attempts = 0;
+    let error = loop {
+        attempts += 1;
+        match input.wait_with_timeout(std::time::Duration::from_millis(50)) {
+            Err(e) if attempts < 3 && should_retry(&e) => continue,
+            Err(e) => break e,
+            Ok(()) => panic!("No data should be received"),
+        }
+    };
+    assert_eq!(attempts, 3);
+    assert_eq!(error.kind(), &ErrorKind::Timeout);
+
+    let error = context
+        .connector
+        .get_input("TestSubscriber::NoSuchReader")
+        .expect_err("The input doesn't exist");
+    assert!(!should_retry(&error));
+    assert_matches!(
+        error.kind(),
+        ErrorKind::NotFound {
+            what: NotFoundErrorKind::Entity,
+            name,
+        } if name == "TestSubscriber::NoSuchReader"
+    );
+}
+
+#[test]
+fn test_native_errors_name_input_and_field() {
+    let mut context = TestContextBuilder::simple()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
+
+    output.write().expect("Failed to write data");
+    input
+        .wait_with_timeout(TEST_TIMEOUT)
+        .expect("Failed to wait for data");
+    input.take().expect("Failed to take data");
+
+    let sample = input
+        .last_sample()
+        .expect("Failed to access samples")
+        .expect("Expected a sample");
+    assert_matches!(
+        sample.get_number("non_existent_field"),
+        Err(e) if e.is_field_not_found()
+            && e.to_string().contains(
+                "while getting 'non_existent_field' on input 'TestSubscriber::TestReader'"
+            ),
+        "The error should name the input and the field"
+    );
+}
+
+#[test]
+fn test_input_wait_and_take_no_data() {
+    let context = TestContextBuilder::simple_input_only()
+        .build()
+        .expect("Failed to create test context");
+    let mut input = context
+        .connector
+        .get_input("TestSubscriber::TestReader")
+        .expect("Failed to get valid Input");
+
+    assert_matches!(
+        input.wait_and_take(Some(std::time::Duration::from_millis(200))),
+        Err(e) if e.is_timeout(),
+        "wait_and_take should time out when no data is available"
+    );
+
+    assert_matches!(
+        input.wait_and_take_until_timeout(std::time::Duration::from_millis(200)),
+        Err(e) if e.is_timeout(),
+        "wait_and_take_until_timeout should time out when no data is available"
+    );
+}
+
 #[test]
 fn test_sample_iterator_basic() {
     let context = TestContextBuilder::simple_input_only()
@@ -123,6 +389,19 @@ fn test_sample_iterator_basic() {
         panic!("Iterator should be empty, and this shouldn't happen");
     }
 
+    assert_matches!(input.len(), Ok(0), "Input should have no samples");
+    assert_matches!(
+        input.samples().map(|iter| iter.len()),
+        Ok(0),
+        "samples() should yield no samples"
+    );
+    assert_matches!(
+        input.valid_samples().map(|mut iter| iter.next().is_none()),
+        Ok(true),
+        "valid_samples() should yield no samples"
+    );
+    assert_matches!(input.is_empty(), Ok(true), "Input should be empty");
+
     // Test iterator creation and basic operations
     let mut iter = input.into_iter();
 
@@ -146,3 +425,269 @@ fn test_sample_iterator_basic() {
         "Iterator should be empty, next() should return None"
     );
 }
+
+#[test]
+fn test_inputs_iterated_concurrently() {
+    use std::sync::Barrier;
+
+    const ITERATIONS: usize = 50;
+
+    let context = TestContextBuilder::multiple_entities()
+        .build()
+        .expect("Failed to create test context");
+    let connector = &context.connector;
+
+    let readers = [
+        "TestSubscriber::SimpleReader",
+        "TestSubscriber::ComplexReader",
+    ];
+    let writers = [
+        "TestPublisher::SimpleWriter",
+        "TestPublisher::ComplexWriter",
+    ];
+
+    let inputs =
+        readers.map(|name| connector.get_input(name).expect("Failed to get Input"));
+    let mut outputs =
+        writers.map(|name| connector.get_output(name).expect("Failed to get Output"));
+    for (input, output) in inputs.iter().zip(&outputs) {
+        assert_matches!(
+            input.wait_for_publications_with_timeout(TEST_TIMEOUT),
+            Ok(_)
+        );
+        assert_matches!(
+            output.wait_for_subscriptions_with_timeout(TEST_TIMEOUT),
+            Ok(_)
+        );
+    }
+    for output in &mut outputs {
+        output.write().expect("Failed to write data");
+    }
+
+    let barrier = Barrier::new(inputs.len());
+    std::thread::scope(|scope| {
+        for mut input in inputs {
+            let barrier = &barrier;
+            scope.spawn(move || {
+                input
+                    .wait_with_timeout(TEST_TIMEOUT)
+                    .expect("Failed to wait for data");
+                barrier.wait();
+
+                // Both threads take and access their samples at the same time
+                for _ in 0..ITERATIONS {
+                    input.read().expect("Failed to read data");
+                    for sample in input.samples().expect("Failed to access samples") {
+                        assert!(sample.is_valid().expect("Failed to check validity"));
+                        serde_json::from_str::<serde_json::Value>(&sample.to_string())
+                            .expect("The sample should be displayed as JSON");
+                    }
+                }
+            });
+        }
+    });
+}
+
+#[test]
+fn test_input_spawn_into_channel() {
+    use test_utils::types::SimpleStruct;
+
+    const SAMPLES: i32 = 10;
+
+    let mut context = TestContextBuilder::simple()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let input = entities
+        .input
+        .expect("This test expects an available input");
+
+    // A capacity smaller than the samples written exercises the backpressure
+    let (handle, receiver) = input.spawn_into_channel::<SimpleStruct>(2);
+    for i in 0..SAMPLES {
+        output
+            .instance()
+            .set_number("long_field", i.into())
+            .expect("Failed to set long_field");
+        output.write().expect("Failed to write");
+    }
+
+    for expected in 0..SAMPLES {
+        let data = receiver
+            .recv_timeout(TEST_TIMEOUT)
+            .expect("Timed out waiting for a sample")
+            .expect("Failed to deserialize sample");
+        assert_eq!(expected, data.long_field);
+    }
+
+    // The thread notices the dropped receiver once it has data to send
+    drop(receiver);
+    output.write().expect("Failed to write");
+    assert_matches!(handle.join().expect("The thread should not panic"), Ok(()));
+    assert_matches!(
+        context.connector.get_input("TestSubscriber::TestReader"),
+        Ok(_),
+        "The thread should have released the input"
+    );
+}
+
+#[test]
+fn test_input_on_data() {
+    use std::sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    };
+
+    const SAMPLES: usize = 5;
+
+    let mut context = TestContextBuilder::simple()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let input = entities
+        .input
+        .expect("This test expects an available input");
+
+    let count = Arc::new(AtomicUsize::new(0));
+    let handle = {
+        let count = Arc::clone(&count);
+        input.on_data(move |sample| {
+            assert_matches!(sample.is_valid(), Ok(true));
+            if count.fetch_add(1, Ordering::SeqCst) == 2 {
+                panic!("Panicking on the third sample");
+            }
+        })
+    };
+
+    for i in 0..SAMPLES {
+        output
+            .instance()
+            .set_number("long_field", i as f64)
+            .expect("Failed to set long_field");
+        output.write().expect("Failed to write");
+    }
+
+    let deadline = std::time::Instant::now() + TEST_TIMEOUT;
+    while count.load(Ordering::SeqCst) < SAMPLES {
+        assert!(
+            std::time::Instant::now() < deadline,
+            "Timed out waiting for the callbacks"
+        );
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    // The panic is reported, and the callback keeps being invoked after it
+    assert_eq!(vec!["Panicking on the third sample"], handle.take_panics());
+    assert!(!handle.is_finished());
+    assert_eq!(SAMPLES, count.load(Ordering::SeqCst));
+
+    let start = std::time::Instant::now();
+    drop(handle);
+    assert!(
+        start.elapsed() < std::time::Duration::from_secs(1),
+        "Dropping the handle should stop the thread within a wait cycle"
+    );
+    assert_matches!(
+        context.connector.get_input("TestSubscriber::TestReader"),
+        Ok(_),
+        "The thread should have released the input"
+    );
+}
+
+#[cfg(feature = "async")]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_input_wait_async() {
+    use std::time::Duration;
+
+    let mut context = TestContextBuilder::simple()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let mut input = entities
+        .input
+        .expect("This test expects an available input");
+
+    assert_matches!(
+        input.wait_async(Some(Duration::from_millis(100))).await,
+        Err(e) if e.is_timeout(),
+        "Nothing has been written yet"
+    );
+
+    let writer = tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        output.write()
+    });
+    assert_matches!(input.wait_async(Some(TEST_TIMEOUT)).await, Ok(()));
+    assert_matches!(
+        writer.await.expect("The writer task should not panic"),
+        Ok(())
+    );
+    assert_matches!(input.take(), Ok(1));
+
+    // The publication was matched already, so no change happens
+    assert_matches!(
+        input
+            .wait_for_publications_async(Some(Duration::from_millis(100)))
+            .await,
+        Err(e) if e.is_timeout()
+    );
+}
+
+#[cfg(feature = "async")]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_input_into_stream() {
+    use futures::StreamExt;
+    use test_utils::types::SimpleStruct;
+
+    const SAMPLES: i32 = 20;
+
+    let mut context = TestContextBuilder::simple()
+        .build()
+        .expect("Failed to create test context");
+    let entities = context
+        .test_entities()
+        .expect("Error in test entities creation")
+        .ensure_discovery();
+    let mut output = entities
+        .output
+        .expect("This test expects an available output");
+    let input = entities
+        .input
+        .expect("This test expects an available input");
+
+    let mut stream = input.into_stream::<SimpleStruct>(TEST_TIMEOUT);
+    let writer = std::thread::spawn(move || {
+        (0..SAMPLES).try_for_each(|i| {
+            output.instance().set_number("long_field", i.into())?;
+            output.write()
+        })
+    });
+
+    for expected in 0..SAMPLES {
+        let data = tokio::time::timeout(TEST_TIMEOUT, stream.next())
+            .await
+            .expect("Timed out waiting for a sample")
+            .expect("The stream should not end")
+            .expect("Failed to deserialize sample");
+        assert_eq!(expected, data.long_field);
+    }
+    assert_matches!(writer.join().expect("The writer should not panic"), Ok(()));
+}
@@ -30,7 +30,7 @@ fn test_output_creation_and_display() -> ConnectorFallible {
     let output = connector.take_output("TestPublisher::TestWriter")?;
 
     assert_eq!(
-        r#"Output { name: "TestPublisher::TestWriter", parent: Connector { name: "TestDomainParticipantLibrary::SimpleParticipant" } }"#,
+        r#"Output { name: "TestPublisher::TestWriter", parent: Connector { name: "TestDomainParticipantLibrary::SimpleParticipant", domain_id: Some(61) } }"#,
         format!("{:?}", output),
     );
 
@@ -104,6 +104,10 @@ fn test_output_wait_operations() -> ConnectorFallible {
         output.display_matched_subscriptions()?,
     );
 
+    let matched = output.matched_subscriptions()?;
+    assert_eq!(1, matched.len());
+    assert_eq!(Some("TestReader"), matched[0].name.as_deref());
+
     assert_matches!(
         output.wait_with_timeout(std::time::Duration::from_secs(1)),
         Ok(_),
@@ -113,6 +117,55 @@ fn test_output_wait_operations() -> ConnectorFallible {
     Ok(())
 }
 
+#[test]
+fn test_output_wait_for_subscription_event() -> ConnectorFallible {
+    use rtiddsconnector::MatchEvent;
+
+    let context = TestContextBuilder::simple_output_only().build()?;
+    let output = context.connector.get_output("TestPublisher::TestWriter")?;
+
+    let event =
+        output.wait_for_subscription_event(Some(std::time::Duration::from_secs(2)))?;
+    assert_eq!(
+        event,
+        MatchEvent {
+            change: 1,
+            total: 1
+        }
+    );
+    assert!(event.is_match());
+    assert!(!event.is_unmatch());
+
+    // No further change happens
+    assert!(
+        output
+            .wait_for_subscription_event(Some(std::time::Duration::from_millis(100)))
+            .is_err_and(|e| e.is_timeout())
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_output_matched_subscription_count() -> ConnectorFallible {
+    let context = TestContextBuilder::simple_output_only().build()?;
+    let output = context.connector.get_output("TestPublisher::TestWriter")?;
+
+    output.wait_for_subscriptions_with_timeout(std::time::Duration::from_secs(2))?;
+    assert_eq!(1, output.matched_subscription_count()?);
+    assert!(output.is_matched()?);
+
+    // Querying doesn't block while another thread waits on the same Output
+    std::thread::scope(|s| -> ConnectorFallible {
+        let waiter = s.spawn(|| {
+            output.wait_for_subscriptions_with_timeout(std::time::Duration::from_secs(1))
+        });
+        assert_eq!(1, output.matched_subscription_count()?);
+        assert!(waiter.join().is_ok_and(|result| result.is_err()));
+        Ok(())
+    })
+}
+
 #[test]
 fn test_output_instance_field_operations() -> ConnectorFallible {
     let context = TestContextBuilder::simple_output_only().build()?;
@@ -155,6 +208,24 @@ fn test_output_instance_field_operations() -> ConnectorFallible {
     Ok(())
 }
 
+#[test]
+fn test_native_errors_name_output_and_field() -> ConnectorFallible {
+    let context = TestContextBuilder::simple_output_only().build()?;
+    let output = context.connector.get_output("TestPublisher::TestWriter")?;
+
+    let mut instance = output.instance();
+    assert_matches!(
+        instance.set_string("non_existent_field", "value"),
+        Err(e) if e.is_field_not_found()
+            && e.to_string().contains(
+                "while setting 'non_existent_field' on output 'TestPublisher::TestWriter'"
+            ),
+        "The error should name the output and the field"
+    );
+
+    Ok(())
+}
+
 #[test]
 fn test_output_wait_for_acknowledgments() -> ConnectorFallible {
     let context = TestContextBuilder::simple_output_only().build()?;
@@ -171,6 +242,47 @@ fn test_output_wait_for_acknowledgments() -> ConnectorFallible {
     Ok(())
 }
 
+#[test]
+fn test_output_wait_for_acknowledgments_detailed() -> ConnectorFallible {
+    use rtiddsconnector::AcknowledgmentStatus;
+
+    let context = TestContextBuilder::simple_output_only().build()?;
+    let mut output = context.connector.get_output("TestPublisher::TestWriter")?;
+    let timeout = std::time::Duration::from_secs(1);
+
+    // Nothing was outstanding
+    assert_eq!(
+        output.wait_for_acknowledgments_detailed(timeout)?,
+        AcknowledgmentStatus {
+            acknowledged: true,
+            written_since_acknowledged: 0,
+        }
+    );
+
+    // Without matched readers, written samples are acknowledged right away
+    for _ in 0..3 {
+        output.write()?;
+    }
+    assert_eq!(
+        output.wait_for_acknowledgments_detailed(timeout)?,
+        AcknowledgmentStatus {
+            acknowledged: true,
+            written_since_acknowledged: 3,
+        }
+    );
+
+    output.write()?;
+    output.wait_with_timeout(timeout)?;
+    assert_eq!(
+        output
+            .wait_for_acknowledgments_detailed(timeout)?
+            .written_since_acknowledged,
+        0
+    );
+
+    Ok(())
+}
+
 #[test]
 fn test_output_instance_display_and_operations() -> ConnectorFallible {
     let context = TestContextBuilder::simple_output_only().build()?;
@@ -227,3 +339,513 @@ fn test_output_instance_display_and_operations() -> ConnectorFallible {
 
     Ok(())
 }
+
+/// Counts the allocations made by the current thread, to compare serialization paths.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+impl CountingAllocator {
+    fn count() {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+    }
+
+    /// Returns the number of allocations made by `f` in the current thread.
+    fn allocations_of<R>(f: impl FnOnce() -> R) -> (usize, R) {
+        let before = ALLOCATIONS.with(|count| count.get());
+        let result = f();
+        (ALLOCATIONS.with(|count| count.get()) - before, result)
+    }
+}
+
+unsafe impl std::alloc::GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: std::alloc::Layout) -> *mut u8 {
+        Self::count();
+        unsafe { std::alloc::System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: std::alloc::Layout) {
+        unsafe { std::alloc::System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(
+        &self,
+        ptr: *mut u8,
+        layout: std::alloc::Layout,
+        new_size: usize,
+    ) -> *mut u8 {
+        Self::count();
+        unsafe { std::alloc::System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn complex_data() -> test_utils::types::ComplexStruct {
+    use test_utils::types::{
+        ComplexStruct, OptionalStruct, SimpleStruct, TestEnum, TestUnion,
+    };
+
+    ComplexStruct {
+        simple: SimpleStruct {
+            long_field: -42,
+            double_field: 2.5,
+            boolean_field: true,
+            string_field: "Hello, direct serialization!".to_string(),
+            enum_field: TestEnum::Blue,
+        },
+        optional: OptionalStruct {
+            double_field: Some(0.125),
+            string_field: Some("Optional".to_string()),
+            ..Default::default()
+        },
+        union: TestUnion::Number(123.45),
+        long_matrix: [[1, 2, 3], [4, 5, 6], [7, 8, 9]],
+        string_array: ["a".to_string(), "b".to_string(), "c".to_string()],
+        double_sequence: vec![0.5, 1.5, 2.5],
+    }
+}
+
+#[test]
+fn test_serialize_direct_matches_json() -> ConnectorFallible {
+    let context = TestContextBuilder::complex().build()?;
+    let mut output = context.connector.get_output("TestPublisher::TestWriter")?;
+
+    let instance_json = |output: &rtiddsconnector::Output| -> serde_json::Value {
+        serde_json::from_str(&output.instance().to_string())
+            .expect("Invalid instance JSON")
+    };
+
+    let data = complex_data();
+    output.instance().serialize(&data)?;
+    let expected = instance_json(&output);
+
+    output.clear_members()?;
+    output.instance().serialize_direct(&data)?;
+    assert_eq!(expected, instance_json(&output));
+
+    // Sequences are replaced rather than overwritten element by element
+    let shorter = test_utils::types::ComplexStruct {
+        double_sequence: vec![1.0, 2.0],
+        ..data
+    };
+    output.instance().serialize_direct(&shorter)?;
+    assert_eq!(
+        serde_json::json!([1.0, 2.0]),
+        instance_json(&output)["double_sequence"]
+    );
+
+    // Only structs and maps can be serialized into the instance
+    assert_matches!(output.instance().serialize_direct(&[1, 2, 3]), Err(_));
+    assert_matches!(output.instance().serialize_direct(&42), Err(_));
+    assert_matches!(
+        output
+            .instance()
+            .serialize_direct(&std::collections::HashMap::from([("non_existent_field", 1)])),
+        Err(e) if e.is_field_not_found()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_serialize_direct_64_bit_integers() -> ConnectorFallible {
+    #[derive(serde::Serialize)]
+    struct Integers {
+        id: i32,
+        int64_field: i64,
+        uint64_field: u64,
+    }
+
+    let context = TestContextBuilder::integers().build()?;
+    let mut output = context.connector.get_output("TestPublisher::TestWriter")?;
+
+    let data = Integers {
+        id: 1,
+        int64_field: i64::MIN + 1,
+        uint64_field: u64::MAX - 1,
+    };
+    let mut instance = output.instance();
+    instance.set_i32("id", data.id)?;
+    instance.set_i64("int64_field", data.int64_field)?;
+    instance.set_u64("uint64_field", data.uint64_field)?;
+    let expected = instance.to_string();
+
+    output.clear_members()?;
+    output.instance().serialize_direct(&data)?;
+    assert_eq!(expected, output.instance().to_string());
+
+    Ok(())
+}
+
+#[test]
+fn test_serialize_direct_allocations() -> ConnectorFallible {
+    let context = TestContextBuilder::complex().build()?;
+    let output = context.connector.get_output("TestPublisher::TestWriter")?;
+    let data = complex_data();
+
+    let (json_allocations, result) =
+        CountingAllocator::allocations_of(|| output.instance().serialize(&data));
+    result?;
+    let (direct_allocations, result) =
+        CountingAllocator::allocations_of(|| output.instance().serialize_direct(&data));
+    result?;
+
+    // The JSON path formats the whole sample into a growing string, while the
+    // direct path reuses a single buffer for all field names and values
+    assert!(
+        direct_allocations < json_allocations,
+        "Expected fewer allocations with serialize_direct ({}) than with serialize ({})",
+        direct_allocations,
+        json_allocations
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_entity_name_not_converted_per_call() -> ConnectorFallible {
+    const CALLS: usize = 100;
+
+    let context = TestContextBuilder::simple_output_only().build()?;
+    let output = context.connector.get_output("TestPublisher::TestWriter")?;
+    let mut instance = output.instance();
+
+    let (allocations, result) = CountingAllocator::allocations_of(|| {
+        (0..CALLS).try_for_each(|i| instance.set_boolean("boolean_field", i % 2 == 0))
+    });
+    result?;
+
+    // Only the field name is converted into a C string on every call
+    assert!(
+        allocations <= CALLS,
+        "Expected at most {} allocations, got {}",
+        CALLS,
+        allocations
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_entity_name_shared_when_acquired() -> ConnectorFallible {
+    const ITERATIONS: usize = 10_000;
+
+    let context = TestContextBuilder::simple_output_only().build()?;
+    // Let the map of owned entities allocate its table first
+    drop(context.connector.get_output("TestPublisher::TestWriter")?);
+
+    let (allocations, result) = CountingAllocator::allocations_of(|| {
+        (0..ITERATIONS).try_for_each(|_| {
+            context
+                .connector
+                .get_output("TestPublisher::TestWriter")
+                .map(drop)
+        })
+    });
+    result?;
+
+    // The name is converted into a C string and into a name shared between
+    // the Output and its ownership record, once per acquisition
+    assert!(
+        allocations <= 2 * ITERATIONS,
+        "Expected at most {} allocations, got {}",
+        2 * ITERATIONS,
+        allocations
+    );
+
+    Ok(())
+}
+
+#[cfg(feature = "unsafe-interop")]
+#[test]
+fn test_native_instance_ptr() -> ConnectorFallible {
+    let context = TestContextBuilder::simple_output_only().build()?;
+    let output = context.connector.get_output("TestPublisher::TestWriter")?;
+
+    let native = output.native_instance_ptr()?;
+    assert!(!native.as_ptr().is_null());
+
+    Ok(())
+}
+
+#[test]
+fn test_non_finite_numbers() -> ConnectorFallible {
+    use rtiddsconnector::{OptionalBehavior, SelectedValue};
+
+    let context = TestContextBuilder::complex().build()?;
+    let mut output = context.connector.get_output("TestPublisher::TestWriter")?;
+
+    let mut data = complex_data();
+    data.simple.double_field = f64::NAN;
+    let mut sequence_data = complex_data();
+    sequence_data.double_sequence[2] = f64::INFINITY;
+
+    let mut instance = output.instance();
+    assert_matches!(
+        instance.set_number("simple.double_field", f64::NAN),
+        Err(e) if e.to_string().contains("'simple.double_field'")
+    );
+    assert_matches!(
+        instance.set_value(
+            "simple.double_field",
+            SelectedValue::Number(f64::NEG_INFINITY)
+        ),
+        Err(e) if e.to_string().contains("'simple.double_field'")
+    );
+    assert_matches!(
+        instance.set("simple.double_field", f64::INFINITY),
+        Err(e) if e.to_string().contains("'simple.double_field'")
+    );
+    assert_matches!(
+        instance.set_sequence("double_sequence", &[1.0, f64::NAN]),
+        Err(e) if e.to_string().contains("'double_sequence[1]'")
+    );
+
+    // The path of the field is reported when serializing
+    assert_matches!(
+        instance.serialize(&data),
+        Err(e) if e.to_string().contains("'simple.double_field'")
+    );
+    assert_matches!(
+        instance.serialize(&sequence_data),
+        Err(e) if e.to_string().contains("'double_sequence[2]'")
+    );
+    assert_matches!(
+        instance.serialize_with(&data, OptionalBehavior::ClearMissing),
+        Err(e) if e.to_string().contains("'simple.double_field'")
+    );
+    assert_matches!(
+        instance.serialize_field("simple", &data.simple),
+        Err(e) if e.to_string().contains("'simple.double_field'")
+    );
+    assert_matches!(
+        instance.serialize_direct(&data),
+        Err(e) if e.to_string().contains("'simple.double_field'")
+    );
+
+    // Unless explicitly allowed, except through JSON
+    output.allow_non_finite_numbers(true);
+    let mut instance = output.instance();
+    instance.set_number("simple.double_field", f64::NAN)?;
+    instance.serialize_direct(&sequence_data)?;
+    assert_matches!(instance.serialize(&data), Err(_));
+
+    Ok(())
+}
+
+#[test]
+fn test_nul_bytes_in_strings() -> ConnectorFallible {
+    use rtiddsconnector::SelectedValue;
+
+    let context = TestContextBuilder::simple().build()?;
+    let mut output = context.connector.get_output("TestPublisher::TestWriter")?;
+
+    let mut instance = output.instance();
+    assert_matches!(
+        instance.set_string("string_field", "Hel\0lo"),
+        Err(e) if e.to_string().contains("'string_field'") && e.to_string().contains("offset 3")
+    );
+    assert_matches!(
+        instance.set_value("string_field", SelectedValue::String("\0".to_string())),
+        Err(e) if e.to_string().contains("'string_field'") && e.to_string().contains("offset 0")
+    );
+    assert_matches!(
+        instance.set_number("long\0field", 1.0),
+        Err(e) if e.to_string().contains("long\\0field") && e.to_string().contains("offset 4")
+    );
+    assert_matches!(
+        instance.set_as_json("{\"string_field\": \"a\0b\"}"),
+        Err(e) if e.to_string().contains("JSON") && e.to_string().contains("offset 19")
+    );
+    assert_matches!(
+        instance.serialize_direct(&std::collections::HashMap::from([("string_field", "ab\0")])),
+        Err(e) if e.to_string().contains("'string_field'") && e.to_string().contains("offset 2")
+    );
+    assert_matches!(
+        context.connector.get_output("TestPublisher::Test\0Writer"),
+        Err(e) if e.to_string().contains("offset 19")
+    );
+
+    // Optionally, strings are truncated at the NUL byte instead
+    output.truncate_strings_at_nul(true);
+    let mut instance = output.instance();
+    instance.set_string("string_field", "Hel\0lo")?;
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&instance.to_string())
+            .expect("Invalid instance JSON")["string_field"],
+        "Hel"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_set_value_null_clears_member() -> ConnectorFallible {
+    use rtiddsconnector::SelectedValue;
+
+    let context = TestContextBuilder::complex().build()?;
+    let output = context.connector.get_output("TestPublisher::TestWriter")?;
+
+    let mut instance = output.instance();
+    instance.set_number("optional.double_field", 0.5)?;
+    assert!(instance.to_string().contains("0.5"));
+
+    instance.set_value("optional.double_field", SelectedValue::Null)?;
+    let json: serde_json::Value =
+        serde_json::from_str(&instance.to_string()).expect("Invalid instance JSON");
+    assert_matches!(
+        json["optional"].get("double_field"),
+        None | Some(serde_json::Value::Null)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_set_value_ref() -> ConnectorFallible {
+    use rtiddsconnector::SelectedValue;
+
+    let context = TestContextBuilder::large_strings_output_only().build()?;
+    let output = context.connector.get_output("TestPublisher::TestWriter")?;
+    let fields = ["first", "second", "third"];
+    let value = SelectedValue::String("x".repeat(1 << 20));
+
+    let instance_json = |output: &rtiddsconnector::Output| -> serde_json::Value {
+        serde_json::from_str(&output.instance().to_string())
+            .expect("Invalid instance JSON")
+    };
+
+    let mut instance = output.instance();
+    for field in fields {
+        instance.set_value_ref(field, &value)?;
+    }
+    let by_ref = instance_json(&output);
+    for field in fields {
+        assert_eq!(by_ref[field].as_str(), value.as_str());
+    }
+
+    // Same outcome as setting an owned value into each field
+    output
+        .instance()
+        .set_value_ref("first", &SelectedValue::String(String::new()))?;
+    for field in fields {
+        output.instance().set_value(field, value.clone())?;
+    }
+    assert_eq!(instance_json(&output), by_ref);
+
+    Ok(())
+}
+
+#[test]
+fn test_set_from_value_and_merge_value() -> ConnectorFallible {
+    use serde_json::json;
+
+    let context = TestContextBuilder::complex().build()?;
+    let output = context.connector.get_output("TestPublisher::TestWriter")?;
+    let data = complex_data();
+    let instance_json = |output: &rtiddsconnector::Output| -> serde_json::Value {
+        serde_json::from_str(&output.instance().to_string())
+            .expect("Invalid instance JSON")
+    };
+
+    let mut instance = output.instance();
+    instance.set_from_value(
+        &serde_json::to_value(&data).expect("Failed to serialize data"),
+    )?;
+    let before = instance_json(&output);
+    assert_eq!(before["simple"]["string_field"], data.simple.string_field);
+
+    // Only a whole object can be set
+    for value in [json!([1, 2]), json!(42), json!("simple"), json!(null)] {
+        assert_matches!(
+            output.instance().set_from_value(&value),
+            Err(e) if e.to_string().contains("Expected a JSON object")
+        );
+        assert_matches!(
+            output.instance().merge_value(&value),
+            Err(e) if e.to_string().contains("Expected a JSON object")
+        );
+    }
+    assert_eq!(instance_json(&output), before);
+
+    // Members missing from the value keep their previous value
+    output
+        .instance()
+        .set_from_value(&json!({ "simple": { "long_field": 7 } }))?;
+    let after = instance_json(&output);
+    assert_eq!(after["simple"]["long_field"], 7);
+    assert_eq!(
+        after["simple"]["string_field"],
+        before["simple"]["string_field"]
+    );
+    assert_eq!(after["double_sequence"], before["double_sequence"]);
+
+    // Merging only touches the members present, and unsets those set to null
+    output.instance().merge_value(&json!({
+        "simple": { "string_field": "Merged" },
+        "optional": { "string_field": null, "long_field": 3 },
+        "double_sequence": [9.5],
+    }))?;
+    let merged = instance_json(&output);
+    assert_eq!(merged["simple"]["string_field"], "Merged");
+    assert_eq!(merged["simple"]["long_field"], 7);
+    assert_eq!(
+        merged["simple"]["enum_field"],
+        before["simple"]["enum_field"]
+    );
+    assert_eq!(merged["optional"]["long_field"], 3);
+    assert_eq!(merged["optional"]["double_field"], 0.125);
+    assert_matches!(
+        merged["optional"].get("string_field"),
+        None | Some(serde_json::Value::Null)
+    );
+    assert_eq!(merged["double_sequence"], json!([9.5]));
+    assert_eq!(merged["long_matrix"], before["long_matrix"]);
+    assert_eq!(merged["union"], before["union"]);
+
+    // A value which can't be merged leaves the instance unchanged
+    assert_matches!(
+        output.instance().merge_value(&json!({
+            "simple": { "string_field": "Lost", "long_field": "not a number" },
+        })),
+        Err(_)
+    );
+    assert_eq!(instance_json(&output), merged);
+
+    Ok(())
+}
+
+#[cfg(feature = "async")]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_output_async_waits_cancelled_on_drop() -> ConnectorFallible {
+    use std::time::Duration;
+
+    let context = TestContextBuilder::simple_output_only().build()?;
+    let mut output = context.connector.get_output("TestPublisher::TestWriter")?;
+
+    // Nothing subscribes, so the wait only ends by dropping its future
+    assert_matches!(
+        tokio::time::timeout(
+            Duration::from_millis(200),
+            output.wait_for_subscriptions_async(None)
+        )
+        .await,
+        Err(_)
+    );
+
+    // The cancelled wait leaves the Output usable, synchronously and not
+    output.write()?;
+    output.wait_with_timeout(Duration::from_secs(1))?;
+    output.write()?;
+    output.wait_async(Some(Duration::from_secs(1))).await?;
+    assert_matches!(
+        output.wait_for_subscriptions_with_timeout(Duration::from_millis(100)),
+        Err(e) if e.is_timeout()
+    );
+
+    Ok(())
+}
@@ -35,6 +35,14 @@ impl TestContextBuilder {
         .with_output(Some("TestPublisher::TestWriter"))
     }
 
+    /// Profile: participant with two inputs and two outputs, none of them selected.
+    pub fn multiple_entities() -> Self {
+        Self::new(
+            TEST_CONFIG_FILE,
+            "TestDomainParticipantLibrary::MultipleEntitiesParticipant",
+        )
+    }
+
     /// Profile: simple participant with input only.
     pub fn simple_input_only() -> Self {
         Self::new(
@@ -63,6 +71,46 @@ impl TestContextBuilder {
         .with_output(Some("TestPublisher::TestWriter"))
     }
 
+    /// Profile: participant with input and output of a type with integer fields of every size,
+    /// and an octet sequence.
+    pub fn integers() -> Self {
+        Self::new(
+            TEST_CONFIG_FILE,
+            "TestDomainParticipantLibrary::IntegerParticipant",
+        )
+        .with_input(Some("TestSubscriber::TestReader"))
+        .with_output(Some("TestPublisher::TestWriter"))
+    }
+
+    /// Profile: participant with both input and output, of a type with three unbounded strings.
+    pub fn large_strings() -> Self {
+        Self::new(
+            TEST_CONFIG_FILE,
+            "TestDomainParticipantLibrary::LargeStringParticipant",
+        )
+        .with_input(Some("TestSubscriber::TestReader"))
+        .with_output(Some("TestPublisher::TestWriter"))
+    }
+
+    /// Profile: participant with both input and output, of a type with a `Time_t`-style member.
+    pub fn time() -> Self {
+        Self::new(
+            TEST_CONFIG_FILE,
+            "TestDomainParticipantLibrary::TimeParticipant",
+        )
+        .with_input(Some("TestSubscriber::TestReader"))
+        .with_output(Some("TestPublisher::TestWriter"))
+    }
+
+    /// Profile: participant with output only, of a type with three unbounded strings.
+    pub fn large_strings_output_only() -> Self {
+        Self::new(
+            TEST_CONFIG_FILE,
+            "TestDomainParticipantLibrary::LargeStringWriterParticipant",
+        )
+        .with_output(Some("TestPublisher::TestWriter"))
+    }
+
     /// Sets the config file path.
     pub fn with_config_file(mut self, config_file: impl Into<PathBuf>) -> Self {
         self.config_file = config_file.into();
@@ -135,7 +183,12 @@ pub struct TestContext {
 }
 
 impl TestContext {
-    pub fn test_entities(&mut self) -> ConnectorResult<TestEntities<'_>> {
+    /// Consume the context, returning its `Connector` and the guard that must outlive it.
+    pub fn into_parts(self) -> (Connector, GlobalsDropGuard) {
+        (self.connector, self._globals)
+    }
+
+    pub fn test_entities(&mut self) -> ConnectorResult<TestEntities> {
         let input = match &self.test_input_name {
             Some(name) => Some(self.connector.get_input(name)?),
             None => None,
@@ -156,12 +209,12 @@ impl TestContext {
 }
 
 #[derive(Debug)]
-pub struct TestEntities<'a> {
-    pub input: Option<Input<'a>>,
-    pub output: Option<Output<'a>>,
+pub struct TestEntities {
+    pub input: Option<Input>,
+    pub output: Option<Output>,
 }
 
-impl TestEntities<'_> {
+impl TestEntities {
     pub fn ensure_discovery(self) -> Self {
         self.ensure_discovery_of_amount_with_timeout(1, TEST_TIMEOUT)
     }
@@ -1,6 +1,6 @@
 //! Type definitions from `Test.xml` used in tests.
 
-use serde::{Deserialize, Serialize, ser::SerializeStruct};
+use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 
 /// Enum corresponding to TestEnum in `Test.xml`
@@ -8,6 +8,7 @@ use serde_repr::{Deserialize_repr, Serialize_repr};
     Debug, Clone, Copy, PartialEq, Eq, Serialize_repr, Deserialize_repr, Default,
 )]
 #[repr(u8)]
+#[cfg_attr(feature = "derive", derive(rtiddsconnector::DdsData))]
 pub enum TestEnum {
     #[default]
     Red = 0,
@@ -15,6 +16,33 @@ pub enum TestEnum {
     Blue = 2,
 }
 
+/// The enumerators of TestEnum have the same names as the variants.
+impl rtiddsconnector::DdsEnum for TestEnum {
+    fn name(&self) -> &'static str {
+        match self {
+            TestEnum::Red => "Red",
+            TestEnum::Green => "Green",
+            TestEnum::Blue => "Blue",
+        }
+    }
+
+    fn value(&self) -> i64 {
+        *self as i64
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        [TestEnum::Red, TestEnum::Green, TestEnum::Blue]
+            .into_iter()
+            .find(|variant| variant.name() == name)
+    }
+
+    fn from_value(value: i64) -> Option<Self> {
+        [TestEnum::Red, TestEnum::Green, TestEnum::Blue]
+            .into_iter()
+            .find(|variant| variant.value() == value)
+    }
+}
+
 /// Enum corresponding to TestUnionKind in `Test.xml`
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize_repr, Deserialize_repr)]
 #[repr(u8)]
@@ -28,10 +56,13 @@ pub enum TestUnionKind {
 
 /// Union corresponding to TestUnion in `Test.xml`
 #[derive(Debug, Clone, PartialEq)]
-
+#[cfg_attr(feature = "derive", derive(rtiddsconnector::DdsData))]
 pub enum TestUnion {
+    #[cfg_attr(feature = "derive", dds(rename = "string"))]
     String(String),
+    #[cfg_attr(feature = "derive", dds(rename = "number"))]
     Number(f64),
+    #[cfg_attr(feature = "derive", dds(rename = "boolean"))]
     Boolean(bool),
 }
 
@@ -41,58 +72,15 @@ impl std::default::Default for TestUnion {
     }
 }
 
-impl Serialize for TestUnion {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        let mut state = serializer.serialize_struct("TestUnion", 1)?;
-        match self {
-            TestUnion::String(s) => {
-                // "string" : "some text"
-                state.serialize_field("string", s)?;
-            }
-            TestUnion::Number(n) => {
-                // "number" : 123.45
-                state.serialize_field("number", n)?;
-            }
-            TestUnion::Boolean(b) => {
-                // "boolean" : "true/false"
-                state.serialize_field("boolean", b)?;
-            }
-        }
-        state.end()
-    }
-}
-
-impl<'a> Deserialize<'a> for TestUnion {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: serde::Deserializer<'a>,
-    {
-        #[derive(Deserialize)]
-        struct TestUnionHelper {
-            string: Option<String>,
-            number: Option<f64>,
-            boolean: Option<bool>,
-        }
-
-        let helper = TestUnionHelper::deserialize(deserializer)?;
-        if let Some(s) = helper.string {
-            Ok(TestUnion::String(s))
-        } else if let Some(n) = helper.number {
-            Ok(TestUnion::Number(n))
-        } else if let Some(b) = helper.boolean {
-            Ok(TestUnion::Boolean(b))
-        } else {
-            Err(serde::de::Error::custom("No valid union member found"))
-        }
-    }
-}
+rtiddsconnector::dds_union!(TestUnion {
+    "string" => String(String),
+    "number" => Number(f64),
+    "boolean" => Boolean(bool),
+});
 
 /// Struct corresponding to SimpleStruct in `Test.xml`
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
-
+#[cfg_attr(feature = "derive", derive(rtiddsconnector::DdsData))]
 pub struct SimpleStruct {
     pub long_field: i32,
     pub double_field: f64,
@@ -103,7 +91,7 @@ pub struct SimpleStruct {
 
 /// Struct corresponding to OptionalStruct in `Test.xml`
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
-
+#[cfg_attr(feature = "derive", derive(rtiddsconnector::DdsData))]
 pub struct OptionalStruct {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub long_field: Option<i32>,
@@ -119,6 +107,7 @@ pub struct OptionalStruct {
 
 /// Struct corresponding to ComplexStruct in `Test.xml`
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "derive", derive(rtiddsconnector::DdsData))]
 pub struct ComplexStruct {
     pub simple: SimpleStruct,
     pub optional: OptionalStruct,
@@ -127,3 +116,21 @@ pub struct ComplexStruct {
     pub string_array: [String; 3],
     pub double_sequence: Vec<f64>,
 }
+
+/// Struct corresponding to LargeStringStruct in `Test.xml`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct LargeStringStruct {
+    pub first: String,
+    pub second: String,
+    pub third: String,
+}
+
+/// Struct corresponding to LargeStringStruct in `Test.xml`, borrowing its strings
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct LargeStringRef<'a> {
+    #[serde(borrow)]
+    pub first: std::borrow::Cow<'a, str>,
+    #[serde(borrow)]
+    pub second: std::borrow::Cow<'a, str>,
+    pub third: &'a str,
+}
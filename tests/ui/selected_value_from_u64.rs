@@ -0,0 +1,5 @@
+use rtiddsconnector::SelectedValue;
+
+fn main() {
+    let _value: SelectedValue = u64::MAX.into();
+}
@@ -0,0 +1,10 @@
+use rtiddsconnector::{Connector, Input, Output};
+
+// Native pointers are only available with the `unsafe-interop` feature
+fn native_pointers(connector: &Connector, input: &Input, output: &Output) {
+    let _participant = connector.native_participant_ptr();
+    let _reader = input.native_datareader_ptr();
+    let _writer = output.native_datawriter_ptr();
+}
+
+fn main() {}